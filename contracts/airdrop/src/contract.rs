@@ -1,12 +1,13 @@
-use crate::crypto::verify_claim;
 use crate::state::{CONFIG, STATE, USERS};
 use astroport::asset::addr_validate_to_lower;
 use astroport_periphery::airdrop::{
     ClaimResponse, Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, State,
     UserInfo,
 };
-use astroport_periphery::auction::Cw20HookMsg::DelegateAstroTokens;
-use astroport_periphery::helpers::{build_send_cw20_token_msg, build_transfer_cw20_token_msg};
+use astroport_periphery::auction::{AllowlistProof, Cw20HookMsg::DelegateAstroTokens};
+use astroport_periphery::helpers::{
+    build_send_cw20_token_msg, build_transfer_cw20_token_msg, verify_merkle_proof,
+};
 use cosmwasm_std::{
     attr, entry_point, from_binary, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
     StdError, StdResult, Uint128,
@@ -99,7 +100,7 @@ pub fn instantiate(
 ///             root_index,
 ///         }** Executes an airdrop claim for Users.
 ///
-/// * **ExecuteMsg::DelegateAstroToBootstrapAuction { amount_to_delegate }** Delegates ASTRO to bootstrap auction contract.
+/// * **ExecuteMsg::DelegateAstroToBootstrapAuction { amount_to_delegate, allowlist_proof }** Delegates ASTRO to bootstrap auction contract.
 ///
 /// * **ExecuteMsg::EnableClaims {}** Enables ASTRO withdrawals by the airdrop recipients.
 ///
@@ -136,9 +137,15 @@ pub fn execute(
             merkle_proof,
             root_index,
         } => handle_claim(deps, env, info, claim_amount, merkle_proof, root_index),
-        ExecuteMsg::DelegateAstroToBootstrapAuction { amount_to_delegate } => {
-            handle_delegate_astro_to_bootstrap_auction(deps, info, amount_to_delegate)
-        }
+        ExecuteMsg::DelegateAstroToBootstrapAuction {
+            amount_to_delegate,
+            allowlist_proof,
+        } => handle_delegate_astro_to_bootstrap_auction(
+            deps,
+            info,
+            amount_to_delegate,
+            allowlist_proof,
+        ),
         ExecuteMsg::EnableClaims {} => handle_enable_claims(deps, info),
         ExecuteMsg::WithdrawAirdropReward {} => handle_withdraw_airdrop_rewards(deps, info),
         ExecuteMsg::TransferUnclaimedTokens { recipient, amount } => {
@@ -176,6 +183,15 @@ pub fn receive_cw20(
         Cw20HookMsg::IncreaseAstroIncentives {} => {
             handle_increase_astro_incentives(deps, cw20_msg.amount)
         }
+        Cw20HookMsg::UndelegateAstroTokens { user_address } => {
+            // CHECK :: ONLY AUCTION CONTRACT CAN RETURN DELEGATED ASTRO
+            if Some(addr_validate_to_lower(deps.api, &cw20_msg.sender)?)
+                != config.auction_contract_address
+            {
+                return Err(StdError::generic_err("Unauthorized"));
+            }
+            handle_undelegate_astro_tokens(deps, user_address, cw20_msg.amount)
+        }
     }
 }
 
@@ -318,6 +334,35 @@ pub fn handle_increase_astro_incentives(
         .add_attribute("total_airdrop_size", state.total_airdrop_size))
 }
 
+/// Returns ASTRO tokens undelegated from the bootstrap auction to the user's airdrop. Returns a [`StdError`] on failure.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **user_address** is an object of type [`String`].
+///
+/// * **amount** is an object of type [`Uint128`].
+pub fn handle_undelegate_astro_tokens(
+    deps: DepsMut,
+    user_address: String,
+    amount: Uint128,
+) -> Result<Response, StdError> {
+    let user_address = addr_validate_to_lower(deps.api, &user_address)?;
+    let mut state = STATE.load(deps.storage)?;
+    let mut user_info = USERS.load(deps.storage, &user_address)?;
+
+    state.total_delegated_amount = state.total_delegated_amount.checked_sub(amount)?;
+    user_info.delegated_amount = user_info.delegated_amount.checked_sub(amount)?;
+
+    USERS.save(deps.storage, &user_address, &user_info)?;
+    STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "undelegate_astro_from_bootstrap_auction"),
+        attr("user", user_address.to_string()),
+        attr("amount_undelegated", amount),
+    ]))
+}
+
 /// Enables ASTRO withdrawals by the airdrop recipients. Returns a [`StdError`] on failure.
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
@@ -387,7 +432,7 @@ pub fn handle_claim(
         .get(root_index as usize)
         .ok_or_else(|| StdError::generic_err("Incorrect Merkle Root Index"))?;
 
-    if !verify_claim(&recipient, claim_amount, merkle_proof, merkle_root)? {
+    if !verify_merkle_proof(&recipient, claim_amount, merkle_proof, merkle_root)? {
         return Err(StdError::generic_err("Incorrect Merkle Proof"));
     }
 
@@ -437,10 +482,13 @@ pub fn handle_claim(
 /// * **info** is an object of type [`MessageInfo`].
 ///
 /// * **amount_to_delegate** is an object of type [`Uint128`].
+///
+/// * **allowlist_proof** is an [`Option`] of type [`AllowlistProof`]. Proof that the user is allowlisted by the auction.
 pub fn handle_delegate_astro_to_bootstrap_auction(
     deps: DepsMut,
     info: MessageInfo,
     amount_to_delegate: Uint128,
+    allowlist_proof: Option<AllowlistProof>,
 ) -> Result<Response, StdError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -463,6 +511,7 @@ pub fn handle_delegate_astro_to_bootstrap_auction(
     // COSMOS MSG :: DELEGATE ASTRO TOKENS TO LP BOOTSTRAP AUCTION CONTRACT
     let msg = to_binary(&DelegateAstroTokens {
         user_address: info.sender.to_string(),
+        allowlist_proof,
     })?;
 
     let delegate_msg = build_send_cw20_token_msg(
@@ -1,3 +1,2 @@
 pub mod contract;
-pub mod crypto;
 pub mod state;
@@ -4,7 +4,7 @@ use astroport_periphery::{
     auction::{ExecuteMsg as AuctionExecuteMsg, UpdateConfigMsg},
 };
 
-use cosmwasm_std::{attr, to_binary, Addr, Timestamp, Uint128};
+use cosmwasm_std::{attr, to_binary, Addr, Decimal, Timestamp, Uint128};
 use cw20::Cw20ExecuteMsg;
 use cw_multi_test::{App, ContractWrapper, Executor};
 
@@ -1256,9 +1256,17 @@ fn test_delegate_astro_to_bootstrap_auction() {
         airdrop_contract_address: airdrop_instance.clone().to_string(),
         lockdrop_contract_address: "lockdrop_contract_address".to_string(),
         lp_tokens_vesting_duration: 2592000u64,
+        astro_incentives_vesting_duration: None,
+        lp_vesting_schedules: None,
         init_timestamp: 1571897419u64,
         deposit_window: 2592000u64,
         withdrawal_window: 1592000u64,
+        astro_delegators_incentives_share: Decimal::percent(50),
+        time_weight: None,
+        keeper: None,
+        init_pool_slippage: None,
+        compound_slippage: None,
+        allowlist_merkle_root: None,
     };
 
     let auction_contract_instance = app
@@ -1280,6 +1288,10 @@ fn test_delegate_astro_to_bootstrap_auction() {
                 astro_ust_pair_address: Some(pair_instance.to_string()),
                 owner: None,
                 generator_contract: None,
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
             },
         },
         &[],
@@ -1364,6 +1376,7 @@ fn test_delegate_astro_to_bootstrap_auction() {
             airdrop_instance.clone(),
             &ExecuteMsg::DelegateAstroToBootstrapAuction {
                 amount_to_delegate: Uint128::from(250000001u64),
+                allowlist_proof: None,
             },
             &[],
         )
@@ -1381,6 +1394,7 @@ fn test_delegate_astro_to_bootstrap_auction() {
             airdrop_instance.clone(),
             &ExecuteMsg::DelegateAstroToBootstrapAuction {
                 amount_to_delegate: Uint128::from(250000000u64),
+                allowlist_proof: None,
             },
             &[],
         )
@@ -1435,4 +1449,97 @@ fn test_delegate_astro_to_bootstrap_auction() {
         Uint128::from(99750000000u64),
         state_query_resp.unclaimed_tokens
     );
+
+    // **** Only the auction contract can return delegated ASTRO ****
+
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_instance.clone(),
+        Uint128::from(100u64),
+        owner.to_string(),
+    );
+
+    let undelegation_err = app
+        .execute_contract(
+            owner.clone(),
+            astro_instance.clone(),
+            &Cw20ExecuteMsg::Send {
+                amount: Uint128::from(100u64),
+                contract: airdrop_instance.to_string(),
+                msg: to_binary(&Cw20HookMsg::UndelegateAstroTokens {
+                    user_address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        undelegation_err.root_cause().to_string(),
+        "Generic error: Unauthorized"
+    );
+
+    // **** Should successfully undelegate ASTRO from the auction ****
+
+    app.execute_contract(
+        Addr::unchecked("terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string()),
+        auction_contract_instance.clone(),
+        &AuctionExecuteMsg::UndelegateAstro {
+            amount: Uint128::from(50000000u64),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Check :: Airdrop :: User state
+    let user_info_query_resp: UserInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &QueryMsg::UserInfo {
+                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(200000000u64),
+        user_info_query_resp.delegated_amount
+    );
+
+    // Check :: Airdrop :: Contract state
+    let state_query_resp: State = app
+        .wrap()
+        .query_wasm_smart(&airdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        Uint128::from(200000000u64),
+        state_query_resp.total_delegated_amount
+    );
+
+    // Check :: Auction :: User state
+    let auction_user_info: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_contract_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp".to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(200000000u64),
+        auction_user_info.astro_delegated
+    );
+
+    let astro_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: airdrop_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(99800000000u64), astro_balance.balance);
 }
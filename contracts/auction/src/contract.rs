@@ -1,27 +1,43 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    attr, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+    attr, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Empty,
+    Env, MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128, Uint256,
+    WasmMsg,
 };
 
+use astroport_periphery::airdrop::Cw20HookMsg::UndelegateAstroTokens as AirdropUndelegateAstroTokens;
 use astroport_periphery::airdrop::ExecuteMsg::EnableClaims as AirdropEnableClaims;
 use astroport_periphery::auction::{
-    CallbackMsg, Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolInfo, QueryMsg,
-    State, UpdateConfigMsg, UserInfo, UserInfoResponse,
+    AllowlistProof, AuctionPhase, CallbackMsg, CompoundRound, Config, Cw20HookMsg, ExecuteMsg,
+    InstantiateMsg, LpVestingSchedules, MigrateMsg, PhaseResponse, PoolInfo, QueryMsg,
+    SimulateInitPoolResponse, State, TimeWeight, TotalsResponse, UpdateConfigMsg, UserInfo,
+    UserInfoResponse, UserInfoWithAddressResponse,
 };
-use astroport_periphery::helpers::{build_approve_cw20_msg, cw20_get_balance};
+use astroport_periphery::helpers::{
+    assert_position_nft_minter, assert_position_owner, build_approve_cw20_msg,
+    build_mint_position_nft_msg, build_send_cw20_token_msg, build_transfer_cw20_token_msg,
+    cw20_get_balance, query_position_nft_owner, verify_merkle_proof,
+};
+use astroport_periphery::lockdrop::Cw20HookMsg::UndelegateAstroTokens as LockdropUndelegateAstroTokens;
 use astroport_periphery::lockdrop::ExecuteMsg::EnableClaims as LockdropEnableClaims;
+use astroport_periphery::U64Key;
 
-use crate::state::{CONFIG, STATE, USERS};
+use crate::migration::{set_delegation_sources, CONFIG_V100, STATE_V100};
+use crate::state::{
+    is_user_v100, CompatibleLoader, ALLOWLIST, COMPOUND_ROUNDS, CONFIG, OPERATORS, STATE, USERS,
+};
 use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
 use astroport::generator::{
     ExecuteMsg as GenExecuteMsg, PendingTokenResponse, QueryMsg as GenQueryMsg, RewardInfoResponse,
 };
-use astroport::pair::QueryMsg as AstroportPairQueryMsg;
+use astroport::pair::{PoolResponse, QueryMsg as AstroportPairQueryMsg};
 use astroport::querier::query_token_balance;
-use cw2::set_contract_version;
+use astroport::restricted_vector::RestrictedVector;
+use cw2::{get_contract_version, set_contract_version};
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw721::NftInfoResponse;
+use cw_storage_plus::Bound;
 
 /// TerraUSD denom.
 const UUSD_DENOM: &str = "uusd";
@@ -31,6 +47,16 @@ const CONTRACT_NAME: &str = "astroport_auction";
 /// Contract version that is used for migration.
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Maximum slippage (in percent) which can be set for compounding.
+const MAX_COMPOUND_SLIPPAGE: u64 = 5;
+/// Maximum slippage (in percent) which can be set for the ASTRO-UST Pool initialization.
+const MAX_INIT_POOL_SLIPPAGE: u64 = 50;
+
+/// Default number of users returned by the users query.
+const DEFAULT_LIMIT: u32 = 10;
+/// Maximum number of users returned by the users query.
+const MAX_LIMIT: u32 = 30;
+
 /// ## Description
 /// Creates a new contract with the specified parameters in the [`InstantiateMsg`].
 /// Returns the [`Response`] with the specified attributes if the operation was successful, or a [`StdError`] if
@@ -59,6 +85,33 @@ pub fn instantiate(
         )));
     }
 
+    // CHECK :: deposit_window needs to be valid
+    if msg.deposit_window == 0 {
+        return Err(StdError::generic_err(
+            "deposit_window should be greater than 0",
+        ));
+    }
+
+    // CHECK :: astro_delegators_incentives_share needs to be valid
+    if msg.astro_delegators_incentives_share > Decimal::one() {
+        return Err(StdError::generic_err(
+            "astro_delegators_incentives_share should be less than or equal to 1",
+        ));
+    }
+
+    if let Some(lp_vesting_schedules) = &msg.lp_vesting_schedules {
+        validate_lp_vesting_schedule(&lp_vesting_schedules.astro_delegators)?;
+        validate_lp_vesting_schedule(&lp_vesting_schedules.ust_depositors)?;
+    }
+
+    if let Some(init_pool_slippage) = msg.init_pool_slippage {
+        validate_init_pool_slippage(init_pool_slippage)?;
+    }
+
+    if let Some(compound_slippage) = msg.compound_slippage {
+        validate_compound_slippage(compound_slippage)?;
+    }
+
     let config = Config {
         owner: msg
             .owner
@@ -75,9 +128,18 @@ pub fn instantiate(
         generator_contract: None,
         astro_incentive_amount: None,
         lp_tokens_vesting_duration: msg.lp_tokens_vesting_duration,
+        astro_incentives_vesting_duration: msg.astro_incentives_vesting_duration,
+        lp_vesting_schedules: msg.lp_vesting_schedules,
         init_timestamp: msg.init_timestamp,
         deposit_window: msg.deposit_window,
         withdrawal_window: msg.withdrawal_window,
+        astro_delegators_incentives_share: msg.astro_delegators_incentives_share,
+        time_weight: msg.time_weight,
+        position_nft: None,
+        keeper: msg.keeper,
+        init_pool_slippage: msg.init_pool_slippage,
+        compound_slippage: msg.compound_slippage,
+        allowlist_merkle_root: msg.allowlist_merkle_root,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -103,15 +165,31 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::UpdateConfig { new_config }** Admin function to update configuration parameters.
 ///
-/// * **ExecuteMsg::DepositUst {}** Facilitates UST deposits by users.
+/// * **ExecuteMsg::SetPositionNft { contract }** Admin function to mirror positions by NFTs of the given contract.
+///
+/// * **ExecuteMsg::DepositUst { beneficiary, allowlist_proof }** Facilitates UST deposits by users, optionally on behalf of the beneficiary.
+///
+/// * **ExecuteMsg::WithdrawUst { amount, user_address }** Facilitates UST withdrawals by users and their operators.
+///
+/// * **ExecuteMsg::UndelegateAstro { amount }** Returns delegated ASTRO to the airdrop and lockdrop contracts.
+///
+/// * **ExecuteMsg::InitPool {}** Facilitates Liquidity addtion to the Astroport ASTRO-UST Pool. Open to keepers after a grace period.
+///
+/// * **ExecuteMsg::StakeLpTokens {}** Stakes ASTRO-UST LP tokens with the generator contract. Open to keepers after a grace period.
+///
+/// * **ExecuteMsg::ClaimRewards { withdraw_lp_shares, user_address }** Facilitates ASTRO rewards claim.
 ///
-/// * **ExecuteMsg::WithdrawUst { amount }** Facilitates UST withdrawals by users.
+/// * **ExecuteMsg::SetCompounding { enabled, user_address }** Enables or disables compounding of user's ASTRO staking rewards.
 ///
-/// * **ExecuteMsg::InitPool { slippage }** Admin function which facilitates Liquidity addtion to the Astroport ASTRO-UST Pool.
+/// * **ExecuteMsg::Compound {}** Compounds ASTRO staking rewards of the compounding pool with the slippage set in config. Can be called by anyone.
 ///
-/// * **ExecuteMsg::StakeLpTokens {}** Admin function to stake ASTRO-UST LP tokens with the generator contract.
+/// * **ExecuteMsg::WithdrawCompoundedLp { units, user_address }** Withdraws LP Tokens of the compounding pool for the given units.
 ///
-/// * **ExecuteMsg::ClaimRewards { withdraw_lp_shares }** Facilitates ASTRO rewards claim.
+/// * **ExecuteMsg::TransferPosition { recipient, fraction }** Transfers a fraction of user's position to the recipient.
+///
+/// * **ExecuteMsg::ApproveOperator { operator }** Allows the operator to withdraw UST on behalf of the sender.
+///
+/// * **ExecuteMsg::RevokeOperator { operator }** Revokes the operator's allowance to withdraw UST on behalf of the sender.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -120,15 +198,53 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, StdError> {
     match msg {
-        ExecuteMsg::UpdateConfig { new_config } => handle_update_config(deps, info, new_config),
+        ExecuteMsg::UpdateConfig { new_config } => {
+            handle_update_config(deps, env, info, new_config)
+        }
+        ExecuteMsg::SetPositionNft { contract } => {
+            handle_set_position_nft(deps, env, info, contract)
+        }
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
-        ExecuteMsg::DepositUst {} => handle_deposit_ust(deps, env, info),
-        ExecuteMsg::WithdrawUst { amount } => handle_withdraw_ust(deps, env, info, amount),
-        ExecuteMsg::InitPool { slippage } => handle_init_pool(deps, env, info, slippage),
+        ExecuteMsg::DepositUst {
+            beneficiary,
+            allowlist_proof,
+        } => handle_deposit_ust(deps, env, info, beneficiary, allowlist_proof),
+        ExecuteMsg::WithdrawUst {
+            amount,
+            user_address,
+        } => handle_withdraw_ust(deps, env, info, amount, user_address),
+        ExecuteMsg::UndelegateAstro { amount } => handle_undelegate_astro(deps, env, info, amount),
+        ExecuteMsg::InitPool {} => handle_init_pool(deps, env, info),
         ExecuteMsg::StakeLpTokens {} => handle_stake_lp_tokens(deps, env, info),
-        ExecuteMsg::ClaimRewards { withdraw_lp_shares } => {
-            handle_claim_rewards_and_withdraw_lp_shares(deps, env, info, withdraw_lp_shares)
+        ExecuteMsg::MigrateGenerator { generator } => {
+            handle_migrate_generator(deps, env, info, generator)
         }
+        ExecuteMsg::EmergencyUnstake {} => handle_emergency_unstake(deps, info),
+        ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares,
+            user_address,
+        } => handle_claim_rewards_and_withdraw_lp_shares(
+            deps,
+            env,
+            info,
+            withdraw_lp_shares,
+            user_address,
+        ),
+        ExecuteMsg::SetCompounding {
+            enabled,
+            user_address,
+        } => handle_set_compounding(deps, info, enabled, user_address),
+        ExecuteMsg::Compound {} => handle_compound(deps, env),
+        ExecuteMsg::WithdrawCompoundedLp {
+            units,
+            user_address,
+        } => handle_withdraw_compounded_lp(deps, env, info, units, user_address),
+        ExecuteMsg::TransferPosition {
+            recipient,
+            fraction,
+        } => handle_transfer_position(deps, env, info, recipient, fraction),
+        ExecuteMsg::ApproveOperator { operator } => handle_approve_operator(deps, info, operator),
+        ExecuteMsg::RevokeOperator { operator } => handle_revoke_operator(deps, info, operator),
         ExecuteMsg::Callback(msg) => handle_callback(deps, env, info, msg),
     }
 }
@@ -162,12 +278,22 @@ pub fn receive_cw20(
     }
 
     match from_binary(&cw20_msg.msg)? {
-        Cw20HookMsg::DelegateAstroTokens { user_address } => {
+        Cw20HookMsg::DelegateAstroTokens {
+            user_address,
+            allowlist_proof,
+        } => {
             // CHECK :: Delegation can happen only via airdrop / lockdrop contracts
             if cw20_msg.sender == config.airdrop_contract_address
                 || cw20_msg.sender == config.lockdrop_contract_address
             {
-                handle_delegate_astro_tokens(deps, env, user_address, cw20_msg.amount)
+                handle_delegate_astro_tokens(
+                    deps,
+                    env,
+                    cw20_msg.sender,
+                    user_address,
+                    cw20_msg.amount,
+                    allowlist_proof,
+                )
             } else {
                 Err(StdError::generic_err("Unauthorized"))
             }
@@ -204,15 +330,66 @@ fn handle_callback(
         CallbackMsg::UpdateStateOnLiquidityAdditionToPool { prev_lp_balance } => {
             update_state_on_liquidity_addition_to_pool(deps, env, prev_lp_balance)
         }
-        CallbackMsg::UpdateStateOnRewardClaim { prev_astro_balance } => {
-            update_state_on_reward_claim(deps, env, prev_astro_balance)
+        CallbackMsg::UpdateStateOnRewardClaim {
+            prev_astro_balance,
+            prev_proxy_reward_balances,
+        } => {
+            update_state_on_reward_claim(deps, env, prev_astro_balance, prev_proxy_reward_balances)
         }
         CallbackMsg::WithdrawUserRewardsCallback {
             user_address,
+            recipient,
             withdraw_lp_shares,
-        } => {
-            callback_withdraw_user_rewards_and_optionally_lp(deps, user_address, withdraw_lp_shares)
-        }
+        } => callback_withdraw_user_rewards_and_optionally_lp(
+            deps,
+            env,
+            user_address,
+            recipient,
+            withdraw_lp_shares,
+        ),
+        CallbackMsg::CompoundAstroRewards {} => callback_compound_astro_rewards(deps, env),
+        CallbackMsg::ProvideCompoundedLiquidity {
+            prev_ust_balance,
+            astro_amount,
+            users_astro,
+            total_astro,
+            compounding_lp_shares,
+        } => callback_provide_compounded_liquidity(
+            deps,
+            env,
+            prev_ust_balance,
+            astro_amount,
+            users_astro,
+            total_astro,
+            compounding_lp_shares,
+        ),
+        CallbackMsg::StakeCompoundedLp {
+            prev_lp_balance,
+            users_astro,
+            total_astro,
+            compounding_lp_shares,
+        } => callback_stake_compounded_lp(
+            deps,
+            env,
+            prev_lp_balance,
+            users_astro,
+            total_astro,
+            compounding_lp_shares,
+        ),
+        CallbackMsg::WithdrawCompoundedLpCallback {
+            user_address,
+            recipient,
+            units,
+        } => callback_withdraw_compounded_lp(deps, env, user_address, recipient, units),
+        CallbackMsg::TransferPositionCallback {
+            user_address,
+            recipient,
+            fraction,
+        } => callback_transfer_position(deps, user_address, recipient, fraction),
+        CallbackMsg::MigrateGeneratorCallback {
+            generator,
+            lp_amount,
+        } => callback_migrate_generator(deps, generator, lp_amount),
     }
 }
 
@@ -230,36 +407,137 @@ fn handle_callback(
 /// * **QueryMsg::State {}** Returns state of the contract.
 ///
 /// * **QueryMsg::UserInfo { address }** Returns user position details.
+///
+/// * **QueryMsg::PositionNftInfo { token_id }** Returns metadata of the position NFT.
+///
+/// * **QueryMsg::Operators { address }** Returns operators approved by the given address.
+///
+/// * **QueryMsg::Phase { timestamp }** Returns the current phase of the auction, its timeline and allowed withdrawals.
+///
+/// * **QueryMsg::SimulateInitPool {}** Simulates liquidity addition to the Astroport ASTRO-UST Pool.
+///
+/// * **QueryMsg::Users { start_after, limit }** Returns positions of users ordered by their addresses.
+///
+/// * **QueryMsg::Totals { start_after, limit }** Sums a page of users' positions and checks the sums against
+/// the contract's state if the page covers all users.
+///
+/// * **QueryMsg::AllowlistCap { address }** Returns the UST deposit cap of an address which has already proven its allowlisting.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
         QueryMsg::State {} => to_binary(&STATE.load(deps.storage)?),
         QueryMsg::UserInfo { address } => to_binary(&query_user_info(deps, _env, address)?),
+        QueryMsg::PositionNftInfo { token_id } => {
+            to_binary(&query_position_nft_info(deps, _env, token_id)?)
+        }
+        QueryMsg::Operators { address } => to_binary(&query_operators(deps, address)?),
+        QueryMsg::Phase { timestamp } => to_binary(&query_phase(deps, _env, timestamp)?),
+        QueryMsg::SimulateInitPool {} => to_binary(&query_simulate_init_pool(deps, _env)?),
+        QueryMsg::Users { start_after, limit } => {
+            to_binary(&query_users(deps, _env, start_after, limit)?)
+        }
+        QueryMsg::Totals { start_after, limit } => {
+            to_binary(&query_totals(deps, start_after, limit)?)
+        }
+        QueryMsg::AllowlistCap { address } => to_binary(&query_allowlist_cap(deps, address)?),
     }
 }
 
 /// Used for contract migration. Returns a default object of type [`Response`].
 /// ## Params
-/// * **_deps** is an object of type [`DepsMut`].
+/// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **_env** is an object of type [`Env`].
 ///
-/// * **_msg** is an object of type [`MigrateMsg`].
+/// * **msg** is an object of type [`MigrateMsg`].
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let contract_version = get_contract_version(deps.storage)?;
+
+    match contract_version.contract.as_ref() {
+        "astroport_auction" => match contract_version.version.as_ref() {
+            "1.0.0" => {
+                let config = CONFIG_V100.load(deps.storage)?;
+                CONFIG.save(
+                    deps.storage,
+                    &Config {
+                        owner: config.owner,
+                        astro_token_address: config.astro_token_address,
+                        airdrop_contract_address: config.airdrop_contract_address,
+                        lockdrop_contract_address: config.lockdrop_contract_address,
+                        pool_info: config.pool_info,
+                        generator_contract: config.generator_contract,
+                        astro_incentive_amount: config.astro_incentive_amount,
+                        lp_tokens_vesting_duration: config.lp_tokens_vesting_duration,
+                        astro_incentives_vesting_duration: msg.astro_incentives_vesting_duration,
+                        lp_vesting_schedules: None,
+                        init_timestamp: config.init_timestamp,
+                        deposit_window: config.deposit_window,
+                        withdrawal_window: config.withdrawal_window,
+                        astro_delegators_incentives_share: Decimal::percent(50),
+                        time_weight: None,
+                        position_nft: None,
+                        keeper: None,
+                        init_pool_slippage: None,
+                        compound_slippage: None,
+                        allowlist_merkle_root: None,
+                    },
+                )?;
+
+                let state = STATE_V100.load(deps.storage)?;
+                STATE.save(
+                    deps.storage,
+                    &State {
+                        total_astro_delegated: state.total_astro_delegated,
+                        total_ust_delegated: state.total_ust_delegated,
+                        is_lp_staked: state.is_lp_staked,
+                        lp_shares_minted: state.lp_shares_minted,
+                        pool_init_timestamp: state.pool_init_timestamp,
+                        generator_astro_per_share: state.generator_astro_per_share,
+                        generator_proxy_per_share: RestrictedVector::default(),
+                        total_astro_weighted: state.total_astro_delegated,
+                        total_ust_weighted: state.total_ust_delegated,
+                        compounding_lp_shares: Uint128::zero(),
+                        pending_compounding_lp_shares: Uint128::zero(),
+                        compound_round: 0,
+                        compound_astro_per_share: Decimal::zero(),
+                        compound_units_per_share: Decimal::zero(),
+                        compounded_lp_shares: Uint128::zero(),
+                        total_compound_units: Uint128::zero(),
+                        keeper_bounty_reserve: Uint128::zero(),
+                    },
+                )?;
+
+                // Users are converted lazily on their first load, see [`CompatibleLoader`]
+            }
+            _ => return Err(StdError::generic_err("Migration error")),
+        },
+        _ => return Err(StdError::generic_err("Migration error")),
+    };
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("previous_contract_name", &contract_version.contract),
+        ("previous_contract_version", &contract_version.version),
+        ("current_contract_name", &CONTRACT_NAME.to_string()),
+        ("current_contract_version", &CONTRACT_VERSION.to_string()),
+    ]))
 }
 
 /// Admin function to update any of the configuration parameters. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
+/// * **env** is an object of type [`Env`].
+///
 /// * **info** is an object of type [`MessageInfo`].
 ///
 /// * **new_config** is an object of type [`UpdateConfigMsg`].
 pub fn handle_update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_config: UpdateConfigMsg,
 ) -> StdResult<Response> {
@@ -307,10 +585,267 @@ pub fn handle_update_config(
         attributes.push(attr("generator", generator_addr.to_string()));
     }
 
+    if let Some(keeper) = new_config.keeper {
+        attributes.push(attr("keeper_grace_period", keeper.grace_period.to_string()));
+        attributes.push(attr("keeper_bounty", keeper.bounty));
+        config.keeper = Some(keeper);
+    }
+
+    if let Some(init_pool_slippage) = new_config.init_pool_slippage {
+        // CHECK :: Slippage is used only once during the pool initialization
+        if state.lp_shares_minted.is_some() {
+            return Err(StdError::generic_err(
+                "Assets had already been provided to previous pool!",
+            ));
+        }
+        validate_init_pool_slippage(init_pool_slippage)?;
+        config.init_pool_slippage = Some(init_pool_slippage);
+        attributes.push(attr("init_pool_slippage", init_pool_slippage.to_string()));
+    }
+
+    if let Some(compound_slippage) = new_config.compound_slippage {
+        validate_compound_slippage(compound_slippage)?;
+        config.compound_slippage = Some(compound_slippage);
+        attributes.push(attr("compound_slippage", compound_slippage.to_string()));
+    }
+
+    if let Some(allowlist_merkle_root) = new_config.allowlist_merkle_root {
+        // CHECK :: Verified caps are cached, so the allowlist can't be replaced once deposits are open
+        if env.block.time.seconds() >= config.init_timestamp {
+            return Err(StdError::generic_err(
+                "Allowlist can't be updated after the auction has started",
+            ));
+        }
+        // CHECK :: Position NFTs can be sold to any address, bypassing the allowlist
+        if config.position_nft.is_some() {
+            return Err(StdError::generic_err(
+                "Allowlist can't be enabled together with position NFTs",
+            ));
+        }
+        attributes.push(attr("allowlist_merkle_root", &allowlist_merkle_root));
+        config.allowlist_merkle_root = Some(allowlist_merkle_root);
+    }
+
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new().add_attributes(attributes))
 }
 
+/// Admin function to mirror every position by an NFT of the given cw721 contract. The contract should allow only
+/// the auction to mint NFTs. Can be set only once, before the first deposit, and only if the allowlist is disabled.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **contract** is an object of type [`String`]. Position NFT contract address.
+pub fn handle_set_position_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: ONLY OWNER CAN CALL THIS FUNCTION
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    if config.position_nft.is_some() {
+        return Err(StdError::generic_err("Position NFT contract already set"));
+    }
+
+    // CHECK :: Position NFTs can be sold to any address, bypassing the allowlist
+    if config.allowlist_merkle_root.is_some() {
+        return Err(StdError::generic_err(
+            "Position NFTs can't be enabled together with the allowlist",
+        ));
+    }
+
+    // CHECK :: Existing positions can't be mirrored
+    if USERS
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Err(StdError::generic_err(
+            "Position NFTs can be enabled only before the first deposit",
+        ));
+    }
+
+    let position_nft = addr_validate_to_lower(deps.api, &contract)?;
+    assert_position_nft_minter(&deps.querier, &position_nft, &env.contract.address)?;
+
+    config.position_nft = Some(position_nft.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "Auction::ExecuteMsg::SetPositionNft"),
+        attr("position_nft", position_nft),
+    ]))
+}
+
+/// Returns a message which mints the position NFT of a new position to the user. If position NFTs are enabled
+/// and the position already exists, checks that the user still owns its NFT.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **user_address** is an object of type [`Addr`].
+///
+/// * **is_new_position** is an object of type [`bool`].
+fn build_position_nft_msgs(
+    deps: Deps,
+    config: &Config,
+    user_address: &Addr,
+    is_new_position: bool,
+) -> StdResult<Vec<CosmosMsg>> {
+    match &config.position_nft {
+        Some(position_nft) if is_new_position => Ok(vec![build_mint_position_nft_msg(
+            position_nft,
+            user_address.to_string(),
+            user_address,
+        )?]),
+        Some(position_nft) => {
+            if query_position_nft_owner(&deps.querier, position_nft, user_address.to_string())?
+                != *user_address
+            {
+                return Err(StdError::generic_err(
+                    "Position NFT is owned by another address",
+                ));
+            }
+            Ok(vec![])
+        }
+        None => Ok(vec![]),
+    }
+}
+
+/// Returns the address of the position which the sender manages. If position NFTs are enabled, the sender
+/// should own the NFT of the position.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **sender** is an object of type [`Addr`].
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Position address, defaults to the sender.
+fn resolve_position_address(
+    deps: Deps,
+    config: &Config,
+    sender: &Addr,
+    user_address: Option<String>,
+) -> StdResult<Addr> {
+    let user_address = user_address
+        .map(|v| addr_validate_to_lower(deps.api, &v))
+        .transpose()?
+        .unwrap_or_else(|| sender.clone());
+
+    assert_position_owner(
+        &deps.querier,
+        &config.position_nft,
+        user_address.to_string(),
+        &user_address,
+        sender,
+    )?;
+
+    Ok(user_address)
+}
+
+/// Returns the address of the position from which UST is withdrawn and the address which receives the UST.
+/// Position owners receive UST themselves, while UST withdrawn by an approved operator is sent to the position owner.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **sender** is an object of type [`Addr`].
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Position address, defaults to the sender.
+fn resolve_withdrawal_addresses(
+    deps: Deps,
+    config: &Config,
+    sender: &Addr,
+    user_address: Option<String>,
+) -> StdResult<(Addr, Addr)> {
+    let user_address = user_address
+        .map(|v| addr_validate_to_lower(deps.api, &v))
+        .transpose()?
+        .unwrap_or_else(|| sender.clone());
+
+    let owner = match &config.position_nft {
+        Some(position_nft) => {
+            query_position_nft_owner(&deps.querier, position_nft, user_address.to_string())?
+        }
+        None => user_address.clone(),
+    };
+
+    if owner == *sender || OPERATORS.has(deps.storage, (&owner, sender)) {
+        Ok((user_address, owner))
+    } else {
+        Err(StdError::generic_err("Sender doesn't own the position"))
+    }
+}
+
+/// Allows the operator to withdraw UST on behalf of the sender. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **operator** is an object of type [`String`].
+pub fn handle_approve_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> StdResult<Response> {
+    let operator = addr_validate_to_lower(deps.api, &operator)?;
+
+    if operator == info.sender {
+        return Err(StdError::generic_err(
+            "Operator should differ from the sender",
+        ));
+    }
+
+    OPERATORS.save(deps.storage, (&info.sender, &operator), &Empty {})?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "Auction::ExecuteMsg::ApproveOperator"),
+        attr("user", info.sender.to_string()),
+        attr("operator", operator.to_string()),
+    ]))
+}
+
+/// Revokes the operator's allowance to withdraw UST on behalf of the sender. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **operator** is an object of type [`String`].
+pub fn handle_revoke_operator(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> StdResult<Response> {
+    let operator = addr_validate_to_lower(deps.api, &operator)?;
+
+    if !OPERATORS.has(deps.storage, (&info.sender, &operator)) {
+        return Err(StdError::generic_err("Operator isn't approved"));
+    }
+
+    OPERATORS.remove(deps.storage, (&info.sender, &operator));
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "Auction::ExecuteMsg::RevokeOperator"),
+        attr("user", info.sender.to_string()),
+        attr("operator", operator.to_string()),
+    ]))
+}
+
 /// Increases ASTRO incentives. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
@@ -345,14 +880,20 @@ pub fn handle_increasing_astro_incentives(
 ///
 /// * **env** is an object of type [`Env`].
 ///
+/// * **delegator** is an object of type [`String`]. Airdrop or lockdrop contract which delegates ASTRO.
+///
 /// * **user_address** is an object of type [`String`].
 ///
 /// * **amount** is an object of type [`Uint128`].
+///
+/// * **allowlist_proof** is an [`Option`] of type [`AllowlistProof`]. Proof that the user is allowlisted.
 pub fn handle_delegate_astro_tokens(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
+    delegator: String,
     user_address: String,
     amount: Uint128,
+    allowlist_proof: Option<AllowlistProof>,
 ) -> Result<Response, StdError> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -363,24 +904,38 @@ pub fn handle_delegate_astro_tokens(
         return Err(StdError::generic_err("Deposit window closed"));
     }
 
+    assert_allowlisted(deps.storage, &config, &user_address, allowlist_proof)?;
+
     let mut state = STATE.load(deps.storage)?;
-    let mut user_info = USERS
-        .may_load(deps.storage, &user_address)?
-        .unwrap_or_default();
+    let user_info = USERS.compatible_may_load_mut(deps.branch(), &user_address)?;
+    let position_nft_msgs =
+        build_position_nft_msgs(deps.as_ref(), &config, &user_address, user_info.is_none())?;
+    let mut user_info = user_info.unwrap_or_default();
+
+    let weighted_amount = calculate_weighted_amount(env.block.time.seconds(), &config, amount);
 
     // UPDATE STATE
     state.total_astro_delegated += amount;
+    state.total_astro_weighted += weighted_amount;
     user_info.astro_delegated += amount;
+    user_info.astro_weighted += weighted_amount;
+    if delegator == config.airdrop_contract_address {
+        user_info.airdrop_astro_delegated += amount;
+    } else {
+        user_info.lockdrop_astro_delegated += amount;
+    }
 
     // SAVE UPDATED STATE
     STATE.save(deps.storage, &state)?;
     USERS.save(deps.storage, &user_address, &user_info)?;
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "Auction::ExecuteMsg::DelegateAstroTokens"),
-        attr("user", user_address.to_string()),
-        attr("astro_delegated", amount),
-    ]))
+    Ok(Response::new()
+        .add_messages(position_nft_msgs)
+        .add_attributes(vec![
+            attr("action", "Auction::ExecuteMsg::DelegateAstroTokens"),
+            attr("user", user_address.to_string()),
+            attr("astro_delegated", amount),
+        ]))
 }
 
 /// Facilitates UST deposits by users. Returns a default object of type [`Response`].
@@ -390,22 +945,36 @@ pub fn handle_delegate_astro_tokens(
 /// * **env** is an object of type [`Env`].
 ///
 /// * **info** is an object of type [`MessageInfo`].
+///
+/// * **beneficiary** is an [`Option`] of type [`String`]. Address credited with the deposit, defaults to the sender.
+///
+/// * **allowlist_proof** is an [`Option`] of type [`AllowlistProof`]. Proof that the beneficiary is allowlisted.
 pub fn handle_deposit_ust(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    beneficiary: Option<String>,
+    allowlist_proof: Option<AllowlistProof>,
 ) -> Result<Response, StdError> {
     let config = CONFIG.load(deps.storage)?;
 
+    let beneficiary = beneficiary
+        .map(|v| addr_validate_to_lower(deps.api, &v))
+        .transpose()?
+        .unwrap_or_else(|| info.sender.clone());
+
     // CHECK :: Auction deposit window open
     if !is_deposit_open(env.block.time.seconds(), &config) {
         return Err(StdError::generic_err("Deposit window closed"));
     }
 
+    let allowlist_cap = assert_allowlisted(deps.storage, &config, &beneficiary, allowlist_proof)?;
+
     let mut state = STATE.load(deps.storage)?;
-    let mut user_info = USERS
-        .may_load(deps.storage, &info.sender)?
-        .unwrap_or_default();
+    let user_info = USERS.compatible_may_load_mut(deps.branch(), &beneficiary)?;
+    let position_nft_msgs =
+        build_position_nft_msgs(deps.as_ref(), &config, &beneficiary, user_info.is_none())?;
+    let mut user_info = user_info.unwrap_or_default();
 
     // Retrieve UST sent by the user
     if info.funds.len() != 1 || info.funds[0].denom != UUSD_DENOM {
@@ -421,52 +990,127 @@ pub fn handle_deposit_ust(
         return Err(StdError::generic_err("Amount must be greater than 0"));
     }
 
+    let weighted_amount = calculate_weighted_amount(env.block.time.seconds(), &config, fund.amount);
+
     // UPDATE STATE
     state.total_ust_delegated += fund.amount;
+    state.total_ust_weighted += weighted_amount;
     user_info.ust_delegated += fund.amount;
+    user_info.ust_weighted += weighted_amount;
+
+    // CHECK :: Allowlisted users can't deposit more than their caps
+    if let Some(cap) = allowlist_cap {
+        if user_info.ust_delegated > cap {
+            return Err(StdError::generic_err("Deposit exceeds the allowlist cap"));
+        }
+    }
 
     // SAVE UPDATED STATE
     STATE.save(deps.storage, &state)?;
-    USERS.save(deps.storage, &info.sender, &user_info)?;
+    USERS.save(deps.storage, &beneficiary, &user_info)?;
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "Auction::ExecuteMsg::DelegateUst"),
-        attr("user", info.sender.to_string()),
-        attr("ust_delegated", fund.amount),
-    ]))
+    Ok(Response::new()
+        .add_messages(position_nft_msgs)
+        .add_attributes(vec![
+            attr("action", "Auction::ExecuteMsg::DelegateUst"),
+            attr("user", beneficiary.to_string()),
+            attr("depositor", info.sender.to_string()),
+            attr("ust_delegated", fund.amount),
+        ]))
 }
 
-/// Returns a boolean value indicating if the deposit is open.
+/// Checks that the address is allowlisted if the allowlist is enabled. A verified address is cached, so the proof
+/// is required only once. Returns the UST deposit cap of the address or [`None`] if the allowlist is disabled.
 /// ## Params
-/// * **current_timestamp** is an object of type [`u64`].
+/// * **storage** is an object of type [`Storage`].
 ///
 /// * **config** is an object of type [`Config`].
-fn is_deposit_open(current_timestamp: u64, config: &Config) -> bool {
-    current_timestamp >= config.init_timestamp
-        && current_timestamp < config.init_timestamp + config.deposit_window
-}
-
-/// Facilitates UST withdrawals by users. Returns a default object of type [`Response`].
-/// ## Params
-/// * **deps** is an object of type [`DepsMut`].
-///
-/// * **env** is an object of type [`Env`].
 ///
-/// * **info** is an object of type [`MessageInfo`].
+/// * **address** is an object of type [`Addr`].
 ///
-/// * **amount** is an object of type [`Uint128`].
-pub fn handle_withdraw_ust(
-    deps: DepsMut,
+/// * **allowlist_proof** is an [`Option`] of type [`AllowlistProof`].
+fn assert_allowlisted(
+    storage: &mut dyn Storage,
+    config: &Config,
+    address: &Addr,
+    allowlist_proof: Option<AllowlistProof>,
+) -> StdResult<Option<Uint128>> {
+    let merkle_root = match &config.allowlist_merkle_root {
+        Some(merkle_root) => merkle_root,
+        None => return Ok(None),
+    };
+
+    if let Some(cap) = ALLOWLIST.may_load(storage, address)? {
+        return Ok(Some(cap));
+    }
+
+    let proof =
+        allowlist_proof.ok_or_else(|| StdError::generic_err("Allowlist proof is required"))?;
+
+    if !verify_merkle_proof(address, proof.cap, proof.merkle_proof, merkle_root)? {
+        return Err(StdError::generic_err("Incorrect allowlist proof"));
+    }
+
+    ALLOWLIST.save(storage, address, &proof.cap)?;
+
+    Ok(Some(proof.cap))
+}
+
+/// Returns a boolean value indicating if the deposit is open.
+/// ## Params
+/// * **current_timestamp** is an object of type [`u64`].
+///
+/// * **config** is an object of type [`Config`].
+fn is_deposit_open(current_timestamp: u64, config: &Config) -> bool {
+    current_timestamp >= config.init_timestamp
+        && current_timestamp < config.init_timestamp + config.deposit_window
+}
+
+/// Returns the deposit amount scored by the time left in the deposit window.
+/// Weighted amount = amount * (1 + max_bonus * time left / deposit window)
+/// ## Params
+/// * **current_timestamp** is an object of type [`u64`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **amount** is an object of type [`Uint128`].
+fn calculate_weighted_amount(current_timestamp: u64, config: &Config, amount: Uint128) -> Uint128 {
+    match &config.time_weight {
+        Some(TimeWeight { max_bonus }) => {
+            let deposit_window_end = config.init_timestamp + config.deposit_window;
+            let time_left = deposit_window_end.saturating_sub(current_timestamp);
+            amount + amount * (*max_bonus * Decimal::from_ratio(time_left, config.deposit_window))
+        }
+        None => amount,
+    }
+}
+
+/// Facilitates UST withdrawals by users. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **amount** is an object of type [`Uint128`].
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Position address, defaults to the sender.
+/// The sender should own the position or be an operator approved by its owner.
+pub fn handle_withdraw_ust(
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     amount: Uint128,
+    user_address: Option<String>,
 ) -> Result<Response, StdError> {
     let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
 
-    let user_address = info.sender;
+    let (user_address, recipient) =
+        resolve_withdrawal_addresses(deps.as_ref(), &config, &info.sender, user_address)?;
 
-    let mut user_info = USERS.load(deps.storage, &user_address)?;
+    let mut user_info = USERS.compatible_load_mut(deps.branch(), &user_address)?;
 
     // CHECK :: Has the user already withdrawn during the current window
     if user_info.ust_withdrawn {
@@ -489,9 +1133,20 @@ pub fn handle_withdraw_ust(
         user_info.ust_withdrawn = true;
     }
 
+    // Weighted amount is reduced proportionally to the withdrawn UST
+    let weighted_amount = if user_info.ust_delegated.is_zero() {
+        Uint128::zero()
+    } else {
+        user_info
+            .ust_weighted
+            .multiply_ratio(amount, user_info.ust_delegated)
+    };
+
     // UPDATE STATE
     state.total_ust_delegated -= amount;
+    state.total_ust_weighted -= weighted_amount;
     user_info.ust_delegated -= amount;
+    user_info.ust_weighted -= weighted_amount;
 
     // SAVE UPDATED STATE
     STATE.save(deps.storage, &state)?;
@@ -512,7 +1167,124 @@ pub fn handle_withdraw_ust(
             attr("ust_withdrawn", amount),
             attr("ust_commission", transfer_ust.compute_tax(&deps.querier)?),
         ])
-        .add_message(transfer_ust.into_msg(&deps.querier, user_address)?))
+        .add_message(transfer_ust.into_msg(&deps.querier, recipient)?))
+}
+
+/// Returns delegated ASTRO to the airdrop and lockdrop contracts following the same withdrawal limits as UST
+/// withdrawals. ASTRO delegated through the airdrop contract is undelegated first. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **amount** is an object of type [`Uint128`].
+pub fn handle_undelegate_astro(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // CHECK :: Undelegated ASTRO is returned to the delegator, so the delegator should still own the position
+    assert_position_owner(
+        &deps.querier,
+        &config.position_nft,
+        info.sender.to_string(),
+        &info.sender,
+        &info.sender,
+    )?;
+
+    let mut user_info = USERS.compatible_load_mut(deps.branch(), &info.sender)?;
+
+    // CHECK ::: Amount needs to be valid
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Amount must be greater than 0"));
+    }
+
+    // CHECK :: Has the user already undelegated during the current window
+    if user_info.astro_undelegated {
+        return Err(StdError::generic_err("Max 1 withdrawal allowed"));
+    }
+
+    // Check :: Amount should be within the allowed withdrawal limit bounds
+    let max_withdrawal_percent = allowed_withdrawal_percent(env.block.time.seconds(), &config);
+    let max_withdrawal_allowed = user_info.astro_delegated * max_withdrawal_percent;
+
+    if amount > max_withdrawal_allowed {
+        return Err(StdError::generic_err(format!(
+            "Amount exceeds maximum allowed withdrawal limit of {}",
+            max_withdrawal_percent
+        )));
+    }
+
+    if amount > user_info.airdrop_astro_delegated + user_info.lockdrop_astro_delegated {
+        return Err(StdError::generic_err(
+            "Amount exceeds ASTRO delegated by the user",
+        ));
+    }
+
+    // After deposit window is closed, we allow to undelegate only once
+    if env.block.time.seconds() >= config.init_timestamp + config.deposit_window {
+        user_info.astro_undelegated = true;
+    }
+
+    // Weighted amount is reduced proportionally to the undelegated ASTRO
+    let weighted_amount = user_info
+        .astro_weighted
+        .multiply_ratio(amount, user_info.astro_delegated);
+
+    let airdrop_amount = amount.min(user_info.airdrop_astro_delegated);
+    let lockdrop_amount = amount - airdrop_amount;
+
+    // UPDATE STATE
+    state.total_astro_delegated -= amount;
+    state.total_astro_weighted -= weighted_amount;
+    user_info.astro_delegated -= amount;
+    user_info.astro_weighted -= weighted_amount;
+    user_info.airdrop_astro_delegated -= airdrop_amount;
+    user_info.lockdrop_astro_delegated -= lockdrop_amount;
+
+    // SAVE UPDATED STATE
+    STATE.save(deps.storage, &state)?;
+    USERS.save(deps.storage, &info.sender, &user_info)?;
+
+    // COSMOS MSGS :: Return ASTRO to the contracts it was delegated from
+    let mut cosmos_msgs = vec![];
+
+    if !airdrop_amount.is_zero() {
+        cosmos_msgs.push(build_send_cw20_token_msg(
+            config.airdrop_contract_address.to_string(),
+            config.astro_token_address.to_string(),
+            airdrop_amount,
+            to_binary(&AirdropUndelegateAstroTokens {
+                user_address: info.sender.to_string(),
+            })?,
+        )?);
+    }
+
+    if !lockdrop_amount.is_zero() {
+        cosmos_msgs.push(build_send_cw20_token_msg(
+            config.lockdrop_contract_address.to_string(),
+            config.astro_token_address.to_string(),
+            lockdrop_amount,
+            to_binary(&LockdropUndelegateAstroTokens {
+                user_address: info.sender.to_string(),
+            })?,
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "Auction::ExecuteMsg::UndelegateAstro"),
+            attr("user", info.sender.to_string()),
+            attr("airdrop_astro_undelegated", airdrop_amount),
+            attr("lockdrop_astro_undelegated", lockdrop_amount),
+        ]))
 }
 
 /// Allow withdrawal percent. Returns a default object of type [`Response`].
@@ -557,21 +1329,14 @@ fn allowed_withdrawal_percent(current_timestamp: u64, config: &Config) -> Decima
 /// * **env** is an object of type [`Env`].
 ///
 /// * **info** is an object of type [`MessageInfo`].
-///
-/// * **slippage** is an optional object of type [`Decimal`].
-pub fn handle_init_pool(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    slippage: Option<Decimal>,
-) -> Result<Response, StdError> {
-    let config = CONFIG.load(deps.storage)?;
-    let state = STATE.load(deps.storage)?;
+pub fn handle_init_pool(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, StdError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
 
-    // CHECK :: Only admin can call this function
-    if info.sender != config.owner {
-        return Err(StdError::generic_err("Unauthorized"));
-    }
+    // CHECK :: Only admin can call this function before the keeper grace period ends
+    let windows_end_timestamp =
+        config.init_timestamp + config.deposit_window + config.withdrawal_window;
+    let is_keeper = assert_owner_or_keeper(&env, &info.sender, &config, windows_end_timestamp)?;
 
     // CHECK :: Can be executed once
     if state.lp_shares_minted.is_some() {
@@ -585,12 +1350,35 @@ pub fn handle_init_pool(
         ));
     }
 
+    // Pay the keeper bounty from the auction incentives before they are distributed among users. The staking bounty
+    // is reserved and users' incentives aren't final until LP Tokens are staked, as it's paid only if a keeper stakes
     let mut msgs = vec![];
+    let mut keeper_bounty = Uint128::zero();
+    if let Some(keeper) = &config.keeper {
+        let mut astro_incentive_amount = config.astro_incentive_amount.unwrap_or_default();
+        if is_keeper {
+            keeper_bounty = keeper.bounty.min(astro_incentive_amount);
+            astro_incentive_amount -= keeper_bounty;
+        }
+        state.keeper_bounty_reserve = keeper.bounty.min(astro_incentive_amount);
+
+        if config.astro_incentive_amount.is_some() {
+            config.astro_incentive_amount = Some(astro_incentive_amount);
+        }
+    }
+
+    if !keeper_bounty.is_zero() {
+        msgs.push(build_transfer_cw20_token_msg(
+            info.sender.clone(),
+            config.astro_token_address.to_string(),
+            keeper_bounty,
+        )?);
+    }
 
     if let Some(PoolInfo {
         astro_ust_pool_address,
         astro_ust_lp_token_address,
-    }) = config.pool_info
+    }) = config.pool_info.clone()
     {
         let ust_coin = deps
             .querier
@@ -616,11 +1404,11 @@ pub fn handle_init_pool(
 
         msgs.push(build_provide_liquidity_to_lp_pool_msg(
             deps.as_ref(),
-            config.astro_token_address,
+            config.astro_token_address.clone(),
             astro_ust_pool_address,
             ust_coin.amount,
             state.total_astro_delegated,
-            slippage,
+            config.init_pool_slippage,
         )?);
 
         msgs.push(
@@ -629,10 +1417,15 @@ pub fn handle_init_pool(
             }
             .to_cosmos_msg(&env)?,
         );
+
+        CONFIG.save(deps.storage, &config)?;
+        STATE.save(deps.storage, &state)?;
+
         Ok(Response::new().add_messages(msgs).add_attributes(vec![
             attr("action", "Auction::ExecuteMsg::AddLiquidityToAstroportPool"),
             attr("astro_provided", state.total_astro_delegated),
             attr("ust_provided", ust_coin.amount),
+            attr("keeper_bounty", keeper_bounty),
         ]))
     } else {
         Err(StdError::generic_err("Pool info isn't set yet!"))
@@ -660,6 +1453,39 @@ fn build_provide_liquidity_to_lp_pool_msg(
     astro_amount: Uint128,
     slippage_tolerance: Option<Decimal>,
 ) -> StdResult<CosmosMsg> {
+    let [ust, astro] =
+        build_provided_liquidity_assets(deps, astro_token_address, ust_amount, astro_amount)?;
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: astro_ust_pool_address.to_string(),
+        funds: vec![Coin {
+            denom: String::from(UUSD_DENOM),
+            amount: ust.amount,
+        }],
+        msg: to_binary(&astroport::pair::ExecuteMsg::ProvideLiquidity {
+            assets: [ust, astro],
+            slippage_tolerance,
+            auto_stake: None,
+            receiver: None,
+        })?,
+    }))
+}
+
+/// Returns UST and ASTRO assets which are provided to the ASTRO-UST Pool. Tax is deducted from the UST amount.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **astro_token_address** is an object of type [`Addr`].
+///
+/// * **ust_amount** is an object of type [`Uint128`].
+///
+/// * **astro_amount** is an object of type [`Uint128`].
+fn build_provided_liquidity_assets(
+    deps: Deps,
+    astro_token_address: Addr,
+    ust_amount: Uint128,
+    astro_amount: Uint128,
+) -> StdResult<[Asset; 2]> {
     let astro = Asset {
         amount: astro_amount,
         info: AssetInfo::Token {
@@ -677,19 +1503,7 @@ fn build_provide_liquidity_to_lp_pool_msg(
     // Deduct tax
     ust.amount = ust.amount.checked_sub(ust.compute_tax(&deps.querier)?)?;
 
-    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: astro_ust_pool_address.to_string(),
-        funds: vec![Coin {
-            denom: String::from(UUSD_DENOM),
-            amount: ust.amount,
-        }],
-        msg: to_binary(&astroport::pair::ExecuteMsg::ProvideLiquidity {
-            assets: [ust, astro],
-            slippage_tolerance,
-            auto_stake: None,
-            receiver: None,
-        })?,
-    }))
+    Ok([ust, astro])
 }
 
 /// Stakes ASTRO-UST LP tokens with the generator contract.
@@ -704,16 +1518,15 @@ pub fn handle_stake_lp_tokens(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, StdError> {
-    let config = CONFIG.load(deps.storage)?;
+    let mut config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
 
-    // CHECK :: Only admin can call this function
-    if info.sender != config.owner {
-        return Err(StdError::generic_err("Unauthorized"));
-    }
+    // CHECK :: Only admin can call this function before the keeper grace period ends
+    let is_keeper = assert_owner_or_keeper(&env, &info.sender, &config, state.pool_init_timestamp)?;
 
     let generator = config
         .generator_contract
+        .clone()
         .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
 
     // CHECK :: Can be staked only once
@@ -728,7 +1541,7 @@ pub fn handle_stake_lp_tokens(
     if let Some(PoolInfo {
         astro_ust_lp_token_address,
         astro_ust_pool_address: _,
-    }) = config.pool_info
+    }) = config.pool_info.clone()
     {
         // QUERY CURRENT LP TOKEN BALANCE (FOR SAFETY - IN ANY CASE)
         let cur_lp_balance = query_token_balance(
@@ -761,6 +1574,25 @@ pub fn handle_stake_lp_tokens(
             })?,
         }));
 
+        // COSMOS MSG :: Pay the reserved bounty from the auction incentives to the keeper. The reserve stays in
+        // the auction incentives if the owner stakes LP Tokens
+        if is_keeper && !state.keeper_bounty_reserve.is_zero() {
+            config.astro_incentive_amount = config
+                .astro_incentive_amount
+                .map(|amount| amount - state.keeper_bounty_reserve);
+            CONFIG.save(deps.storage, &config)?;
+
+            response
+                .messages
+                .push(SubMsg::new(build_transfer_cw20_token_msg(
+                    info.sender,
+                    config.astro_token_address.to_string(),
+                    state.keeper_bounty_reserve,
+                )?));
+            response = response.add_attribute("keeper_bounty", state.keeper_bounty_reserve);
+        }
+
+        state.keeper_bounty_reserve = Uint128::zero();
         state.is_lp_staked = true;
         STATE.save(deps.storage, &state)?;
 
@@ -770,7 +1602,8 @@ pub fn handle_stake_lp_tokens(
     }
 }
 
-/// Facilitates ASTRO Reward claim for users.
+/// Admin function to move ASTRO-UST LP Tokens staked by the contract to another generator. Pending rewards are
+/// claimed from the current generator before all LP Tokens are unstaked and restaked in the new one.
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
@@ -778,139 +1611,1120 @@ pub fn handle_stake_lp_tokens(
 ///
 /// * **info** is an object of type [`MessageInfo`].
 ///
-/// * **withdraw_lp_shares** is an optional object of type [`Uint128`].
-pub fn handle_claim_rewards_and_withdraw_lp_shares(
+/// * **generator** is an object of type [`String`]. New generator contract address.
+pub fn handle_migrate_generator(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    withdraw_lp_shares: Option<Uint128>,
-) -> Result<Response, StdError> {
+    generator: String,
+) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
-    let user_address = info.sender;
-    let mut user_info = USERS.load(deps.storage, &user_address)?;
 
-    // CHECK :: User has valid delegation / deposit balances
-    if user_info.astro_delegated.is_zero() && user_info.ust_delegated.is_zero() {
-        return Err(StdError::generic_err("No delegated assets"));
+    // CHECK :: Only admin can call this function
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
     }
 
-    let mut cosmos_msgs = vec![];
+    // CHECK :: LP Tokens should be staked with the generator
+    if !state.is_lp_staked {
+        return Err(StdError::generic_err("LP Tokens aren't staked yet!"));
+    }
 
-    if let Some(lp_balance) = state.lp_shares_minted {
-        // Calculate user's LP shares & ASTRO incentives (if possible)
-        if user_info.lp_shares.is_none() {
-            update_user_lp_shares(&state, lp_balance, &mut user_info)?;
-            update_user_astro_incentives(
-                config.astro_incentive_amount,
-                user_info.lp_shares,
-                lp_balance,
-                &mut user_info,
-            )?;
-            USERS.save(deps.storage, &user_address, &user_info)?;
-        }
-        // If user's ASTRO incentives are not set, but the total ASTRO incentives have been set
-        if config.astro_incentive_amount.is_some() && user_info.auction_incentive_amount.is_none() {
-            update_user_astro_incentives(
-                config.astro_incentive_amount,
-                user_info.lp_shares,
-                lp_balance,
-                &mut user_info,
-            )?;
-            USERS.save(deps.storage, &user_address, &user_info)?;
-        }
+    let current_generator = config
+        .generator_contract
+        .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+    let new_generator = addr_validate_to_lower(deps.api, &generator)?;
 
-        // If user wants to withdraw LP tokens, then we calculate the max amount he can withdraw for check
-        if let Some(withdraw_lp_shares) = withdraw_lp_shares {
-            let max_withdrawable = calculate_withdrawable_lp_shares(
-                env.block.time.seconds(),
-                &config,
-                &state,
-                &user_info,
-            )?;
-            if max_withdrawable.is_none() || withdraw_lp_shares > max_withdrawable.unwrap() {
-                return Err(StdError::generic_err(
-                    "No available LP shares to withdraw / Invalid amount",
-                ));
-            }
-        }
+    if new_generator == current_generator {
+        return Err(StdError::generic_err(
+            "New generator should differ from the current one",
+        ));
+    }
 
-        if state.is_lp_staked {
-            let generator = config
-                .generator_contract
-                .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+    if let Some(PoolInfo {
+        astro_ust_pool_address: _,
+        astro_ust_lp_token_address,
+    }) = config.pool_info
+    {
+        let lp_amount: Uint128 = deps.querier.query_wasm_smart(
+            &current_generator,
+            &GenQueryMsg::Deposit {
+                lp_token: astro_ust_lp_token_address.to_string(),
+                user: env.contract.address.to_string(),
+            },
+        )?;
 
-            if let Some(PoolInfo {
-                astro_ust_pool_address: _,
-                astro_ust_lp_token_address,
-            }) = config.pool_info
-            {
-                // QUERY :: Check if there are any pending staking rewards
-                let pending_rewards: PendingTokenResponse = deps.querier.query_wasm_smart(
-                    &generator,
-                    &GenQueryMsg::PendingToken {
-                        lp_token: astro_ust_lp_token_address.to_string(),
-                        user: env.contract.address.to_string(),
-                    },
-                )?;
+        let mut cosmos_msgs = build_claim_generator_rewards_msgs(
+            deps.as_ref(),
+            &env,
+            &current_generator,
+            &astro_ust_lp_token_address,
+        )?;
+        cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: current_generator.to_string(),
+            funds: vec![],
+            msg: to_binary(&GenExecuteMsg::Withdraw {
+                lp_token: astro_ust_lp_token_address.to_string(),
+                amount: lp_amount,
+            })?,
+        }));
+        cosmos_msgs.push(
+            CallbackMsg::MigrateGeneratorCallback {
+                generator: new_generator.clone(),
+                lp_amount,
+            }
+            .to_cosmos_msg(&env)?,
+        );
+
+        Ok(Response::new()
+            .add_messages(cosmos_msgs)
+            .add_attributes(vec![
+                attr("action", "Auction::ExecuteMsg::MigrateGenerator"),
+                attr("old_generator", current_generator),
+                attr("new_generator", new_generator),
+                attr("lp_amount", lp_amount),
+            ]))
+    } else {
+        Err(StdError::generic_err("Pool info isn't set yet!"))
+    }
+}
+
+/// Admin function to unstake all ASTRO-UST LP Tokens from the generator without claiming pending rewards.
+/// The call must not depend on the misbehaving generator, so rewards pending in it are forfeited and
+/// the reward indexes are left unchanged. LP Tokens are kept in the contract, so users can still
+/// withdraw vested LP shares and rewards settled before (e.g. with [`handle_migrate_generator`] or any user claim).
+/// The generator is unset, so LP Tokens can be staked again with [`handle_stake_lp_tokens`] once a new one is set.
+/// If LP Tokens aren't staked yet, the generator is unset and the keeper bounty reserved for staking is
+/// released to users, so their ASTRO incentives don't stay frozen while staking isn't possible.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+pub fn handle_emergency_unstake(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    // CHECK :: Only admin can call this function
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    // LP Tokens can't be staked, so the generator is unset and the staking bounty is released to users
+    if !state.is_lp_staked {
+        if state.keeper_bounty_reserve.is_zero() {
+            return Err(StdError::generic_err("LP Tokens aren't staked yet!"));
+        }
+
+        let keeper_bounty_released = state.keeper_bounty_reserve;
+        state.keeper_bounty_reserve = Uint128::zero();
+        config.generator_contract = None;
+        STATE.save(deps.storage, &state)?;
+        CONFIG.save(deps.storage, &config)?;
+
+        return Ok(Response::new().add_attributes(vec![
+            attr("action", "Auction::ExecuteMsg::EmergencyUnstake"),
+            attr("keeper_bounty_released", keeper_bounty_released),
+        ]));
+    }
+
+    let generator = config
+        .generator_contract
+        .take()
+        .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+
+    if let Some(PoolInfo {
+        astro_ust_pool_address: _,
+        astro_ust_lp_token_address,
+    }) = &config.pool_info
+    {
+        state.is_lp_staked = false;
+        state.keeper_bounty_reserve = Uint128::zero();
+        STATE.save(deps.storage, &state)?;
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_message(WasmMsg::Execute {
+                contract_addr: generator.to_string(),
+                funds: vec![],
+                msg: to_binary(&GenExecuteMsg::EmergencyWithdraw {
+                    lp_token: astro_ust_lp_token_address.to_string(),
+                })?,
+            })
+            .add_attributes(vec![
+                attr("action", "Auction::ExecuteMsg::EmergencyUnstake"),
+                attr("generator", generator),
+            ]))
+    } else {
+        Err(StdError::generic_err("Pool info isn't set yet!"))
+    }
+}
+
+/// Facilitates ASTRO Reward claim for users.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **withdraw_lp_shares** is an optional object of type [`Uint128`].
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Position address, defaults to the sender.
+pub fn handle_claim_rewards_and_withdraw_lp_shares(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    withdraw_lp_shares: Option<Uint128>,
+    user_address: Option<String>,
+) -> Result<Response, StdError> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let user_address =
+        resolve_position_address(deps.as_ref(), &config, &info.sender, user_address)?;
+    let mut user_info = USERS.compatible_load_mut(deps.branch(), &user_address)?;
+
+    // CHECK :: User has valid delegation / deposit balances
+    if user_info.astro_delegated.is_zero() && user_info.ust_delegated.is_zero() {
+        return Err(StdError::generic_err("No delegated assets"));
+    }
+
+    let mut cosmos_msgs = vec![];
+
+    if let Some(lp_balance) = state.lp_shares_minted {
+        // Calculate user's LP shares & ASTRO incentives (if possible)
+        if user_info.lp_shares.is_none() {
+            update_user_lp_shares(&state, lp_balance, &mut user_info)?;
+            update_user_astro_incentives(&config, &state, &mut user_info)?;
+            USERS.save(deps.storage, &user_address, &user_info)?;
+        }
+        // If user's ASTRO incentives are not set, but the total ASTRO incentives have been set
+        if config.astro_incentive_amount.is_some() && user_info.auction_incentive_amount.is_none() {
+            update_user_astro_incentives(&config, &state, &mut user_info)?;
+            USERS.save(deps.storage, &user_address, &user_info)?;
+        }
+
+        // If user wants to withdraw LP tokens, then we calculate the max amount he can withdraw for check
+        if let Some(withdraw_lp_shares) = withdraw_lp_shares {
+            let max_withdrawable = calculate_withdrawable_lp_shares(
+                env.block.time.seconds(),
+                &config,
+                &state,
+                &user_info,
+            )?;
+            if max_withdrawable.map_or(true, |max_withdrawable| {
+                withdraw_lp_shares > max_withdrawable
+            }) {
+                return Err(StdError::generic_err(
+                    "No available LP shares to withdraw / Invalid amount",
+                ));
+            }
+        }
+
+        if state.is_lp_staked {
+            let generator = config
+                .generator_contract
+                .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+
+            if let Some(PoolInfo {
+                astro_ust_pool_address: _,
+                astro_ust_lp_token_address,
+            }) = config.pool_info
+            {
+                cosmos_msgs.extend(build_claim_generator_rewards_msgs(
+                    deps.as_ref(),
+                    &env,
+                    &generator,
+                    &astro_ust_lp_token_address,
+                )?);
+            } else {
+                return Err(StdError::generic_err("Pool info isn't set yet!"));
+            }
+        }
+        // If LP tokens have never been staked, no rewards to claim and no LP tokens to be withdrawn.
+        else if state.generator_astro_per_share.is_zero()
+            && calculate_claimable_incentives(env.block.time.seconds(), &config, &state, &user_info)
+                .is_zero()
+            && withdraw_lp_shares.is_none()
+        {
+            return Err(StdError::generic_err(
+                "Rewards already claimed. Provide number of LP tokens to claim!",
+            ));
+        }
+    } else {
+        return Err(StdError::generic_err(
+            "Astro/USD should be provided to the pool!",
+        ));
+    };
+
+    cosmos_msgs.push(
+        CallbackMsg::WithdrawUserRewardsCallback {
+            user_address,
+            recipient: info.sender,
+            withdraw_lp_shares,
+        }
+        .to_cosmos_msg(&env)?,
+    );
+
+    Ok(Response::new().add_messages(cosmos_msgs))
+}
+
+/// Builds messages which claim pending generator rewards of the contract and update the state with the received amounts.
+/// Returns an empty vector if there are no pending rewards.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **generator** is an object of type [`Addr`]. Astroport Generator contract address.
+///
+/// * **astro_ust_lp_token_address** is an object of type [`Addr`].
+fn build_claim_generator_rewards_msgs(
+    deps: Deps,
+    env: &Env,
+    generator: &Addr,
+    astro_ust_lp_token_address: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut cosmos_msgs = vec![];
+
+    // QUERY :: Check if there are any pending staking rewards
+    let pending_rewards: PendingTokenResponse = deps.querier.query_wasm_smart(
+        generator,
+        &GenQueryMsg::PendingToken {
+            lp_token: astro_ust_lp_token_address.to_string(),
+            user: env.contract.address.to_string(),
+        },
+    )?;
+
+    let pending_on_proxy = &pending_rewards.pending_on_proxy.unwrap_or_default();
+
+    if !pending_rewards.pending.is_zero()
+        || pending_on_proxy.iter().any(|asset| !asset.amount.is_zero())
+    {
+        let rwi: RewardInfoResponse = deps.querier.query_wasm_smart(
+            generator,
+            &GenQueryMsg::RewardInfo {
+                lp_token: astro_ust_lp_token_address.to_string(),
+            },
+        )?;
+
+        let astro_balance = {
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                rwi.base_reward_token,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            res.balance
+        };
+
+        let prev_proxy_reward_balances = pending_on_proxy
+            .iter()
+            .map(|asset| {
+                let balance = asset
+                    .info
+                    .query_pool(&deps.querier, env.contract.address.clone())?;
+
+                Ok(Asset {
+                    info: asset.info.clone(),
+                    amount: balance,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: generator.to_string(),
+            funds: vec![],
+            msg: to_binary(&GenExecuteMsg::Withdraw {
+                lp_token: astro_ust_lp_token_address.to_string(),
+                amount: Uint128::zero(),
+            })?,
+        }));
+
+        cosmos_msgs.push(
+            CallbackMsg::UpdateStateOnRewardClaim {
+                prev_astro_balance: astro_balance,
+                prev_proxy_reward_balances,
+            }
+            .to_cosmos_msg(env)?,
+        );
+    }
+
+    Ok(cosmos_msgs)
+}
+
+/// Enables or disables compounding of user's ASTRO staking rewards. ASTRO staking rewards which are not compounded
+/// are sent to the user. LP shares of the user who opts in join the compounding pool after the next compounding round.
+/// LP shares received for compounded ASTRO don't earn proxy rewards, the proxy rewards they accrue are shared among
+/// vesting LP shares of all users.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **enabled** is an object of type [`bool`].
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Position address, defaults to the sender.
+pub fn handle_set_compounding(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    enabled: bool,
+    user_address: Option<String>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let user_address =
+        resolve_position_address(deps.as_ref(), &config, &info.sender, user_address)?;
+    let mut user_info = USERS.compatible_load_mut(deps.branch(), &user_address)?;
+
+    // CHECK :: LP Tokens should be staked with the generator
+    if !state.is_lp_staked {
+        return Err(StdError::generic_err("LP Tokens aren't staked yet!"));
+    }
+
+    if user_info.compounding == enabled {
+        return Err(StdError::generic_err(if enabled {
+            "Compounding is already enabled"
+        } else {
+            "Compounding is already disabled"
+        }));
+    }
+
+    // Calculate user's LP shares & ASTRO incentives (if possible)
+    if user_info.lp_shares.is_none() {
+        update_user_lp_shares(
+            &state,
+            state.lp_shares_minted.unwrap_or_default(),
+            &mut user_info,
+        )?;
+        update_user_astro_incentives(&config, &state, &mut user_info)?;
+    }
+
+    let astroport_lp_amount = user_info.lp_shares.unwrap_or_default() - user_info.claimed_lp_shares;
+    if enabled && astroport_lp_amount.is_zero() {
+        return Err(StdError::generic_err("No LP shares to compound"));
+    }
+
+    let mut pending_astro_rewards =
+        settle_compound_position(deps.storage, &state, &mut user_info, astroport_lp_amount)?;
+
+    if enabled {
+        pending_astro_rewards += (state.generator_astro_per_share * astroport_lp_amount)
+            - (user_info.user_gen_astro_per_share * astroport_lp_amount);
+        user_info.compound_join_round = state.compound_round + 1;
+        state.pending_compounding_lp_shares += astroport_lp_amount;
+    } else if is_compound_joined(&state, &user_info) {
+        // Rewards accrued before the last compounding round are already compounded
+        pending_astro_rewards += (state.generator_astro_per_share * astroport_lp_amount)
+            - (state.compound_astro_per_share * astroport_lp_amount);
+        state.compounding_lp_shares -= astroport_lp_amount;
+    } else {
+        pending_astro_rewards += (state.generator_astro_per_share * astroport_lp_amount)
+            - (user_info.user_gen_astro_per_share * astroport_lp_amount);
+        user_info.compound_join_round = 0;
+        state.pending_compounding_lp_shares -= astroport_lp_amount;
+    }
+    user_info.user_gen_astro_per_share = state.generator_astro_per_share;
+    user_info.generator_astro_debt += pending_astro_rewards;
+    user_info.compounding = enabled;
+
+    USERS.save(deps.storage, &user_address, &user_info)?;
+    STATE.save(deps.storage, &state)?;
+
+    let mut response = Response::new().add_attributes(vec![
+        attr("action", "Auction::ExecuteMsg::SetCompounding"),
+        attr("user_address", &user_address),
+        attr("enabled", enabled.to_string()),
+        attr("generator_astro_reward", pending_astro_rewards),
+    ]);
+
+    if !pending_astro_rewards.is_zero() {
+        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.astro_token_address.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount: pending_astro_rewards,
+            })?,
+        }));
+    }
+
+    Ok(response)
+}
+
+/// Claims generator rewards and compounds ASTRO rewards accrued by LP shares of compounding users and by the
+/// compounding pool: half of ASTRO is swapped to UST, both are provided to the ASTRO-UST pool and the received
+/// LP Tokens are staked with the generator. Can be called by anyone, the slippage is set in config by the owner.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+pub fn handle_compound(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    // CHECK :: LP Tokens should be staked with the generator
+    if !state.is_lp_staked {
+        return Err(StdError::generic_err("LP Tokens aren't staked yet!"));
+    }
+
+    let generator = config
+        .generator_contract
+        .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+
+    if let Some(PoolInfo {
+        astro_ust_pool_address: _,
+        astro_ust_lp_token_address,
+    }) = config.pool_info
+    {
+        let mut cosmos_msgs = build_claim_generator_rewards_msgs(
+            deps.as_ref(),
+            &env,
+            &generator,
+            &astro_ust_lp_token_address,
+        )?;
+        cosmos_msgs.push(CallbackMsg::CompoundAstroRewards {}.to_cosmos_msg(&env)?);
+
+        Ok(Response::new()
+            .add_messages(cosmos_msgs)
+            .add_attribute("action", "Auction::ExecuteMsg::Compound"))
+    } else {
+        Err(StdError::generic_err("Pool info isn't set yet!"))
+    }
+}
+
+/// Starts a new compounding round and swaps half of ASTRO rewards to be compounded to UST.
+/// LP shares of users who opted in to compounding join the compounding pool once the round is finished.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+pub fn callback_compound_astro_rewards(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    if let Some(PoolInfo {
+        astro_ust_pool_address,
+        astro_ust_lp_token_address: _,
+    }) = config.pool_info
+    {
+        // ASTRO rewards accrued by LP shares of compounding users and by the compounding pool since the last round
+        let users_astro = (state.generator_astro_per_share * state.compounding_lp_shares)
+            - (state.compound_astro_per_share * state.compounding_lp_shares);
+        let pool_astro = (state.generator_astro_per_share * state.compounded_lp_shares)
+            - (state.compound_astro_per_share * state.compounded_lp_shares);
+        let total_astro = users_astro + pool_astro;
+        let compounding_lp_shares = state.compounding_lp_shares;
+
+        if total_astro.is_zero() && state.pending_compounding_lp_shares.is_zero() {
+            return Err(StdError::generic_err("Nothing to compound!"));
+        }
+
+        state.compound_round += 1;
+        state.compound_astro_per_share = state.generator_astro_per_share;
+        state.compounding_lp_shares += state.pending_compounding_lp_shares;
+        state.pending_compounding_lp_shares = Uint128::zero();
+
+        let astro_to_swap = total_astro.multiply_ratio(1u128, 2u128);
+
+        let mut response = Response::new().add_attributes(vec![
+            attr("action", "Auction::CallbackMsg::CompoundAstroRewards"),
+            attr("compound_round", state.compound_round.to_string()),
+            attr("astro_compounded", total_astro),
+        ]);
+
+        // Nothing to provide to the pool, so the round is finished right away
+        if astro_to_swap.is_zero() {
+            COMPOUND_ROUNDS.save(
+                deps.storage,
+                U64Key::new(state.compound_round),
+                &CompoundRound {
+                    astro_per_share: state.compound_astro_per_share,
+                    units_per_share: state.compound_units_per_share,
+                },
+            )?;
+            STATE.save(deps.storage, &state)?;
+            return Ok(response);
+        }
+
+        let prev_ust_balance = deps
+            .querier
+            .query_balance(&env.contract.address, UUSD_DENOM)?
+            .amount;
+
+        // COSMOS MSGS
+        // :: 1. SWAP HALF OF ASTRO TO UST
+        // :: 2. CallbackMsg :: Provide ASTRO and received UST to the pool
+        response = response
+            .add_message(WasmMsg::Execute {
+                contract_addr: config.astro_token_address.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: astro_ust_pool_address.to_string(),
+                    amount: astro_to_swap,
+                    msg: to_binary(&astroport::pair::Cw20HookMsg::Swap {
+                        belief_price: None,
+                        max_spread: config.compound_slippage,
+                        to: None,
+                    })?,
+                })?,
+            })
+            .add_message(
+                CallbackMsg::ProvideCompoundedLiquidity {
+                    prev_ust_balance,
+                    astro_amount: total_astro - astro_to_swap,
+                    users_astro,
+                    total_astro,
+                    compounding_lp_shares,
+                }
+                .to_cosmos_msg(&env)?,
+            );
+
+        STATE.save(deps.storage, &state)?;
+
+        Ok(response)
+    } else {
+        Err(StdError::generic_err("Pool info isn't set yet!"))
+    }
+}
+
+/// Provides ASTRO and UST received from the swap to the ASTRO-UST pool.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **prev_ust_balance** is an object of type [`Uint128`]. UST balance of the contract before the swap.
+///
+/// * **astro_amount** is an object of type [`Uint128`]. Amount of ASTRO to provide.
+///
+/// * **users_astro** is an object of type [`Uint128`]. ASTRO rewards compounded for users.
+///
+/// * **total_astro** is an object of type [`Uint128`]. Total ASTRO rewards compounded in the round.
+///
+/// * **compounding_lp_shares** is an object of type [`Uint128`]. LP shares of compounding users in the round.
+pub fn callback_provide_compounded_liquidity(
+    deps: DepsMut,
+    env: Env,
+    prev_ust_balance: Uint128,
+    astro_amount: Uint128,
+    users_astro: Uint128,
+    total_astro: Uint128,
+    compounding_lp_shares: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if let Some(PoolInfo {
+        astro_ust_pool_address,
+        astro_ust_lp_token_address,
+    }) = config.pool_info
+    {
+        let ust_received = deps
+            .querier
+            .query_balance(&env.contract.address, UUSD_DENOM)?
+            .amount
+            - prev_ust_balance;
+
+        let prev_lp_balance = query_token_balance(
+            &deps.querier,
+            astro_ust_lp_token_address,
+            env.contract.address.clone(),
+        )?;
+
+        // COSMOS MSGS
+        // :: 1. APPROVE ASTRO WITH LP POOL ADDRESS AS BENEFICIARY
+        // :: 2. ADD LIQUIDITY
+        // :: 3. CallbackMsg :: Stake received LP Tokens
+        let msgs = vec![
+            build_approve_cw20_msg(
+                config.astro_token_address.to_string(),
+                astro_ust_pool_address.to_string(),
+                astro_amount,
+                env.block.height + 1u64,
+            )?,
+            build_provide_liquidity_to_lp_pool_msg(
+                deps.as_ref(),
+                config.astro_token_address,
+                astro_ust_pool_address,
+                ust_received,
+                astro_amount,
+                config.compound_slippage,
+            )?,
+            CallbackMsg::StakeCompoundedLp {
+                prev_lp_balance,
+                users_astro,
+                total_astro,
+                compounding_lp_shares,
+            }
+            .to_cosmos_msg(&env)?,
+        ];
+
+        Ok(Response::new().add_messages(msgs).add_attributes(vec![
+            attr("action", "Auction::CallbackMsg::ProvideCompoundedLiquidity"),
+            attr("astro_provided", astro_amount),
+            attr("ust_provided", ust_received),
+        ]))
+    } else {
+        Err(StdError::generic_err("Pool info isn't set yet!"))
+    }
+}
+
+/// Stakes LP Tokens received from compounding with the generator and mints compounding pool units
+/// for the share of ASTRO rewards compounded for users.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **prev_lp_balance** is an object of type [`Uint128`]. LP Token balance of the contract before the liquidity provision.
+///
+/// * **users_astro** is an object of type [`Uint128`]. ASTRO rewards compounded for users.
+///
+/// * **total_astro** is an object of type [`Uint128`]. Total ASTRO rewards compounded in the round.
+///
+/// * **compounding_lp_shares** is an object of type [`Uint128`]. LP shares of compounding users in the round.
+pub fn callback_stake_compounded_lp(
+    deps: DepsMut,
+    env: Env,
+    prev_lp_balance: Uint128,
+    users_astro: Uint128,
+    total_astro: Uint128,
+    compounding_lp_shares: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    let generator = config
+        .generator_contract
+        .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+
+    if let Some(PoolInfo {
+        astro_ust_pool_address: _,
+        astro_ust_lp_token_address,
+    }) = config.pool_info
+    {
+        let lp_minted = query_token_balance(
+            &deps.querier,
+            astro_ust_lp_token_address.clone(),
+            env.contract.address.clone(),
+        )? - prev_lp_balance;
+
+        // Units are minted for the LP shares received for users' ASTRO at the current units price.
+        // LP shares received for the compounding pool's ASTRO increase the price of existing units
+        let users_lp = lp_minted.multiply_ratio(users_astro, total_astro);
+        let units_minted = if state.total_compound_units.is_zero() {
+            users_lp
+        } else {
+            users_lp.multiply_ratio(
+                state.total_compound_units,
+                state.compounded_lp_shares + (lp_minted - users_lp),
+            )
+        };
+
+        if !compounding_lp_shares.is_zero() {
+            state.compound_units_per_share +=
+                Decimal::from_ratio(units_minted, compounding_lp_shares);
+        }
+        state.total_compound_units += units_minted;
+        state.compounded_lp_shares += lp_minted;
+
+        COMPOUND_ROUNDS.save(
+            deps.storage,
+            U64Key::new(state.compound_round),
+            &CompoundRound {
+                astro_per_share: state.compound_astro_per_share,
+                units_per_share: state.compound_units_per_share,
+            },
+        )?;
+        STATE.save(deps.storage, &state)?;
+
+        // COSMOS MSGs
+        // :: Add increase allowance msg so generator contract can transfer tokens to itself
+        // :: To stake LP Tokens to the Astroport generator contract
+        let mut msgs = vec![];
+        if !lp_minted.is_zero() {
+            msgs.push(build_approve_cw20_msg(
+                astro_ust_lp_token_address.to_string(),
+                generator.to_string(),
+                lp_minted,
+                env.block.height + 1u64,
+            )?);
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: astro_ust_lp_token_address.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: generator.to_string(),
+                    msg: to_binary(&astroport::generator::Cw20HookMsg::Deposit {})?,
+                    amount: lp_minted,
+                })?,
+            }));
+        }
+
+        Ok(Response::new().add_messages(msgs).add_attributes(vec![
+            attr("action", "Auction::CallbackMsg::StakeCompoundedLp"),
+            attr("lp_staked", lp_minted),
+            attr("units_minted", units_minted),
+        ]))
+    } else {
+        Err(StdError::generic_err("Pool info isn't set yet!"))
+    }
+}
+
+/// Withdraws LP Tokens of the compounding pool for the given units.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **units** is an object of type [`Uint128`]. Compounding pool units to burn.
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Position address, defaults to the sender.
+pub fn handle_withdraw_compounded_lp(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    units: Uint128,
+    user_address: Option<String>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    // CHECK ::: Amount needs to be valid
+    if units.is_zero() {
+        return Err(StdError::generic_err("Amount must be greater than 0"));
+    }
+
+    // CHECK :: User should exist
+    let user_address =
+        resolve_position_address(deps.as_ref(), &config, &info.sender, user_address)?;
+    USERS.compatible_load(deps.as_ref(), &user_address)?;
+
+    // CHECK :: LP Tokens should be provided to the pool
+    if state.lp_shares_minted.is_none() {
+        return Err(StdError::generic_err("LP Tokens aren't staked yet!"));
+    }
+
+    if let Some(PoolInfo {
+        astro_ust_pool_address: _,
+        astro_ust_lp_token_address,
+    }) = config.pool_info
+    {
+        // LP Tokens unstaked in emergency are withdrawn without claiming generator rewards
+        let mut cosmos_msgs = vec![];
+        if state.is_lp_staked {
+            let generator = config
+                .generator_contract
+                .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+            cosmos_msgs.extend(build_claim_generator_rewards_msgs(
+                deps.as_ref(),
+                &env,
+                &generator,
+                &astro_ust_lp_token_address,
+            )?);
+        }
+        cosmos_msgs.push(
+            CallbackMsg::WithdrawCompoundedLpCallback {
+                user_address,
+                recipient: info.sender,
+                units,
+            }
+            .to_cosmos_msg(&env)?,
+        );
+
+        Ok(Response::new().add_messages(cosmos_msgs))
+    } else {
+        Err(StdError::generic_err("Pool info isn't set yet!"))
+    }
+}
+
+/// Burns user's compounding pool units and sends the corresponding LP Tokens to the user alongside ASTRO rewards
+/// accrued by them since the last compounding round.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **user_address** is an object of type [`Addr`].
+///
+/// * **recipient** is an object of type [`Addr`]. Address which receives withdrawn LP Tokens and rewards.
+///
+/// * **units** is an object of type [`Uint128`]. Compounding pool units to burn.
+pub fn callback_withdraw_compounded_lp(
+    mut deps: DepsMut,
+    env: Env,
+    user_address: Addr,
+    recipient: Addr,
+    units: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let mut user_info = USERS.compatible_load_mut(deps.branch(), &user_address)?;
+
+    if let Some(PoolInfo {
+        astro_ust_pool_address: _,
+        astro_ust_lp_token_address,
+    }) = config.pool_info
+    {
+        let astroport_lp_amount =
+            user_info.lp_shares.unwrap_or_default() - user_info.claimed_lp_shares;
+        let mut pending_astro_rewards =
+            settle_compound_position(deps.storage, &state, &mut user_info, astroport_lp_amount)?;
+
+        if units > user_info.compound_units {
+            return Err(StdError::generic_err("Not enough compounding pool units"));
+        }
+
+        let lp_to_withdraw = state
+            .compounded_lp_shares
+            .multiply_ratio(units, state.total_compound_units);
+
+        // ASTRO rewards accrued by withdrawn LP shares since the last compounding round
+        pending_astro_rewards += (state.generator_astro_per_share * lp_to_withdraw)
+            - (state.compound_astro_per_share * lp_to_withdraw);
+
+        state.compounded_lp_shares -= lp_to_withdraw;
+        state.total_compound_units -= units;
+        user_info.compound_units -= units;
+        user_info.generator_astro_debt += pending_astro_rewards;
+
+        USERS.save(deps.storage, &user_address, &user_info)?;
+        STATE.save(deps.storage, &state)?;
+
+        let mut cosmos_msgs = vec![];
+
+        //  COSMOS MSG :: Unstake LP Tokens from the generator (if staked) and send them to the user
+        if !lp_to_withdraw.is_zero() {
+            if state.is_lp_staked {
+                let generator = config
+                    .generator_contract
+                    .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+                cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: generator.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&GenExecuteMsg::Withdraw {
+                        lp_token: astro_ust_lp_token_address.to_string(),
+                        amount: lp_to_withdraw,
+                    })?,
+                }));
+            }
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: astro_ust_lp_token_address.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: lp_to_withdraw,
+                })?,
+            }));
+        }
+
+        if !pending_astro_rewards.is_zero() {
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.astro_token_address.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: pending_astro_rewards,
+                })?,
+            }));
+        }
+
+        Ok(Response::new()
+            .add_messages(cosmos_msgs)
+            .add_attributes(vec![
+                attr("action", "Auction::ExecuteMsg::WithdrawCompoundedLp"),
+                attr("user_address", &user_address),
+                attr("recipient", &recipient),
+                attr("units_burned", units),
+                attr("lp_withdrawn", lp_to_withdraw),
+                attr("generator_astro_reward", pending_astro_rewards),
+            ]))
+    } else {
+        Err(StdError::generic_err("Pool info isn't set yet!"))
+    }
+}
+
+/// Returns true if LP shares of the user have joined the compounding pool, else returns false
+/// ## Params
+/// * **state** is an object of type [`State`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+fn is_compound_joined(state: &State, user_info: &UserInfo) -> bool {
+    user_info.compounding && state.compound_round >= user_info.compound_join_round
+}
+
+/// Accounts compounding pool units minted for user's LP shares since the last update.
+/// If user's LP shares have just joined the compounding pool, returns ASTRO rewards accrued by them
+/// before the joining, otherwise returns zero.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **state** is an object of type [`State`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+///
+/// * **astroport_lp_amount** is an object of type [`Uint128`]. User's LP shares which aren't withdrawn.
+fn settle_compound_position(
+    storage: &dyn Storage,
+    state: &State,
+    user_info: &mut UserInfo,
+    astroport_lp_amount: Uint128,
+) -> StdResult<Uint128> {
+    let mut pending_astro_rewards = Uint128::zero();
 
-                if !pending_rewards.pending.is_zero()
-                    || (pending_rewards.pending_on_proxy.is_some()
-                        && !pending_rewards.pending_on_proxy.unwrap().is_empty())
-                {
-                    let rwi: RewardInfoResponse = deps.querier.query_wasm_smart(
-                        &generator,
-                        &GenQueryMsg::RewardInfo {
-                            lp_token: astro_ust_lp_token_address.to_string(),
-                        },
-                    )?;
+    if !is_compound_joined(state, user_info) {
+        return Ok(pending_astro_rewards);
+    }
 
-                    let astro_balance = {
-                        let res: BalanceResponse = deps.querier.query_wasm_smart(
-                            rwi.base_reward_token,
-                            &Cw20QueryMsg::Balance {
-                                address: env.contract.address.to_string(),
-                            },
-                        )?;
-                        res.balance
-                    };
+    if user_info.compound_join_round > 0 {
+        let round = COMPOUND_ROUNDS.load(storage, U64Key::new(user_info.compound_join_round))?;
+        pending_astro_rewards = (round.astro_per_share * astroport_lp_amount)
+            - (user_info.user_gen_astro_per_share * astroport_lp_amount);
+        user_info.user_gen_astro_per_share = round.astro_per_share;
+        user_info.user_compound_units_per_share = round.units_per_share;
+        user_info.compound_join_round = 0;
+    }
 
-                    cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                        contract_addr: generator.to_string(),
-                        funds: vec![],
-                        msg: to_binary(&GenExecuteMsg::Withdraw {
-                            lp_token: astro_ust_lp_token_address.to_string(),
-                            amount: Uint128::zero(),
-                        })?,
-                    }));
-
-                    cosmos_msgs.push(
-                        CallbackMsg::UpdateStateOnRewardClaim {
-                            prev_astro_balance: astro_balance,
-                        }
-                        .to_cosmos_msg(&env)?,
-                    );
-                }
-            } else {
-                return Err(StdError::generic_err("Pool info isn't set yet!"));
-            }
+    user_info.compound_units += (state.compound_units_per_share * astroport_lp_amount)
+        - (user_info.user_compound_units_per_share * astroport_lp_amount);
+    user_info.user_compound_units_per_share = state.compound_units_per_share;
+
+    Ok(pending_astro_rewards)
+}
+
+/// Returns Generator Proxy rewards accrued by user's LP shares since the last claim and updates
+/// user's Proxy reward debt and checkpoints accordingly.
+/// ## Params
+/// * **state** is an object of type [`State`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+///
+/// * **astroport_lp_amount** is an object of type [`Uint128`]. User's LP shares which aren't withdrawn.
+fn settle_generator_proxy_rewards(
+    state: &State,
+    user_info: &mut UserInfo,
+    astroport_lp_amount: Uint128,
+) -> StdResult<Vec<Asset>> {
+    let mut pending_proxy_rewards: Vec<Asset> = vec![];
+    for (asset, generator_proxy_per_share) in state.generator_proxy_per_share.inner_ref() {
+        let user_gen_proxy_per_share = user_info
+            .user_gen_proxy_per_share
+            .load(asset)
+            .unwrap_or_default();
+        let pending_proxy_reward = (*generator_proxy_per_share * astroport_lp_amount)
+            - (user_gen_proxy_per_share * astroport_lp_amount);
+
+        if !pending_proxy_reward.is_zero() {
+            user_info
+                .generator_proxy_debt
+                .update(asset, pending_proxy_reward)?;
+            pending_proxy_rewards.push(Asset {
+                info: asset.clone(),
+                amount: pending_proxy_reward,
+            });
         }
-        // If no rewards to claim and no LP tokens to be withdrawn.
-        else if user_info.astro_incentive_transferred && withdraw_lp_shares.is_none() {
+    }
+    user_info.user_gen_proxy_per_share = state.generator_proxy_per_share.clone();
+
+    Ok(pending_proxy_rewards)
+}
+
+/// Transfers a fraction of user's position to the recipient. Generator rewards are claimed first,
+/// so pending rewards of both the user and the recipient can be settled before the transfer.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **recipient** is an object of type [`String`].
+///
+/// * **fraction** is an object of type [`Decimal`]. Fraction of the position to transfer.
+pub fn handle_transfer_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    fraction: Decimal,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let recipient = addr_validate_to_lower(deps.api, &recipient)?;
+
+    // CHECK :: Fraction needs to be valid
+    if fraction.is_zero() || fraction > Decimal::one() {
+        return Err(StdError::generic_err(
+            "Fraction should be more than 0 and less than or equal to 1",
+        ));
+    }
+
+    if recipient == info.sender {
+        return Err(StdError::generic_err(
+            "Recipient should differ from the sender",
+        ));
+    }
+
+    // CHECK :: Positions mirrored by NFTs are transferred with their NFTs
+    if config.position_nft.is_some() {
+        return Err(StdError::generic_err(
+            "Positions are transferred with their NFTs",
+        ));
+    }
+
+    let user_info = USERS.compatible_load(deps.as_ref(), &info.sender)?;
+    let recipient_info = USERS
+        .compatible_may_load(deps.as_ref(), &recipient)?
+        .unwrap_or_default();
+
+    // CHECK :: Positions can be transferred only to allowlisted addresses and within their caps
+    if config.allowlist_merkle_root.is_some() {
+        let cap = ALLOWLIST
+            .may_load(deps.storage, &recipient)?
+            .ok_or_else(|| StdError::generic_err("Recipient isn't allowlisted"))?;
+        if recipient_info.ust_delegated + user_info.ust_delegated * fraction > cap {
             return Err(StdError::generic_err(
-                "Rewards already claimed. Provide number of LP tokens to claim!",
+                "Transfer exceeds the recipient's allowlist cap",
             ));
         }
-    } else {
+    }
+
+    // CHECK :: LP shares of compounding users are accounted in the compounding pool
+    if user_info.compounding || recipient_info.compounding {
         return Err(StdError::generic_err(
-            "Astro/USD should be provided to the pool!",
+            "Positions with enabled compounding can't be transferred",
         ));
-    };
+    }
+
+    let mut cosmos_msgs = vec![];
+
+    if state.is_lp_staked {
+        let generator = config
+            .generator_contract
+            .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+
+        if let Some(PoolInfo {
+            astro_ust_pool_address: _,
+            astro_ust_lp_token_address,
+        }) = config.pool_info
+        {
+            cosmos_msgs.extend(build_claim_generator_rewards_msgs(
+                deps.as_ref(),
+                &env,
+                &generator,
+                &astro_ust_lp_token_address,
+            )?);
+        } else {
+            return Err(StdError::generic_err("Pool info isn't set yet!"));
+        }
+    }
 
     cosmos_msgs.push(
-        CallbackMsg::WithdrawUserRewardsCallback {
-            user_address,
-            withdraw_lp_shares,
+        CallbackMsg::TransferPositionCallback {
+            user_address: info.sender,
+            recipient,
+            fraction,
         }
         .to_cosmos_msg(&env)?,
     );
@@ -918,10 +2732,212 @@ pub fn handle_claim_rewards_and_withdraw_lp_shares(
     Ok(Response::new().add_messages(cosmos_msgs))
 }
 
+/// Settles pending generator rewards of the user and the recipient and moves a fraction of every field
+/// of user's position to the recipient's position.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **user_address** is an object of type [`Addr`].
+///
+/// * **recipient** is an object of type [`Addr`].
+///
+/// * **fraction** is an object of type [`Decimal`]. Fraction of the position to transfer.
+pub fn callback_transfer_position(
+    mut deps: DepsMut,
+    user_address: Addr,
+    recipient: Addr,
+    fraction: Decimal,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let mut user_info = USERS.compatible_load_mut(deps.branch(), &user_address)?;
+    let mut recipient_info = USERS
+        .compatible_may_load_mut(deps.branch(), &recipient)?
+        .unwrap_or_default();
+
+    let mut cosmos_msgs = vec![];
+
+    for (address, position) in [
+        (&user_address, &mut user_info),
+        (&recipient, &mut recipient_info),
+    ] {
+        // Calculate LP shares & ASTRO incentives (if possible)
+        if let Some(lp_balance) = state.lp_shares_minted {
+            if position.lp_shares.is_none() {
+                update_user_lp_shares(&state, lp_balance, position)?;
+            }
+            if config.astro_incentive_amount.is_some()
+                && position.auction_incentive_amount.is_none()
+            {
+                update_user_astro_incentives(&config, &state, position)?;
+            }
+        }
+
+        // Settle pending generator rewards, so both positions share the same reward checkpoints.
+        // Rewards claimed from the generator stay receivable after LP Tokens are unstaked in emergency
+        let astroport_lp_amount =
+            position.lp_shares.unwrap_or_default() - position.claimed_lp_shares;
+
+        let pending_astro_rewards = (state.generator_astro_per_share * astroport_lp_amount)
+            - (position.user_gen_astro_per_share * astroport_lp_amount);
+        position.user_gen_astro_per_share = state.generator_astro_per_share;
+        position.generator_astro_debt += pending_astro_rewards;
+
+        if !pending_astro_rewards.is_zero() {
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.astro_token_address.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: address.to_string(),
+                    amount: pending_astro_rewards,
+                })?,
+            }));
+        }
+
+        for pending_proxy_reward in
+            settle_generator_proxy_rewards(&state, position, astroport_lp_amount)?
+        {
+            cosmos_msgs.push(pending_proxy_reward.into_msg(&deps.querier, address.clone())?);
+        }
+    }
+
+    let transferred_position = split_user_position(&mut user_info, fraction);
+    merge_user_positions(&mut recipient_info, transferred_position)?;
+
+    if fraction == Decimal::one() {
+        USERS.remove(deps.storage, &user_address);
+    } else {
+        USERS.save(deps.storage, &user_address, &user_info)?;
+    }
+    USERS.save(deps.storage, &recipient, &recipient_info)?;
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "Auction::ExecuteMsg::TransferPosition"),
+            attr("user_address", &user_address),
+            attr("recipient", &recipient),
+            attr("fraction", fraction.to_string()),
+        ]))
+}
+
+/// Takes a fraction of every field of user's position and returns it as a separate position.
+/// Reward checkpoints are copied as they are the same for both parts.
+/// ## Params
+/// * **user_info** is an object of type [`UserInfo`].
+///
+/// * **fraction** is an object of type [`Decimal`]. Fraction of the position to take.
+fn split_user_position(user_info: &mut UserInfo, fraction: Decimal) -> UserInfo {
+    let take = |amount: Uint128| amount * fraction;
+
+    let transferred = UserInfo {
+        astro_delegated: take(user_info.astro_delegated),
+        ust_delegated: take(user_info.ust_delegated),
+        astro_weighted: take(user_info.astro_weighted),
+        ust_weighted: take(user_info.ust_weighted),
+        ust_withdrawn: user_info.ust_withdrawn,
+        // Undelegated ASTRO is returned to the delegator only, so the transferred part can't be undelegated
+        airdrop_astro_delegated: Uint128::zero(),
+        lockdrop_astro_delegated: Uint128::zero(),
+        astro_undelegated: user_info.astro_undelegated,
+        lp_shares: user_info.lp_shares.map(take),
+        astro_lp_shares: take(user_info.astro_lp_shares),
+        claimed_lp_shares: take(user_info.claimed_lp_shares),
+        auction_incentive_amount: user_info.auction_incentive_amount.map(take),
+        claimed_incentives: take(user_info.claimed_incentives),
+        generator_astro_debt: take(user_info.generator_astro_debt),
+        user_gen_astro_per_share: user_info.user_gen_astro_per_share,
+        generator_proxy_debt: user_info
+            .generator_proxy_debt
+            .inner_ref()
+            .iter()
+            .map(|(asset, amount)| (asset.clone(), take(*amount)))
+            .collect::<Vec<_>>()
+            .into(),
+        user_gen_proxy_per_share: user_info.user_gen_proxy_per_share.clone(),
+        compounding: false,
+        compound_join_round: 0,
+        compound_units: take(user_info.compound_units),
+        user_compound_units_per_share: user_info.user_compound_units_per_share,
+    };
+
+    user_info.astro_delegated -= transferred.astro_delegated;
+    user_info.ust_delegated -= transferred.ust_delegated;
+    user_info.astro_weighted -= transferred.astro_weighted;
+    user_info.ust_weighted -= transferred.ust_weighted;
+    user_info.airdrop_astro_delegated -= take(user_info.airdrop_astro_delegated);
+    user_info.lockdrop_astro_delegated -= take(user_info.lockdrop_astro_delegated);
+    user_info.lp_shares = user_info
+        .lp_shares
+        .map(|lp_shares| lp_shares - transferred.lp_shares.unwrap_or_default());
+    user_info.astro_lp_shares -= transferred.astro_lp_shares;
+    user_info.claimed_lp_shares -= transferred.claimed_lp_shares;
+    user_info.auction_incentive_amount = user_info
+        .auction_incentive_amount
+        .map(|amount| amount - transferred.auction_incentive_amount.unwrap_or_default());
+    user_info.claimed_incentives -= transferred.claimed_incentives;
+    user_info.generator_astro_debt -= transferred.generator_astro_debt;
+    user_info.generator_proxy_debt = user_info
+        .generator_proxy_debt
+        .inner_ref()
+        .iter()
+        .map(|(asset, amount)| {
+            let transferred_amount = transferred
+                .generator_proxy_debt
+                .load(asset)
+                .unwrap_or_default();
+            (asset.clone(), *amount - transferred_amount)
+        })
+        .collect::<Vec<_>>()
+        .into();
+    user_info.compound_units -= transferred.compound_units;
+
+    transferred
+}
+
+/// Adds a position to user's position.
+/// ## Params
+/// * **user_info** is an object of type [`UserInfo`].
+///
+/// * **position** is an object of type [`UserInfo`]. Position to add.
+fn merge_user_positions(user_info: &mut UserInfo, position: UserInfo) -> StdResult<()> {
+    let merge = |a: Option<Uint128>, b: Option<Uint128>| match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    };
+
+    user_info.astro_delegated += position.astro_delegated;
+    user_info.ust_delegated += position.ust_delegated;
+    user_info.astro_weighted += position.astro_weighted;
+    user_info.ust_weighted += position.ust_weighted;
+    user_info.ust_withdrawn |= position.ust_withdrawn;
+    user_info.airdrop_astro_delegated += position.airdrop_astro_delegated;
+    user_info.lockdrop_astro_delegated += position.lockdrop_astro_delegated;
+    user_info.astro_undelegated |= position.astro_undelegated;
+    user_info.lp_shares = merge(user_info.lp_shares, position.lp_shares);
+    user_info.astro_lp_shares += position.astro_lp_shares;
+    user_info.claimed_lp_shares += position.claimed_lp_shares;
+    user_info.auction_incentive_amount = merge(
+        user_info.auction_incentive_amount,
+        position.auction_incentive_amount,
+    );
+    user_info.claimed_incentives += position.claimed_incentives;
+    user_info.generator_astro_debt += position.generator_astro_debt;
+    user_info.user_gen_astro_per_share = position.user_gen_astro_per_share;
+    for (asset, amount) in position.generator_proxy_debt.inner_ref() {
+        user_info.generator_proxy_debt.update(asset, *amount)?;
+    }
+    user_info.user_gen_proxy_per_share = position.user_gen_proxy_per_share;
+    user_info.compound_units += position.compound_units;
+
+    Ok(())
+}
+
 /// Calculates user's ASTRO - UST LP shares based on amount delegated.
 /// User LP shares (ASTRO delegation share) = (1/2) *  (ASTRO delegated / total ASTRO delegated)
 /// User LP shares (UST deposit share) = (1/2) *  (UST deposited / total UST deposited)
 /// User's total LP shares  = User's ASTRO delegation LP share + User's UST deposit LP share
+/// The ASTRO delegation LP share is stored separately to be vested by its own schedule
 /// ## Params
 /// * **state** is an object of type [`State`].
 ///
@@ -933,63 +2949,134 @@ fn update_user_lp_shares(
     lp_balance: Uint128,
     mut user_info: &mut UserInfo,
 ) -> StdResult<()> {
-    let user_lp_share = (Decimal::from_ratio(
-        user_info.astro_delegated,
-        state.total_astro_delegated * Uint128::new(2),
-    ) + Decimal::from_ratio(
-        user_info.ust_delegated,
-        state.total_ust_delegated * Uint128::new(2),
-    )) * lp_balance;
+    let astro_lp_share = cohort_lp_share(user_info.astro_delegated, state.total_astro_delegated);
+    let user_lp_share = (astro_lp_share
+        + cohort_lp_share(user_info.ust_delegated, state.total_ust_delegated))
+        * lp_balance;
     user_info.lp_shares = Some(user_lp_share);
+    user_info.astro_lp_shares = user_lp_share.min(astro_lp_share * lp_balance);
 
     Ok(())
 }
 
-/// Calculates user's ASTRO incentives for auction participation
-/// Formula, == User's auction incentives (ASTRO) = (User's total LP shares / Total LP shares minted) * Total ASTRO auction incentives
+/// Returns the share of LP tokens for the amount delegated to one side of the pool. Each side gets half of LP tokens,
+/// which is split among its participants proportionally to their delegated amounts.
 /// ## Params
-/// * **total_astro_incentives** is an optional object of type [`Uint128`].
+/// * **delegated** is an object of type [`Uint128`]. ASTRO or UST delegated by the participant.
 ///
-/// * **user_lp_share** is an optional object of type [`Uint128`].
+/// * **total_delegated** is an object of type [`Uint128`]. Total ASTRO or UST delegated to the auction.
+fn cohort_lp_share(delegated: Uint128, total_delegated: Uint128) -> Decimal {
+    if total_delegated.is_zero() {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(delegated, total_delegated * Uint128::new(2))
+    }
+}
+
+/// Calculates user's ASTRO incentives for auction participation once user's LP shares are known.
+/// See [`calculate_auction_reward_for_user`] for the formula
+/// ## Params
+/// * **config** is an object of type [`Config`].
 ///
-/// * **lp_balance** is an object of type [`Uint128`].
+/// * **state** is an object of type [`State`].
 ///
 /// * **user_info** is an object of type [`UserInfo`].
 fn update_user_astro_incentives(
-    total_astro_incentives: Option<Uint128>,
-    user_lp_share: Option<Uint128>,
-    lp_balance: Uint128,
+    config: &Config,
+    state: &State,
     mut user_info: &mut UserInfo,
 ) -> StdResult<()> {
-    if let Some(total_astro_incentives) = total_astro_incentives {
-        if let Some(user_lp_share) = user_lp_share {
-            user_info.auction_incentive_amount =
-                Some(Decimal::from_ratio(user_lp_share, lp_balance) * total_astro_incentives);
-        }
+    // Incentives aren't final while the staking bounty can still be paid to a keeper
+    if !state.keeper_bounty_reserve.is_zero() {
+        return Ok(());
+    }
+
+    if let Some(user_lp_shares) = user_info.lp_shares {
+        user_info.auction_incentive_amount =
+            match (config.astro_incentive_amount, state.lp_shares_minted) {
+                // The default split keeps following LP shares, as it did before the split was configurable
+                (Some(total_astro_incentives), Some(lp_shares_minted))
+                    if has_default_incentives_split(config) =>
+                {
+                    Some(
+                        Decimal::from_ratio(user_lp_shares, lp_shares_minted)
+                            * total_astro_incentives,
+                    )
+                }
+                _ => calculate_auction_reward_for_user(config, state, user_info),
+            };
     }
     Ok(())
 }
 
+/// Returns true if the incentives are split equally between ASTRO delegators and UST depositors without time-weighting.
+/// ## Params
+/// * **config** is an object of type [`Config`].
+fn has_default_incentives_split(config: &Config) -> bool {
+    config.time_weight.is_none() && config.astro_delegators_incentives_share == Decimal::percent(50)
+}
+
+/// Returns ASTRO auction incentives unlocked for the user and not yet withdrawn.
+/// Incentives are unlocked linearly starting at the pool initialization over
+/// `astro_incentives_vesting_duration` or alongside LP Tokens if it isn't set
+/// ## Params
+/// * **cur_timestamp** is an object of type [`u64`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **state** is an object of type [`State`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+fn calculate_claimable_incentives(
+    cur_timestamp: u64,
+    config: &Config,
+    state: &State,
+    user_info: &UserInfo,
+) -> Uint128 {
+    if let Some(auction_incentive_amount) = user_info.auction_incentive_amount {
+        let vesting_duration = config
+            .astro_incentives_vesting_duration
+            .unwrap_or(config.lp_tokens_vesting_duration);
+        let time_elapsed = cur_timestamp.saturating_sub(state.pool_init_timestamp);
+
+        let unlocked_incentives = if time_elapsed >= vesting_duration {
+            auction_incentive_amount
+        } else {
+            auction_incentive_amount.multiply_ratio(time_elapsed, vesting_duration)
+        };
+        unlocked_incentives.saturating_sub(user_info.claimed_incentives)
+    } else {
+        Uint128::zero()
+    }
+}
+
 /// Withdraws user rewards and LP Tokens if available
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
+/// * **env** is an object of type [`Env`].
+///
 /// * **user_address** is an object of type [`Addr`].
 ///
+/// * **recipient** is an object of type [`Addr`]. Address which receives rewards and LP Tokens.
+///
 /// * **withdraw_lp_shares** is an optional object of type [`Uint128`].
 pub fn callback_withdraw_user_rewards_and_optionally_lp(
-    deps: DepsMut,
+    mut deps: DepsMut,
+    env: Env,
     user_address: Addr,
+    recipient: Addr,
     withdraw_lp_shares: Option<Uint128>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
-    let state = STATE.load(deps.storage)?;
-    let mut user_info = USERS.load(deps.storage, &user_address)?;
+    let mut state = STATE.load(deps.storage)?;
+    let mut user_info = USERS.compatible_load_mut(deps.branch(), &user_address)?;
 
     let mut cosmos_msgs = vec![];
     let mut attributes = vec![
         attr("action", "Withdraw rewards and lp tokens"),
         attr("user_address", &user_address),
+        attr("recipient", &recipient),
     ];
 
     if let Some(PoolInfo {
@@ -1002,43 +3089,71 @@ pub fn callback_withdraw_user_rewards_and_optionally_lp(
             .ok_or_else(|| StdError::generic_err("Lp share should be calculated"))?;
 
         let astroport_lp_amount = user_lp_shares - user_info.claimed_lp_shares;
-
-        if state.is_lp_staked {
-            let generator = config
-                .generator_contract
-                .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
-
-            let rwi: RewardInfoResponse = deps.querier.query_wasm_smart(
-                &generator,
-                &GenQueryMsg::RewardInfo {
-                    lp_token: astro_ust_lp_token_address.to_string(),
-                },
-            )?;
-
-            // Calculate ASTRO staking reward receivable by the user
-            let pending_astro_rewards = (state.generator_astro_per_share * astroport_lp_amount)
+        let claimable_incentives =
+            calculate_claimable_incentives(env.block.time.seconds(), &config, &state, &user_info);
+
+        // Calculate ASTRO staking reward receivable by the user. Rewards of users who joined the compounding pool
+        // are compounded, except for rewards accrued by withdrawn LP shares since the last compounding round.
+        // Rewards claimed from the generator stay receivable after LP Tokens are unstaked in emergency
+        let mut pending_astro_rewards =
+            settle_compound_position(deps.storage, &state, &mut user_info, astroport_lp_amount)?;
+        if is_compound_joined(&state, &user_info) {
+            if let Some(withdrawn_lp_shares) = withdraw_lp_shares {
+                pending_astro_rewards += (state.generator_astro_per_share * withdrawn_lp_shares)
+                    - (state.compound_astro_per_share * withdrawn_lp_shares);
+                state.compounding_lp_shares -= withdrawn_lp_shares;
+            }
+        } else {
+            pending_astro_rewards += (state.generator_astro_per_share * astroport_lp_amount)
                 - (user_info.user_gen_astro_per_share * astroport_lp_amount);
             user_info.user_gen_astro_per_share = state.generator_astro_per_share;
-            user_info.generator_astro_debt += pending_astro_rewards;
-
-            // If no rewards / LP tokens to be claimed
-            if pending_astro_rewards == Uint128::zero()
-                && user_info.astro_incentive_transferred
-                && withdraw_lp_shares.is_none()
-            {
-                return Err(StdError::generic_err("Nothing to claim!"));
+            if user_info.compounding {
+                if let Some(withdrawn_lp_shares) = withdraw_lp_shares {
+                    state.pending_compounding_lp_shares -= withdrawn_lp_shares;
+                }
             }
+        }
+        user_info.generator_astro_debt += pending_astro_rewards;
+
+        // Calculate Proxy staking rewards receivable by the user
+        let pending_proxy_rewards =
+            settle_generator_proxy_rewards(&state, &mut user_info, astroport_lp_amount)?;
+
+        // If no rewards / LP tokens to be claimed
+        if pending_astro_rewards == Uint128::zero()
+            && pending_proxy_rewards.is_empty()
+            && claimable_incentives.is_zero()
+            && withdraw_lp_shares.is_none()
+        {
+            return Err(StdError::generic_err("Nothing to claim!"));
+        }
 
-            // COSMOS MSG ::: CLAIM Pending Generator ASTRO Rewards
+        // COSMOS MSG ::: CLAIM Pending Generator ASTRO Rewards
+        if !pending_astro_rewards.is_zero() {
             cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: rwi.base_reward_token.to_string(),
+                contract_addr: config.astro_token_address.to_string(),
                 funds: vec![],
                 msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: user_address.to_string(),
+                    recipient: recipient.to_string(),
                     amount: pending_astro_rewards,
                 })?,
             }));
-            attributes.push(attr("generator_astro_reward", pending_astro_rewards));
+        }
+        attributes.push(attr("generator_astro_reward", pending_astro_rewards));
+
+        // COSMOS MSG ::: CLAIM Pending Generator Proxy Rewards
+        for pending_proxy_reward in pending_proxy_rewards {
+            attributes.push(attr(
+                "generator_proxy_reward",
+                pending_proxy_reward.to_string(),
+            ));
+            cosmos_msgs.push(pending_proxy_reward.into_msg(&deps.querier, recipient.clone())?);
+        }
+
+        if state.is_lp_staked {
+            let generator = config
+                .generator_contract
+                .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
 
             //  COSMOS MSG :: If LP Tokens are staked, we unstake the amount which needs to be returned to the user
             if let Some(withdrawn_lp_shares) = withdraw_lp_shares {
@@ -1053,43 +3168,88 @@ pub fn callback_withdraw_user_rewards_and_optionally_lp(
             }
         }
 
-        // Transfer ASTRO incentives when they have been calculated
-        if user_info.auction_incentive_amount.is_some() && !user_info.astro_incentive_transferred {
+        // Transfer unlocked ASTRO incentives
+        if !claimable_incentives.is_zero() {
             cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: config.astro_token_address.to_string(),
                 funds: vec![],
                 msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: user_address.to_string(),
-                    amount: user_info.auction_incentive_amount.unwrap(),
+                    recipient: recipient.to_string(),
+                    amount: claimable_incentives,
                 })?,
             }));
-            user_info.astro_incentive_transferred = true;
-            attributes.push(attr(
-                "auction_astro_reward",
-                user_info.auction_incentive_amount.unwrap(),
-            ));
+            user_info.claimed_incentives += claimable_incentives;
+            attributes.push(attr("auction_astro_reward", claimable_incentives));
         }
 
         if let Some(withdrawn_lp_shares) = withdraw_lp_shares {
             cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: astro_ust_lp_token_address.to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: user_address.to_string(),
-                    amount: withdrawn_lp_shares,
-                })?,
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: withdrawn_lp_shares,
+                })?,
+                funds: vec![],
+            }));
+            attributes.push(attr("lp_withdrawn", withdrawn_lp_shares));
+            user_info.claimed_lp_shares += withdrawn_lp_shares;
+        }
+        USERS.save(deps.storage, &user_address, &user_info)?;
+        STATE.save(deps.storage, &state)?;
+    } else {
+        return Err(StdError::generic_err("Pool info isn't set yet!"));
+    }
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(attributes))
+}
+
+/// Stakes LP Tokens unstaked from the previous generator in the new one and saves the new generator in the config.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **generator** is an object of type [`Addr`]. New generator contract address.
+///
+/// * **lp_amount** is an object of type [`Uint128`]. Amount of LP Tokens unstaked from the previous generator.
+pub fn callback_migrate_generator(
+    deps: DepsMut,
+    generator: Addr,
+    lp_amount: Uint128,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if let Some(PoolInfo {
+        astro_ust_pool_address: _,
+        astro_ust_lp_token_address,
+    }) = &config.pool_info
+    {
+        let mut cosmos_msgs = vec![];
+        if !lp_amount.is_zero() {
+            cosmos_msgs.push(WasmMsg::Execute {
+                contract_addr: astro_ust_lp_token_address.to_string(),
                 funds: vec![],
-            }));
-            attributes.push(attr("lp_withdrawn", withdrawn_lp_shares));
-            user_info.claimed_lp_shares += withdrawn_lp_shares;
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: generator.to_string(),
+                    msg: to_binary(&astroport::generator::Cw20HookMsg::Deposit {})?,
+                    amount: lp_amount,
+                })?,
+            });
         }
-        USERS.save(deps.storage, &user_address, &user_info)?;
+
+        config.generator_contract = Some(generator.clone());
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new()
+            .add_messages(cosmos_msgs)
+            .add_attributes(vec![
+                attr("action", "Auction::CallbackMsg::MigrateGenerator"),
+                attr("generator", generator),
+                attr("staked_amount", lp_amount),
+            ]))
     } else {
-        return Err(StdError::generic_err("Pool info isn't set yet!"));
+        Err(StdError::generic_err("Pool info isn't set yet!"))
     }
-
-    Ok(Response::new()
-        .add_messages(cosmos_msgs)
-        .add_attributes(attributes))
 }
 
 /// Updates state after liquidity is added to the ASTRO-UST Pool
@@ -1159,10 +3319,13 @@ pub fn update_state_on_liquidity_addition_to_pool(
 /// * **env** is an object of type [`Env`].
 ///
 /// * **prev_astro_balance** is an object of type [`Uint128`]. Number of ASTRO tokens available with the contract before the claim
+///
+/// * **prev_proxy_reward_balances** is a vector of type [`Asset`]. Contract's Generator Proxy reward token balance before claim.
 pub fn update_state_on_reward_claim(
     deps: DepsMut,
     env: Env,
     prev_astro_balance: Uint128,
+    prev_proxy_reward_balances: Vec<Asset>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
@@ -1203,6 +3366,22 @@ pub fn update_state_on_reward_claim(
             Decimal::from_ratio(base_reward_received, lp_balance)
         };
 
+        // Increment claimed Proxy rewards per LP share. Proxy rewards are distributed among
+        // vesting LP shares only, as LP shares owned by the compounding pool can only compound ASTRO
+        let vesting_lp_balance = lp_balance - state.compounded_lp_shares;
+        if !vesting_lp_balance.is_zero() {
+            for prev_balance in prev_proxy_reward_balances {
+                let current_balance = prev_balance
+                    .info
+                    .query_pool(&deps.querier, env.contract.address.clone())?;
+                let received_amount = current_balance - prev_balance.amount;
+                state.generator_proxy_per_share.update(
+                    &prev_balance.info,
+                    Decimal::from_ratio(received_amount, vesting_lp_balance),
+                )?;
+            }
+        }
+
         // SAVE UPDATED STATE OF THE POOL
         STATE.save(deps.storage, &state)?;
 
@@ -1217,6 +3396,60 @@ pub fn update_state_on_reward_claim(
     }
 }
 
+/// Checks that the sender is the owner or a keeper whose grace period has passed.
+/// Returns true if the sender acts as a keeper.
+/// ## Params
+/// * **env** is an object of type [`Env`].
+///
+/// * **sender** is an object of type [`Addr`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **grace_period_start** is an object of type [`u64`]. Timestamp since which the keeper grace period is counted.
+fn assert_owner_or_keeper(
+    env: &Env,
+    sender: &Addr,
+    config: &Config,
+    grace_period_start: u64,
+) -> StdResult<bool> {
+    if *sender == config.owner {
+        return Ok(false);
+    }
+
+    match &config.keeper {
+        Some(keeper) if env.block.time.seconds() >= grace_period_start + keeper.grace_period => {
+            Ok(true)
+        }
+        _ => Err(StdError::generic_err("Unauthorized")),
+    }
+}
+
+/// Validates the slippage tolerance used for the ASTRO-UST Pool initialization.
+/// ## Params
+/// * **slippage** is an object of type [`Decimal`].
+fn validate_init_pool_slippage(slippage: Decimal) -> StdResult<()> {
+    if slippage > Decimal::percent(MAX_INIT_POOL_SLIPPAGE) {
+        return Err(StdError::generic_err(format!(
+            "Slippage should be less than or equal to {}%",
+            MAX_INIT_POOL_SLIPPAGE
+        )));
+    }
+    Ok(())
+}
+
+/// Validates the slippage used for compounding.
+/// ## Params
+/// * **slippage** is an object of type [`Decimal`].
+fn validate_compound_slippage(slippage: Decimal) -> StdResult<()> {
+    if slippage > Decimal::percent(MAX_COMPOUND_SLIPPAGE) {
+        return Err(StdError::generic_err(format!(
+            "Slippage should be less than or equal to {}%",
+            MAX_COMPOUND_SLIPPAGE
+        )));
+    }
+    Ok(())
+}
+
 /// Returns true if the deposit & withdrawal windows are closed, else returns false
 /// ## Params
 /// * **current_timestamp** is an object of type [`u64`].
@@ -1227,6 +3460,103 @@ fn are_windows_closed(current_timestamp: u64, config: &Config) -> bool {
     current_timestamp >= window_end
 }
 
+/// Validates LP Tokens vesting schedule. Points should be sorted by their timestamp offsets,
+/// unlock a non-decreasing cumulative percent and the last point should unlock 100% of LP Tokens.
+/// ## Params
+/// * **schedule** is an array of `(timestamp_offset, cumulative_percent)` points.
+fn validate_lp_vesting_schedule(schedule: &[(u64, Decimal)]) -> StdResult<()> {
+    match schedule.last() {
+        Some((_, cumulative_percent)) if *cumulative_percent == Decimal::one() => {}
+        _ => {
+            return Err(StdError::generic_err(
+                "LP vesting schedule should end with 100% unlocked",
+            ))
+        }
+    }
+
+    for points in schedule.windows(2) {
+        let (prev_offset, prev_percent) = points[0];
+        let (offset, percent) = points[1];
+        if offset <= prev_offset || percent < prev_percent {
+            return Err(StdError::generic_err(
+                "LP vesting schedule points should be sorted by timestamp and percent",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the cumulative percent unlocked by the vesting schedule after `time_elapsed` seconds.
+/// Nothing is unlocked before the first point, the unlocked percent grows linearly between points.
+/// ## Params
+/// * **schedule** is an array of `(timestamp_offset, cumulative_percent)` points.
+///
+/// * **time_elapsed** is an object of type [`u64`]. Number of seconds since the pool initialization
+fn vested_percent(schedule: &[(u64, Decimal)], time_elapsed: u64) -> Decimal {
+    let mut prev_point: Option<(u64, Decimal)> = None;
+    for &(offset, percent) in schedule {
+        if time_elapsed < offset {
+            return match prev_point {
+                None => Decimal::zero(),
+                Some((prev_offset, prev_percent)) => {
+                    prev_percent
+                        + (percent - prev_percent)
+                            * Decimal::from_ratio(time_elapsed - prev_offset, offset - prev_offset)
+                }
+            };
+        }
+        prev_point = Some((offset, percent));
+    }
+
+    Decimal::one()
+}
+
+/// Returns user's LP shares vested by the given timestamp.
+/// If LP vesting schedules are set, user's LP shares received for delegated ASTRO and for deposited UST
+/// follow their own schedules, otherwise all LP shares are vested linearly over `lp_tokens_vesting_duration`.
+/// ## Params
+/// * **timestamp** is an object of type [`u64`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **state** is an object of type [`State`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+fn calculate_vested_lp_shares(
+    timestamp: u64,
+    config: &Config,
+    state: &State,
+    user_info: &UserInfo,
+) -> Option<Uint128> {
+    let user_lp_shares = user_info.lp_shares?;
+    let time_elapsed = timestamp.saturating_sub(state.pool_init_timestamp);
+
+    match &config.lp_vesting_schedules {
+        Some(LpVestingSchedules {
+            astro_delegators,
+            ust_depositors,
+        }) => {
+            let ust_lp_shares = user_lp_shares - user_info.astro_lp_shares;
+
+            Some(
+                user_info.astro_lp_shares * vested_percent(astro_delegators, time_elapsed)
+                    + ust_lp_shares * vested_percent(ust_depositors, time_elapsed),
+            )
+        }
+        None => {
+            if time_elapsed >= config.lp_tokens_vesting_duration {
+                Some(user_lp_shares)
+            } else {
+                Some(
+                    user_lp_shares
+                        * Decimal::from_ratio(time_elapsed, config.lp_tokens_vesting_duration),
+                )
+            }
+        }
+    }
+}
+
 /// Returns LP Balance  that a user can withdraw based on a vesting schedule
 /// ## Params
 /// * **cur_timestamp** is an object of type [`u64`].
@@ -1242,18 +3572,231 @@ pub fn calculate_withdrawable_lp_shares(
     state: &State,
     user_info: &UserInfo,
 ) -> StdResult<Option<Uint128>> {
-    if let Some(user_lp_shares) = user_info.lp_shares {
-        let time_elapsed = cur_timestamp - state.pool_init_timestamp;
-        if time_elapsed >= config.lp_tokens_vesting_duration {
-            return Ok(Some(user_lp_shares - user_info.claimed_lp_shares));
+    calculate_vested_lp_shares(cur_timestamp, config, state, user_info)
+        .map(|vested_lp_shares| {
+            vested_lp_shares
+                .checked_sub(user_info.claimed_lp_shares)
+                .map_err(StdError::from)
+        })
+        .transpose()
+}
+
+/// Returns the timestamp of the next LP vesting schedule point and user's LP shares unlocked by then
+/// ## Params
+/// * **cur_timestamp** is an object of type [`u64`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **state** is an object of type [`State`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+fn calculate_next_lp_unlock(
+    cur_timestamp: u64,
+    config: &Config,
+    state: &State,
+    user_info: &UserInfo,
+) -> Option<(u64, Uint128)> {
+    let time_elapsed = cur_timestamp.saturating_sub(state.pool_init_timestamp);
+
+    let next_offset = match &config.lp_vesting_schedules {
+        Some(schedules) => schedules
+            .astro_delegators
+            .iter()
+            .chain(schedules.ust_depositors.iter())
+            .map(|(offset, _)| *offset)
+            .filter(|offset| *offset > time_elapsed)
+            .min(),
+        None => Some(config.lp_tokens_vesting_duration).filter(|offset| *offset > time_elapsed),
+    }?;
+
+    let next_unlock_timestamp = state.pool_init_timestamp + next_offset;
+    let vested_now = calculate_vested_lp_shares(cur_timestamp, config, state, user_info)?;
+    let vested_next = calculate_vested_lp_shares(next_unlock_timestamp, config, state, user_info)?;
+
+    Some((next_unlock_timestamp, vested_next - vested_now))
+}
+
+/// Returns metadata of the position NFT which is the position info of the user.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **token_id** is an object of type [`String`]. Position NFT ID which is the user address.
+fn query_position_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+) -> StdResult<NftInfoResponse<UserInfoResponse>> {
+    let user_address = addr_validate_to_lower(deps.api, &token_id)?;
+    USERS.compatible_load(deps, &user_address)?;
+
+    Ok(NftInfoResponse {
+        token_uri: None,
+        extension: query_user_info(deps, env, token_id)?,
+    })
+}
+
+/// Returns operators approved by the given address.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **address** is an object of type [`String`].
+fn query_operators(deps: Deps, address: String) -> StdResult<Vec<Addr>> {
+    let address = addr_validate_to_lower(deps.api, &address)?;
+
+    OPERATORS
+        .prefix(&address)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+/// Returns the current phase of the auction, timestamps of its boundaries and the percent of deposits
+/// which can be withdrawn now and at the requested timestamp.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **timestamp** is an [`Option`] of type [`u64`]. Timestamp to project the allowed withdrawal percent for.
+fn query_phase(deps: Deps, env: Env, timestamp: Option<u64>) -> StdResult<PhaseResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let current_timestamp = env.block.time.seconds();
+
+    let phase = if current_timestamp < config.init_timestamp {
+        AuctionPhase::NotStarted
+    } else if is_deposit_open(current_timestamp, &config) {
+        AuctionPhase::Deposit
+    } else if !are_windows_closed(current_timestamp, &config) {
+        AuctionPhase::WithdrawOnly
+    } else if state.is_lp_staked {
+        AuctionPhase::Staked
+    } else if state.lp_shares_minted.is_some() {
+        AuctionPhase::PoolInitialized
+    } else {
+        AuctionPhase::Closed
+    };
+
+    let deposit_end_timestamp = config.init_timestamp + config.deposit_window;
+
+    // Nothing can be withdrawn before the auction starts
+    let withdrawal_percent = |timestamp: u64| {
+        if timestamp < config.init_timestamp {
+            Decimal::zero()
+        } else {
+            allowed_withdrawal_percent(timestamp, &config)
         }
+    };
+
+    Ok(PhaseResponse {
+        phase,
+        deposit_start_timestamp: config.init_timestamp,
+        deposit_end_timestamp,
+        withdrawal_decrease_timestamp: deposit_end_timestamp + config.withdrawal_window / 2,
+        withdrawal_end_timestamp: deposit_end_timestamp + config.withdrawal_window,
+        pool_init_timestamp: state.lp_shares_minted.map(|_| state.pool_init_timestamp),
+        withdrawal_percent: withdrawal_percent(current_timestamp),
+        projected_withdrawal_percent: timestamp.map(withdrawal_percent),
+    })
+}
+
+/// Simulates liquidity addition to the Astroport ASTRO-UST Pool with the amounts [`handle_init_pool`] would provide.
+/// LP tokens are estimated from the pair's reserves and total share. The pair has no query simulating
+/// liquidity provision, so the initial share of an empty pair follows the pair's `sqrt(astro * ust)` formula.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+fn query_simulate_init_pool(deps: Deps, env: Env) -> StdResult<SimulateInitPoolResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    let PoolInfo {
+        astro_ust_pool_address,
+        astro_ust_lp_token_address: _,
+    } = config
+        .pool_info
+        .ok_or_else(|| StdError::generic_err("Pool info isn't set yet!"))?;
+
+    let ust_coin = deps
+        .querier
+        .query_balance(&env.contract.address, UUSD_DENOM)?;
+    let [ust, astro] = build_provided_liquidity_assets(
+        deps,
+        config.astro_token_address.clone(),
+        ust_coin.amount,
+        state.total_astro_delegated,
+    )?;
+
+    // Reserves which are already held by the pair
+    let pool: PoolResponse = deps
+        .querier
+        .query_wasm_smart(&astro_ust_pool_address, &AstroportPairQueryMsg::Pool {})?;
+    let reserve = |info: &AssetInfo| {
+        pool.assets
+            .iter()
+            .find(|asset| asset.info == *info)
+            .map(|asset| asset.amount)
+            .unwrap_or_default()
+    };
+    let pool_astro = reserve(&astro.info);
+    let pool_ust = reserve(&ust.info);
 
-        let withdrawable_lp_balance =
-            user_lp_shares * Decimal::from_ratio(time_elapsed, config.lp_tokens_vesting_duration);
-        Ok(Some(withdrawable_lp_balance - user_info.claimed_lp_shares))
+    let lp_shares = if pool.total_share.is_zero() {
+        integer_sqrt(ust.amount.full_mul(astro.amount))
+    } else if pool_astro.is_zero() || pool_ust.is_zero() {
+        return Err(StdError::generic_err(
+            "Pool has LP tokens but no ASTRO or UST reserves",
+        ));
     } else {
-        Ok(None)
+        std::cmp::min(
+            ust.amount.multiply_ratio(pool.total_share, pool_ust),
+            astro.amount.multiply_ratio(pool.total_share, pool_astro),
+        )
+    };
+
+    // LP tokens are split between ASTRO delegators and UST depositors as in [`update_user_lp_shares`]
+    let astro_delegators_lp_shares =
+        cohort_lp_share(state.total_astro_delegated, state.total_astro_delegated) * lp_shares;
+    let ust_depositors_lp_shares =
+        cohort_lp_share(state.total_ust_delegated, state.total_ust_delegated) * lp_shares;
+
+    Ok(SimulateInitPoolResponse {
+        astro_amount: astro.amount,
+        ust_amount: ust.amount,
+        initial_price: if astro.amount.is_zero() {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(ust.amount, astro.amount)
+        },
+        lp_shares,
+        astro_delegators_lp_shares,
+        ust_depositors_lp_shares,
+        pool_has_liquidity: !pool.total_share.is_zero()
+            || !pool_astro.is_zero()
+            || !pool_ust.is_zero(),
+    })
+}
+
+/// Returns the integer square root of the value rounded down.
+/// ## Params
+/// * **value** is an object of type [`Uint256`].
+fn integer_sqrt(value: Uint256) -> Uint128 {
+    if value.is_zero() {
+        return Uint128::zero();
+    }
+
+    // Newton's method starting from a value which is not less than the root
+    let mut x = value;
+    let mut y = (x + Uint256::from(1u8)) / Uint256::from(2u8);
+    while y < x {
+        x = y;
+        y = (x + value / x) / Uint256::from(2u8);
     }
+
+    // The root of a product of two Uint128 numbers always fits into Uint128
+    Uint128::try_from(x).unwrap_or(Uint128::MAX)
 }
 
 /// Returns User's Info
@@ -1268,22 +3811,40 @@ fn query_user_info(deps: Deps, env: Env, user_address: String) -> StdResult<User
     let mut state = STATE.load(deps.storage)?;
     let user_address = addr_validate_to_lower(deps.api, &user_address)?;
     let mut user_info = USERS
-        .may_load(deps.storage, &user_address)?
+        .compatible_may_load(deps, &user_address)?
         .unwrap_or_default();
+    // Sources of ASTRO delegated by v1.0.0 users are set once they are updated
+    if is_user_v100(deps.storage, &user_address)? {
+        set_delegation_sources(deps, &user_address, &mut user_info)?;
+    }
 
     // User Info Response
     let mut user_info_response = UserInfoResponse {
         astro_delegated: user_info.astro_delegated,
         ust_delegated: user_info.ust_delegated,
+        astro_weighted: user_info.astro_weighted,
+        ust_weighted: user_info.ust_weighted,
         ust_withdrawn: user_info.ust_withdrawn,
+        astro_undelegated: user_info.astro_undelegated,
+        airdrop_astro_delegated: user_info.airdrop_astro_delegated,
+        lockdrop_astro_delegated: user_info.lockdrop_astro_delegated,
         lp_shares: user_info.lp_shares,
         claimed_lp_shares: user_info.claimed_lp_shares,
         withdrawable_lp_shares: None,
+        next_lp_unlock_timestamp: None,
+        next_lp_unlock_amount: None,
         auction_incentive_amount: user_info.auction_incentive_amount,
-        astro_incentive_transferred: user_info.astro_incentive_transferred,
+        claimed_incentives: user_info.claimed_incentives,
+        claimable_incentives: Uint128::zero(),
         generator_astro_debt: user_info.generator_astro_debt,
         claimable_generator_astro: Uint128::zero(),
         user_gen_astro_per_share: user_info.user_gen_astro_per_share,
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        generator_proxy_debt: user_info.generator_proxy_debt.clone(),
+        user_gen_proxy_per_share: user_info.user_gen_proxy_per_share.clone(),
+        compounding: user_info.compounding,
+        compound_units: user_info.compound_units,
+        compounded_lp_shares: Uint128::zero(),
     };
 
     // If ASTRO - UST Pool info is present
@@ -1303,48 +3864,94 @@ fn query_user_info(deps: Deps, env: Env, user_address: String) -> StdResult<User
             if config.astro_incentive_amount.is_some()
                 && user_info.auction_incentive_amount.is_none()
             {
-                update_user_astro_incentives(
-                    config.astro_incentive_amount,
-                    user_info.lp_shares,
-                    lp_balance,
-                    &mut user_info,
-                )?;
+                update_user_astro_incentives(&config, &state, &mut user_info)?;
                 user_info_response.auction_incentive_amount = user_info.auction_incentive_amount;
             }
             let astroport_lp_amount = user_info.lp_shares.unwrap() - user_info.claimed_lp_shares;
-            // If LP tokens are staked and user has a > 0 LP share balance, we calculate user's claimable ASTRO staking rewards
-            if state.is_lp_staked && !astroport_lp_amount.is_zero() {
-                let generator = config
-                    .generator_contract
-                    .clone()
-                    .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
-                // Auction contract's staked LP balance
-                let lp_balance: Uint128 = deps.querier.query_wasm_smart(
-                    &generator,
-                    &GenQueryMsg::Deposit {
-                        lp_token: astro_ust_lp_token_address.to_string(),
-                        user: env.contract.address.to_string(),
-                    },
-                )?;
+            let compound_astro_rewards = settle_compound_position(
+                deps.storage,
+                &state,
+                &mut user_info,
+                astroport_lp_amount,
+            )?;
+            user_info_response.compound_units = user_info.compound_units;
+            if !state.total_compound_units.is_zero() {
+                user_info_response.compounded_lp_shares = state
+                    .compounded_lp_shares
+                    .multiply_ratio(user_info.compound_units, state.total_compound_units);
+            }
 
-                // QUERY :: Check if there are any pending staking rewards
-                let pending_rewards: PendingTokenResponse = deps.querier.query_wasm_smart(
-                    &generator,
-                    &GenQueryMsg::PendingToken {
-                        lp_token: astro_ust_lp_token_address.to_string(),
-                        user: env.contract.address.to_string(),
-                    },
-                )?;
+            // If user has a > 0 LP share balance, we calculate user's claimable ASTRO staking rewards. Rewards pending
+            // in the generator are accounted only while LP tokens are staked
+            if !astroport_lp_amount.is_zero() {
+                if state.is_lp_staked {
+                    let generator = config
+                        .generator_contract
+                        .clone()
+                        .ok_or_else(|| StdError::generic_err("Generator should be set!"))?;
+                    // Auction contract's staked LP balance
+                    let lp_balance: Uint128 = deps.querier.query_wasm_smart(
+                        &generator,
+                        &GenQueryMsg::Deposit {
+                            lp_token: astro_ust_lp_token_address.to_string(),
+                            user: env.contract.address.to_string(),
+                        },
+                    )?;
+
+                    // QUERY :: Check if there are any pending staking rewards
+                    let pending_rewards: PendingTokenResponse = deps.querier.query_wasm_smart(
+                        &generator,
+                        &GenQueryMsg::PendingToken {
+                            lp_token: astro_ust_lp_token_address.to_string(),
+                            user: env.contract.address.to_string(),
+                        },
+                    )?;
+
+                    state.generator_astro_per_share +=
+                        Decimal::from_ratio(pending_rewards.pending, lp_balance);
+
+                    let vesting_lp_balance = lp_balance - state.compounded_lp_shares;
+                    for reward in pending_rewards.pending_on_proxy.unwrap_or_default() {
+                        state.generator_proxy_per_share.update(
+                            &reward.info,
+                            Decimal::from_ratio(reward.amount, vesting_lp_balance),
+                        )?;
+                    }
+                }
 
-                state.generator_astro_per_share +=
-                    Decimal::from_ratio(pending_rewards.pending, lp_balance);
+                // Calculated claimable ASTRO staking rewards. Rewards of users who joined the compounding pool are compounded
+                user_info_response.claimable_generator_astro =
+                    if is_compound_joined(&state, &user_info) {
+                        compound_astro_rewards
+                    } else {
+                        compound_astro_rewards
+                            + (state.generator_astro_per_share * astroport_lp_amount)
+                            - (user_info.user_gen_astro_per_share * astroport_lp_amount)
+                    };
 
-                // Calculated claimable ASTRO staking rewards
-                user_info_response.claimable_generator_astro = (state.generator_astro_per_share
-                    * astroport_lp_amount)
-                    - (user_info.user_gen_astro_per_share * astroport_lp_amount);
+                // Calculated claimable Proxy staking rewards
+                for (asset, generator_proxy_per_share) in
+                    state.generator_proxy_per_share.inner_ref()
+                {
+                    let user_gen_proxy_per_share = user_info
+                        .user_gen_proxy_per_share
+                        .load(asset)
+                        .unwrap_or_default();
+                    user_info_response.claimable_generator_proxy_debt.update(
+                        asset,
+                        (*generator_proxy_per_share * astroport_lp_amount)
+                            - (user_gen_proxy_per_share * astroport_lp_amount),
+                    )?;
+                }
             }
 
+            user_info_response.claimable_incentives = calculate_claimable_incentives(
+                env.block.time.seconds(),
+                &config,
+                &state,
+                &user_info,
+            );
+
             // Updated withdrawable LP shares balance
             user_info_response.withdrawable_lp_shares = calculate_withdrawable_lp_shares(
                 env.block.time.seconds(),
@@ -1352,47 +3959,222 @@ fn query_user_info(deps: Deps, env: Env, user_address: String) -> StdResult<User
                 &state,
                 &user_info,
             )?;
+
+            if let Some((next_unlock_timestamp, next_unlock_amount)) =
+                calculate_next_lp_unlock(env.block.time.seconds(), &config, &state, &user_info)
+            {
+                user_info_response.next_lp_unlock_timestamp = Some(next_unlock_timestamp);
+                user_info_response.next_lp_unlock_amount = Some(next_unlock_amount);
+            }
         }
     }
 
     if user_info_response.auction_incentive_amount.is_none() {
         user_info_response.auction_incentive_amount =
-            calculate_auction_reward_for_user(&state, &user_info, config.astro_incentive_amount);
+            calculate_auction_reward_for_user(&config, &state, &user_info);
     }
 
     Ok(user_info_response)
 }
+
+/// Returns positions of users ordered by their addresses. Positions are resolved the same way as in [`query_user_info`].
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **start_after** is an [`Option`] of type [`String`]. Address after which users are returned.
+///
+/// * **limit** is an [`Option`] of type [`u32`]. Maximum number of returned users.
+fn query_users(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<UserInfoWithAddressResponse>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|v| addr_validate_to_lower(deps.api, &v))
+        .transpose()?;
+
+    USERS
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|address| {
+            let address = address?;
+            Ok(UserInfoWithAddressResponse {
+                user_info: query_user_info(deps, env.clone(), address.to_string())?,
+                address,
+            })
+        })
+        .collect()
+}
+
+/// Sums a page of users' positions. The sums are checked against the contract's state if the page covers all users,
+/// otherwise sums of all pages should be checked against the contract's state by the client.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **start_after** is an [`Option`] of type [`String`]. Address of the last summed user of the previous page.
+///
+/// * **limit** is an [`Option`] of type [`u32`]. Maximum number of summed users.
+fn query_totals(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TotalsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|v| addr_validate_to_lower(deps.api, &v))
+        .transpose()?;
+
+    let mut users = USERS
+        .keys(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let is_last_page = users.len() <= limit;
+    users.truncate(limit);
+
+    let mut totals = TotalsResponse {
+        users_count: 0,
+        astro_delegated: Uint128::zero(),
+        ust_delegated: Uint128::zero(),
+        astro_weighted: Uint128::zero(),
+        ust_weighted: Uint128::zero(),
+        lp_shares: Uint128::zero(),
+        auction_incentive_amount: Uint128::zero(),
+        compound_units: Uint128::zero(),
+        compounded_lp_shares: Uint128::zero(),
+        last_user: if is_last_page {
+            None
+        } else {
+            users.last().cloned()
+        },
+        is_consistent: None,
+    };
+
+    for user_address in users {
+        let mut user_info = USERS.compatible_load(deps, &user_address)?;
+
+        if let Some(lp_balance) = state.lp_shares_minted {
+            if user_info.lp_shares.is_none() {
+                update_user_lp_shares(&state, lp_balance, &mut user_info)?;
+            }
+            let astroport_lp_amount =
+                user_info.lp_shares.unwrap_or_default() - user_info.claimed_lp_shares;
+            settle_compound_position(deps.storage, &state, &mut user_info, astroport_lp_amount)?;
+        }
+
+        totals.users_count += 1;
+        totals.astro_delegated += user_info.astro_delegated;
+        totals.ust_delegated += user_info.ust_delegated;
+        totals.astro_weighted += user_info.astro_weighted;
+        totals.ust_weighted += user_info.ust_weighted;
+        totals.lp_shares += user_info.lp_shares.unwrap_or_default();
+        totals.auction_incentive_amount += user_info
+            .auction_incentive_amount
+            .or_else(|| calculate_auction_reward_for_user(&config, &state, &user_info))
+            .unwrap_or_default();
+        totals.compound_units += user_info.compound_units;
+        if !state.total_compound_units.is_zero() {
+            totals.compounded_lp_shares += state
+                .compounded_lp_shares
+                .multiply_ratio(user_info.compound_units, state.total_compound_units);
+        }
+    }
+
+    // The sums can be checked against the contract's state only if all users are summed in the page
+    if start_after.is_none() && is_last_page {
+        totals.is_consistent = Some(
+            totals.astro_delegated == state.total_astro_delegated
+                && totals.ust_delegated == state.total_ust_delegated
+                && totals.astro_weighted == state.total_astro_weighted
+                && totals.ust_weighted == state.total_ust_weighted
+                && totals.lp_shares <= state.lp_shares_minted.unwrap_or_default()
+                && totals.auction_incentive_amount
+                    <= config.astro_incentive_amount.unwrap_or_default()
+                && totals.compound_units <= state.total_compound_units
+                && totals.compounded_lp_shares <= state.compounded_lp_shares,
+        );
+    }
+
+    Ok(totals)
+}
+
+/// Returns the UST deposit cap of an address which has already proven its allowlisting.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **address** is an object of type [`String`].
+fn query_allowlist_cap(deps: Deps, address: String) -> StdResult<Option<Uint128>> {
+    let address = addr_validate_to_lower(deps.api, &address)?;
+    ALLOWLIST.may_load(deps.storage, &address)
+}
+
 /// Calculates ASTRO tokens receivable by a user for participating (providing UST & ASTRO) in the bootstraping phase of the ASTRO-UST Pool
+/// User's auction incentives (ASTRO) = (User's weighted ASTRO delegated / Total weighted ASTRO delegated) * ASTRO delegators incentives
+///                                   + (User's weighted UST deposited / Total weighted UST deposited) * UST depositors incentives
 /// ## Params
+/// * **config** is an object of type [`Config`].
+///
 /// * **state** is an object of type [`State`].
 ///
 /// * **user_info** is an object of type [`UserInfo`].
-///
-/// * **total_astro_rewards** is an optional object of type [`Uint128`].
 fn calculate_auction_reward_for_user(
+    config: &Config,
     state: &State,
     user_info: &UserInfo,
-    total_astro_rewards: Option<Uint128>,
 ) -> Option<Uint128> {
     if !user_info.astro_delegated.is_zero() || !user_info.ust_delegated.is_zero() {
-        if let Some(total_astro_rewards) = total_astro_rewards {
+        if let Some(total_astro_rewards) = config.astro_incentive_amount {
+            // The default split keeps the formula used before the split was configurable
+            if has_default_incentives_split(config) {
+                let mut user_astro_incentives = Uint128::zero();
+                if !state.total_astro_delegated.is_zero() {
+                    user_astro_incentives += Decimal::from_ratio(
+                        user_info.astro_delegated,
+                        state.total_astro_delegated * Uint128::new(2),
+                    ) * total_astro_rewards;
+                }
+                if !state.total_ust_delegated.is_zero() {
+                    user_astro_incentives += Decimal::from_ratio(
+                        user_info.ust_delegated,
+                        state.total_ust_delegated * Uint128::new(2),
+                    ) * total_astro_rewards;
+                }
+                return Some(user_astro_incentives);
+            }
+
+            let astro_delegators_rewards =
+                total_astro_rewards * config.astro_delegators_incentives_share;
+            let ust_depositors_rewards = total_astro_rewards - astro_delegators_rewards;
             let mut user_astro_incentives = Uint128::zero();
 
             // ASTRO incentives from ASTRO delegated
-            if state.total_astro_delegated > Uint128::zero() {
-                let astro_incentives_from_astro = Decimal::from_ratio(
-                    user_info.astro_delegated,
-                    state.total_astro_delegated * Uint128::new(2),
-                ) * total_astro_rewards;
+            if !state.total_astro_weighted.is_zero() {
+                let astro_incentives_from_astro =
+                    Decimal::from_ratio(user_info.astro_weighted, state.total_astro_weighted)
+                        * astro_delegators_rewards;
                 user_astro_incentives += astro_incentives_from_astro;
             }
 
             // ASTRO incentives from UST delegated
-            if state.total_ust_delegated > Uint128::zero() {
-                let astro_incentives_from_ust = Decimal::from_ratio(
-                    user_info.ust_delegated,
-                    state.total_ust_delegated * Uint128::new(2),
-                ) * total_astro_rewards;
+            if !state.total_ust_weighted.is_zero() {
+                let astro_incentives_from_ust =
+                    Decimal::from_ratio(user_info.ust_weighted, state.total_ust_weighted)
+                        * ust_depositors_rewards;
                 user_astro_incentives += astro_incentives_from_ust;
             }
             return Some(user_astro_incentives);
@@ -1,2 +1,3 @@
 pub mod contract;
+mod migration;
 pub mod state;
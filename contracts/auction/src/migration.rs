@@ -0,0 +1,134 @@
+use astroport::restricted_vector::RestrictedVector;
+use astroport_periphery::airdrop::{QueryMsg as AirdropQueryMsg, UserInfo as AirdropUserInfo};
+use astroport_periphery::auction::{PoolInfo, UserInfo};
+use astroport_periphery::lockdrop::{QueryMsg as LockdropQueryMsg, UserInfoWithListResponse};
+use cosmwasm_std::{Addr, Decimal, Deps, StdResult, Uint128};
+use cw_storage_plus::{Item, Map};
+
+use crate::state::CONFIG;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigV100 {
+    pub owner: Addr,
+    pub astro_token_address: Addr,
+    pub airdrop_contract_address: Addr,
+    pub lockdrop_contract_address: Addr,
+    pub pool_info: Option<PoolInfo>,
+    pub generator_contract: Option<Addr>,
+    pub astro_incentive_amount: Option<Uint128>,
+    pub lp_tokens_vesting_duration: u64,
+    pub init_timestamp: u64,
+    pub deposit_window: u64,
+    pub withdrawal_window: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateV100 {
+    pub total_astro_delegated: Uint128,
+    pub total_ust_delegated: Uint128,
+    pub is_lp_staked: bool,
+    pub lp_shares_minted: Option<Uint128>,
+    pub pool_init_timestamp: u64,
+    pub generator_astro_per_share: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserInfoV100 {
+    pub astro_delegated: Uint128,
+    pub ust_delegated: Uint128,
+    pub ust_withdrawn: bool,
+    pub lp_shares: Option<Uint128>,
+    pub claimed_lp_shares: Uint128,
+    pub auction_incentive_amount: Option<Uint128>,
+    /// ASTRO incentives were transferred to the user in full
+    pub astro_incentive_transferred: bool,
+    pub generator_astro_debt: Uint128,
+    pub user_gen_astro_per_share: Decimal,
+}
+
+pub const CONFIG_V100: Item<ConfigV100> = Item::new("config");
+pub const STATE_V100: Item<StateV100> = Item::new("state");
+pub const USERS_V100: Map<&Addr, UserInfoV100> = Map::new("users");
+
+impl From<UserInfoV100> for UserInfo {
+    fn from(user_info: UserInfoV100) -> Self {
+        // Users who have already been paid keep their incentives fully claimed
+        let claimed_incentives = if user_info.astro_incentive_transferred {
+            user_info.auction_incentive_amount.unwrap_or_default()
+        } else {
+            Uint128::zero()
+        };
+
+        UserInfo {
+            astro_delegated: user_info.astro_delegated,
+            ust_delegated: user_info.ust_delegated,
+            astro_weighted: user_info.astro_delegated,
+            ust_weighted: user_info.ust_delegated,
+            ust_withdrawn: user_info.ust_withdrawn,
+            // Sources of migrated delegations are set by [`set_delegation_sources`]
+            airdrop_astro_delegated: Uint128::zero(),
+            lockdrop_astro_delegated: Uint128::zero(),
+            astro_undelegated: false,
+            lp_shares: user_info.lp_shares,
+            // LP vesting schedules aren't set for migrated auctions
+            astro_lp_shares: Uint128::zero(),
+            claimed_lp_shares: user_info.claimed_lp_shares,
+            auction_incentive_amount: user_info.auction_incentive_amount,
+            claimed_incentives,
+            generator_astro_debt: user_info.generator_astro_debt,
+            user_gen_astro_per_share: user_info.user_gen_astro_per_share,
+            generator_proxy_debt: RestrictedVector::default(),
+            user_gen_proxy_per_share: RestrictedVector::default(),
+            compounding: false,
+            compound_join_round: 0,
+            compound_units: Uint128::zero(),
+            user_compound_units_per_share: Decimal::zero(),
+        }
+    }
+}
+
+/// Sets sources of ASTRO delegated by a v1.0.0 user from the ASTRO delegated amounts stored by the airdrop and
+/// lockdrop contracts, so the user can undelegate ASTRO back to them. The sources are set once, when the converted
+/// user is saved for the first time.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **user_address** is an object of type [`Addr`].
+///
+/// * **user_info** is an object of type [`UserInfo`]. User info converted from the v1.0.0 user info.
+pub fn set_delegation_sources(
+    deps: Deps,
+    user_address: &Addr,
+    user_info: &mut UserInfo,
+) -> StdResult<()> {
+    if user_info.astro_delegated.is_zero() {
+        return Ok(());
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    let airdrop_user_info: AirdropUserInfo = deps.querier.query_wasm_smart(
+        &config.airdrop_contract_address,
+        &AirdropQueryMsg::UserInfo {
+            address: user_address.to_string(),
+        },
+    )?;
+    let lockdrop_user_info: UserInfoWithListResponse = deps.querier.query_wasm_smart(
+        &config.lockdrop_contract_address,
+        &LockdropQueryMsg::UserInfoWithLockupsList {
+            address: user_address.to_string(),
+        },
+    )?;
+
+    user_info.airdrop_astro_delegated = airdrop_user_info
+        .delegated_amount
+        .min(user_info.astro_delegated);
+    user_info.lockdrop_astro_delegated = lockdrop_user_info
+        .delegated_astro_rewards
+        .min(user_info.astro_delegated - user_info.airdrop_astro_delegated);
+
+    Ok(())
+}
@@ -1,7 +1,86 @@
-use astroport_periphery::auction::{Config, State, UserInfo};
-use cosmwasm_std::Addr;
+use std::any::type_name;
+
+use astroport_periphery::auction::{CompoundRound, Config, State, UserInfo};
+use astroport_periphery::U64Key;
+use cosmwasm_std::{Addr, Deps, DepsMut, Empty, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Item, Map};
 
+use crate::migration::{set_delegation_sources, USERS_V100};
+
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const STATE: Item<State> = Item::new("state");
 pub const USERS: Map<&Addr, UserInfo> = Map::new("users");
+pub const COMPOUND_ROUNDS: Map<U64Key, CompoundRound> = Map::new("compound_rounds");
+/// Operators approved by a position owner to withdraw UST on its behalf
+pub const OPERATORS: Map<(&Addr, &Addr), Empty> = Map::new("operators");
+/// Allowlisted addresses with their UST deposit caps
+pub const ALLOWLIST: Map<&Addr, Uint128> = Map::new("allowlist");
+
+pub trait CompatibleLoader<K, R> {
+    fn compatible_load(&self, deps: Deps, key: K) -> StdResult<R>;
+
+    fn compatible_may_load(&self, deps: Deps, key: K) -> StdResult<Option<R>>;
+
+    fn compatible_load_mut(&self, deps: DepsMut, key: K) -> StdResult<R>;
+
+    fn compatible_may_load_mut(&self, deps: DepsMut, key: K) -> StdResult<Option<R>>;
+}
+
+/// Loads users stored by the v1.0.0 contract converting them to the current [`UserInfo`]
+/// as they can't be migrated in a single transaction. Read-only loads leave sources of ASTRO delegated
+/// by v1.0.0 users unset, while `_mut` loads set them and save the converted user once.
+impl CompatibleLoader<&Addr, UserInfo> for Map<'_, &Addr, UserInfo> {
+    fn compatible_load(&self, deps: Deps, key: &Addr) -> StdResult<UserInfo> {
+        self.compatible_may_load(deps, key)?
+            .ok_or_else(|| StdError::not_found(type_name::<UserInfo>()))
+    }
+
+    fn compatible_may_load(&self, deps: Deps, key: &Addr) -> StdResult<Option<UserInfo>> {
+        Ok(may_load_user(self, deps.storage, key)?.map(|(user_info, _)| user_info))
+    }
+
+    fn compatible_load_mut(&self, deps: DepsMut, key: &Addr) -> StdResult<UserInfo> {
+        self.compatible_may_load_mut(deps, key)?
+            .ok_or_else(|| StdError::not_found(type_name::<UserInfo>()))
+    }
+
+    fn compatible_may_load_mut(&self, deps: DepsMut, key: &Addr) -> StdResult<Option<UserInfo>> {
+        match may_load_user(self, deps.storage, key)? {
+            Some((mut user_info, true)) => {
+                set_delegation_sources(deps.as_ref(), key, &mut user_info)?;
+                self.save(deps.storage, key, &user_info)?;
+                Ok(Some(user_info))
+            }
+            user_info => Ok(user_info.map(|(user_info, _)| user_info)),
+        }
+    }
+}
+
+/// Returns true if the user is stored in the v1.0.0 layout.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **user_address** is an object of type [`Addr`].
+pub fn is_user_v100(storage: &dyn Storage, user_address: &Addr) -> StdResult<bool> {
+    Ok(matches!(
+        may_load_user(&USERS, storage, user_address)?,
+        Some((_, true))
+    ))
+}
+
+/// Loads the user, users stored in the v1.0.0 layout are converted and returned with `true`.
+/// Other deserialization errors aren't hidden.
+fn may_load_user<'a>(
+    users: &Map<'a, &'a Addr, UserInfo>,
+    storage: &dyn Storage,
+    user_address: &'a Addr,
+) -> StdResult<Option<(UserInfo, bool)>> {
+    match users.load(storage, user_address) {
+        Ok(user_info) => Ok(Some((user_info, false))),
+        Err(StdError::NotFound { .. }) => Ok(None),
+        Err(err) => match USERS_V100.may_load(storage, user_address) {
+            Ok(Some(user_info)) => Ok(Some((user_info.into(), true))),
+            _ => Err(err),
+        },
+    }
+}
@@ -1,17 +1,28 @@
-use astroport::asset::{AssetInfo, PairInfo};
+use astroport::asset::{Asset, AssetInfo, PairInfo};
 use astroport::factory::{
     ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg, PairConfig, PairType,
     QueryMsg as FactoryQueryMsg,
 };
-use astroport::generator::ExecuteMsg as GeneratorExecuteMsg;
+use astroport::generator::{
+    ExecuteMsg as GeneratorExecuteMsg, PendingTokenResponse, QueryMsg as GeneratorQueryMsg,
+    RewardInfoResponse,
+};
 use astroport_periphery::auction::{
-    Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, State, UpdateConfigMsg,
-    UserInfoResponse,
+    AllowlistProof, AuctionPhase, Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, KeeperConfig,
+    LpVestingSchedules, MigrateMsg, PhaseResponse, PoolInfo, QueryMsg, SimulateInitPoolResponse,
+    State, TimeWeight, TotalsResponse, UpdateConfigMsg, UserInfoResponse,
+    UserInfoWithAddressResponse,
 };
 
-use cosmwasm_std::{attr, to_binary, Addr, Binary, Coin, Timestamp, Uint128, Uint64};
+use cosmwasm_std::{
+    attr, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Timestamp, Uint128, Uint64, WasmMsg,
+};
 use cw20::Cw20ExecuteMsg;
+use cw721::{NftInfoResponse, OwnerOfResponse};
+use cw721_base::{ContractError as Cw721ContractError, Cw721Contract};
 use cw_multi_test::{App, ContractWrapper, Executor};
+use sha3::{Digest, Keccak256};
 
 const OWNER: &str = "owner";
 
@@ -144,6 +155,53 @@ fn instantiate_astro_token(app: &mut App, owner: Addr) -> Addr {
     .unwrap()
 }
 
+fn position_nft_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::ExecuteMsg<Empty>,
+) -> Result<Response, Cw721ContractError> {
+    Cw721Contract::<Empty, Empty>::default().execute(deps, env, info, msg)
+}
+
+fn position_nft_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::InstantiateMsg,
+) -> StdResult<Response> {
+    Cw721Contract::<Empty, Empty>::default().instantiate(deps, env, info, msg)
+}
+
+fn position_nft_query(deps: Deps, env: Env, msg: cw721_base::QueryMsg) -> StdResult<Binary> {
+    Cw721Contract::<Empty, Empty>::default().query(deps, env, msg)
+}
+
+// Instantiate Position NFT Contract
+fn instantiate_position_nft(app: &mut App, owner: Addr, minter: &Addr) -> Addr {
+    let position_nft_contract = Box::new(ContractWrapper::new(
+        position_nft_execute,
+        position_nft_instantiate,
+        position_nft_query,
+    ));
+
+    let position_nft_code_id = app.store_code(position_nft_contract);
+
+    app.instantiate_contract(
+        position_nft_code_id,
+        owner,
+        &cw721_base::InstantiateMsg {
+            name: String::from("Auction positions"),
+            symbol: String::from("AUCTION"),
+            minter: minter.to_string(),
+        },
+        &[],
+        String::from("Position NFT"),
+        None,
+    )
+    .unwrap()
+}
+
 // Instantiate AUCTION Contract
 fn instantiate_auction_contract(
     app: &mut App,
@@ -167,9 +225,17 @@ fn instantiate_auction_contract(
         airdrop_contract_address: airdrop_instance.to_string(),
         lockdrop_contract_address: lockdrop_instance.to_string(),
         lp_tokens_vesting_duration: 7776000u64,
+        astro_incentives_vesting_duration: None,
+        lp_vesting_schedules: None,
         init_timestamp: 1_000_00,
         deposit_window: 100_000_00,
         withdrawal_window: 5_000_00,
+        astro_delegators_incentives_share: Decimal::percent(50),
+        time_weight: None,
+        keeper: None,
+        init_pool_slippage: None,
+        compound_slippage: None,
+        allowlist_merkle_root: None,
     };
 
     // Init contract
@@ -192,6 +258,10 @@ fn instantiate_auction_contract(
                 astro_ust_pair_address: Some(pair_instance.to_string()),
                 owner: None,
                 generator_contract: None,
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
             },
         },
         &[],
@@ -396,6 +466,7 @@ fn instantiate_airdrop_lockdrop_contracts(
         min_lock_duration: 1u64,
         max_lock_duration: 52u64,
         max_positions_per_user: 24,
+        weight_curve: None,
     };
 
     let airdrop_instance = app
@@ -718,6 +789,230 @@ fn instantiate_generator_and_vesting(
     (generator_instance, vesting_instance)
 }
 
+// Generator mock which pays ASTRO and Proxy rewards at fixed rates per second to the LP Tokens depositor
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+struct MockGeneratorInstantiateMsg {
+    astro_token: Addr,
+    proxy_reward_token: Addr,
+    astro_per_second: Uint128,
+    proxy_reward_per_second: Uint128,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+struct MockGeneratorState {
+    config: MockGeneratorInstantiateMsg,
+    lp_token: Option<Addr>,
+    deposited: Uint128,
+    last_reward_timestamp: u64,
+}
+
+const MOCK_GENERATOR_STATE: cw_storage_plus::Item<MockGeneratorState> =
+    cw_storage_plus::Item::new("state");
+
+fn mock_generator_pending_rewards(state: &MockGeneratorState, env: &Env) -> (Uint128, Uint128) {
+    if state.deposited.is_zero() {
+        return (Uint128::zero(), Uint128::zero());
+    }
+    let time_elapsed = Uint128::from(env.block.time.seconds() - state.last_reward_timestamp);
+    (
+        state.config.astro_per_second * time_elapsed,
+        state.config.proxy_reward_per_second * time_elapsed,
+    )
+}
+
+fn mock_generator_claim_rewards(
+    state: &mut MockGeneratorState,
+    env: &Env,
+    recipient: &Addr,
+) -> StdResult<Vec<WasmMsg>> {
+    let (astro_rewards, proxy_rewards) = mock_generator_pending_rewards(state, env);
+    state.last_reward_timestamp = env.block.time.seconds();
+
+    let mut msgs = vec![];
+    for (token, amount) in [
+        (&state.config.astro_token, astro_rewards),
+        (&state.config.proxy_reward_token, proxy_rewards),
+    ] {
+        if !amount.is_zero() {
+            msgs.push(WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            });
+        }
+    }
+    Ok(msgs)
+}
+
+fn mock_generator_instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: MockGeneratorInstantiateMsg,
+) -> StdResult<Response> {
+    MOCK_GENERATOR_STATE.save(
+        deps.storage,
+        &MockGeneratorState {
+            config: msg,
+            lp_token: None,
+            deposited: Uint128::zero(),
+            last_reward_timestamp: env.block.time.seconds(),
+        },
+    )?;
+    Ok(Response::default())
+}
+
+fn mock_generator_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: GeneratorExecuteMsg,
+) -> StdResult<Response> {
+    let mut state = MOCK_GENERATOR_STATE.load(deps.storage)?;
+
+    let msgs = match msg {
+        GeneratorExecuteMsg::Receive(cw20_msg) => {
+            let msgs =
+                mock_generator_claim_rewards(&mut state, &env, &Addr::unchecked(cw20_msg.sender))?;
+            state.lp_token = Some(info.sender);
+            state.deposited += cw20_msg.amount;
+            msgs
+        }
+        GeneratorExecuteMsg::Withdraw { amount, .. } => {
+            let mut msgs = mock_generator_claim_rewards(&mut state, &env, &info.sender)?;
+            state.deposited = state.deposited.checked_sub(amount)?;
+            if !amount.is_zero() {
+                msgs.push(WasmMsg::Execute {
+                    contract_addr: state.lp_token.clone().unwrap().to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: info.sender.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                });
+            }
+            msgs
+        }
+        _ => return Err(StdError::generic_err("Not supported")),
+    };
+
+    MOCK_GENERATOR_STATE.save(deps.storage, &state)?;
+    Ok(Response::new().add_messages(msgs))
+}
+
+fn mock_generator_query(deps: Deps, env: Env, msg: GeneratorQueryMsg) -> StdResult<Binary> {
+    let state = MOCK_GENERATOR_STATE.load(deps.storage)?;
+
+    match msg {
+        GeneratorQueryMsg::Deposit { .. } => to_binary(&state.deposited),
+        GeneratorQueryMsg::PendingToken { .. } => {
+            let (astro_rewards, proxy_rewards) = mock_generator_pending_rewards(&state, &env);
+            to_binary(&PendingTokenResponse {
+                pending: astro_rewards,
+                pending_on_proxy: Some(vec![Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: state.config.proxy_reward_token,
+                    },
+                    amount: proxy_rewards,
+                }]),
+            })
+        }
+        GeneratorQueryMsg::RewardInfo { .. } => to_binary(&RewardInfoResponse {
+            base_reward_token: state.config.astro_token,
+            proxy_reward_token: Some(state.config.proxy_reward_token),
+        }),
+        _ => Err(StdError::generic_err("Not supported")),
+    }
+}
+
+// Instantiates the generator mock and a Proxy reward token funding it
+fn instantiate_mock_generator_with_proxy(
+    app: &mut App,
+    owner: Addr,
+    astro_token_instance: Addr,
+    token_code_id: u64,
+    astro_per_second: Uint128,
+    proxy_reward_per_second: Uint128,
+) -> (Addr, Addr) {
+    let proxy_reward_token = app
+        .instantiate_contract(
+            token_code_id,
+            owner.clone(),
+            &astroport::token::InstantiateMsg {
+                name: String::from("Proxy reward token"),
+                symbol: String::from("PROXY"),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: owner.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            String::from("PROXY"),
+            None,
+        )
+        .unwrap();
+
+    let generator_code_id = app.store_code(Box::new(ContractWrapper::new(
+        mock_generator_execute,
+        mock_generator_instantiate,
+        mock_generator_query,
+    )));
+    let generator_instance = app
+        .instantiate_contract(
+            generator_code_id,
+            owner.clone(),
+            &MockGeneratorInstantiateMsg {
+                astro_token: astro_token_instance,
+                proxy_reward_token: proxy_reward_token.clone(),
+                astro_per_second,
+                proxy_reward_per_second,
+            },
+            &[],
+            "Generator mock",
+            None,
+        )
+        .unwrap();
+
+    mint_some_astro(
+        app,
+        owner,
+        proxy_reward_token.clone(),
+        Uint128::new(1_000_000_000_000),
+        generator_instance.to_string(),
+    );
+
+    (generator_instance, proxy_reward_token)
+}
+
+fn claimable_proxy_reward(user_info: &UserInfoResponse, reward_info: &AssetInfo) -> Uint128 {
+    user_info
+        .claimable_generator_proxy_debt
+        .inner_ref()
+        .iter()
+        .find(|(asset, _)| asset == reward_info)
+        .map(|(_, amount)| *amount)
+        .unwrap_or_default()
+}
+
+fn query_cw20_balance(app: &App, token: &Addr, address: &Addr) -> Uint128 {
+    let res: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            token,
+            &cw20::Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    res.balance
+}
+
 // Mints some ASTRO to "to" recipient
 fn mint_some_astro(
     app: &mut App,
@@ -764,6 +1059,7 @@ fn make_astro_ust_deposits(
             amount: Uint128::new(100000000),
             msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
                 user_address: user1_address.to_string(),
+                allowlist_proof: None,
             })
             .unwrap(),
         },
@@ -779,6 +1075,7 @@ fn make_astro_ust_deposits(
             amount: Uint128::new(65435340),
             msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
                 user_address: user2_address.to_string(),
+                allowlist_proof: None,
             })
             .unwrap(),
         },
@@ -794,6 +1091,7 @@ fn make_astro_ust_deposits(
             amount: Uint128::new(76754654),
             msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
                 user_address: user3_address.to_string(),
+                allowlist_proof: None,
             })
             .unwrap(),
         },
@@ -833,7 +1131,10 @@ fn make_astro_ust_deposits(
     );
 
     // deposit UST Msg
-    let deposit_ust_msg = &ExecuteMsg::DepositUst {};
+    let deposit_ust_msg = &ExecuteMsg::DepositUst {
+        beneficiary: None,
+        allowlist_proof: None,
+    };
 
     // ######    SUCCESS :: UST Successfully deposited     ######
     app.execute_contract(
@@ -1025,6 +1326,7 @@ fn test_delegate_astro_tokens_from_airdrop() {
         amount: Uint128::new(100000000),
         msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
             user_address: "airdrop_recipient".to_string(),
+            allowlist_proof: None,
         })
         .unwrap(),
     };
@@ -1050,6 +1352,7 @@ fn test_delegate_astro_tokens_from_airdrop() {
                 amount: Uint128::new(0),
                 msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
                     user_address: "airdrop_recipient".to_string(),
+                    allowlist_proof: None,
                 })
                 .unwrap(),
             },
@@ -1171,6 +1474,37 @@ fn test_delegate_astro_tokens_from_airdrop() {
         err.root_cause().to_string(),
         "Generic error: Deposit window closed"
     );
+
+    // ######    ERROR :: Undelegation is limited like UST withdrawals     ######
+    err = app
+        .execute_contract(
+            Addr::unchecked("airdrop_recipient"),
+            auction_instance.clone(),
+            &ExecuteMsg::UndelegateAstro {
+                amount: Uint128::new(100000001),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Amount exceeds maximum allowed withdrawal limit of 0.5"
+    );
+
+    err = app
+        .execute_contract(
+            Addr::unchecked("not_delegator"),
+            auction_instance.clone(),
+            &ExecuteMsg::UndelegateAstro {
+                amount: Uint128::new(100),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "astroport_periphery::auction::UserInfo not found"
+    );
 }
 
 #[test]
@@ -1216,6 +1550,7 @@ fn test_delegate_astro_tokens_from_lockdrop() {
         amount: Uint128::new(100000000),
         msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
             user_address: "lockdrop_participant".to_string(),
+            allowlist_proof: None,
         })
         .unwrap(),
     };
@@ -1241,6 +1576,7 @@ fn test_delegate_astro_tokens_from_lockdrop() {
                 amount: Uint128::new(0),
                 msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
                     user_address: "lockdrop_participant".to_string(),
+                    allowlist_proof: None,
                 })
                 .unwrap(),
             },
@@ -1374,6 +1710,10 @@ fn test_update_config() {
         owner: Some("new_owner".to_string()),
         astro_ust_pair_address: None,
         generator_contract: Some("generator_contract".to_string()),
+        keeper: None,
+        init_pool_slippage: None,
+        compound_slippage: None,
+        allowlist_merkle_root: None,
     };
 
     // ######    ERROR :: Only owner can update configuration     ######
@@ -1446,7 +1786,10 @@ fn test_deposit_ust() {
     );
 
     // deposit UST Msg
-    let deposit_ust_msg = &ExecuteMsg::DepositUst {};
+    let deposit_ust_msg = &ExecuteMsg::DepositUst {
+        beneficiary: None,
+        allowlist_proof: None,
+    };
     let coins = [Coin {
         denom: "uusd".to_string(),
         amount: Uint128::from(10000u128),
@@ -1572,117 +1915,435 @@ fn test_deposit_ust() {
 }
 
 #[test]
-fn test_withdraw_ust() {
+fn test_time_weighted_auction_incentives() {
     let owner = Addr::unchecked("owner");
     let mut app = mock_app(
         owner.clone(),
-        vec![
-            Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::new(100_000_000_000u128),
-            },
-            Coin {
-                denom: "uluna".to_string(),
-                amount: Uint128::new(100_000_000_000u128),
-            },
-        ],
-    );
-    let (_, _, auction_instance, _, _) = init_auction_astro_contracts(&mut app);
-    let user1_address = Addr::unchecked("user1");
-    let user2_address = Addr::unchecked("user2");
-    let user3_address = Addr::unchecked("user3");
-
-    // Set user balances
-    validate_and_send_funds(
-        &mut app,
-        &owner,
-        &user1_address,
         vec![Coin {
             denom: "uusd".to_string(),
-            amount: Uint128::new(20_000_000u128),
+            amount: Uint128::new(100_000_000_000u128),
         }],
     );
+    let (_, lockdrop_instance, _, astro_token_instance, auction_init_msg) =
+        init_auction_astro_contracts(&mut app);
 
-    validate_and_send_funds(
-        &mut app,
-        &owner,
-        &user2_address,
-        vec![Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::new(20_000_000u128),
-        }],
-    );
+    let auction_code_id = app.store_code(Box::new(ContractWrapper::new(
+        astroport_auction::contract::execute,
+        astroport_auction::contract::instantiate,
+        astroport_auction::contract::query,
+    )));
 
-    validate_and_send_funds(
-        &mut app,
-        &owner,
-        &user3_address,
-        vec![Coin {
-            denom: "uusd".to_string(),
-            amount: Uint128::new(20_000_000u128),
-        }],
+    // ######    ERROR :: Invalid deposit window     ######
+    let err = app
+        .instantiate_contract(
+            auction_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                deposit_window: 0,
+                ..auction_init_msg.clone()
+            },
+            &[],
+            "auction",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: deposit_window should be greater than 0"
     );
 
-    // deposit UST Msg
-    let deposit_ust_msg = &ExecuteMsg::DepositUst {};
-    let coins = [Coin {
-        denom: "uusd".to_string(),
-        amount: Uint128::from(10000u128),
-    }];
+    // ######    ERROR :: Invalid incentives share     ######
+    let err = app
+        .instantiate_contract(
+            auction_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                astro_delegators_incentives_share: Decimal::percent(101),
+                ..auction_init_msg.clone()
+            },
+            &[],
+            "auction",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: astro_delegators_incentives_share should be less than or equal to 1"
+    );
 
-    // open claim period for successful deposit
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(1_000_01)
-    });
+    // 60% of incentives go to ASTRO delegators, deposits at the window start weigh 2x
+    let auction_instance = app
+        .instantiate_contract(
+            auction_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                astro_delegators_incentives_share: Decimal::percent(60),
+                time_weight: Some(TimeWeight {
+                    max_bonus: Decimal::one(),
+                }),
+                ..auction_init_msg.clone()
+            },
+            &[],
+            "auction",
+            None,
+        )
+        .unwrap();
 
-    // ######    SUCCESS :: UST Successfully deposited     ######
-    app.execute_contract(
-        user1_address.clone(),
-        auction_instance.clone(),
-        &deposit_ust_msg,
-        &coins,
-    )
-    .unwrap();
-    app.execute_contract(
-        user2_address.clone(),
-        auction_instance.clone(),
-        &deposit_ust_msg,
-        &coins,
-    )
-    .unwrap();
-    app.execute_contract(
-        user3_address.clone(),
-        auction_instance.clone(),
-        &deposit_ust_msg,
-        &coins,
-    )
-    .unwrap();
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        Uint128::new(1_000_000_000),
+        lockdrop_instance.to_string(),
+    );
 
-    // ######    SUCCESS :: UST Successfully withdrawn (when withdrawals allowed)     ######
     app.execute_contract(
-        user1_address.clone(),
-        auction_instance.clone(),
-        &ExecuteMsg::WithdrawUst {
-            amount: Uint128::from(10000u64),
+        lockdrop_instance.clone(),
+        astro_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::new(1_000_000),
+            contract: auction_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
         },
         &[],
     )
     .unwrap();
-    // Check state response
-    let mut state_resp: State = app
-        .wrap()
-        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(Uint128::from(20000u64), state_resp.total_ust_delegated);
 
-    // Check user response
-    let mut user_resp: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    for user in [&user1_address, &user2_address] {
+        validate_and_send_funds(
+            &mut app,
+            &owner,
+            user,
+            vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1000u128),
+            }],
+        );
+    }
+
+    let deposit = |app: &mut App, user: &Addr| {
+        app.execute_contract(
+            lockdrop_instance.clone(),
+            astro_token_instance.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: auction_instance.to_string(),
+                amount: Uint128::new(1000),
+                msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
+                    user_address: user.to_string(),
+                    allowlist_proof: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            user.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                beneficiary: None,
+                allowlist_proof: None,
+            },
+            &[Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(1000u128),
+            }],
+        )
+        .unwrap();
+    };
+
+    // Deposit window start :: deposits are scored 2x
+    app.update_block(|b| b.time = Timestamp::from_seconds(auction_init_msg.init_timestamp));
+    deposit(&mut app, &user1_address);
+
+    // Middle of the deposit window :: deposits are scored 1.5x
+    app.update_block(|b| {
+        b.time = Timestamp::from_seconds(
+            auction_init_msg.init_timestamp + auction_init_msg.deposit_window / 2,
+        )
+    });
+    deposit(&mut app, &user2_address);
+
+    let state_resp: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::new(3500), state_resp.total_astro_weighted);
+    assert_eq!(Uint128::new(3500), state_resp.total_ust_weighted);
+
+    let user1_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::new(2000), user1_resp.astro_weighted);
+    assert_eq!(Uint128::new(2000), user1_resp.ust_weighted);
+    assert_eq!(
+        Some(Uint128::new(342857 + 228571)),
+        user1_resp.auction_incentive_amount
+    );
+
+    let user2_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::new(1500), user2_resp.astro_weighted);
+    assert_eq!(Uint128::new(1500), user2_resp.ust_weighted);
+    assert_eq!(
+        Some(Uint128::new(257142 + 171428)),
+        user2_resp.auction_incentive_amount
+    );
+
+    // Withdrawal reduces the weighted amount proportionally
+    app.execute_contract(
+        user2_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::WithdrawUst {
+            amount: Uint128::new(500),
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let state_resp: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::new(2750), state_resp.total_ust_weighted);
+
+    let user1_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Some(Uint128::new(342857 + 290909)),
+        user1_resp.auction_incentive_amount
+    );
+}
+
+#[test]
+fn test_lp_vesting_schedules_validation() {
+    let owner = Addr::unchecked(OWNER);
+    let mut app = mock_app(owner.clone(), vec![]);
+    let (_, _, _, _, auction_init_msg) = init_auction_astro_contracts(&mut app);
+
+    let auction_code_id = app.store_code(Box::new(ContractWrapper::new(
+        astroport_auction::contract::execute,
+        astroport_auction::contract::instantiate,
+        astroport_auction::contract::query,
+    )));
+
+    let cliff_schedule = vec![
+        (2_592_000u64, Decimal::percent(25)),
+        (7_776_000u64, Decimal::one()),
+    ];
+
+    // ######    ERROR :: Schedule doesn't unlock all LP tokens     ######
+    let err = app
+        .instantiate_contract(
+            auction_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                lp_vesting_schedules: Some(LpVestingSchedules {
+                    astro_delegators: cliff_schedule.clone(),
+                    ust_depositors: vec![(7_776_000u64, Decimal::percent(90))],
+                }),
+                ..auction_init_msg.clone()
+            },
+            &[],
+            "auction",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: LP vesting schedule should end with 100% unlocked"
+    );
+
+    // ######    ERROR :: Schedule points aren't sorted     ######
+    let err = app
+        .instantiate_contract(
+            auction_code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                lp_vesting_schedules: Some(LpVestingSchedules {
+                    astro_delegators: cliff_schedule.clone(),
+                    ust_depositors: vec![
+                        (7_776_000u64, Decimal::percent(50)),
+                        (2_592_000u64, Decimal::one()),
+                    ],
+                }),
+                ..auction_init_msg.clone()
+            },
+            &[],
+            "auction",
+            None,
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: LP vesting schedule points should be sorted by timestamp and percent"
+    );
+
+    // ######    SUCCESS :: Cliff for ASTRO delegators, linear vesting for UST depositors     ######
+    let lp_vesting_schedules = LpVestingSchedules {
+        astro_delegators: cliff_schedule,
+        ust_depositors: vec![(0u64, Decimal::zero()), (7_776_000u64, Decimal::one())],
+    };
+    let auction_instance = app
+        .instantiate_contract(
+            auction_code_id,
+            owner,
+            &InstantiateMsg {
+                lp_vesting_schedules: Some(lp_vesting_schedules.clone()),
+                ..auction_init_msg
+            },
+            &[],
+            "auction",
+            None,
+        )
+        .unwrap();
+
+    let resp: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(Some(lp_vesting_schedules), resp.lp_vesting_schedules);
+}
+
+#[test]
+fn test_withdraw_ust() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (_, _, auction_instance, _, _) = init_auction_astro_contracts(&mut app);
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    let user3_address = Addr::unchecked("user3");
+
+    // Set user balances
+    validate_and_send_funds(
+        &mut app,
+        &owner,
+        &user1_address,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20_000_000u128),
+        }],
+    );
+
+    validate_and_send_funds(
+        &mut app,
+        &owner,
+        &user2_address,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20_000_000u128),
+        }],
+    );
+
+    validate_and_send_funds(
+        &mut app,
+        &owner,
+        &user3_address,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20_000_000u128),
+        }],
+    );
+
+    // deposit UST Msg
+    let deposit_ust_msg = &ExecuteMsg::DepositUst {
+        beneficiary: None,
+        allowlist_proof: None,
+    };
+    let coins = [Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(10000u128),
+    }];
+
+    // open claim period for successful deposit
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1_000_01)
+    });
+
+    // ######    SUCCESS :: UST Successfully deposited     ######
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &deposit_ust_msg,
+        &coins,
+    )
+    .unwrap();
+    app.execute_contract(
+        user2_address.clone(),
+        auction_instance.clone(),
+        &deposit_ust_msg,
+        &coins,
+    )
+    .unwrap();
+    app.execute_contract(
+        user3_address.clone(),
+        auction_instance.clone(),
+        &deposit_ust_msg,
+        &coins,
+    )
+    .unwrap();
+
+    // ######    SUCCESS :: UST Successfully withdrawn (when withdrawals allowed)     ######
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::WithdrawUst {
+            amount: Uint128::from(10000u64),
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+    // Check state response
+    let mut state_resp: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(20000u64), state_resp.total_ust_delegated);
+
+    // Check user response
+    let mut user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
             },
         )
         .unwrap();
@@ -1710,6 +2371,7 @@ fn test_withdraw_ust() {
             auction_instance.clone(),
             &ExecuteMsg::WithdrawUst {
                 amount: Uint128::from(10000u64),
+                user_address: None,
             },
             &[],
         )
@@ -1726,6 +2388,7 @@ fn test_withdraw_ust() {
         auction_instance.clone(),
         &ExecuteMsg::WithdrawUst {
             amount: Uint128::from(5000u64),
+            user_address: None,
         },
         &[],
     )
@@ -1757,6 +2420,7 @@ fn test_withdraw_ust() {
             auction_instance.clone(),
             &ExecuteMsg::WithdrawUst {
                 amount: Uint128::from(10u64),
+                user_address: None,
             },
             &[],
         )
@@ -1780,6 +2444,7 @@ fn test_withdraw_ust() {
             auction_instance.clone(),
             &ExecuteMsg::WithdrawUst {
                 amount: Uint128::from(10000u64),
+                user_address: None,
             },
             &[],
         )
@@ -1796,6 +2461,7 @@ fn test_withdraw_ust() {
         auction_instance.clone(),
         &ExecuteMsg::WithdrawUst {
             amount: Uint128::from(2000u64),
+            user_address: None,
         },
         &[],
     )
@@ -1827,6 +2493,7 @@ fn test_withdraw_ust() {
             auction_instance.clone(),
             &ExecuteMsg::WithdrawUst {
                 amount: Uint128::from(10u64),
+                user_address: None,
             },
             &[],
         )
@@ -1848,6 +2515,7 @@ fn test_withdraw_ust() {
             auction_instance.clone(),
             &ExecuteMsg::WithdrawUst {
                 amount: Uint128::from(10u64),
+                user_address: None,
             },
             &[],
         )
@@ -1859,857 +2527,4491 @@ fn test_withdraw_ust() {
 }
 
 #[test]
-fn test_add_liquidity_to_astroport_pool() {
+fn test_deposit_ust_on_behalf_of_beneficiary() {
     let owner = Addr::unchecked("owner");
     let mut app = mock_app(
         owner.clone(),
-        vec![
-            Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::new(100_000_000_000u128),
-            },
-            Coin {
-                denom: "uluna".to_string(),
-                amount: Uint128::new(100_000_000_000u128),
-            },
-        ],
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100_000_000_000u128),
+        }],
     );
-    let (
-        auction_instance,
-        astro_token_instance,
-        airdrop_instance,
-        lockdrop_instance,
-        pair_instance,
-        _,
-        auction_init_msg,
-        _,
-        _,
-    ) = init_all_contracts(&mut app);
+    let (_, _, auction_instance, _, _) = init_auction_astro_contracts(&mut app);
+    let custodian_address = Addr::unchecked("custodian");
+    let beneficiary_address = Addr::unchecked("beneficiary");
+    let operator_address = Addr::unchecked("operator");
 
-    // mint ASTRO to Lockdrop Contract
-    mint_some_astro(
+    validate_and_send_funds(
         &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
-        astro_token_instance.clone(),
-        Uint128::new(100_000_000_000),
-        auction_init_msg.lockdrop_contract_address.to_string(),
+        &owner,
+        &custodian_address,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20_000_000u128),
+        }],
     );
 
-    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
-        &mut app,
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1_000_01)
+    });
+
+    // ######    SUCCESS :: UST deposited on behalf of the beneficiary     ######
+    app.execute_contract(
+        custodian_address.clone(),
         auction_instance.clone(),
-        auction_init_msg.clone(),
-        astro_token_instance.clone(),
-    );
+        &ExecuteMsg::DepositUst {
+            beneficiary: Some(beneficiary_address.to_string()),
+            allowlist_proof: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(10000u128),
+        }],
+    )
+    .unwrap();
 
-    // ######    ERROR :: Unauthorized   ######
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: beneficiary_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(10000u64), user_resp.ust_delegated);
 
-    let mut err = app
+    // ######    ERROR :: Depositor can't withdraw UST of the beneficiary     ######
+    let err = app
         .execute_contract(
-            Addr::unchecked("not_owner".to_string()),
+            custodian_address.clone(),
             auction_instance.clone(),
-            &ExecuteMsg::InitPool { slippage: None },
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(1000u64),
+                user_address: Some(beneficiary_address.to_string()),
+            },
             &[],
         )
         .unwrap_err();
-    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
-
-    // ######    ERROR :: Deposit/withdrawal windows are still open   ######
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Sender doesn't own the position"
+    );
 
-    err = app
+    // ######    ERROR :: Operator should differ from the sender     ######
+    let err = app
         .execute_contract(
-            Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+            beneficiary_address.clone(),
             auction_instance.clone(),
-            &ExecuteMsg::InitPool { slippage: None },
+            &ExecuteMsg::ApproveOperator {
+                operator: beneficiary_address.to_string(),
+            },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "Generic error: Deposit/withdrawal windows are still open"
-    );
-
-    // finish deposit / withdraw period
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10611001)
-    });
-
-    // mint ASTRO to owner
-    mint_some_astro(
-        &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
-        astro_token_instance.clone(),
-        Uint128::new(100_000_000_000),
-        lockdrop_instance.to_string(),
+        "Generic error: Operator should differ from the sender"
     );
 
+    // ######    SUCCESS :: Beneficiary approves the operator     ######
     app.execute_contract(
-        lockdrop_instance.clone(),
-        astro_token_instance,
-        &Cw20ExecuteMsg::Send {
-            amount: Uint128::new(100_000_000000),
-            contract: auction_instance.to_string(),
-            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        beneficiary_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::ApproveOperator {
+            operator: operator_address.to_string(),
         },
         &[],
     )
     .unwrap();
 
-    let success_ = app
-        .execute_contract(
-            Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
-            auction_instance.clone(),
-            &ExecuteMsg::InitPool { slippage: None },
-            &[],
-        )
-        .unwrap();
-    assert_eq!(
-        success_.events[1].attributes[1],
-        attr("action", "Auction::ExecuteMsg::AddLiquidityToAstroportPool")
-    );
-    assert_eq!(
-        success_.events[1].attributes[2],
-        attr("astro_provided", "242189994")
-    );
-    assert_eq!(
-        success_.events[1].attributes[3],
-        attr("ust_provided", "6530319")
-    );
-
-    // Auction :: Check state response
-    let state_resp: State = app
-        .wrap()
-        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(
-        Uint128::from(242189994u64),
-        state_resp.total_astro_delegated
-    );
-    assert_eq!(Uint128::from(6530319u64), state_resp.total_ust_delegated);
-    assert_eq!(
-        Some(Uint128::from(39769057u64)),
-        state_resp.lp_shares_minted
-    );
-    assert!(!state_resp.is_lp_staked);
-    assert!(state_resp.generator_astro_per_share.is_zero());
-    assert_eq!(10611001u64, state_resp.pool_init_timestamp);
-
-    // Astroport Pool :: Check response
-    let pool_resp: astroport::pair::PoolResponse = app
-        .wrap()
-        .query_wasm_smart(&pair_instance, &astroport::pair::QueryMsg::Pool {})
-        .unwrap();
-    assert_eq!(Uint128::from(39769057u64), pool_resp.total_share);
-
-    // Airdrop :: Check config for claims
-    let airdrop_config_resp: astroport_periphery::airdrop::Config = app
-        .wrap()
-        .query_wasm_smart(
-            &airdrop_instance,
-            &astroport_periphery::airdrop::QueryMsg::Config {},
-        )
-        .unwrap();
-    assert_eq!(true, airdrop_config_resp.are_claims_enabled);
-
-    // Lockdrop :: Check state for claims
-    let lockdrop_config_resp: astroport_periphery::lockdrop::StateResponse = app
+    let operators: Vec<Addr> = app
         .wrap()
         .query_wasm_smart(
-            &lockdrop_instance,
-            &astroport_periphery::lockdrop::QueryMsg::State {},
+            &auction_instance,
+            &QueryMsg::Operators {
+                address: beneficiary_address.to_string(),
+            },
         )
         .unwrap();
-    assert_eq!(true, lockdrop_config_resp.are_claims_allowed);
+    assert_eq!(vec![operator_address.clone()], operators);
 
+    // close deposit window. Max 50% withdrawals allowed now
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(10911001)
+        b.time = Timestamp::from_seconds(10100001)
     });
 
-    // Auction :: Check user-1 state
-    let user1info_resp: astroport_periphery::auction::UserInfoResponse = app
+    // ######    SUCCESS :: Operator withdraws UST to the beneficiary     ######
+    app.execute_contract(
+        operator_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::WithdrawUst {
+            amount: Uint128::from(2000u64),
+            user_address: Some(beneficiary_address.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+            &QueryMsg::UserInfo {
+                address: beneficiary_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(100000000u64), user1info_resp.astro_delegated);
-    assert_eq!(Uint128::from(432423u64), user1info_resp.ust_delegated);
-    assert_eq!(Some(Uint128::from(9527010u64)), user1info_resp.lp_shares);
-    assert_eq!(
-        Some(Uint128::from(367554u64)),
-        user1info_resp.withdrawable_lp_shares
-    );
-    assert_eq!(
-        Some(Uint128::from(23955835814u64)),
-        user1info_resp.auction_incentive_amount
-    );
-    assert!(user1info_resp.generator_astro_debt.is_zero());
+    assert_eq!(Uint128::from(8000u64), user_resp.ust_delegated);
+    assert!(user_resp.ust_withdrawn);
 
-    // Auction :: Check user-2 state
-    let user2info_resp: astroport_periphery::auction::UserInfoResponse = app
+    let beneficiary_balance = app
         .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user2_address.to_string(),
+        .query_balance(&beneficiary_address, "uusd")
+        .unwrap();
+    assert_eq!(Uint128::from(2000u64), beneficiary_balance.amount);
+    let operator_balance = app.wrap().query_balance(&operator_address, "uusd").unwrap();
+    assert_eq!(Uint128::zero(), operator_balance.amount);
+
+    // ######    ERROR :: Max 1 withdrawal allowed for the position     ######
+    let err = app
+        .execute_contract(
+            beneficiary_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(10u64),
+                user_address: None,
             },
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(65435340u64), user2info_resp.astro_delegated);
-    assert_eq!(Uint128::from(454353u64), user2info_resp.ust_delegated);
-    assert_eq!(Some(Uint128::from(6755923u64)), user2info_resp.lp_shares);
-    assert_eq!(
-        Some(Uint128::from(260645u64)),
-        user2info_resp.withdrawable_lp_shares
-    );
+        .unwrap_err();
     assert_eq!(
-        Some(Uint128::from(16987888347u64)),
-        user2info_resp.auction_incentive_amount
+        err.root_cause().to_string(),
+        "Generic error: Max 1 withdrawal allowed"
     );
 
-    // Auction :: Check user-3 state
-    let user3info_resp: astroport_periphery::auction::UserInfoResponse = app
+    // ######    SUCCESS :: Beneficiary revokes the operator     ######
+    app.execute_contract(
+        beneficiary_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::RevokeOperator {
+            operator: operator_address.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let operators: Vec<Addr> = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user3_address.to_string(),
+            &QueryMsg::Operators {
+                address: beneficiary_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(76754654u64), user3info_resp.astro_delegated);
-    assert_eq!(Uint128::from(5643543u64), user3info_resp.ust_delegated);
-    assert_eq!(Some(Uint128::from(23486123u64)), user3info_resp.lp_shares);
-    assert_eq!(
-        Some(Uint128::from(906100u64)),
-        user3info_resp.withdrawable_lp_shares
-    );
-    assert_eq!(
-        Some(Uint128::from(59056273323u64)),
-        user3info_resp.auction_incentive_amount
-    );
+    assert!(operators.is_empty());
 
-    // ######    ERROR :: Liquidity already added   ######
-    // user1_address, user2_address, user3_address
-    err = app
+    let err = app
         .execute_contract(
-            Addr::unchecked(auction_init_msg.owner.unwrap()),
+            beneficiary_address.clone(),
             auction_instance.clone(),
-            &ExecuteMsg::InitPool { slippage: None },
+            &ExecuteMsg::RevokeOperator {
+                operator: operator_address.to_string(),
+            },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "Generic error: Liquidity already added"
+        "Generic error: Operator isn't approved"
     );
 }
 
+// Returns the Keccak256 hash of an allowlist leaf
+fn allowlist_leaf_hash(address: &Addr, cap: Uint128) -> [u8; 32] {
+    Keccak256::digest((address.to_string() + &cap.to_string()).as_bytes()).into()
+}
+
 #[test]
-fn test_stake_lp_tokens() {
+fn test_allowlisted_deposits_and_delegations() {
     let owner = Addr::unchecked("owner");
     let mut app = mock_app(
         owner.clone(),
-        vec![
-            Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::new(100_000_000_000u128),
-            },
-            Coin {
-                denom: "uluna".to_string(),
-                amount: Uint128::new(100_000_000_000u128),
-            },
-        ],
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100_000_000_000u128),
+        }],
     );
-    let (
-        auction_instance,
-        astro_token_instance,
-        _,
-        _,
-        _,
-        lp_token_instance,
-        auction_init_msg,
-        token_code_id,
-        pair_code_id,
-    ) = init_all_contracts(&mut app);
+    let (airdrop_instance, _, auction_instance, astro_token_instance, auction_init_msg) =
+        init_auction_astro_contracts(&mut app);
+    let auction_owner = Addr::unchecked(auction_init_msg.owner.unwrap());
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    let user3_address = Addr::unchecked("user3");
 
-    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+    for user in [&user1_address, &user3_address] {
+        validate_and_send_funds(
+            &mut app,
+            &owner,
+            user,
+            vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(20_000_000u128),
+            }],
+        );
+    }
 
-    // mint ASTRO to Lockdrop Contract
     mint_some_astro(
         &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        auction_owner.clone(),
         astro_token_instance.clone(),
         Uint128::new(100_000_000_000),
-        auction_init_msg.lockdrop_contract_address.to_string(),
-    );
-
-    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
-        &mut app,
-        auction_instance.clone(),
-        auction_init_msg.clone(),
-        astro_token_instance.clone(),
-    );
-
-    // ######    Initialize generator and vesting instance   ######
-    let (generator_instance, _) = instantiate_generator_and_vesting(
-        &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
-        astro_token_instance.clone(),
-        lp_token_instance.clone(),
-        token_code_id,
-        pair_code_id,
+        airdrop_instance.to_string(),
     );
 
-    let update_msg = UpdateConfigMsg {
-        owner: None,
-        astro_ust_pair_address: None,
-        generator_contract: Some(generator_instance.to_string()),
+    // Allowlist consists of user1 with 10000 uusd cap and user2 with 5000 uusd cap
+    let user1_cap = Uint128::new(10000u128);
+    let user2_cap = Uint128::new(5000u128);
+    let user1_leaf = allowlist_leaf_hash(&user1_address, user1_cap);
+    let user2_leaf = allowlist_leaf_hash(&user2_address, user2_cap);
+    let merkle_root = if user1_leaf < user2_leaf {
+        Keccak256::digest(&[user1_leaf, user2_leaf].concat())
+    } else {
+        Keccak256::digest(&[user2_leaf, user1_leaf].concat())
     };
 
     app.execute_contract(
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        auction_owner.clone(),
         auction_instance.clone(),
         &ExecuteMsg::UpdateConfig {
-            new_config: update_msg.clone(),
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: None,
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: Some(hex::encode(merkle_root)),
+            },
         },
         &[],
     )
     .unwrap();
 
-    // finish deposit / withdraw period
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(10611001)
+        b.time = Timestamp::from_seconds(1_000_01)
     });
 
-    app.execute_contract(
-        owner.clone(),
-        astro_token_instance,
-        &Cw20ExecuteMsg::Send {
-            amount: Uint128::new(100_000_000000),
-            contract: auction_instance.to_string(),
-            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
-        },
-        &[],
-    )
-    .unwrap();
+    let coins = [Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(6000u128),
+    }];
 
-    let _success = app
+    // ######    ERROR :: Allowlist proof is required     ######
+    let err = app
         .execute_contract(
-            Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+            user1_address.clone(),
             auction_instance.clone(),
-            &ExecuteMsg::InitPool { slippage: None },
-            &[],
+            &ExecuteMsg::DepositUst {
+                beneficiary: None,
+                allowlist_proof: None,
+            },
+            &coins,
         )
-        .unwrap();
-
-    // ######    ERROR :: Unauthorized   ######
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Allowlist proof is required"
+    );
 
-    let mut err = app
+    // ######    ERROR :: Incorrect allowlist proof     ######
+    let err = app
         .execute_contract(
-            Addr::unchecked("not_owner".to_string()),
+            user1_address.clone(),
             auction_instance.clone(),
-            &ExecuteMsg::StakeLpTokens {},
-            &[],
+            &ExecuteMsg::DepositUst {
+                beneficiary: None,
+                allowlist_proof: Some(AllowlistProof {
+                    cap: Uint128::new(20000u128),
+                    merkle_proof: vec![hex::encode(user2_leaf)],
+                }),
+            },
+            &coins,
         )
         .unwrap_err();
-    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
-
-    // ######    SUCCESS :: Stake successfully   ######
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Incorrect allowlist proof"
+    );
 
-    let success_ = app
+    // Not allowlisted address can't deposit with a proof of another address
+    let err = app
         .execute_contract(
-            Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+            user3_address.clone(),
             auction_instance.clone(),
-            &ExecuteMsg::StakeLpTokens {},
-            &[],
+            &ExecuteMsg::DepositUst {
+                beneficiary: None,
+                allowlist_proof: Some(AllowlistProof {
+                    cap: user1_cap,
+                    merkle_proof: vec![hex::encode(user2_leaf)],
+                }),
+            },
+            &coins,
         )
-        .unwrap();
-    assert_eq!(
-        success_.events[1].attributes[1],
-        attr("action", "Auction::ExecuteMsg::StakeLPTokens")
-    );
+        .unwrap_err();
     assert_eq!(
-        success_.events[1].attributes[2],
-        attr("staked_amount", "39769057")
+        err.root_cause().to_string(),
+        "Generic error: Incorrect allowlist proof"
     );
 
-    // Auction :: Check state response
-    let state_resp: State = app
+    let cap: Option<Uint128> = app
         .wrap()
-        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::AllowlistCap {
+                address: user1_address.to_string(),
+            },
+        )
         .unwrap();
-    assert_eq!(
-        Uint128::from(242189994u64),
-        state_resp.total_astro_delegated
-    );
-    assert_eq!(Uint128::from(6530319u64), state_resp.total_ust_delegated);
-    assert_eq!(
-        Some(Uint128::from(39769057u64)),
-        state_resp.lp_shares_minted
-    );
-    assert!(state_resp.is_lp_staked);
-    assert_eq!(10611001u64, state_resp.pool_init_timestamp);
+    assert_eq!(cap, None);
 
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10911001)
-    });
+    // ######    SUCCESS :: UST deposited by the allowlisted user     ######
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: Some(AllowlistProof {
+                cap: user1_cap,
+                merkle_proof: vec![hex::encode(user2_leaf)],
+            }),
+        },
+        &coins,
+    )
+    .unwrap();
 
-    // Auction :: Check user-1 state
-    let user1info_resp: astroport_periphery::auction::UserInfoResponse = app
+    let cap: Option<Uint128> = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
+            &QueryMsg::AllowlistCap {
                 address: user1_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(100000000u64), user1info_resp.astro_delegated);
-    assert_eq!(Uint128::from(432423u64), user1info_resp.ust_delegated);
-    assert_eq!(Some(Uint128::from(9527010u64)), user1info_resp.lp_shares);
-    assert_eq!(Uint128::from(0u64), user1info_resp.claimed_lp_shares);
-    assert_eq!(
-        Some(Uint128::from(367554u64)),
-        user1info_resp.withdrawable_lp_shares
-    );
+    assert_eq!(cap, Some(user1_cap));
+
+    // ######    ERROR :: Deposit exceeds the allowlist cap     ######
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::DepositUst {
+                beneficiary: None,
+                allowlist_proof: None,
+            },
+            &coins,
+        )
+        .unwrap_err();
     assert_eq!(
-        Some(Uint128::from(23955835814u64)),
-        user1info_resp.auction_incentive_amount
+        err.root_cause().to_string(),
+        "Generic error: Deposit exceeds the allowlist cap"
     );
 
-    // Auction :: Check user-2 state
-    let user2info_resp: astroport_periphery::auction::UserInfoResponse = app
+    // ######    SUCCESS :: Verified user deposits without a proof up to the cap     ######
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(4000u128),
+        }],
+    )
+    .unwrap();
+
+    let user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user2_address.to_string(),
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(65435340u64), user2info_resp.astro_delegated);
-    assert_eq!(Uint128::from(454353u64), user2info_resp.ust_delegated);
-    assert_eq!(Some(Uint128::from(6755923u64)), user2info_resp.lp_shares);
-    assert_eq!(Uint128::from(0u64), user2info_resp.claimed_lp_shares);
-    assert_eq!(
-        Some(Uint128::from(260645u64)),
-        user2info_resp.withdrawable_lp_shares
-    );
+    assert_eq!(user_resp.ust_delegated, user1_cap);
+
+    // ######    ERROR :: ASTRO can't be delegated to not allowlisted user     ######
+    let err = app
+        .execute_contract(
+            airdrop_instance.clone(),
+            astro_token_instance.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: auction_instance.to_string(),
+                amount: Uint128::new(100000000),
+                msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
+                    user_address: user2_address.to_string(),
+                    allowlist_proof: None,
+                })
+                .unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
     assert_eq!(
-        Some(Uint128::from(16987888347u64)),
-        user2info_resp.auction_incentive_amount
+        err.root_cause().to_string(),
+        "Generic error: Allowlist proof is required"
     );
 
-    // Auction :: Check user-3 state
-    let user3info_resp: astroport_periphery::auction::UserInfoResponse = app
+    // ######    SUCCESS :: ASTRO delegated to the allowlisted user     ######
+    app.execute_contract(
+        airdrop_instance,
+        astro_token_instance,
+        &Cw20ExecuteMsg::Send {
+            contract: auction_instance.to_string(),
+            amount: Uint128::new(100000000),
+            msg: to_binary(&Cw20HookMsg::DelegateAstroTokens {
+                user_address: user2_address.to_string(),
+                allowlist_proof: Some(AllowlistProof {
+                    cap: user2_cap,
+                    merkle_proof: vec![hex::encode(user1_leaf)],
+                }),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user3_address.to_string(),
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(76754654u64), user3info_resp.astro_delegated);
-    assert_eq!(Uint128::from(5643543u64), user3info_resp.ust_delegated);
-    assert_eq!(Some(Uint128::from(23486123u64)), user3info_resp.lp_shares);
-    assert_eq!(Uint128::from(0u64), user3info_resp.claimed_lp_shares);
-    assert_eq!(
-        Some(Uint128::from(906100u64)),
-        user3info_resp.withdrawable_lp_shares
-    );
-    assert_eq!(
-        Some(Uint128::from(59056273323u64)),
-        user3info_resp.auction_incentive_amount
-    );
-
-    // ######    ERROR :: Already staked   ######
+    assert_eq!(user_resp.astro_delegated, Uint128::new(100000000));
 
-    err = app
+    // ######    ERROR :: Position can't be transferred to not allowlisted user     ######
+    let err = app
         .execute_contract(
-            Addr::unchecked(auction_init_msg.owner.unwrap()),
+            user1_address.clone(),
             auction_instance.clone(),
-            &ExecuteMsg::StakeLpTokens {},
+            &ExecuteMsg::TransferPosition {
+                recipient: user3_address.to_string(),
+                fraction: Decimal::percent(50),
+            },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "Generic error: Already staked"
+        "Generic error: Recipient isn't allowlisted"
     );
-}
 
-#[test]
-fn test_claim_rewards() {
-    let owner = Addr::unchecked("owner");
-    let mut app = mock_app(
-        owner.clone(),
-        vec![
-            Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::new(100_000_000_000u128),
+    // ######    ERROR :: Position can't be transferred above the recipient's cap     ######
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::TransferPosition {
+                recipient: user2_address.to_string(),
+                fraction: Decimal::percent(60),
             },
-            Coin {
-                denom: "uluna".to_string(),
-                amount: Uint128::new(100_000_000_000u128),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Transfer exceeds the recipient's allowlist cap"
+    );
+
+    // ######    SUCCESS :: Position transferred up to the recipient's cap     ######
+    app.execute_contract(
+        user1_address,
+        auction_instance.clone(),
+        &ExecuteMsg::TransferPosition {
+            recipient: user2_address.to_string(),
+            fraction: Decimal::percent(50),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
             },
-        ],
+        )
+        .unwrap();
+    assert_eq!(user_resp.ust_delegated, user2_cap);
+
+    // ######    ERROR :: Allowlist can't be updated after the auction has started     ######
+    let err = app
+        .execute_contract(
+            auction_owner.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                new_config: UpdateConfigMsg {
+                    owner: None,
+                    astro_ust_pair_address: None,
+                    generator_contract: None,
+                    keeper: None,
+                    init_pool_slippage: None,
+                    compound_slippage: None,
+                    allowlist_merkle_root: Some(hex::encode(user1_leaf)),
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Allowlist can't be updated after the auction has started"
     );
-    let (
-        auction_instance,
-        astro_token_instance,
-        _,
-        _,
-        _,
-        lp_token_instance,
-        auction_init_msg,
-        token_code_id,
-        pair_code_id,
-    ) = init_all_contracts(&mut app);
 
-    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+    // ######    ERROR :: Position NFTs can't be enabled together with the allowlist     ######
+    let err = app
+        .execute_contract(
+            auction_owner,
+            auction_instance,
+            &ExecuteMsg::SetPositionNft {
+                contract: "position_nft".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Position NFTs can't be enabled together with the allowlist"
+    );
+}
 
-    let claim_rewards_msg = ExecuteMsg::ClaimRewards {
-        withdraw_lp_shares: None,
+#[test]
+fn test_phase_query() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(owner, vec![]);
+    let (_, _, auction_instance, _, _) = init_auction_astro_contracts(&mut app);
+
+    let query_phase = |app: &App, timestamp: Option<u64>| -> PhaseResponse {
+        app.wrap()
+            .query_wasm_smart(&auction_instance, &QueryMsg::Phase { timestamp })
+            .unwrap()
     };
 
-    // mint ASTRO to Lockdrop Contract
-    mint_some_astro(
-        &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
-        astro_token_instance.clone(),
-        Uint128::new(100_000_000_000),
-        auction_init_msg.lockdrop_contract_address.to_string(),
+    let phase_resp = query_phase(&app, Some(10475000));
+    assert_eq!(AuctionPhase::NotStarted, phase_resp.phase);
+    assert_eq!(1_000_00u64, phase_resp.deposit_start_timestamp);
+    assert_eq!(10100000u64, phase_resp.deposit_end_timestamp);
+    assert_eq!(10350000u64, phase_resp.withdrawal_decrease_timestamp);
+    assert_eq!(10600000u64, phase_resp.withdrawal_end_timestamp);
+    assert_eq!(None, phase_resp.pool_init_timestamp);
+    assert_eq!(
+        Some(Decimal::percent(25)),
+        phase_resp.projected_withdrawal_percent
+    );
+    // Nothing can be withdrawn before the auction starts
+    assert_eq!(Decimal::zero(), phase_resp.withdrawal_percent);
+    let phase_resp = query_phase(&app, Some(1_000_00 - 1));
+    assert_eq!(
+        Some(Decimal::zero()),
+        phase_resp.projected_withdrawal_percent
     );
 
-    // mint ASTRO to owner
-    mint_some_astro(
-        &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
-        astro_token_instance.clone(),
-        Uint128::new(100_000_000_000),
-        owner.to_string(),
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1_000_01)
+    });
+    let phase_resp = query_phase(&app, None);
+    assert_eq!(AuctionPhase::Deposit, phase_resp.phase);
+    assert_eq!(Decimal::one(), phase_resp.withdrawal_percent);
+    assert_eq!(None, phase_resp.projected_withdrawal_percent);
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10100001)
+    });
+    let phase_resp = query_phase(&app, Some(10600000));
+    assert_eq!(AuctionPhase::WithdrawOnly, phase_resp.phase);
+    assert_eq!(Decimal::percent(50), phase_resp.withdrawal_percent);
+    assert_eq!(
+        Some(Decimal::zero()),
+        phase_resp.projected_withdrawal_percent
     );
 
-    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
-        &mut app,
-        auction_instance.clone(),
-        auction_init_msg.clone(),
-        astro_token_instance.clone(),
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10600000)
+    });
+    let phase_resp = query_phase(&app, None);
+    assert_eq!(AuctionPhase::Closed, phase_resp.phase);
+    assert_eq!(Decimal::zero(), phase_resp.withdrawal_percent);
+}
+
+#[test]
+fn test_position_nft() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100_000_000_000u128),
+        }],
     );
+    let (_, _, auction_instance, _, _) = init_auction_astro_contracts(&mut app);
+    let auction_owner = Addr::unchecked("contract_owner");
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
 
-    // ######    Initialize generator and vesting instance   ######
-    let (generator_instance, _) = instantiate_generator_and_vesting(
+    validate_and_send_funds(
         &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
-        astro_token_instance.clone(),
-        lp_token_instance.clone(),
-        token_code_id,
-        pair_code_id,
+        &owner,
+        &user1_address,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(20_000_000u128),
+        }],
     );
 
-    let update_msg = UpdateConfigMsg {
-        owner: None,
-        astro_ust_pair_address: None,
-        generator_contract: Some(generator_instance.to_string()),
+    // ######    ERROR :: NFTs should be minted by the auction     ######
+    let foreign_nft = instantiate_position_nft(&mut app, owner.clone(), &owner);
+    let err = app
+        .execute_contract(
+            auction_owner.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::SetPositionNft {
+                contract: foreign_nft.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Position NFTs should be minted by this contract"
+    );
+
+    let position_nft = instantiate_position_nft(&mut app, owner.clone(), &auction_instance);
+    let set_position_nft_msg = ExecuteMsg::SetPositionNft {
+        contract: position_nft.to_string(),
     };
 
+    // ######    ERROR :: Unauthorized     ######
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &set_position_nft_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESS :: Position NFT contract is set     ######
     app.execute_contract(
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        auction_owner.clone(),
         auction_instance.clone(),
-        &ExecuteMsg::UpdateConfig {
-            new_config: update_msg.clone(),
-        },
+        &set_position_nft_msg,
         &[],
     )
     .unwrap();
 
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.position_nft, Some(position_nft.clone()));
+
+    // ######    ERROR :: Position NFT contract already set     ######
+    let err = app
+        .execute_contract(
+            auction_owner,
+            auction_instance.clone(),
+            &set_position_nft_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Position NFT contract already set"
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(1_000_01)
+    });
+
+    let coins = [Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(10000u128),
+    }];
+
+    // ######    SUCCESS :: Deposit mints the position NFT to the user     ######
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
+        },
+        &coins,
+    )
+    .unwrap();
+
+    let owner_resp: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &position_nft,
+            &cw721_base::QueryMsg::OwnerOf {
+                token_id: user1_address.to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_resp.owner, user1_address.to_string());
+
+    // Second deposit doesn't mint another NFT
     app.execute_contract(
-        owner.clone(),
-        astro_token_instance,
-        &Cw20ExecuteMsg::Send {
-            amount: Uint128::new(100_000_000000),
-            contract: auction_instance.to_string(),
-            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
         },
-        &[],
+        &coins,
     )
     .unwrap();
 
-    // ######    ERROR :: Deposit/withdrawal windows are open   ######
+    // The position is sold to user2
+    app.execute_contract(
+        user1_address.clone(),
+        position_nft.clone(),
+        &cw721_base::ExecuteMsg::<Empty>::TransferNft {
+            recipient: user2_address.to_string(),
+            token_id: user1_address.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
 
-    let mut err = app
+    // ######    ERROR :: The user can't deposit into the sold position     ######
+    let err = app
         .execute_contract(
-            owner,
+            user1_address.clone(),
             auction_instance.clone(),
-            &ExecuteMsg::InitPool { slippage: None },
-            &[],
+            &ExecuteMsg::DepositUst {
+                beneficiary: None,
+                allowlist_proof: None,
+            },
+            &coins,
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "Generic error: Deposit/withdrawal windows are still open"
+        "Generic error: Position NFT is owned by another address"
     );
 
-    // Astro/USD should be provided to the pool
-
-    err = app
+    // ######    ERROR :: The user can't withdraw from the sold position     ######
+    let err = app
         .execute_contract(
             user1_address.clone(),
             auction_instance.clone(),
-            &claim_rewards_msg,
+            &ExecuteMsg::WithdrawUst {
+                amount: Uint128::from(5000u64),
+                user_address: None,
+            },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "Generic error: Astro/USD should be provided to the pool!"
+        "Generic error: Sender doesn't own the position"
     );
 
-    // finish deposit / withdraw period
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10611001)
-    });
-
-    // ######    ERROR :: Invalid request   ######
-
-    err = app
+    // ######    ERROR :: Positions are transferred with their NFTs     ######
+    let err = app
         .execute_contract(
-            Addr::unchecked("not_user".to_string()),
+            user1_address.clone(),
             auction_instance.clone(),
-            &claim_rewards_msg,
+            &ExecuteMsg::TransferPosition {
+                recipient: user2_address.to_string(),
+                fraction: Decimal::percent(50),
+            },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "astroport_periphery::auction::UserInfo not found"
+        "Generic error: Positions are transferred with their NFTs"
     );
 
-    // ######    Sucess :: Initialize ASTRO-UST Pool   ######
+    // ######    SUCCESS :: The NFT owner withdraws UST from the position     ######
+    app.execute_contract(
+        user2_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::WithdrawUst {
+            amount: Uint128::from(5000u64),
+            user_address: Some(user1_address.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user2_balance = app.wrap().query_balance(&user2_address, "uusd").unwrap();
+    assert_eq!(user2_balance.amount, Uint128::from(5000u64));
+
+    // Position NFT metadata is the position info
+    let nft_info: NftInfoResponse<UserInfoResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::PositionNftInfo {
+                token_id: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(nft_info.token_uri, None);
+    assert_eq!(nft_info.extension.ust_delegated, Uint128::from(15000u64));
+
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(nft_info.extension, user_info);
+}
+
+#[test]
+fn test_add_liquidity_to_astroport_pool() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (
+        auction_instance,
+        astro_token_instance,
+        airdrop_instance,
+        lockdrop_instance,
+        pair_instance,
+        _,
+        auction_init_msg,
+        _,
+        _,
+    ) = init_all_contracts(&mut app);
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    // ######    ERROR :: Unauthorized   ######
+
+    let mut err = app
+        .execute_contract(
+            Addr::unchecked("not_owner".to_string()),
+            auction_instance.clone(),
+            &ExecuteMsg::InitPool {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    ERROR :: Deposit/withdrawal windows are still open   ######
+
+    err = app
+        .execute_contract(
+            Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+            auction_instance.clone(),
+            &ExecuteMsg::InitPool {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Deposit/withdrawal windows are still open"
+    );
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    // mint ASTRO to owner
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        lockdrop_instance.to_string(),
+    );
+
+    app.execute_contract(
+        lockdrop_instance.clone(),
+        astro_token_instance,
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::new(100_000_000000),
+            contract: auction_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let success_ = app
+        .execute_contract(
+            Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+            auction_instance.clone(),
+            &ExecuteMsg::InitPool {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Auction::ExecuteMsg::AddLiquidityToAstroportPool")
+    );
+    assert_eq!(
+        success_.events[1].attributes[2],
+        attr("astro_provided", "242189994")
+    );
+    assert_eq!(
+        success_.events[1].attributes[3],
+        attr("ust_provided", "6530319")
+    );
+
+    // Auction :: Check state response
+    let state_resp: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        Uint128::from(242189994u64),
+        state_resp.total_astro_delegated
+    );
+    assert_eq!(Uint128::from(6530319u64), state_resp.total_ust_delegated);
+    assert_eq!(
+        Some(Uint128::from(39769057u64)),
+        state_resp.lp_shares_minted
+    );
+    assert!(!state_resp.is_lp_staked);
+    assert!(state_resp.generator_astro_per_share.is_zero());
+    assert_eq!(10611001u64, state_resp.pool_init_timestamp);
+
+    // Astroport Pool :: Check response
+    let pool_resp: astroport::pair::PoolResponse = app
+        .wrap()
+        .query_wasm_smart(&pair_instance, &astroport::pair::QueryMsg::Pool {})
+        .unwrap();
+    assert_eq!(Uint128::from(39769057u64), pool_resp.total_share);
+
+    // Airdrop :: Check config for claims
+    let airdrop_config_resp: astroport_periphery::airdrop::Config = app
+        .wrap()
+        .query_wasm_smart(
+            &airdrop_instance,
+            &astroport_periphery::airdrop::QueryMsg::Config {},
+        )
+        .unwrap();
+    assert_eq!(true, airdrop_config_resp.are_claims_enabled);
+
+    // Lockdrop :: Check state for claims
+    let lockdrop_config_resp: astroport_periphery::lockdrop::StateResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &astroport_periphery::lockdrop::QueryMsg::State {},
+        )
+        .unwrap();
+    assert_eq!(true, lockdrop_config_resp.are_claims_allowed);
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10911001)
+    });
+
+    // Auction :: Check user-1 state
+    let user1info_resp: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(100000000u64), user1info_resp.astro_delegated);
+    assert_eq!(Uint128::from(432423u64), user1info_resp.ust_delegated);
+    assert_eq!(Some(Uint128::from(9527010u64)), user1info_resp.lp_shares);
+    assert_eq!(
+        Some(Uint128::from(367554u64)),
+        user1info_resp.withdrawable_lp_shares
+    );
+    // LP shares are vested linearly, the rest is unlocked at the end of the vesting period
+    assert_eq!(Some(18387001u64), user1info_resp.next_lp_unlock_timestamp);
+    assert_eq!(
+        Some(Uint128::from(9527010u64 - 367554u64)),
+        user1info_resp.next_lp_unlock_amount
+    );
+    assert_eq!(
+        Some(Uint128::from(23955835814u64)),
+        user1info_resp.auction_incentive_amount
+    );
+    assert!(user1info_resp.generator_astro_debt.is_zero());
+
+    // Auction :: Check user-2 state
+    let user2info_resp: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(65435340u64), user2info_resp.astro_delegated);
+    assert_eq!(Uint128::from(454353u64), user2info_resp.ust_delegated);
+    assert_eq!(Some(Uint128::from(6755923u64)), user2info_resp.lp_shares);
+    assert_eq!(
+        Some(Uint128::from(260645u64)),
+        user2info_resp.withdrawable_lp_shares
+    );
+    assert_eq!(
+        Some(Uint128::from(16987888347u64)),
+        user2info_resp.auction_incentive_amount
+    );
+
+    // Auction :: Check user-3 state
+    let user3info_resp: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(76754654u64), user3info_resp.astro_delegated);
+    assert_eq!(Uint128::from(5643543u64), user3info_resp.ust_delegated);
+    assert_eq!(Some(Uint128::from(23486123u64)), user3info_resp.lp_shares);
+    assert_eq!(
+        Some(Uint128::from(906100u64)),
+        user3info_resp.withdrawable_lp_shares
+    );
+    assert_eq!(
+        Some(Uint128::from(59056273323u64)),
+        user3info_resp.auction_incentive_amount
+    );
+
+    // ######    ERROR :: Liquidity already added   ######
+    // user1_address, user2_address, user3_address
+    err = app
+        .execute_contract(
+            Addr::unchecked(auction_init_msg.owner.unwrap()),
+            auction_instance.clone(),
+            &ExecuteMsg::InitPool {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Liquidity already added"
+    );
+}
+
+#[test]
+fn test_stake_lp_tokens() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (
+        auction_instance,
+        astro_token_instance,
+        _,
+        _,
+        _,
+        lp_token_instance,
+        auction_init_msg,
+        token_code_id,
+        pair_code_id,
+    ) = init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    // ######    Initialize generator and vesting instance   ######
+    let (generator_instance, _) = instantiate_generator_and_vesting(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        lp_token_instance.clone(),
+        token_code_id,
+        pair_code_id,
+    );
+
+    let update_msg = UpdateConfigMsg {
+        owner: None,
+        astro_ust_pair_address: None,
+        generator_contract: Some(generator_instance.to_string()),
+        keeper: None,
+        init_pool_slippage: None,
+        compound_slippage: None,
+        allowlist_merkle_root: None,
+    };
+
+    app.execute_contract(
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: update_msg.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    app.execute_contract(
+        owner.clone(),
+        astro_token_instance,
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::new(100_000_000000),
+            contract: auction_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let simulation: SimulateInitPoolResponse = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::SimulateInitPool {})
+        .unwrap();
+    assert_eq!(
+        SimulateInitPoolResponse {
+            astro_amount: Uint128::from(242189994u64),
+            ust_amount: Uint128::from(6530319u64),
+            initial_price: Decimal::from_ratio(6530319u64, 242189994u64),
+            lp_shares: Uint128::from(39769057u64),
+            astro_delegators_lp_shares: Uint128::from(19884528u64),
+            ust_depositors_lp_shares: Uint128::from(19884528u64),
+            pool_has_liquidity: false,
+        },
+        simulation
+    );
+
+    let _success = app
+        .execute_contract(
+            Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+            auction_instance.clone(),
+            &ExecuteMsg::InitPool {},
+            &[],
+        )
+        .unwrap();
+
+    let simulation: SimulateInitPoolResponse = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::SimulateInitPool {})
+        .unwrap();
+    assert!(simulation.pool_has_liquidity);
+
+    let phase_resp: PhaseResponse = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Phase { timestamp: None })
+        .unwrap();
+    assert_eq!(AuctionPhase::PoolInitialized, phase_resp.phase);
+    assert_eq!(Some(10611001u64), phase_resp.pool_init_timestamp);
+
+    // ######    ERROR :: Unauthorized   ######
+
+    let mut err = app
+        .execute_contract(
+            Addr::unchecked("not_owner".to_string()),
+            auction_instance.clone(),
+            &ExecuteMsg::StakeLpTokens {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESS :: Stake successfully   ######
+
+    let success_ = app
+        .execute_contract(
+            Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+            auction_instance.clone(),
+            &ExecuteMsg::StakeLpTokens {},
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        success_.events[1].attributes[1],
+        attr("action", "Auction::ExecuteMsg::StakeLPTokens")
+    );
+    assert_eq!(
+        success_.events[1].attributes[2],
+        attr("staked_amount", "39769057")
+    );
+
+    // Auction :: Check state response
+    let state_resp: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        Uint128::from(242189994u64),
+        state_resp.total_astro_delegated
+    );
+    assert_eq!(Uint128::from(6530319u64), state_resp.total_ust_delegated);
+    assert_eq!(
+        Some(Uint128::from(39769057u64)),
+        state_resp.lp_shares_minted
+    );
+    assert!(state_resp.is_lp_staked);
+    assert_eq!(10611001u64, state_resp.pool_init_timestamp);
+
+    let phase_resp: PhaseResponse = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Phase { timestamp: None })
+        .unwrap();
+    assert_eq!(AuctionPhase::Staked, phase_resp.phase);
+
+    // Auction :: Check totals of users' positions
+    let totals_resp: TotalsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::Totals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(3u64, totals_resp.users_count);
+    assert_eq!(
+        state_resp.total_astro_delegated,
+        totals_resp.astro_delegated
+    );
+    assert_eq!(state_resp.total_ust_delegated, totals_resp.ust_delegated);
+    assert!(totals_resp.lp_shares <= state_resp.lp_shares_minted.unwrap());
+    assert_eq!(None, totals_resp.last_user);
+    assert_eq!(Some(true), totals_resp.is_consistent);
+
+    // Auction :: Sum totals page by page
+    let first_page: TotalsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::Totals {
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+    assert_eq!(2u64, first_page.users_count);
+    assert!(first_page.last_user.is_some());
+    assert_eq!(None, first_page.is_consistent);
+    let second_page: TotalsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::Totals {
+                start_after: first_page.last_user.as_ref().map(|v| v.to_string()),
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+    assert_eq!(1u64, second_page.users_count);
+    assert_eq!(None, second_page.last_user);
+    // Consistency of a page which doesn't cover all users isn't checked
+    assert_eq!(None, second_page.is_consistent);
+    assert_eq!(
+        totals_resp.astro_delegated,
+        first_page.astro_delegated + second_page.astro_delegated
+    );
+    assert_eq!(
+        totals_resp.ust_weighted,
+        first_page.ust_weighted + second_page.ust_weighted
+    );
+    assert_eq!(
+        totals_resp.lp_shares,
+        first_page.lp_shares + second_page.lp_shares
+    );
+
+    // Auction :: Check paginated users
+    let users_resp: Vec<UserInfoWithAddressResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::Users {
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        vec![user1_address.clone(), user2_address.clone()],
+        users_resp
+            .iter()
+            .map(|user| user.address.clone())
+            .collect::<Vec<_>>()
+    );
+    let user1_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(user1_resp, users_resp[0].user_info);
+
+    let users_resp: Vec<UserInfoWithAddressResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::Users {
+                start_after: Some(user2_address.to_string()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(1, users_resp.len());
+    assert_eq!(user3_address, users_resp[0].address);
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10911001)
+    });
+
+    // Auction :: Check user-1 state
+    let user1info_resp: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(100000000u64), user1info_resp.astro_delegated);
+    assert_eq!(Uint128::from(432423u64), user1info_resp.ust_delegated);
+    assert_eq!(Some(Uint128::from(9527010u64)), user1info_resp.lp_shares);
+    assert_eq!(Uint128::from(0u64), user1info_resp.claimed_lp_shares);
+    assert_eq!(
+        Some(Uint128::from(367554u64)),
+        user1info_resp.withdrawable_lp_shares
+    );
+    assert_eq!(
+        Some(Uint128::from(23955835814u64)),
+        user1info_resp.auction_incentive_amount
+    );
+
+    // Auction :: Check user-2 state
+    let user2info_resp: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(65435340u64), user2info_resp.astro_delegated);
+    assert_eq!(Uint128::from(454353u64), user2info_resp.ust_delegated);
+    assert_eq!(Some(Uint128::from(6755923u64)), user2info_resp.lp_shares);
+    assert_eq!(Uint128::from(0u64), user2info_resp.claimed_lp_shares);
+    assert_eq!(
+        Some(Uint128::from(260645u64)),
+        user2info_resp.withdrawable_lp_shares
+    );
+    assert_eq!(
+        Some(Uint128::from(16987888347u64)),
+        user2info_resp.auction_incentive_amount
+    );
+
+    // Auction :: Check user-3 state
+    let user3info_resp: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(76754654u64), user3info_resp.astro_delegated);
+    assert_eq!(Uint128::from(5643543u64), user3info_resp.ust_delegated);
+    assert_eq!(Some(Uint128::from(23486123u64)), user3info_resp.lp_shares);
+    assert_eq!(Uint128::from(0u64), user3info_resp.claimed_lp_shares);
+    assert_eq!(
+        Some(Uint128::from(906100u64)),
+        user3info_resp.withdrawable_lp_shares
+    );
+    assert_eq!(
+        Some(Uint128::from(59056273323u64)),
+        user3info_resp.auction_incentive_amount
+    );
+
+    // ######    ERROR :: Already staked   ######
+
+    err = app
+        .execute_contract(
+            Addr::unchecked(auction_init_msg.owner.unwrap()),
+            auction_instance.clone(),
+            &ExecuteMsg::StakeLpTokens {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Already staked"
+    );
+}
+
+#[test]
+fn test_keeper_init_pool_and_stake_lp_tokens() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (
+        auction_instance,
+        astro_token_instance,
+        _,
+        _,
+        _,
+        lp_token_instance,
+        auction_init_msg,
+        token_code_id,
+        pair_code_id,
+    ) = init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+    let keeper = Addr::unchecked("keeper");
+    let bounty = Uint128::new(1_000000);
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    let (generator_instance, _) = instantiate_generator_and_vesting(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        lp_token_instance.clone(),
+        token_code_id,
+        pair_code_id,
+    );
+
+    // ######    ERROR :: Invalid slippage   ######
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::UpdateConfig {
+                new_config: UpdateConfigMsg {
+                    owner: None,
+                    astro_ust_pair_address: None,
+                    generator_contract: None,
+                    keeper: None,
+                    init_pool_slippage: Some(Decimal::percent(51)),
+                    compound_slippage: None,
+                    allowlist_merkle_root: None,
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Slippage should be less than or equal to 50%"
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: Some(generator_instance.to_string()),
+                keeper: Some(KeeperConfig {
+                    grace_period: 86400,
+                    bounty,
+                }),
+                init_pool_slippage: Some(Decimal::percent(1)),
+                compound_slippage: None,
+                allowlist_merkle_root: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(Some(Decimal::percent(1)), config.init_pool_slippage);
+
+    app.execute_contract(
+        owner.clone(),
+        astro_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::new(100_000_000000),
+            contract: auction_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    // ######    ERROR :: Keeper grace period isn't over   ######
+
+    let err = app
+        .execute_contract(
+            keeper.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::InitPool {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESS :: Keeper initializes the pool   ######
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10686400)
+    });
+
+    app.execute_contract(
+        keeper.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    let keeper_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: keeper.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bounty, keeper_balance.balance);
+
+    // The InitPool bounty is deducted from the auction incentives, the staking bounty is reserved
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        Some(Uint128::new(100_000_000000) - bounty),
+        config.astro_incentive_amount
+    );
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert!(state.lp_shares_minted.is_some());
+    assert_eq!(bounty, state.keeper_bounty_reserve);
+
+    // ######    ERROR :: Keeper grace period isn't over   ######
+
+    let err = app
+        .execute_contract(
+            keeper.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::StakeLpTokens {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESS :: Keeper stakes LP Tokens   ######
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10772800)
+    });
+
+    app.execute_contract(
+        keeper.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
+
+    let keeper_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: keeper.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(bounty + bounty, keeper_balance.balance);
+
+    // Both bounties are deducted from the auction incentives
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        Some(Uint128::new(100_000_000000) - bounty - bounty),
+        config.astro_incentive_amount
+    );
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert!(state.is_lp_staked);
+    assert!(state.keeper_bounty_reserve.is_zero());
+
+    let totals: TotalsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::Totals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(Some(true), totals.is_consistent);
+}
+
+#[test]
+fn test_owner_init_pool_and_stake_lp_tokens_with_keeper() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (
+        auction_instance,
+        astro_token_instance,
+        _,
+        _,
+        _,
+        lp_token_instance,
+        auction_init_msg,
+        token_code_id,
+        pair_code_id,
+    ) = init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+    let bounty = Uint128::new(1_000000);
+    let total_incentives = Uint128::new(100_000_000000);
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    let (generator_instance, _) = instantiate_generator_and_vesting(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        lp_token_instance.clone(),
+        token_code_id,
+        pair_code_id,
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: Some(generator_instance.to_string()),
+                keeper: Some(KeeperConfig {
+                    grace_period: 86400,
+                    bounty,
+                }),
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        astro_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: total_incentives,
+            contract: auction_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    let query_astro_balance = |app: &App, address: &Addr| -> Uint128 {
+        let res: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &astro_token_instance,
+                &cw20::Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.balance
+    };
+    let owner_balance = query_astro_balance(&app, &owner);
+
+    // ######    SUCCESS :: Owner initializes the pool   ######
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(bounty, state.keeper_bounty_reserve);
+
+    // Users' incentives aren't final until LP Tokens are staked
+    let user1_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(None, user1_info.auction_incentive_amount);
+
+    // ######    SUCCESS :: Owner stakes LP Tokens   ######
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
+
+    // The reserved bounty stays in the auction incentives
+    assert_eq!(owner_balance, query_astro_balance(&app, &owner));
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(Some(total_incentives), config.astro_incentive_amount);
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert!(state.is_lp_staked);
+    assert!(state.keeper_bounty_reserve.is_zero());
+    let lp_shares_minted = state.lp_shares_minted.unwrap();
+
+    // Participants get the same incentives as without a keeper
+    for user_address in [user1_address, user2_address, user3_address] {
+        let user_info: UserInfoResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &auction_instance,
+                &QueryMsg::UserInfo {
+                    address: user_address.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            Some(
+                Decimal::from_ratio(user_info.lp_shares.unwrap(), lp_shares_minted)
+                    * total_incentives
+            ),
+            user_info.auction_incentive_amount
+        );
+    }
+
+    let totals: TotalsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::Totals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(Some(true), totals.is_consistent);
+}
+
+#[test]
+fn test_release_keeper_bounty_reserve() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (auction_instance, astro_token_instance, _, _, _, _, auction_init_msg, _, _) =
+        init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+    let bounty = Uint128::new(1_000000);
+    let total_incentives = Uint128::new(100_000_000000);
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    let (user1_address, _, _) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    // The generator isn't set
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: None,
+                keeper: Some(KeeperConfig {
+                    grace_period: 86400,
+                    bounty,
+                }),
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        astro_token_instance.clone(),
+        &Cw20ExecuteMsg::Send {
+            amount: total_incentives,
+            contract: auction_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    // ######    SUCCESS :: The staking bounty is reserved without the generator   ######
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(bounty, state.keeper_bounty_reserve);
+
+    let user1_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(None, user1_info.auction_incentive_amount);
+
+    // ######    ERROR :: Unauthorized   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::EmergencyUnstake {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESS :: Staking isn't possible, the reserve is released to users   ######
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::EmergencyUnstake {},
+        &[],
+    )
+    .unwrap();
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert!(!state.is_lp_staked);
+    assert!(state.keeper_bounty_reserve.is_zero());
+    let lp_shares_minted = state.lp_shares_minted.unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(Some(total_incentives), config.astro_incentive_amount);
+    assert_eq!(None, config.generator_contract);
+
+    let user1_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Some(
+            Decimal::from_ratio(user1_info.lp_shares.unwrap(), lp_shares_minted) * total_incentives
+        ),
+        user1_info.auction_incentive_amount
+    );
+
+    // ######    ERROR :: Nothing to release   ######
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::EmergencyUnstake {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: LP Tokens aren't staked yet!"
+    );
+}
+
+#[test]
+fn test_claim_rewards() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (
+        auction_instance,
+        astro_token_instance,
+        _,
+        _,
+        _,
+        lp_token_instance,
+        auction_init_msg,
+        token_code_id,
+        pair_code_id,
+    ) = init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+
+    let claim_rewards_msg = ExecuteMsg::ClaimRewards {
+        withdraw_lp_shares: None,
+        user_address: None,
+    };
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    // mint ASTRO to owner
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        owner.to_string(),
+    );
+
+    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    // ######    Initialize generator and vesting instance   ######
+    let (generator_instance, _) = instantiate_generator_and_vesting(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        lp_token_instance.clone(),
+        token_code_id,
+        pair_code_id,
+    );
+
+    let update_msg = UpdateConfigMsg {
+        owner: None,
+        astro_ust_pair_address: None,
+        generator_contract: Some(generator_instance.to_string()),
+        keeper: None,
+        init_pool_slippage: None,
+        compound_slippage: None,
+        allowlist_merkle_root: None,
+    };
+
+    app.execute_contract(
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: update_msg.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        astro_token_instance,
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::new(100_000_000000),
+            contract: auction_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Deposit/withdrawal windows are open   ######
+
+    let mut err = app
+        .execute_contract(
+            owner,
+            auction_instance.clone(),
+            &ExecuteMsg::InitPool {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Deposit/withdrawal windows are still open"
+    );
+
+    // Astro/USD should be provided to the pool
+
+    err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &claim_rewards_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Astro/USD should be provided to the pool!"
+    );
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    // ######    ERROR :: Invalid request   ######
+
+    err = app
+        .execute_contract(
+            Addr::unchecked("not_user".to_string()),
+            auction_instance.clone(),
+            &claim_rewards_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "astroport_periphery::auction::UserInfo not found"
+    );
+
+    // ######    Sucess :: Initialize ASTRO-UST Pool   ######
+
+    app.execute_contract(
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    SUCCESS :: Stake successfully   ######
+
+    app.execute_contract(
+        Addr::unchecked(auction_init_msg.owner.unwrap()),
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10911001)
+    });
+
+    // ######    SUCCESS :: Successfully claim staking rewards for User-1 ######
+
+    // Auction :: Check user-1 state (before claim)
+    let user1info_before_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    // Auction :: Claim rewards for the user
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &claim_rewards_msg,
+        &[],
+    )
+    .unwrap();
+
+    // Auction :: Check user-1 state (After Claim)
+    let user1info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user1info_before_claim.claimed_lp_shares,
+        user1info_after_claim.claimed_lp_shares
+    );
+    assert_eq!(
+        user1info_before_claim.withdrawable_lp_shares,
+        user1info_after_claim.withdrawable_lp_shares
+    );
+    // Auction incentives are unlocked linearly alongside LP shares
+    assert_eq!(
+        Uint128::from(924222066u64),
+        user1info_before_claim.claimable_incentives
+    );
+    assert_eq!(
+        Uint128::from(924222066u64),
+        user1info_after_claim.claimed_incentives
+    );
+    assert!(user1info_after_claim.claimable_incentives.is_zero());
+
+    // ######    SUCCESS :: Successfully claim staking rewards for User-2 ######
+
+    // Auction :: Check user-2 state (before claim)
+    let user2info_before_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    // Auction :: Claim rewards for the user 2
+    app.execute_contract(
+        user2_address.clone(),
+        auction_instance.clone(),
+        &claim_rewards_msg,
+        &[],
+    )
+    .unwrap();
+
+    // Auction :: Check user-2 state (After Claim)
+    let user2info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user2info_before_claim.claimed_lp_shares,
+        user2info_after_claim.claimed_lp_shares
+    );
+    assert_eq!(
+        user2info_before_claim.withdrawable_lp_shares,
+        user2info_after_claim.withdrawable_lp_shares
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10991001)
+    });
+
+    // ######    SUCCESS :: Successfully claim staking rewards for User-3 ######
+
+    // Auction :: Check user-3 state (before claim)
+    let user3info_before_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    // Auction :: Claim rewards for the user 3
+    app.execute_contract(
+        user3_address.clone(),
+        auction_instance.clone(),
+        &claim_rewards_msg,
+        &[],
+    )
+    .unwrap();
+
+    // Auction :: Check user-3 state (After Claim)
+    let user3info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user3info_before_claim.claimed_lp_shares,
+        user3info_after_claim.claimed_lp_shares
+    );
+    assert_eq!(
+        user3info_before_claim.withdrawable_lp_shares,
+        user3info_after_claim.withdrawable_lp_shares
+    );
+
+    // ######    SUCCESS :: Successfully again claim staking rewards for User-1 ######
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10992001)
+    });
+
+    // Auction :: Check user-1 state (before claim)
+    let user1info_before_claim2: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    // Auction :: Claim rewards for the user
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &claim_rewards_msg,
+        &[],
+    )
+    .unwrap();
+
+    // Auction :: Check user-1 state (After Claim)
+    let user1info_after_claim2: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user1info_before_claim2.claimed_lp_shares,
+        user1info_after_claim2.claimed_lp_shares
+    );
+    assert_eq!(
+        user1info_before_claim2.withdrawable_lp_shares,
+        user1info_after_claim2.withdrawable_lp_shares
+    );
+    assert_eq!(
+        Uint128::from(249539957u64),
+        user1info_before_claim2.claimable_incentives
+    );
+    assert_eq!(
+        Uint128::from(924222066u64 + 249539957u64),
+        user1info_after_claim2.claimed_incentives
+    );
+}
+
+#[test]
+fn test_migrate_generator_and_emergency_unstake() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (
+        auction_instance,
+        astro_token_instance,
+        _,
+        _,
+        _,
+        lp_token_instance,
+        auction_init_msg,
+        token_code_id,
+        pair_code_id,
+    ) = init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    let (user1_address, _, _) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    // ######    Initialize generator and vesting instance   ######
+    let (generator_instance, _) = instantiate_generator_and_vesting(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        lp_token_instance.clone(),
+        token_code_id,
+        pair_code_id,
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: Some(generator_instance.to_string()),
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: LP Tokens aren't staked yet   ######
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::EmergencyUnstake {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: LP Tokens aren't staked yet!"
+    );
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    let lp_shares_minted = state.lp_shares_minted.unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10911001)
+    });
+
+    // ######    Initialize the new generator   ######
+    let (new_generator_instance, _) = instantiate_generator_and_vesting(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        lp_token_instance.clone(),
+        token_code_id,
+        pair_code_id,
+    );
+
+    // ######    ERROR :: Unauthorized   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::MigrateGenerator {
+                generator: new_generator_instance.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    ERROR :: Same generator   ######
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::MigrateGenerator {
+                generator: generator_instance.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: New generator should differ from the current one"
+    );
+
+    // ######    SUCCESS :: Migrate to the new generator   ######
+
+    let user1info_before_migration: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!user1info_before_migration
+        .claimable_generator_astro
+        .is_zero());
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::MigrateGenerator {
+            generator: new_generator_instance.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(
+        Some(new_generator_instance.clone()),
+        config.generator_contract
+    );
+
+    for (generator, expected_deposit) in [
+        (&generator_instance, Uint128::zero()),
+        (&new_generator_instance, lp_shares_minted),
+    ] {
+        let deposit: Uint128 = app
+            .wrap()
+            .query_wasm_smart(
+                generator,
+                &astroport::generator::QueryMsg::Deposit {
+                    lp_token: lp_token_instance.to_string(),
+                    user: auction_instance.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(expected_deposit, deposit);
+    }
+
+    // Rewards pending in the old generator are settled in the contract
+    let user1info_after_migration: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user1info_before_migration.claimable_generator_astro,
+        user1info_after_migration.claimable_generator_astro
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10951001)
+    });
+
+    // ######    ERROR :: Unauthorized   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::EmergencyUnstake {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESS :: Emergency unstake   ######
+
+    let pending_rewards: PendingTokenResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &new_generator_instance,
+            &GeneratorQueryMsg::PendingToken {
+                lp_token: lp_token_instance.to_string(),
+                user: auction_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!pending_rewards.pending.is_zero());
+    let state_before_unstake: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::EmergencyUnstake {},
+        &[],
+    )
+    .unwrap();
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert!(!state.is_lp_staked);
+    // Pending rewards aren't claimed, so the reward indexes stay as they were
+    assert_eq!(
+        state_before_unstake.generator_astro_per_share,
+        state.generator_astro_per_share
+    );
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(None, config.generator_contract);
+
+    let lp_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lp_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: auction_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(lp_shares_minted, lp_balance.balance);
+
+    // Rewards pending in the generator are forfeited, rewards settled before are still claimable
+    let user1info_before_claim: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user1info_after_migration.claimable_generator_astro,
+        user1info_before_claim.claimable_generator_astro
+    );
+    let withdraw_lp_shares = user1info_before_claim.withdrawable_lp_shares.unwrap();
+    assert!(!withdraw_lp_shares.is_zero());
+
+    let astro_balance_before: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    // ######    SUCCESS :: Withdraw vested LP shares without the generator   ######
+
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: Some(withdraw_lp_shares),
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let lp_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lp_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(withdraw_lp_shares, lp_balance.balance);
+
+    let astro_balance_after: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        astro_balance_before.balance
+            + user1info_before_claim.claimable_generator_astro
+            + user1info_before_claim.claimable_incentives,
+        astro_balance_after.balance
+    );
+
+    let user1info_after_claim: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(withdraw_lp_shares, user1info_after_claim.claimed_lp_shares);
+    assert!(user1info_after_claim.claimable_generator_astro.is_zero());
+
+    // ######    ERROR :: Already unstaked   ######
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::MigrateGenerator {
+                generator: generator_instance.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: LP Tokens aren't staked yet!"
+    );
+
+    // ######    ERROR :: Generator isn't set   ######
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::StakeLpTokens {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Generator should be set!"
+    );
+
+    // ######    SUCCESS :: Stake LP Tokens again   ######
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: Some(new_generator_instance.to_string()),
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        owner,
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
+
+    let deposit: Uint128 = app
+        .wrap()
+        .query_wasm_smart(
+            &new_generator_instance,
+            &astroport::generator::QueryMsg::Deposit {
+                lp_token: lp_token_instance.to_string(),
+                user: auction_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(lp_shares_minted - withdraw_lp_shares, deposit);
+}
+
+#[test]
+fn test_compound_generator_rewards() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (
+        auction_instance,
+        astro_token_instance,
+        _,
+        _,
+        pair_instance,
+        lp_token_instance,
+        auction_init_msg,
+        token_code_id,
+        pair_code_id,
+    ) = init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+    let keeper = Addr::unchecked("keeper");
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    let (user1_address, _, _) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    // ######    Initialize generator and vesting instance   ######
+    let (generator_instance, _) = instantiate_generator_and_vesting(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        lp_token_instance.clone(),
+        token_code_id,
+        pair_code_id,
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: Some(generator_instance.to_string()),
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: LP Tokens aren't staked yet   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::SetCompounding {
+                enabled: true,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: LP Tokens aren't staked yet!"
+    );
+
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10911001)
+    });
+
+    // ######    ERROR :: Only the owner sets the compounding slippage   ######
+
+    let compound_slippage_msg = |compound_slippage: Decimal| ExecuteMsg::UpdateConfig {
+        new_config: UpdateConfigMsg {
+            owner: None,
+            astro_ust_pair_address: None,
+            generator_contract: None,
+            keeper: None,
+            init_pool_slippage: None,
+            compound_slippage: Some(compound_slippage),
+            allowlist_merkle_root: None,
+        },
+    };
+
+    let err = app
+        .execute_contract(
+            keeper.clone(),
+            auction_instance.clone(),
+            &compound_slippage_msg(Decimal::percent(5)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Only owner can update configuration"
+    );
+
+    // ######    ERROR :: Slippage is too high   ######
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            auction_instance.clone(),
+            &compound_slippage_msg(Decimal::percent(10)),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Slippage should be less than or equal to 5%"
+    );
+
+    app.execute_contract(
+        owner,
+        auction_instance.clone(),
+        &compound_slippage_msg(Decimal::percent(5)),
+        &[],
+    )
+    .unwrap();
+
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.compound_slippage, Some(Decimal::percent(5)));
+
+    // ######    ERROR :: Nobody compounds   ######
+
+    let err = app
+        .execute_contract(
+            keeper.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::Compound {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Nothing to compound!"
+    );
+
+    // ######    SUCCESS :: User-1 opts in to compounding   ######
+
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::SetCompounding {
+            enabled: true,
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::SetCompounding {
+                enabled: true,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Compounding is already enabled"
+    );
+
+    let user1_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(user1_info.compounding);
+    let user1_lp_shares = user1_info.lp_shares.unwrap();
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.pending_compounding_lp_shares, user1_lp_shares);
+    assert!(state.compounding_lp_shares.is_zero());
+
+    // ######    SUCCESS :: First round only lets User-1 join the compounding pool   ######
+
+    app.execute_contract(
+        keeper.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::Compound {},
+        &[],
+    )
+    .unwrap();
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.compound_round, 1);
+    assert_eq!(state.compounding_lp_shares, user1_lp_shares);
+    assert!(state.pending_compounding_lp_shares.is_zero());
+    assert!(state.compounded_lp_shares.is_zero());
+    assert!(state.total_compound_units.is_zero());
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10991001)
+    });
+
+    // ######    SUCCESS :: Second round compounds User-1's ASTRO rewards   ######
+
+    let query_staked_lp = |app: &App| -> Uint128 {
+        app.wrap()
+            .query_wasm_smart(
+                &generator_instance,
+                &astroport::generator::QueryMsg::Deposit {
+                    lp_token: lp_token_instance.to_string(),
+                    user: auction_instance.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    let query_lp_supply = |app: &App| -> Uint128 {
+        let pool: astroport::pair::PoolResponse = app
+            .wrap()
+            .query_wasm_smart(&pair_instance, &astroport::pair::QueryMsg::Pool {})
+            .unwrap();
+        pool.total_share
+    };
+    let staked_lp_before = query_staked_lp(&app);
+    let lp_supply_before = query_lp_supply(&app);
+    let prev_compound_astro_per_share = state.compound_astro_per_share;
+
+    let res = app
+        .execute_contract(
+            keeper,
+            auction_instance.clone(),
+            &ExecuteMsg::Compound {},
+            &[],
+        )
+        .unwrap();
+    let attribute = |key: &str| -> Uint128 {
+        res.events
+            .iter()
+            .flat_map(|event| event.attributes.iter())
+            .find(|attribute| attribute.key == key)
+            .unwrap()
+            .value
+            .parse()
+            .unwrap()
+    };
+    let astro_compounded = attribute("astro_compounded");
+    assert_eq!(
+        attribute("astro_provided"),
+        astro_compounded - astro_compounded.multiply_ratio(1u128, 2u128)
+    );
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.compound_round, 2);
+    // Only User-1's LP shares accrued the compounded ASTRO rewards
+    assert_eq!(
+        astro_compounded,
+        state.compound_astro_per_share * user1_lp_shares
+            - prev_compound_astro_per_share * user1_lp_shares
+    );
+    // All LP Tokens minted by the pool are staked and accounted in the compounding pool
+    let lp_minted = query_lp_supply(&app) - lp_supply_before;
+    assert!(!lp_minted.is_zero());
+    assert_eq!(query_staked_lp(&app) - staked_lp_before, lp_minted);
+    assert_eq!(state.compounded_lp_shares, lp_minted);
+    // The first units are minted one per LP share
+    assert_eq!(state.total_compound_units, lp_minted);
+    assert_eq!(
+        state.compound_units_per_share,
+        Decimal::from_ratio(lp_minted, user1_lp_shares)
+    );
+
+    let user1_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    // User-1 is the only compounding user, so all units except rounding dust belong to User-1
+    assert_eq!(
+        user1_info.compound_units,
+        state.compound_units_per_share * user1_lp_shares
+    );
+    assert_eq!(
+        user1_info.compounded_lp_shares,
+        lp_minted.multiply_ratio(user1_info.compound_units, state.total_compound_units)
+    );
+
+    // Compounding pool units and LP tokens of users are consistent with the state
+    let totals: TotalsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::Totals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(totals.compound_units, user1_info.compound_units);
+    assert_eq!(totals.compounded_lp_shares, user1_info.compounded_lp_shares);
+    assert_eq!(Some(true), totals.is_consistent);
+    // ASTRO rewards accrued before joining the compounding pool are still claimable
+    assert!(!user1_info.claimable_generator_astro.is_zero());
+
+    // ######    ERROR :: Not enough units   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::WithdrawCompoundedLp {
+                units: user1_info.compound_units + Uint128::new(1),
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Not enough compounding pool units"
+    );
+
+    // ######    SUCCESS :: User-1 withdraws compounded LP Tokens   ######
+
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::WithdrawCompoundedLp {
+            units: user1_info.compound_units,
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let lp_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lp_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(lp_balance.balance, user1_info.compounded_lp_shares);
+
+    let state_after_withdrawal: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(
+        state_after_withdrawal.total_compound_units,
+        state.total_compound_units - user1_info.compound_units
+    );
+    assert_eq!(
+        state_after_withdrawal.compounded_lp_shares,
+        state.compounded_lp_shares - user1_info.compounded_lp_shares
+    );
+
+    // ######    SUCCESS :: User-1 opts out of compounding   ######
+
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::SetCompounding {
+            enabled: false,
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert!(state.compounding_lp_shares.is_zero());
+
+    let user1_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!user1_info.compounding);
+    assert!(user1_info.compound_units.is_zero());
+}
+
+#[test]
+fn test_transfer_position() {
+    let owner = Addr::unchecked("owner");
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000u128),
+            },
+        ],
+    );
+    let (auction_instance, astro_token_instance, _, _, _, _, auction_init_msg, _, _) =
+        init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+    let user4_address = Addr::unchecked("user4");
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    // ######    ERROR :: Invalid fraction   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::TransferPosition {
+                recipient: user4_address.to_string(),
+                fraction: Decimal::percent(101),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Fraction should be more than 0 and less than or equal to 1"
+    );
+
+    // ######    ERROR :: Transfer to yourself   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::TransferPosition {
+                recipient: user1_address.to_string(),
+                fraction: Decimal::percent(50),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Recipient should differ from the sender"
+    );
+
+    // ######    SUCCESS :: Transfer half of the position to a new address   ######
+
+    app.execute_contract(
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::TransferPosition {
+            recipient: user4_address.to_string(),
+            fraction: Decimal::percent(50),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user1_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(user1_info.astro_delegated, Uint128::from(50000000u64));
+    assert_eq!(user1_info.ust_delegated, Uint128::from(216212u64));
+    assert_eq!(user1_info.astro_weighted, Uint128::from(50000000u64));
+    assert_eq!(user1_info.ust_weighted, Uint128::from(216212u64));
+
+    let user4_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user4_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(user4_info.astro_delegated, Uint128::from(50000000u64));
+    assert_eq!(user4_info.ust_delegated, Uint128::from(216211u64));
+    assert_eq!(user4_info.astro_weighted, Uint128::from(50000000u64));
+    assert_eq!(user4_info.ust_weighted, Uint128::from(216211u64));
+
+    // ######    SUCCESS :: Transfer the whole position to an existing participant   ######
+
+    app.execute_contract(
+        user2_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::TransferPosition {
+            recipient: user3_address.to_string(),
+            fraction: Decimal::one(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user2_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(user2_info.astro_delegated.is_zero());
+    assert!(user2_info.ust_delegated.is_zero());
+
+    let user3_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(user3_info.astro_delegated, Uint128::from(142189994u64));
+    assert_eq!(user3_info.ust_delegated, Uint128::from(6097896u64));
+
+    // ######    ERROR :: Sender has no position   ######
+
+    let err = app
+        .execute_contract(
+            user2_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::TransferPosition {
+                recipient: user3_address.to_string(),
+                fraction: Decimal::one(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "astroport_periphery::auction::UserInfo not found"
+    );
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    app.execute_contract(
+        owner,
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    SUCCESS :: LP shares are merged once the pool is initialized   ######
+
+    let user1_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    let user4_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user4_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        user4_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::TransferPosition {
+            recipient: user1_address.to_string(),
+            fraction: Decimal::one(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user1_info_after_transfer: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user1_info_after_transfer.lp_shares.unwrap(),
+        user1_info.lp_shares.unwrap() + user4_info.lp_shares.unwrap()
+    );
+    assert_eq!(
+        user1_info_after_transfer.astro_delegated,
+        Uint128::from(100000000u64)
+    );
+    assert_eq!(
+        user1_info_after_transfer.ust_delegated,
+        Uint128::from(432423u64)
+    );
+}
+
+#[test]
+fn test_withdraw_unlocked_lp_shares() {
+    let owner = Addr::unchecked(OWNER);
+    let mut app = mock_app(
+        owner.clone(),
+        vec![
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100_000_000_000_000u128),
+            },
+            Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(100_000_000_000_000u128),
+            },
+        ],
+    );
+    let (
+        auction_instance,
+        astro_token_instance,
+        _,
+        _,
+        _,
+        lp_token_instance,
+        auction_init_msg,
+        token_code_id,
+        pair_code_id,
+    ) = init_all_contracts(&mut app);
+
+    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
+
+    let withdraw_lp_msg = ExecuteMsg::ClaimRewards {
+        withdraw_lp_shares: Some(Uint128::new(1)),
+        user_address: None,
+    };
+
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
+
+    // mint ASTRO to Auction Contract
+    mint_some_astro(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_instance.to_string(),
+    );
+
+    let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    // ######    Initialize generator and vesting instance   ######
+    let (generator_instance, _) = instantiate_generator_and_vesting(
+        &mut app,
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        astro_token_instance.clone(),
+        lp_token_instance.clone(),
+        token_code_id,
+        pair_code_id,
+    );
+
+    let update_msg = UpdateConfigMsg {
+        owner: None,
+        astro_ust_pair_address: None,
+        generator_contract: Some(generator_instance.to_string()),
+        keeper: None,
+        init_pool_slippage: None,
+        compound_slippage: None,
+        allowlist_merkle_root: None,
+    };
+
+    app.execute_contract(
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        auction_instance.clone(),
+        &ExecuteMsg::UpdateConfig {
+            new_config: update_msg.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Deposit/withdrawal windows are open   ######
+
+    let mut err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &withdraw_lp_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Astro/USD should be provided to the pool!"
+    );
+
+    // finish deposit / withdraw period
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10611001)
+    });
+
+    // ######    ERROR :: Invalid request. No LP Tokens to claim   ######
+
+    err = app
+        .execute_contract(
+            Addr::unchecked("not_user".to_string()),
+            auction_instance.clone(),
+            &withdraw_lp_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "astroport_periphery::auction::UserInfo not found"
+    );
+
+    // ######    Sucess :: Initialize ASTRO-UST Pool   ######
+
+    app.execute_contract(
+        owner.clone(),
+        astro_token_instance,
+        &Cw20ExecuteMsg::Send {
+            amount: Uint128::new(100_000_000000),
+            contract: auction_instance.to_string(),
+            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    SUCCESS :: Stake successfully   ######
+
+    app.execute_contract(
+        Addr::unchecked(auction_init_msg.owner.unwrap()),
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10911001)
+    });
+
+    // ######    SUCCESS :: Successfully withdraw LP shares (which also claims rewards) for User-1 ######
+
+    // Auction :: Check user-1 state (before claim)
+    let user1info_before_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(0u64),
+        user1info_before_claim.claimed_lp_shares
+    );
 
+    // Auction :: Withdraw unvested LP shares for the user
     app.execute_contract(
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: Some(user1info_before_claim.withdrawable_lp_shares.unwrap()),
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Auction :: Check user-1 state (After Claim)
+    let user1info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user1info_before_claim.withdrawable_lp_shares.unwrap(),
+        user1info_after_claim.claimed_lp_shares
+    );
+    assert_eq!(
+        Uint128::from(0u64),
+        user1info_after_claim.withdrawable_lp_shares.unwrap()
+    );
+
+    // ######    SUCCESS :: Successfully withdraw LP shares (which also claims rewards) for User-2 ######
+
+    // Auction :: Check user-2 state (before claim)
+    let user2info_before_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(0u64),
+        user2info_before_claim.claimed_lp_shares
+    );
+
+    // Auction :: Withdraw unvested LP shares for the user
+    app.execute_contract(
+        user2_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: Some(user2info_before_claim.withdrawable_lp_shares.unwrap()),
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Auction :: Check user-2 state (After Claim)
+    let user2info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user2info_before_claim.withdrawable_lp_shares.unwrap(),
+        user2info_after_claim.claimed_lp_shares
+    );
+    assert_eq!(
+        Uint128::from(0u64),
+        user2info_after_claim.withdrawable_lp_shares.unwrap()
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(10991001)
+    });
+
+    // ######    SUCCESS :: Successfully withdraw LP shares (which also claims rewards) for User-3 ######
+
+    // Auction :: Check user-3 state (before claim)
+    let user3info_before_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(0u64),
+        user3info_before_claim.claimed_lp_shares
+    );
+
+    // Auction :: Withdraw unvested LP shares for the user
+    app.execute_contract(
+        user3_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: Some(user3info_before_claim.withdrawable_lp_shares.unwrap()),
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Auction :: Check user-3 state (After Claim)
+    let user3info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user3_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user3info_before_claim.withdrawable_lp_shares.unwrap(),
+        user3info_after_claim.claimed_lp_shares
+    );
+    assert_eq!(
+        Some(Uint128::zero()),
+        user3info_after_claim.withdrawable_lp_shares
+    );
+
+    // ######    SUCCESS :: Successfully again withdraw LP shares (which also claims rewards) for User-1 ######
+
+    // Auction :: Check user-1 state (before claim)
+    let user1info_before_claim2: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    // Auction :: Withdraw LP for the user
+    app.execute_contract(
+        user1_address.clone(),
         auction_instance.clone(),
-        &ExecuteMsg::InitPool { slippage: None },
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: Some(user1info_before_claim2.withdrawable_lp_shares.unwrap()),
+            user_address: None,
+        },
         &[],
     )
     .unwrap();
 
-    // ######    SUCCESS :: Stake successfully   ######
+    // Auction :: Check user-1 state (After Claim)
+    let user1info_after_claim2: astroport_periphery::auction::UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &auction_instance,
+            &astroport_periphery::auction::QueryMsg::UserInfo {
+                address: user1_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        user1info_before_claim2.claimed_lp_shares
+            + user1info_before_claim2.withdrawable_lp_shares.unwrap(),
+        user1info_after_claim2.claimed_lp_shares
+    );
+    assert_eq!(
+        Some(Uint128::zero()),
+        user1info_after_claim2.withdrawable_lp_shares
+    );
+}
+
+// Auction state stored by the v1.0.0 contract
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+struct ConfigV100 {
+    owner: Addr,
+    astro_token_address: Addr,
+    airdrop_contract_address: Addr,
+    lockdrop_contract_address: Addr,
+    pool_info: Option<PoolInfo>,
+    generator_contract: Option<Addr>,
+    astro_incentive_amount: Option<Uint128>,
+    lp_tokens_vesting_duration: u64,
+    init_timestamp: u64,
+    deposit_window: u64,
+    withdrawal_window: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+struct StateV100 {
+    total_astro_delegated: Uint128,
+    total_ust_delegated: Uint128,
+    is_lp_staked: bool,
+    lp_shares_minted: Option<Uint128>,
+    pool_init_timestamp: u64,
+    generator_astro_per_share: Decimal,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+struct UserInfoV100 {
+    astro_delegated: Uint128,
+    ust_delegated: Uint128,
+    ust_withdrawn: bool,
+    lp_shares: Option<Uint128>,
+    claimed_lp_shares: Uint128,
+    auction_incentive_amount: Option<Uint128>,
+    astro_incentive_transferred: bool,
+    generator_astro_debt: Uint128,
+    user_gen_astro_per_share: Decimal,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+struct V100InstantiateMsg {
+    config: ConfigV100,
+    state: StateV100,
+    users: Vec<(Addr, UserInfoV100)>,
+}
+
+// Stores the v1.0.0 auction state to be migrated
+fn v100_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: V100InstantiateMsg,
+) -> StdResult<Response> {
+    cw2::set_contract_version(deps.storage, "astroport_auction", "1.0.0")?;
+    cw_storage_plus::Item::new("config").save(deps.storage, &msg.config)?;
+    cw_storage_plus::Item::new("state").save(deps.storage, &msg.state)?;
+    let users: cw_storage_plus::Map<&Addr, UserInfoV100> = cw_storage_plus::Map::new("users");
+    for (address, user_info) in msg.users {
+        users.save(deps.storage, &address, &user_info)?;
+    }
+    Ok(Response::default())
+}
+
+fn v100_execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Err(StdError::generic_err("Not supported"))
+}
+
+fn v100_query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("Not supported"))
+}
+
+const MOCK_DELEGATED_ASTRO: cw_storage_plus::Map<&Addr, Uint128> =
+    cw_storage_plus::Map::new("delegated_astro");
+
+// Stores ASTRO delegated to the v1.0.0 auction by the airdrop / lockdrop users
+fn mock_delegator_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: Vec<(Addr, Uint128)>,
+) -> StdResult<Response> {
+    for (address, amount) in msg {
+        MOCK_DELEGATED_ASTRO.save(deps.storage, &address, &amount)?;
+    }
+    Ok(Response::default())
+}
+
+fn mock_airdrop_query(
+    deps: Deps,
+    _env: Env,
+    msg: astroport_periphery::airdrop::QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        astroport_periphery::airdrop::QueryMsg::UserInfo { address } => {
+            let delegated_amount = MOCK_DELEGATED_ASTRO
+                .may_load(deps.storage, &Addr::unchecked(address))?
+                .unwrap_or_default();
+            to_binary(&astroport_periphery::airdrop::UserInfo {
+                claimed_amount: delegated_amount,
+                delegated_amount,
+                tokens_withdrawn: false,
+            })
+        }
+        _ => Err(StdError::generic_err("Not supported")),
+    }
+}
+
+fn mock_lockdrop_query(
+    deps: Deps,
+    _env: Env,
+    msg: astroport_periphery::lockdrop::QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        astroport_periphery::lockdrop::QueryMsg::UserInfoWithLockupsList { address } => {
+            let delegated_astro_rewards = MOCK_DELEGATED_ASTRO
+                .may_load(deps.storage, &Addr::unchecked(address))?
+                .unwrap_or_default();
+            to_binary(&astroport_periphery::lockdrop::UserInfoWithListResponse {
+                total_astro_rewards: delegated_astro_rewards,
+                delegated_astro_rewards,
+                astro_transferred: false,
+                lockup_infos: vec![],
+                lockup_positions_index: 1,
+            })
+        }
+        _ => Err(StdError::generic_err("Not supported")),
+    }
+}
+
+#[test]
+fn test_migrate_from_v100() {
+    let owner = Addr::unchecked(OWNER);
+    let mut app = mock_app(owner.clone(), vec![]);
+
+    let astro_token_instance = instantiate_astro_token(&mut app, owner.clone());
+    let user1_address = Addr::unchecked("user1");
+    let user2_address = Addr::unchecked("user2");
+    let pool_init_timestamp = 20_000_000u64;
+
+    let v100_code_id = app.store_code(Box::new(ContractWrapper::new(
+        v100_execute,
+        v100_instantiate,
+        v100_query,
+    )));
+
+    // user1 delegated ASTRO from both airdrop and lockdrop
+    let airdrop_code_id = app.store_code(Box::new(ContractWrapper::new(
+        v100_execute,
+        mock_delegator_instantiate,
+        mock_airdrop_query,
+    )));
+    let airdrop_instance = app
+        .instantiate_contract(
+            airdrop_code_id,
+            owner.clone(),
+            &vec![(user1_address.clone(), Uint128::new(600))],
+            &[],
+            "airdrop",
+            None,
+        )
+        .unwrap();
+    let lockdrop_code_id = app.store_code(Box::new(ContractWrapper::new(
+        v100_execute,
+        mock_delegator_instantiate,
+        mock_lockdrop_query,
+    )));
+    let lockdrop_instance = app
+        .instantiate_contract(
+            lockdrop_code_id,
+            owner.clone(),
+            &vec![(user1_address.clone(), Uint128::new(400))],
+            &[],
+            "lockdrop",
+            None,
+        )
+        .unwrap();
+    let auction_code_id = app.store_code(Box::new(
+        ContractWrapper::new(
+            astroport_auction::contract::execute,
+            astroport_auction::contract::instantiate,
+            astroport_auction::contract::query,
+        )
+        .with_migrate(astroport_auction::contract::migrate),
+    ));
+
+    let auction_instance = app
+        .instantiate_contract(
+            v100_code_id,
+            owner.clone(),
+            &V100InstantiateMsg {
+                config: ConfigV100 {
+                    owner: owner.clone(),
+                    astro_token_address: astro_token_instance.clone(),
+                    airdrop_contract_address: airdrop_instance,
+                    lockdrop_contract_address: lockdrop_instance,
+                    pool_info: Some(PoolInfo {
+                        astro_ust_pool_address: Addr::unchecked("pair"),
+                        astro_ust_lp_token_address: Addr::unchecked("lp_token"),
+                    }),
+                    generator_contract: None,
+                    astro_incentive_amount: Some(Uint128::new(2000)),
+                    lp_tokens_vesting_duration: 7776000,
+                    init_timestamp: 1_000_00,
+                    deposit_window: 100_000_00,
+                    withdrawal_window: 5_000_00,
+                },
+                state: StateV100 {
+                    total_astro_delegated: Uint128::new(1000),
+                    total_ust_delegated: Uint128::new(4000),
+                    is_lp_staked: false,
+                    lp_shares_minted: Some(Uint128::new(2000)),
+                    pool_init_timestamp,
+                    generator_astro_per_share: Decimal::zero(),
+                },
+                users: vec![
+                    // Already received ASTRO incentives in full
+                    (
+                        user1_address.clone(),
+                        UserInfoV100 {
+                            astro_delegated: Uint128::new(1000),
+                            ust_delegated: Uint128::new(1000),
+                            ust_withdrawn: false,
+                            lp_shares: Some(Uint128::new(1250)),
+                            claimed_lp_shares: Uint128::zero(),
+                            auction_incentive_amount: Some(Uint128::new(1250)),
+                            astro_incentive_transferred: true,
+                            generator_astro_debt: Uint128::zero(),
+                            user_gen_astro_per_share: Decimal::zero(),
+                        },
+                    ),
+                    // Hasn't claimed anything yet
+                    (
+                        user2_address.clone(),
+                        UserInfoV100 {
+                            astro_delegated: Uint128::zero(),
+                            ust_delegated: Uint128::new(3000),
+                            ust_withdrawn: false,
+                            lp_shares: None,
+                            claimed_lp_shares: Uint128::zero(),
+                            auction_incentive_amount: None,
+                            astro_incentive_transferred: false,
+                            generator_astro_debt: Uint128::zero(),
+                            user_gen_astro_per_share: Decimal::zero(),
+                        },
+                    ),
+                ],
+            },
+            &[],
+            "auction",
+            Some(owner.to_string()),
+        )
+        .unwrap();
+
+    // Unclaimed ASTRO incentives of user2
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(750),
+        auction_instance.to_string(),
+    );
 
-    app.execute_contract(
-        Addr::unchecked(auction_init_msg.owner.unwrap()),
+    app.migrate_contract(
+        owner.clone(),
         auction_instance.clone(),
-        &ExecuteMsg::StakeLpTokens {},
-        &[],
+        &MigrateMsg {
+            astro_incentives_vesting_duration: Some(1000),
+        },
+        auction_code_id,
     )
     .unwrap();
 
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10911001)
-    });
+    let config: Config = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.astro_incentives_vesting_duration, Some(1000));
+    assert_eq!(
+        config.astro_delegators_incentives_share,
+        Decimal::percent(50)
+    );
 
-    // ######    SUCCESS :: Successfully claim staking rewards for User-1 ######
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(state.total_astro_weighted, Uint128::new(1000));
+    assert_eq!(state.total_ust_weighted, Uint128::new(4000));
 
-    // Auction :: Check user-1 state (before claim)
-    let user1info_before_claim: astroport_periphery::auction::UserInfoResponse = app
+    // Users are converted on load
+    let totals: TotalsResponse = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+            &QueryMsg::Totals {
+                start_after: None,
+                limit: None,
             },
         )
         .unwrap();
+    assert_eq!(totals.users_count, 2);
+    assert_eq!(totals.astro_weighted, Uint128::new(1000));
+    assert_eq!(totals.ust_weighted, Uint128::new(4000));
+    assert_eq!(totals.lp_shares, Uint128::new(2000));
 
-    // Auction :: Claim rewards for the user
-    app.execute_contract(
-        user1_address.clone(),
-        auction_instance.clone(),
-        &claim_rewards_msg,
-        &[],
-    )
-    .unwrap();
+    // Half of the incentives vesting duration has passed
+    app.update_block(|b| b.time = Timestamp::from_seconds(pool_init_timestamp + 500));
 
-    // Auction :: Check user-1 state (After Claim)
-    let user1info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+    let user1_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
+            &QueryMsg::UserInfo {
                 address: user1_address.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(user1_info.astro_weighted, Uint128::new(1000));
+    assert_eq!(user1_info.ust_weighted, Uint128::new(1000));
+    // Sources of ASTRO delegations are set from the airdrop and lockdrop contracts
+    assert_eq!(user1_info.airdrop_astro_delegated, Uint128::new(600));
+    assert_eq!(user1_info.lockdrop_astro_delegated, Uint128::new(400));
+    assert_eq!(user1_info.lp_shares, Some(Uint128::new(1250)));
     assert_eq!(
-        user1info_before_claim.claimed_lp_shares,
-        user1info_after_claim.claimed_lp_shares
-    );
-    assert_eq!(
-        user1info_before_claim.withdrawable_lp_shares,
-        user1info_after_claim.withdrawable_lp_shares
+        user1_info.auction_incentive_amount,
+        Some(Uint128::new(1250))
     );
+    assert_eq!(user1_info.claimed_incentives, Uint128::new(1250));
+    assert_eq!(user1_info.claimable_incentives, Uint128::zero());
 
-    // ######    SUCCESS :: Successfully claim staking rewards for User-2 ######
-
-    // Auction :: Check user-2 state (before claim)
-    let user2info_before_claim: astroport_periphery::auction::UserInfoResponse = app
+    let user2_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
+            &QueryMsg::UserInfo {
                 address: user2_address.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(user2_info.lp_shares, Some(Uint128::new(750)));
+    assert_eq!(user2_info.auction_incentive_amount, Some(Uint128::new(750)));
+    assert_eq!(user2_info.claimed_incentives, Uint128::zero());
+    assert_eq!(user2_info.claimable_incentives, Uint128::new(375));
+
+    // ######    ERROR :: Incentives of user1 were paid before the migration   ######
+
+    let err = app
+        .execute_contract(
+            user1_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::ClaimRewards {
+                withdraw_lp_shares: None,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Rewards already claimed. Provide number of LP tokens to claim!"
+    );
+
+    // ######    SUCCESS :: Claim the vested half of user2 incentives   ######
 
-    // Auction :: Claim rewards for the user 2
     app.execute_contract(
         user2_address.clone(),
         auction_instance.clone(),
-        &claim_rewards_msg,
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: None,
+            user_address: None,
+        },
         &[],
     )
     .unwrap();
 
-    // Auction :: Check user-2 state (After Claim)
-    let user2info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+    let user2_balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
+            &astro_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
                 address: user2_address.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(user2_balance.balance, Uint128::new(375));
+
+    let err = app
+        .execute_contract(
+            user2_address.clone(),
+            auction_instance.clone(),
+            &ExecuteMsg::ClaimRewards {
+                withdraw_lp_shares: None,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
     assert_eq!(
-        user2info_before_claim.claimed_lp_shares,
-        user2info_after_claim.claimed_lp_shares
-    );
-    assert_eq!(
-        user2info_before_claim.withdrawable_lp_shares,
-        user2info_after_claim.withdrawable_lp_shares
+        err.root_cause().to_string(),
+        "Generic error: Rewards already claimed. Provide number of LP tokens to claim!"
     );
 
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10991001)
-    });
-
-    // ######    SUCCESS :: Successfully claim staking rewards for User-3 ######
+    // ######    SUCCESS :: Incentives are fully vested   ######
 
-    // Auction :: Check user-3 state (before claim)
-    let user3info_before_claim: astroport_periphery::auction::UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user3_address.to_string(),
-            },
-        )
-        .unwrap();
+    app.update_block(|b| b.time = Timestamp::from_seconds(pool_init_timestamp + 1500));
 
-    // Auction :: Claim rewards for the user 3
     app.execute_contract(
-        user3_address.clone(),
+        user2_address.clone(),
         auction_instance.clone(),
-        &claim_rewards_msg,
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: None,
+            user_address: None,
+        },
         &[],
     )
     .unwrap();
 
-    // Auction :: Check user-3 state (After Claim)
-    let user3info_after_claim: astroport_periphery::auction::UserInfoResponse = app
+    let user2_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user3_address.to_string(),
+            &QueryMsg::UserInfo {
+                address: user2_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(
-        user3info_before_claim.claimed_lp_shares,
-        user3info_after_claim.claimed_lp_shares
-    );
-    assert_eq!(
-        user3info_before_claim.withdrawable_lp_shares,
-        user3info_after_claim.withdrawable_lp_shares
-    );
-
-    // ######    SUCCESS :: Successfully again claim staking rewards for User-1 ######
+    assert_eq!(user2_info.claimed_incentives, Uint128::new(750));
+    assert_eq!(user2_info.claimable_incentives, Uint128::zero());
 
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10992001)
-    });
-
-    // Auction :: Check user-1 state (before claim)
-    let user1info_before_claim2: astroport_periphery::auction::UserInfoResponse = app
+    let user2_balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user1_address.to_string(),
+            &astro_token_instance,
+            &cw20::Cw20QueryMsg::Balance {
+                address: user2_address.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(user2_balance.balance, Uint128::new(750));
 
-    // Auction :: Claim rewards for the user
+    // ######    SUCCESS :: Delegation sources of user1 are set once its position is updated   ######
+
+    let user3_address = Addr::unchecked("user3");
     app.execute_contract(
         user1_address.clone(),
         auction_instance.clone(),
-        &claim_rewards_msg,
+        &ExecuteMsg::TransferPosition {
+            recipient: user3_address.to_string(),
+            fraction: Decimal::percent(50),
+        },
         &[],
     )
     .unwrap();
 
-    // Auction :: Check user-1 state (After Claim)
-    let user1info_after_claim2: astroport_periphery::auction::UserInfoResponse = app
+    let user1_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
+            &QueryMsg::UserInfo {
                 address: user1_address.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(
-        user1info_before_claim2.claimed_lp_shares,
-        user1info_after_claim2.claimed_lp_shares
-    );
-    assert_eq!(
-        user1info_before_claim2.withdrawable_lp_shares,
-        user1info_after_claim2.withdrawable_lp_shares
-    );
+    assert_eq!(user1_info.astro_delegated, Uint128::new(500));
+    assert_eq!(user1_info.airdrop_astro_delegated, Uint128::new(300));
+    assert_eq!(user1_info.lockdrop_astro_delegated, Uint128::new(200));
 }
 
 #[test]
-fn test_withdraw_unlocked_lp_shares() {
+fn test_generator_proxy_rewards() {
     let owner = Addr::unchecked(OWNER);
     let mut app = mock_app(
         owner.clone(),
-        vec![
-            Coin {
-                denom: "uusd".to_string(),
-                amount: Uint128::new(100_000_000_000_000u128),
-            },
-            Coin {
-                denom: "uluna".to_string(),
-                amount: Uint128::new(100_000_000_000_000u128),
-            },
-        ],
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100_000_000_000u128),
+        }],
     );
     let (
         auction_instance,
@@ -2720,326 +7022,411 @@ fn test_withdraw_unlocked_lp_shares() {
         lp_token_instance,
         auction_init_msg,
         token_code_id,
-        pair_code_id,
+        _,
     ) = init_all_contracts(&mut app);
 
-    let owner = Addr::unchecked(auction_init_msg.owner.clone().unwrap());
-
-    let withdraw_lp_msg = ExecuteMsg::ClaimRewards {
-        withdraw_lp_shares: Some(Uint128::new(1)),
-    };
-
     // mint ASTRO to Lockdrop Contract
     mint_some_astro(
         &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        owner.clone(),
         astro_token_instance.clone(),
         Uint128::new(100_000_000_000),
         auction_init_msg.lockdrop_contract_address.to_string(),
     );
 
-    // mint ASTRO to Auction Contract
-    mint_some_astro(
-        &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
-        astro_token_instance.clone(),
-        Uint128::new(100_000_000_000),
-        auction_instance.to_string(),
-    );
-
     let (user1_address, user2_address, user3_address) = make_astro_ust_deposits(
         &mut app,
         auction_instance.clone(),
         auction_init_msg.clone(),
         astro_token_instance.clone(),
     );
+    let user4_address = Addr::unchecked("user4");
 
-    // ######    Initialize generator and vesting instance   ######
-    let (generator_instance, _) = instantiate_generator_and_vesting(
+    let proxy_reward_per_second = Uint128::new(100);
+    let (generator_instance, proxy_reward_token) = instantiate_mock_generator_with_proxy(
         &mut app,
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        owner.clone(),
         astro_token_instance.clone(),
-        lp_token_instance.clone(),
         token_code_id,
-        pair_code_id,
+        Uint128::zero(),
+        proxy_reward_per_second,
     );
-
-    let update_msg = UpdateConfigMsg {
-        owner: None,
-        astro_ust_pair_address: None,
-        generator_contract: Some(generator_instance.to_string()),
+    let proxy_reward_info = AssetInfo::Token {
+        contract_addr: proxy_reward_token.clone(),
     };
 
     app.execute_contract(
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        owner.clone(),
         auction_instance.clone(),
         &ExecuteMsg::UpdateConfig {
-            new_config: update_msg.clone(),
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: Some(generator_instance.to_string()),
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
+            },
         },
         &[],
     )
     .unwrap();
 
-    // ######    ERROR :: Deposit/withdrawal windows are open   ######
-
-    let mut err = app
-        .execute_contract(
-            user1_address.clone(),
-            auction_instance.clone(),
-            &withdraw_lp_msg,
-            &[],
-        )
-        .unwrap_err();
-    assert_eq!(
-        err.root_cause().to_string(),
-        "Generic error: Astro/USD should be provided to the pool!"
-    );
-
     // finish deposit / withdraw period
+    let pool_init_timestamp = 10611001u64;
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(10611001)
+        b.time = Timestamp::from_seconds(pool_init_timestamp)
     });
 
-    // ######    ERROR :: Invalid request. No LP Tokens to claim   ######
-
-    err = app
-        .execute_contract(
-            Addr::unchecked("not_user".to_string()),
-            auction_instance.clone(),
-            &withdraw_lp_msg,
-            &[],
-        )
-        .unwrap_err();
-    assert_eq!(
-        err.root_cause().to_string(),
-        "astroport_periphery::auction::UserInfo not found"
-    );
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
 
-    // ######    Sucess :: Initialize ASTRO-UST Pool   ######
+    let state: State = app
+        .wrap()
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
+        .unwrap();
+    let lp_shares_minted = state.lp_shares_minted.unwrap();
+
+    let query_user_info = |app: &App, address: &Addr| -> UserInfoResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &auction_instance,
+                &QueryMsg::UserInfo {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    let user1_lp_shares = query_user_info(&app, &user1_address).lp_shares.unwrap();
+    let user2_lp_shares = query_user_info(&app, &user2_address).lp_shares.unwrap();
+    let user3_lp_shares = query_user_info(&app, &user3_address).lp_shares.unwrap();
+
+    // Proxy rewards received by the auction per LP share
+    let mut proxy_per_share = Decimal::zero();
+    let mut proxy_rewards_claimed_after = |seconds: u64, lp_balance: Uint128| {
+        proxy_per_share = proxy_per_share
+            + Decimal::from_ratio(proxy_reward_per_second * Uint128::from(seconds), lp_balance);
+        proxy_per_share
+    };
+
+    // ######    SUCCESS :: Proxy rewards are distributed by LP shares   ######
+
+    app.update_block(|b| b.time = Timestamp::from_seconds(pool_init_timestamp + 1000));
+    let per_share_1 = proxy_rewards_claimed_after(1000, lp_shares_minted);
+
+    for (address, lp_shares) in [
+        (&user1_address, user1_lp_shares),
+        (&user2_address, user2_lp_shares),
+        (&user3_address, user3_lp_shares),
+    ] {
+        assert_eq!(
+            claimable_proxy_reward(&query_user_info(&app, address), &proxy_reward_info),
+            per_share_1 * lp_shares
+        );
+    }
 
     app.execute_contract(
-        owner.clone(),
-        astro_token_instance,
-        &Cw20ExecuteMsg::Send {
-            amount: Uint128::new(100_000_000000),
-            contract: auction_instance.to_string(),
-            msg: to_binary(&Cw20HookMsg::IncreaseAstroIncentives {}).unwrap(),
+        user1_address.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: None,
+            user_address: None,
         },
         &[],
     )
     .unwrap();
+    assert_eq!(
+        query_cw20_balance(&app, &proxy_reward_token, &user1_address),
+        per_share_1 * user1_lp_shares
+    );
+    assert_eq!(
+        claimable_proxy_reward(&query_user_info(&app, &user1_address), &proxy_reward_info),
+        Uint128::zero()
+    );
+    // Rewards of other users stay in the auction
+    assert_eq!(
+        claimable_proxy_reward(&query_user_info(&app, &user2_address), &proxy_reward_info),
+        per_share_1 * user2_lp_shares
+    );
+
+    // ######    SUCCESS :: Proxy rewards are settled on position transfer   ######
+
+    app.update_block(|b| b.time = Timestamp::from_seconds(pool_init_timestamp + 2000));
+    let per_share_2 = proxy_rewards_claimed_after(1000, lp_shares_minted);
 
     app.execute_contract(
-        Addr::unchecked(auction_init_msg.owner.clone().unwrap()),
+        user2_address.clone(),
         auction_instance.clone(),
-        &ExecuteMsg::InitPool { slippage: None },
+        &ExecuteMsg::TransferPosition {
+            recipient: user4_address.to_string(),
+            fraction: Decimal::one(),
+        },
         &[],
     )
     .unwrap();
+    assert_eq!(
+        query_cw20_balance(&app, &proxy_reward_token, &user2_address),
+        per_share_2 * user2_lp_shares
+    );
+    assert_eq!(
+        query_cw20_balance(&app, &proxy_reward_token, &user4_address),
+        Uint128::zero()
+    );
 
-    // ######    SUCCESS :: Stake successfully   ######
+    let user4_info = query_user_info(&app, &user4_address);
+    assert_eq!(user4_info.lp_shares, Some(user2_lp_shares));
+    assert_eq!(
+        claimable_proxy_reward(&user4_info, &proxy_reward_info),
+        Uint128::zero()
+    );
+
+    // The recipient receives rewards accrued after the transfer only
+    app.update_block(|b| b.time = Timestamp::from_seconds(pool_init_timestamp + 3000));
+    let per_share_3 = proxy_rewards_claimed_after(1000, lp_shares_minted);
 
     app.execute_contract(
-        Addr::unchecked(auction_init_msg.owner.unwrap()),
+        user4_address.clone(),
         auction_instance.clone(),
-        &ExecuteMsg::StakeLpTokens {},
+        &ExecuteMsg::ClaimRewards {
+            withdraw_lp_shares: None,
+            user_address: None,
+        },
         &[],
     )
     .unwrap();
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10911001)
-    });
-
-    // ######    SUCCESS :: Successfully withdraw LP shares (which also claims rewards) for User-1 ######
-
-    // Auction :: Check user-1 state (before claim)
-    let user1info_before_claim: astroport_periphery::auction::UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user1_address.to_string(),
-            },
-        )
-        .unwrap();
     assert_eq!(
-        Uint128::from(0u64),
-        user1info_before_claim.claimed_lp_shares
+        query_cw20_balance(&app, &proxy_reward_token, &user4_address),
+        per_share_3 * user2_lp_shares - per_share_2 * user2_lp_shares
     );
 
-    // Auction :: Withdraw unvested LP shares for the user
+    // ######    SUCCESS :: Proxy rewards are settled on LP shares withdrawal   ######
+
+    // LP shares are fully vested
+    let withdrawal_timestamp = pool_init_timestamp + 7776000;
+    app.update_block(|b| b.time = Timestamp::from_seconds(withdrawal_timestamp));
+    let per_share_4 = proxy_rewards_claimed_after(7776000 - 3000, lp_shares_minted);
+
     app.execute_contract(
         user1_address.clone(),
         auction_instance.clone(),
         &ExecuteMsg::ClaimRewards {
-            withdraw_lp_shares: Some(user1info_before_claim.withdrawable_lp_shares.unwrap()),
+            withdraw_lp_shares: Some(user1_lp_shares),
+            user_address: None,
         },
         &[],
     )
     .unwrap();
-
-    // Auction :: Check user-1 state (After Claim)
-    let user1info_after_claim: astroport_periphery::auction::UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user1_address.to_string(),
-            },
-        )
-        .unwrap();
     assert_eq!(
-        user1info_before_claim.withdrawable_lp_shares.unwrap(),
-        user1info_after_claim.claimed_lp_shares
+        query_cw20_balance(&app, &lp_token_instance, &user1_address),
+        user1_lp_shares
     );
     assert_eq!(
-        Uint128::from(0u64),
-        user1info_after_claim.withdrawable_lp_shares.unwrap()
+        query_cw20_balance(&app, &proxy_reward_token, &user1_address),
+        per_share_4 * user1_lp_shares
     );
 
-    // ######    SUCCESS :: Successfully withdraw LP shares (which also claims rewards) for User-2 ######
+    // Withdrawn LP shares don't receive rewards anymore
+    app.update_block(|b| b.time = Timestamp::from_seconds(withdrawal_timestamp + 1000));
+    let per_share_5 = proxy_rewards_claimed_after(1000, lp_shares_minted - user1_lp_shares);
 
-    // Auction :: Check user-2 state (before claim)
-    let user2info_before_claim: astroport_periphery::auction::UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user2_address.to_string(),
-            },
-        )
-        .unwrap();
     assert_eq!(
-        Uint128::from(0u64),
-        user2info_before_claim.claimed_lp_shares
+        claimable_proxy_reward(&query_user_info(&app, &user1_address), &proxy_reward_info),
+        Uint128::zero()
     );
 
-    // Auction :: Withdraw unvested LP shares for the user
     app.execute_contract(
-        user2_address.clone(),
+        user3_address.clone(),
         auction_instance.clone(),
         &ExecuteMsg::ClaimRewards {
-            withdraw_lp_shares: Some(user2info_before_claim.withdrawable_lp_shares.unwrap()),
+            withdraw_lp_shares: None,
+            user_address: None,
         },
         &[],
     )
     .unwrap();
-
-    // Auction :: Check user-2 state (After Claim)
-    let user2info_after_claim: astroport_periphery::auction::UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user2_address.to_string(),
-            },
-        )
-        .unwrap();
     assert_eq!(
-        user2info_before_claim.withdrawable_lp_shares.unwrap(),
-        user2info_after_claim.claimed_lp_shares
+        query_cw20_balance(&app, &proxy_reward_token, &user3_address),
+        per_share_5 * user3_lp_shares
     );
     assert_eq!(
-        Uint128::from(0u64),
-        user2info_after_claim.withdrawable_lp_shares.unwrap()
+        query_cw20_balance(&app, &proxy_reward_token, &user1_address),
+        per_share_4 * user1_lp_shares
     );
+}
 
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(10991001)
-    });
+#[test]
+fn test_compounded_lp_shares_forfeit_proxy_rewards() {
+    let owner = Addr::unchecked(OWNER);
+    let mut app = mock_app(
+        owner.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100_000_000_000u128),
+        }],
+    );
+    let (auction_instance, astro_token_instance, _, _, _, _, auction_init_msg, token_code_id, _) =
+        init_all_contracts(&mut app);
 
-    // ######    SUCCESS :: Successfully withdraw LP shares (which also claims rewards) for User-3 ######
+    // mint ASTRO to Lockdrop Contract
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(100_000_000_000),
+        auction_init_msg.lockdrop_contract_address.to_string(),
+    );
 
-    // Auction :: Check user-3 state (before claim)
-    let user3info_before_claim: astroport_periphery::auction::UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user3_address.to_string(),
-            },
-        )
-        .unwrap();
-    assert_eq!(
-        Uint128::from(0u64),
-        user3info_before_claim.claimed_lp_shares
+    let (user1_address, user2_address, _) = make_astro_ust_deposits(
+        &mut app,
+        auction_instance.clone(),
+        auction_init_msg.clone(),
+        astro_token_instance.clone(),
+    );
+
+    let proxy_reward_per_second = Uint128::new(100);
+    let (generator_instance, proxy_reward_token) = instantiate_mock_generator_with_proxy(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        token_code_id,
+        Uint128::new(100),
+        proxy_reward_per_second,
+    );
+    let proxy_reward_info = AssetInfo::Token {
+        contract_addr: proxy_reward_token,
+    };
+    mint_some_astro(
+        &mut app,
+        owner.clone(),
+        astro_token_instance.clone(),
+        Uint128::new(1_000_000_000_000),
+        generator_instance.to_string(),
     );
 
-    // Auction :: Withdraw unvested LP shares for the user
     app.execute_contract(
-        user3_address.clone(),
+        owner.clone(),
         auction_instance.clone(),
-        &ExecuteMsg::ClaimRewards {
-            withdraw_lp_shares: Some(user3info_before_claim.withdrawable_lp_shares.unwrap()),
+        &ExecuteMsg::UpdateConfig {
+            new_config: UpdateConfigMsg {
+                owner: None,
+                astro_ust_pair_address: None,
+                generator_contract: Some(generator_instance.to_string()),
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: Some(Decimal::percent(5)),
+                allowlist_merkle_root: None,
+            },
         },
         &[],
     )
     .unwrap();
 
-    // Auction :: Check user-3 state (After Claim)
-    let user3info_after_claim: astroport_periphery::auction::UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user3_address.to_string(),
-            },
-        )
-        .unwrap();
-    assert_eq!(
-        user3info_before_claim.withdrawable_lp_shares.unwrap(),
-        user3info_after_claim.claimed_lp_shares
-    );
-    assert_eq!(
-        Some(Uint128::zero()),
-        user3info_after_claim.withdrawable_lp_shares
-    );
+    // finish deposit / withdraw period
+    let pool_init_timestamp = 10611001u64;
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(pool_init_timestamp)
+    });
 
-    // ######    SUCCESS :: Successfully again withdraw LP shares (which also claims rewards) for User-1 ######
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {},
+        &[],
+    )
+    .unwrap();
 
-    // Auction :: Check user-1 state (before claim)
-    let user1info_before_claim2: astroport_periphery::auction::UserInfoResponse = app
+    let state: State = app
         .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user1_address.to_string(),
-            },
-        )
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
         .unwrap();
+    let lp_shares_minted = state.lp_shares_minted.unwrap();
+
+    let query_user_info = |app: &App, address: &Addr| -> UserInfoResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &auction_instance,
+                &QueryMsg::UserInfo {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap()
+    };
+    let user2_lp_shares = query_user_info(&app, &user2_address).lp_shares.unwrap();
 
-    // Auction :: Withdraw LP for the user
+    // User-1 joins the compounding pool in the first round
     app.execute_contract(
         user1_address.clone(),
         auction_instance.clone(),
-        &ExecuteMsg::ClaimRewards {
-            withdraw_lp_shares: Some(user1info_before_claim2.withdrawable_lp_shares.unwrap()),
+        &ExecuteMsg::SetCompounding {
+            enabled: true,
+            user_address: None,
         },
         &[],
     )
     .unwrap();
+    app.execute_contract(
+        owner.clone(),
+        auction_instance.clone(),
+        &ExecuteMsg::Compound {},
+        &[],
+    )
+    .unwrap();
 
-    // Auction :: Check user-1 state (After Claim)
-    let user1info_after_claim2: astroport_periphery::auction::UserInfoResponse = app
+    // The second round compounds User-1's ASTRO rewards into LP shares of the compounding pool
+    app.update_block(|b| b.time = Timestamp::from_seconds(pool_init_timestamp + 1000));
+    app.execute_contract(
+        owner,
+        auction_instance.clone(),
+        &ExecuteMsg::Compound {},
+        &[],
+    )
+    .unwrap();
+
+    let state: State = app
         .wrap()
-        .query_wasm_smart(
-            &auction_instance,
-            &astroport_periphery::auction::QueryMsg::UserInfo {
-                address: user1_address.to_string(),
-            },
-        )
+        .query_wasm_smart(&auction_instance, &QueryMsg::State {})
         .unwrap();
+    assert!(!state.compounded_lp_shares.is_zero());
+    let per_share_1 = Decimal::from_ratio(
+        proxy_reward_per_second * Uint128::new(1000),
+        lp_shares_minted,
+    );
     assert_eq!(
-        user1info_before_claim2.claimed_lp_shares
-            + user1info_before_claim2.withdrawable_lp_shares.unwrap(),
-        user1info_after_claim2.claimed_lp_shares
+        state.generator_proxy_per_share.load(&proxy_reward_info),
+        Some(per_share_1)
     );
+
+    // Proxy rewards of LP shares owned by the compounding pool are shared among vesting LP shares only
+    app.update_block(|b| b.time = Timestamp::from_seconds(pool_init_timestamp + 2000));
+    let per_share_2 = per_share_1
+        + Decimal::from_ratio(
+            proxy_reward_per_second * Uint128::new(1000),
+            lp_shares_minted,
+        );
     assert_eq!(
-        Some(Uint128::zero()),
-        user1info_after_claim2.withdrawable_lp_shares
+        claimable_proxy_reward(&query_user_info(&app, &user2_address), &proxy_reward_info),
+        per_share_2 * user2_lp_shares
     );
 }
@@ -10,29 +10,38 @@ use astroport::DecimalCheckedOps;
 use astroport_periphery::utils::Decimal256CheckedOps;
 use cosmwasm_std::{
     attr, entry_point, from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Decimal256,
-    Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage, SubMsg,
-    Uint128, Uint256, WasmMsg,
+    Deps, DepsMut, Env, MessageInfo, Order, QueryRequest, Response, StdError, StdResult, Storage,
+    Uint128, Uint256, WasmMsg, WasmQuery,
 };
 use cw2::{get_contract_version, set_contract_version};
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw721::NftInfoResponse;
 use cw_storage_plus::Path;
+use serde::Deserialize;
 
 use crate::migration::{
-    migrate_generator_proxy_per_share_to_v120, ASSET_POOLS_V101, ASSET_POOLS_V111,
+    migrate_config_and_state_to_v130, migrate_generator_proxy_per_share_to_v120, ASSET_POOLS_V101,
+    ASSET_POOLS_V111, ASSET_POOLS_V120,
 };
 use crate::raw_queries::{raw_balance, raw_generator_deposit};
-use astroport_periphery::auction::Cw20HookMsg::DelegateAstroTokens;
+use astroport_periphery::auction::{AllowlistProof, Cw20HookMsg::DelegateAstroTokens};
+use astroport_periphery::helpers::{
+    assert_position_nft_minter, assert_position_owner, build_burn_position_nft_msg,
+    build_mint_position_nft_msg, query_position_nft_owner,
+};
 use astroport_periphery::lockdrop::{
-    CallbackMsg, Config, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockUpInfoResponse,
-    LockUpInfoSummary, LockupInfoV2, MigrateMsg, MigrationInfo, PendingAssetRewardResponse,
-    PoolInfo, QueryMsg, State, StateResponse, UpdateConfigMsg, UserInfoResponse,
-    UserInfoWithListResponse,
+    lockup_position_token_id, pool_key, AssetRewardsConfig, CallbackMsg, Config, Cw20HookMsg,
+    ExecuteMsg, ForceUnlockParams, InstantiateMsg, LockUpInfoResponse, LockUpInfoSummary,
+    LockupInfoV2, MigrateMsg, MigrationInfo, PenaltyDestination, PendingAssetRewardResponse,
+    PoolInfo, PoolLockupParams, QueryMsg, SimulateWeightResponse, SourceDex, State, StateResponse,
+    UpdateConfigMsg, UserInfo, UserInfoResponse, UserInfoWithListResponse, WeightCurve,
+    NATIVE_POOL_KEY_PREFIX,
 };
 use astroport_periphery::U64Key;
 
 use crate::state::{
-    CompatibleLoader, ASSET_POOLS, CONFIG, LOCKUP_INFO, OWNERSHIP_PROPOSAL, STATE,
-    TOTAL_ASSET_REWARD_INDEX, USERS_ASSET_REWARD_INDEX, USER_INFO,
+    AssetRewardIndexes, CompatibleLoader, ASSET_POOLS, CONFIG, DETACHED_ASTRO_REWARDS, LOCKUP_INFO,
+    OWNERSHIP_PROPOSAL, STATE, USER_INFO,
 };
 
 const SECONDS_PER_WEEK: u64 = 86400 * 7;
@@ -89,6 +98,9 @@ pub fn instantiate(
         ));
     }
 
+    let weight_curve = msg.weight_curve.unwrap_or_default();
+    validate_weight_curve(&weight_curve, msg.max_lock_duration)?;
+
     let config = Config {
         owner: msg
             .owner
@@ -107,6 +119,9 @@ pub fn instantiate(
         weekly_divider: msg.weekly_divider,
         lockdrop_incentives: Uint128::zero(),
         max_positions_per_user: msg.max_positions_per_user,
+        position_nft: None,
+        force_unlock_params: None,
+        weight_curve,
     };
 
     CONFIG.save(deps.storage, &config)?;
@@ -129,12 +144,37 @@ pub fn instantiate(
 ///
 /// * **ExecuteMsg::Receive(msg)** Parse incoming messages from the ASTRO token.
 ///
+/// * **ExecuteMsg::IncreaseLockup { duration }** Facilitates opening a new user position or adding to an existing position with native LP tokens.
+///
+/// * **ExecuteMsg::ExtendLockup {
+///     terraswap_lp_token,
+///     from_duration,
+///     to_duration,
+///     amount,
+///     user_address,
+/// }** Moves LP units of a lockup position to a position with a longer lockup duration.
+///
+/// * **ExecuteMsg::TransferLockup {
+///     terraswap_lp_token,
+///     duration,
+///     recipient,
+/// }** Moves a lockup position to another address.
+///
 /// * **ExecuteMsg::UpdateConfig { new_config }** Admin function to update configuration parameters.
 ///
+/// * **ExecuteMsg::SetPositionNft { contract }** Admin function to mirror lockup positions by NFTs of the given contract.
+///
+/// * **ExecuteMsg::UpdateForceUnlockParams { params }** Admin function to set parameters of forced unlocks.
+///
+/// * **ExecuteMsg::UpdateWeightCurve { weight_curve }** Admin function to replace the lockup weight curve before deposits open.
+///
 /// * **ExecuteMsg::InitializePool {
 ///     terraswap_lp_token,
 ///     incentives_share,
-/// }** Facilitates addition of new Pool (Terraswap Pools) whose LP tokens can then be locked in the lockdrop contract.
+///     source_dex,
+///     native_lp_pool,
+///     lockup_params,
+/// }** Facilitates addition of new Pool (Terraswap Pools by default) whose LP tokens can then be locked in the lockdrop contract.
 ///
 /// * **ExecuteMsg::UpdatePool {
 ///     terraswap_lp_token,
@@ -145,30 +185,37 @@ pub fn instantiate(
 ///     terraswap_lp_token,
 ///     astroport_pool_addr,
 ///     slippage_tolerance,
-/// }** Migrate Liquidity from Terraswap to Astroport.
+/// }** Migrate Liquidity from the pool's source DEX to Astroport.
 ///
 /// * **ExecuteMsg::StakeLpTokens { terraswap_lp_token }** Facilitates staking of Astroport LP tokens for a particular LP pool with the generator contract.
 ///
 /// * **ExecuteMsg::EnableClaims {}** Enables ASTRO Claims by users.
 ///
-/// * **ExecuteMsg::DelegateAstroToAuction { amount }** Facilitates ASTRO tokens delegation to the Bootstrap auction contract during the bootstrap auction phase.
+/// * **ExecuteMsg::DelegateAstroToAuction { amount, allowlist_proof }** Facilitates ASTRO tokens delegation to the Bootstrap auction contract during the bootstrap auction phase.
 ///
 /// * **ExecuteMsg::WithdrawFromLockup {
 ///             terraswap_lp_token,
 ///             duration,
 ///             amount,
+///             user_address,
 ///         }** Withdraws LP Tokens from an existing Lockup position.
 ///
 /// * **ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
 ///             terraswap_lp_token,
 ///             duration,
 ///             withdraw_lp_stake,
-///         }** Claims user Rewards for a particular Lockup position.
+///             user_address,
+///             force_unlock,
+///             amount,
+///         }** Claims user Rewards for a particular Lockup position, optionally unlocking it (partially) or unlocking it before its unlock timestamp for a penalty.
+///
+/// * **ExecuteMsg::ClaimAll { withdraw_matured_lp }** Claims user Rewards for all the sender's Lockup positions, optionally unlocking the matured ones.
 ///
 /// * **ExecuteMsg::TogglePoolRewards {
 ///             terraswap_lp_token,
 ///             enable,
-///         }** Sets `enable` flag for liquidity pool.
+///             asset_rewards,
+///         }** Sets `enable` flag for liquidity pool, optionally updating the source of its asset rewards.
 ///
 /// * **ExecuteMsg::ProposeNewOwner { owner, expires_in }** Creates a request to change contract ownership.
 ///
@@ -179,12 +226,57 @@ pub fn instantiate(
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::IncreaseLockup { duration } => {
+            handle_increase_native_lockup(deps, env, info, duration)
+        }
+        ExecuteMsg::ExtendLockup {
+            terraswap_lp_token,
+            from_duration,
+            to_duration,
+            amount,
+            user_address,
+        } => handle_extend_lockup(
+            deps,
+            env,
+            info,
+            terraswap_lp_token,
+            from_duration,
+            to_duration,
+            amount,
+            user_address,
+        ),
+        ExecuteMsg::TransferLockup {
+            terraswap_lp_token,
+            duration,
+            recipient,
+        } => handle_transfer_lockup(deps, env, info, terraswap_lp_token, duration, recipient),
 
         ExecuteMsg::UpdateConfig { new_config } => handle_update_config(deps, info, new_config),
+        ExecuteMsg::SetPositionNft { contract } => {
+            handle_set_position_nft(deps, env, info, contract)
+        }
+        ExecuteMsg::UpdateForceUnlockParams { params } => {
+            handle_update_force_unlock_params(deps, info, params)
+        }
+        ExecuteMsg::UpdateWeightCurve { weight_curve } => {
+            handle_update_weight_curve(deps, env, info, weight_curve)
+        }
         ExecuteMsg::InitializePool {
             terraswap_lp_token,
             incentives_share,
-        } => handle_initialize_pool(deps, env, info, terraswap_lp_token, incentives_share),
+            source_dex,
+            native_lp_pool,
+            lockup_params,
+        } => handle_initialize_pool(
+            deps,
+            env,
+            info,
+            terraswap_lp_token,
+            incentives_share,
+            source_dex,
+            native_lp_pool,
+            lockup_params,
+        ),
         ExecuteMsg::UpdatePool {
             terraswap_lp_token,
             incentives_share,
@@ -205,18 +297,31 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             handle_stake_lp_tokens(deps, env, info, terraswap_lp_token)
         }
         ExecuteMsg::EnableClaims {} => handle_enable_claims(deps, env, info),
-        ExecuteMsg::DelegateAstroToAuction { amount } => {
-            handle_delegate_astro_to_auction(deps, env, info, amount)
-        }
+        ExecuteMsg::DelegateAstroToAuction {
+            amount,
+            allowlist_proof,
+        } => handle_delegate_astro_to_auction(deps, env, info, amount, allowlist_proof),
         ExecuteMsg::WithdrawFromLockup {
             terraswap_lp_token,
             duration,
             amount,
-        } => handle_withdraw_from_lockup(deps, env, info, terraswap_lp_token, duration, amount),
+            user_address,
+        } => handle_withdraw_from_lockup(
+            deps,
+            env,
+            info,
+            terraswap_lp_token,
+            duration,
+            amount,
+            user_address,
+        ),
         ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
             terraswap_lp_token,
             duration,
             withdraw_lp_stake,
+            user_address,
+            force_unlock,
+            amount,
         } => handle_claim_rewards_and_unlock_for_lockup(
             deps,
             env,
@@ -224,12 +329,19 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             terraswap_lp_token,
             duration,
             withdraw_lp_stake,
+            user_address,
+            force_unlock,
+            amount,
         ),
+        ExecuteMsg::ClaimAll {
+            withdraw_matured_lp,
+        } => handle_claim_all(deps, env, info, withdraw_matured_lp),
         ExecuteMsg::Callback(msg) => _handle_callback(deps, env, info, msg),
         ExecuteMsg::ClaimAssetReward {
             recipient,
             terraswap_lp_token,
             duration,
+            user_address,
         } => {
             let recipient = recipient.map_or_else(
                 || Ok(info.sender.clone()),
@@ -242,12 +354,14 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 recipient,
                 terraswap_lp_token,
                 duration,
+                user_address,
             )
         }
         ExecuteMsg::TogglePoolRewards {
             terraswap_lp_token,
             enable,
-        } => handle_toggle_rewards(deps, info, terraswap_lp_token, enable),
+            asset_rewards,
+        } => handle_toggle_rewards(deps, env, info, terraswap_lp_token, enable, asset_rewards),
         ExecuteMsg::ProposeNewOwner { owner, expires_in } => {
             let config: Config = CONFIG.load(deps.storage)?;
 
@@ -310,11 +424,24 @@ pub fn receive_cw20(
 
     match from_binary(&cw20_msg.msg)? {
         Cw20HookMsg::IncreaseLockup { duration } => {
-            handle_increase_lockup(deps, env, info, user_address, duration, amount)
+            let terraswap_lp_token = pool_key(&AssetInfo::Token {
+                contract_addr: info.sender,
+            });
+            handle_increase_lockup(
+                deps,
+                env,
+                terraswap_lp_token,
+                user_address,
+                duration,
+                amount,
+            )
         }
         Cw20HookMsg::IncreaseAstroIncentives {} => {
             handle_increasing_astro_incentives(deps, env, info, amount)
         }
+        Cw20HookMsg::UndelegateAstroTokens {
+            user_address: delegator,
+        } => handle_undelegate_astro_tokens(deps, info, user_address, delegator, amount),
     }
 }
 
@@ -355,17 +482,23 @@ fn _handle_callback(
         CallbackMsg::WithdrawUserLockupRewardsCallback {
             terraswap_lp_token,
             user_address,
+            recipient,
             duration,
             withdraw_lp_stake,
+            force_unlock_penalty,
+            amount,
         } => callback_withdraw_user_rewards_for_lockup_optional_withdraw(
             deps,
             env,
             terraswap_lp_token,
             user_address,
+            recipient,
             duration,
             withdraw_lp_stake,
+            force_unlock_penalty,
+            amount,
         ),
-        CallbackMsg::WithdrawLiquidityFromTerraswapCallback {
+        CallbackMsg::WithdrawLiquidityFromSourceDexCallback {
             terraswap_lp_token,
             astroport_pool,
             prev_assets,
@@ -379,7 +512,7 @@ fn _handle_callback(
             slippage_tolerance,
         ),
         CallbackMsg::DistributeAssetReward {
-            previous_balance,
+            previous_balances,
             terraswap_lp_token,
             user_address,
             recipient,
@@ -387,12 +520,30 @@ fn _handle_callback(
         } => callback_distribute_asset_reward(
             deps,
             env,
-            previous_balance,
+            previous_balances,
             terraswap_lp_token,
             user_address,
             recipient,
             lock_duration,
         ),
+        CallbackMsg::TransferLockupCallback {
+            terraswap_lp_token,
+            user_address,
+            recipient,
+            duration,
+        } => callback_transfer_lockup(
+            deps,
+            env,
+            terraswap_lp_token,
+            user_address,
+            recipient,
+            duration,
+        ),
+        CallbackMsg::ClaimAllCallback {
+            user_address,
+            lockups,
+            withdraw_matured_lp,
+        } => callback_claim_all(deps, env, user_address, lockups, withdraw_matured_lp),
     }
 }
 
@@ -425,7 +576,11 @@ fn _handle_callback(
 ///             user_address,
 ///             terraswap_lp_token,
 ///             duration,
-///         }** Returns the amount of pending asset rewards for the specified recipient and for a specific lockup position.
+///         }** Returns the pending rewards of every reward asset of the pool for a specific lockup position.
+///
+/// * **QueryMsg::PositionNftInfo { token_id }** Returns metadata of the lockup position NFT and the recipient of its one-time ASTRO rewards.
+///
+/// * **QueryMsg::SimulateWeight { amount, duration }** Returns the weight of a lockup position of `amount` LP units locked for `duration` weeks.
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -458,6 +613,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             terraswap_lp_token,
             duration,
         )?),
+        QueryMsg::PositionNftInfo { token_id } => {
+            to_binary(&query_position_nft_info(deps, env, token_id)?)
+        }
+        QueryMsg::SimulateWeight { amount, duration } => {
+            to_binary(&query_simulate_weight(deps, amount, duration)?)
+        }
     }
 }
 
@@ -502,8 +663,13 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response
                         generator_proxy_per_share,
                         is_staked: pool.is_staked,
                         has_asset_rewards: false,
+                        source_dex: SourceDex::Terraswap,
+                        native_lp_token: false,
+                        penalty_astro_per_share: Decimal::zero(),
+                        lockup_params: PoolLockupParams::default(),
+                        asset_rewards: None,
                     };
-                    ASSET_POOLS.save(deps.storage, &key, &new_pool_info)?
+                    ASSET_POOLS.save(deps.storage, key.as_str(), &new_pool_info)?
                 }
             }
             "1.1.0" | "1.1.1" => {
@@ -528,8 +694,37 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response
                         generator_proxy_per_share,
                         is_staked: pool.is_staked,
                         has_asset_rewards: pool.has_asset_rewards,
+                        source_dex: SourceDex::Terraswap,
+                        native_lp_token: false,
+                        penalty_astro_per_share: Decimal::zero(),
+                        lockup_params: PoolLockupParams::default(),
+                        asset_rewards: None,
+                    };
+                    ASSET_POOLS.save(deps.storage, key.as_str(), &new_pool_info)?
+                }
+            }
+            "1.2.0" => {
+                let pools = ASSET_POOLS_V120
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<_>>>()?;
+                for (key, pool) in pools {
+                    let new_pool_info = PoolInfo {
+                        terraswap_pool: pool.terraswap_pool,
+                        terraswap_amount_in_lockups: pool.terraswap_amount_in_lockups,
+                        migration_info: pool.migration_info,
+                        incentives_share: pool.incentives_share,
+                        weighted_amount: pool.weighted_amount,
+                        generator_astro_per_share: pool.generator_astro_per_share,
+                        generator_proxy_per_share: pool.generator_proxy_per_share,
+                        is_staked: pool.is_staked,
+                        has_asset_rewards: pool.has_asset_rewards,
+                        source_dex: SourceDex::Terraswap,
+                        native_lp_token: false,
+                        penalty_astro_per_share: Decimal::zero(),
+                        lockup_params: PoolLockupParams::default(),
+                        asset_rewards: None,
                     };
-                    ASSET_POOLS.save(deps.storage, &key, &new_pool_info)?
+                    ASSET_POOLS.save(deps.storage, key.as_str(), &new_pool_info)?
                 }
             }
             _ => return Err(StdError::generic_err("Migration error")),
@@ -537,6 +732,8 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response
         _ => return Err(StdError::generic_err("Migration error")),
     };
 
+    migrate_config_and_state_to_v130(deps.storage)?;
+
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new().add_attributes(vec![
@@ -593,7 +790,7 @@ pub fn handle_update_config(
         if config.generator.is_some() {
             for pool in ASSET_POOLS
                 .keys(deps.storage, None, None, Order::Ascending)
-                .collect::<Result<Vec<Addr>, StdError>>()?
+                .collect::<Result<Vec<String>, StdError>>()?
             {
                 let pool_info = ASSET_POOLS.load(deps.storage, &pool)?;
                 if pool_info.is_staked {
@@ -613,6 +810,252 @@ pub fn handle_update_config(
     Ok(Response::new().add_attributes(attributes))
 }
 
+/// Admin function to mirror every lockup position by an NFT of the given cw721 contract. The contract should allow
+/// only the lockdrop to mint NFTs. Can be set only once, before the first lockup. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **contract** is an object of type [`String`]. Position NFT contract address.
+pub fn handle_set_position_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: Only owner can call this function
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    if config.position_nft.is_some() {
+        return Err(StdError::generic_err("Position NFT contract already set"));
+    }
+
+    // CHECK :: Existing lockup positions can't be mirrored
+    if USER_INFO
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+    {
+        return Err(StdError::generic_err(
+            "Position NFTs can be enabled only before the first lockup",
+        ));
+    }
+
+    let position_nft = addr_validate_to_lower(deps.api, &contract)?;
+    assert_position_nft_minter(&deps.querier, &position_nft, &env.contract.address)?;
+
+    config.position_nft = Some(position_nft.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_position_nft"),
+        attr("position_nft", position_nft),
+    ]))
+}
+
+/// Admin function to set parameters of forced unlocks. Forced unlocks are disabled if `params` is None.
+/// Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **params** is an [`Option`] of type [`ForceUnlockParams`]. New parameters of forced unlocks.
+pub fn handle_update_force_unlock_params(
+    deps: DepsMut,
+    info: MessageInfo,
+    params: Option<ForceUnlockParams>,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: Only owner can call this function
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    let mut attributes = vec![attr("action", "update_force_unlock_params")];
+    if let Some(params) = &params {
+        // CHECK :: Penalty can't exceed the lockup ASTRO rewards
+        if params.penalty_per_week > Decimal::one() || params.max_penalty > Decimal::one() {
+            return Err(StdError::generic_err(
+                "Penalty share cannot be greater than 1",
+            ));
+        }
+
+        attributes.extend(vec![
+            attr("penalty_per_week", params.penalty_per_week.to_string()),
+            attr("max_penalty", params.max_penalty.to_string()),
+            attr(
+                "penalty_destination",
+                format!("{:?}", params.penalty_destination),
+            ),
+        ]);
+    } else {
+        attributes.push(attr("force_unlock", "disabled"));
+    }
+
+    config.force_unlock_params = params;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+/// Admin function to replace the lockup weight curve. The curve is frozen once the deposit window opens.
+/// Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **weight_curve** is an object of type [`WeightCurve`]. New lockup weight curve.
+pub fn handle_update_weight_curve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    weight_curve: WeightCurve,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: Only owner can call this function
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    // CHECK :: Weights of existing lockups depend on the curve. Pools can't open deposits before the config
+    if env.block.time.seconds() >= config.init_timestamp {
+        return Err(StdError::generic_err(
+            "Weight curve can't be changed once deposits are open",
+        ));
+    }
+
+    validate_weight_curve(&weight_curve, config.max_lock_duration)?;
+
+    config.weight_curve = weight_curve;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_weight_curve"),
+        attr("weight_curve", format!("{:?}", config.weight_curve)),
+    ]))
+}
+
+/// Checks that the lockup weight curve is valid for lockups of up to `max_lock_duration` weeks.
+/// ## Params
+/// * **weight_curve** is an object of type [`WeightCurve`].
+///
+/// * **max_lock_duration** is an object of type [`u64`].
+fn validate_weight_curve(weight_curve: &WeightCurve, max_lock_duration: u64) -> StdResult<()> {
+    match weight_curve {
+        WeightCurve::Linear {} | WeightCurve::Quadratic {} => {}
+        WeightCurve::Capped { max_boost } => {
+            if *max_boost < Decimal256::one() {
+                return Err(StdError::generic_err(
+                    "Max weight boost cannot be lower than 1",
+                ));
+            }
+        }
+        WeightCurve::Table { boosts } => {
+            if (boosts.len() as u64) < max_lock_duration {
+                return Err(StdError::generic_err(format!(
+                    "Weight curve table should contain a boost for every lockup week up to {}",
+                    max_lock_duration
+                )));
+            }
+            if boosts.iter().any(|boost| boost.is_zero()) {
+                return Err(StdError::generic_err(
+                    "Weight boosts should be greater than 0",
+                ));
+            }
+            // CHECK :: Extending a lockup can't lower its weight
+            if boosts.windows(2).any(|pair| pair[1] < pair[0]) {
+                return Err(StdError::generic_err(
+                    "Weight boosts should be non-decreasing with the lockup duration",
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the owner of the lockup position which the sender manages. If position NFTs are enabled, the sender
+/// should own the NFT of the lockup position. The contract itself can manage any lockup position.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **sender** is an object of type [`Addr`].
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Lockup owner, defaults to the sender.
+///
+/// * **terraswap_lp_token** is an object of type [`str`]. Key of the pool.
+///
+/// * **duration** is an object of type [`u64`].
+fn resolve_lockup_user_address(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    sender: &Addr,
+    user_address: Option<String>,
+    terraswap_lp_token: &str,
+    duration: u64,
+) -> StdResult<Addr> {
+    let user_address = user_address
+        .map(|v| addr_validate_to_lower(deps.api, &v))
+        .transpose()?
+        .unwrap_or_else(|| sender.clone());
+
+    if *sender != env.contract.address {
+        assert_position_owner(
+            &deps.querier,
+            &config.position_nft,
+            lockup_position_token_id(terraswap_lp_token, &user_address, duration),
+            &user_address,
+            sender,
+        )?;
+    }
+
+    Ok(user_address)
+}
+
+/// Returns the key of a pool whose LP token is the given cw20 token address or native denom, see [`pool_key`].
+/// A native pool is found by its exact denom or pool key, while cw20 token addresses are validated and normalized.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **terraswap_lp_token** is an object of type [`str`].
+fn validate_lp_token(deps: Deps, terraswap_lp_token: &str) -> StdResult<String> {
+    let native_lp_token = pool_key(&AssetInfo::NativeToken {
+        denom: terraswap_lp_token.to_string(),
+    });
+    if ASSET_POOLS.has(deps.storage, &native_lp_token) {
+        return Ok(native_lp_token);
+    }
+
+    // Position NFT IDs contain pool keys
+    if terraswap_lp_token.starts_with(NATIVE_POOL_KEY_PREFIX)
+        && ASSET_POOLS.has(deps.storage, terraswap_lp_token)
+    {
+        return Ok(terraswap_lp_token.to_string());
+    }
+
+    Ok(pool_key(&AssetInfo::Token {
+        contract_addr: addr_validate_to_lower(deps.api, terraswap_lp_token)?,
+    }))
+}
+
 /// Facilitates increasing ASTRO incentives that are to be distributed as Lockdrop participation reward. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
@@ -639,9 +1082,8 @@ pub fn handle_increasing_astro_incentives(
         return Err(StdError::generic_err("Only astro tokens are received!"));
     }
 
-    if env.block.time.seconds()
-        >= config.init_timestamp + config.deposit_window + config.withdrawal_window
-    {
+    let state = STATE.load(deps.storage)?;
+    if env.block.time.seconds() >= config.windows_end(&state) {
         return Err(StdError::generic_err("ASTRO is already being distributed"));
     };
 
@@ -667,12 +1109,22 @@ pub fn handle_increasing_astro_incentives(
 /// * **terraswap_lp_token** is an object of type [`String`]. Terraswap LP token address
 ///
 /// * **incentives_share** is an object of type [`u64`]. Parameter defining share of total ASTRO incentives are allocated for this pool
+///
+/// * **source_dex** is an [`Option`] of type [`SourceDex`]. DEX whose LP tokens are locked in the pool, Terraswap by default
+///
+/// * **native_lp_pool** is an [`Option`] of type [`String`]. Pair address which should be set if the LP token is a native denom
+///
+/// * **lockup_params** is an [`Option`] of type [`PoolLockupParams`]. Overrides of the lockup parameters of the config for the pool
+#[allow(clippy::too_many_arguments)]
 pub fn handle_initialize_pool(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     terraswap_lp_token: String,
     incentives_share: u64,
+    source_dex: Option<SourceDex>,
+    native_lp_pool: Option<String>,
+    lockup_params: Option<PoolLockupParams>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let mut state = STATE.load(deps.storage)?;
@@ -689,7 +1141,18 @@ pub fn handle_initialize_pool(
         ));
     }
 
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
+    let source_dex = source_dex.unwrap_or_default();
+    let native_lp_token = native_lp_pool.is_some();
+    let lp_token_info = if native_lp_token {
+        AssetInfo::NativeToken {
+            denom: terraswap_lp_token,
+        }
+    } else {
+        AssetInfo::Token {
+            contract_addr: addr_validate_to_lower(deps.api, &terraswap_lp_token)?,
+        }
+    };
+    let terraswap_lp_token = pool_key(&lp_token_info);
 
     // CHECK ::: Is LP Token Pool already initialized
     if ASSET_POOLS
@@ -699,15 +1162,27 @@ pub fn handle_initialize_pool(
         return Err(StdError::generic_err("Already supported"));
     }
 
-    let terraswap_pool = {
-        let res: Option<cw20::MinterResponse> = deps
-            .querier
-            .query_wasm_smart(&terraswap_lp_token, &Cw20QueryMsg::Minter {})?;
-        addr_validate_to_lower(
-            deps.api,
-            &res.ok_or_else(|| StdError::generic_err("No minter for the LP token!"))?
-                .minter,
-        )?
+    let terraswap_pool = match native_lp_pool {
+        Some(native_lp_pool) => {
+            let native_lp_pool = addr_validate_to_lower(deps.api, &native_lp_pool)?;
+            // CHECK :: The pair should issue the native LP token
+            if source_dex.query_pair_liquidity_token(&deps.querier, &native_lp_pool)?
+                != lp_token_info.to_string()
+            {
+                return Err(StdError::generic_err("The pair doesn't issue the LP token"));
+            }
+            native_lp_pool
+        }
+        None => {
+            let res: Option<cw20::MinterResponse> = deps
+                .querier
+                .query_wasm_smart(&terraswap_lp_token, &Cw20QueryMsg::Minter {})?;
+            addr_validate_to_lower(
+                deps.api,
+                &res.ok_or_else(|| StdError::generic_err("No minter for the LP token!"))?
+                    .minter,
+            )?
+        }
     };
 
     // POOL INFO :: Initialize new pool
@@ -721,34 +1196,76 @@ pub fn handle_initialize_pool(
         generator_proxy_per_share: RestrictedVector::default(),
         is_staked: false,
         has_asset_rewards: false,
+        source_dex,
+        native_lp_token,
+        penalty_astro_per_share: Decimal::zero(),
+        lockup_params: lockup_params.unwrap_or_default(),
+        asset_rewards: None,
     };
+    validate_pool_lockup_params(&config, &pool_info)?;
     // STATE UPDATE :: Save state and PoolInfo
     ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
 
     state.total_incentives_share += incentives_share;
+    state.max_pool_windows_end = state
+        .max_pool_windows_end
+        .max(config.pool_windows_end(&pool_info));
     STATE.save(deps.storage, &state)?;
 
     Ok(Response::new().add_attributes(vec![
         attr("action", "initialize_pool"),
         attr("terraswap_lp_token", terraswap_lp_token),
         attr("incentives_share", incentives_share.to_string()),
+        attr("source_dex", format!("{:?}", pool_info.source_dex)),
+        attr("native_lp_token", native_lp_token.to_string()),
     ]))
 }
 
-/// Admin function to update LP Pool Configuration. Returns a default object of type [`Response`].
+/// Checks that the lockup parameters of the pool are valid. The pool lockup windows can't open before
+/// the windows of the config, as the weight curve is frozen once the config deposit window opens.
 /// ## Params
-/// * **deps** is an object of type [`DepsMut`].
-///
-/// * **env** is an object of type [`Env`].
-///
-/// * **info** is an object of type [`MessageInfo`].
-///
-/// * **amount** is an object of type [`Uint128`]. Number of ASTRO tokens which are to be added to current incentives
-///
-/// * **terraswap_lp_token** is an object of type [`String`]. Parameter to identify the pool. Equals pool's terraswap Lp token address
+/// * **config** is an object of type [`Config`].
 ///
-/// * **incentives_share** is an object of type [`u64`]. Parameter defining share of total ASTRO incentives are allocated for this pool
-pub fn handle_update_pool(
+/// * **pool_info** is an object of type [`PoolInfo`].
+fn validate_pool_lockup_params(config: &Config, pool_info: &PoolInfo) -> StdResult<()> {
+    let pool_config = config.for_pool(pool_info);
+
+    // CHECK :: min_lock_duration , max_lock_duration need to be valid (min_lock_duration < max_lock_duration)
+    if pool_config.max_lock_duration < pool_config.min_lock_duration
+        || pool_config.min_lock_duration == 0u64
+    {
+        return Err(StdError::generic_err("Invalid Lockup durations"));
+    }
+    validate_weight_curve(&config.weight_curve, pool_config.max_lock_duration)?;
+
+    // CHECK :: Pool lockup windows can't open before the lockdrop windows
+    if pool_config.init_timestamp < config.init_timestamp {
+        return Err(StdError::generic_err(
+            "Pool lockup windows cannot open before the lockdrop windows",
+        ));
+    }
+
+    if pool_info.lockup_params.max_lockup_amount == Some(Uint128::zero()) {
+        return Err(StdError::generic_err("Max lockup amount should be > 0"));
+    }
+
+    Ok(())
+}
+
+/// Admin function to update LP Pool Configuration. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **amount** is an object of type [`Uint128`]. Number of ASTRO tokens which are to be added to current incentives
+///
+/// * **terraswap_lp_token** is an object of type [`String`]. Parameter to identify the pool. Equals pool's terraswap Lp token address
+///
+/// * **incentives_share** is an object of type [`u64`]. Parameter defining share of total ASTRO incentives are allocated for this pool
+pub fn handle_update_pool(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
@@ -770,7 +1287,7 @@ pub fn handle_update_pool(
         ));
     }
 
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
+    let terraswap_lp_token = validate_lp_token(deps.as_ref(), &terraswap_lp_token)?;
 
     // CHECK ::: Is LP Token Pool initialized
     let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
@@ -822,9 +1339,7 @@ pub fn handle_enable_claims(deps: DepsMut, env: Env, info: MessageInfo) -> StdRe
     }
 
     // CHECK :: Have the deposit / withdraw windows concluded
-    if env.block.time.seconds()
-        < (config.init_timestamp + config.deposit_window + config.withdrawal_window)
-    {
+    if env.block.time.seconds() < config.windows_end(&state) {
         return Err(StdError::generic_err(
             "Deposit / withdraw windows not closed yet",
         ));
@@ -840,7 +1355,7 @@ pub fn handle_enable_claims(deps: DepsMut, env: Env, info: MessageInfo) -> StdRe
     Ok(Response::new().add_attribute("action", "allow_claims"))
 }
 
-/// Migrates Liquidity from Terraswap to Astroport. Returns a default object of type [`Response`].
+/// Migrates Liquidity from the pool's source DEX to Astroport. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
@@ -868,24 +1383,30 @@ pub fn handle_migrate_liquidity(
         return Err(StdError::generic_err("Unauthorized"));
     }
 
+    let terraswap_lp_token = validate_lp_token(deps.as_ref(), &terraswap_lp_token)?;
+    let astroport_pool = addr_validate_to_lower(deps.api, &astroport_pool_addr)?;
+
+    let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+
     // CHECK :: may the liquidity be migrated or not ?
-    if env.block.time.seconds()
-        < config.init_timestamp + config.deposit_window + config.withdrawal_window
-    {
+    if env.block.time.seconds() < config.pool_windows_end(&pool_info) {
         return Err(StdError::generic_err(
             "Deposit / Withdrawal windows not closed",
         ));
     }
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
-    let astroport_pool = addr_validate_to_lower(deps.api, &astroport_pool_addr)?;
-
-    let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
 
     // CHECK :: has the liquidity already been migrated or not ?
     if pool_info.migration_info.is_some() {
         return Err(StdError::generic_err("Liquidity already migrated"));
     }
 
+    // CHECK :: Terraswap and Astroport pairs withdraw liquidity only for cw20 LP tokens
+    if pool_info.native_lp_token {
+        return Err(StdError::generic_err(
+            "Liquidity of native LP tokens can't be migrated",
+        ));
+    }
+
     let mut cosmos_msgs: Vec<CosmosMsg> = vec![];
 
     let lp_balance: BalanceResponse = deps.querier.query_wasm_smart(
@@ -895,52 +1416,34 @@ pub fn handle_migrate_liquidity(
         },
     )?;
 
-    // COSMOS MSG :: WITHDRAW LIQUIDITY FROM TERRASWAP
-    let msg = WasmMsg::Execute {
-        contract_addr: terraswap_lp_token.to_string(),
-        funds: vec![],
-        msg: to_binary(&Cw20ExecuteMsg::Send {
-            contract: pool_info.terraswap_pool.to_string(),
-            msg: to_binary(&terraswap::pair::Cw20HookMsg::WithdrawLiquidity {})?,
-            amount: lp_balance.balance,
-        })?,
-    };
-    cosmos_msgs.push(msg.into());
+    // COSMOS MSG :: WITHDRAW LIQUIDITY FROM THE SOURCE DEX
+    cosmos_msgs.push(pool_info.source_dex.withdraw_liquidity_msg(
+        &terraswap_lp_token,
+        &pool_info.terraswap_pool,
+        lp_balance.balance,
+    )?);
 
-    let terraswap_lp_info: terraswap::asset::PairInfo = deps.querier.query_wasm_smart(
+    let [first_asset_info, second_asset_info] = pool_info.source_dex.query_pair_asset_infos(
+        &deps.querier,
+        deps.api,
         &pool_info.terraswap_pool,
-        &terraswap::pair::QueryMsg::Pair {},
     )?;
 
-    let mut assets = vec![];
-
-    for asset_info in terraswap_lp_info.asset_infos.iter() {
-        assets.push(terraswap::asset::Asset {
-            amount: match &asset_info {
-                terraswap::asset::AssetInfo::NativeToken { denom } => {
-                    terraswap::querier::query_balance(
-                        &deps.querier,
-                        env.contract.address.clone(),
-                        denom.clone(),
-                    )?
-                }
-                terraswap::asset::AssetInfo::Token { contract_addr } => {
-                    terraswap::querier::query_token_balance(
-                        &deps.querier,
-                        addr_validate_to_lower(deps.api, contract_addr)?,
-                        env.contract.address.clone(),
-                    )?
-                }
-            },
-            info: asset_info.to_owned(),
-        })
-    }
-
     // COSMOS MSG :: CALLBACK AFTER LIQUIDITY WITHDRAWAL
-    let update_state_msg = CallbackMsg::WithdrawLiquidityFromTerraswapCallback {
+    let update_state_msg = CallbackMsg::WithdrawLiquidityFromSourceDexCallback {
         terraswap_lp_token: terraswap_lp_token.clone(),
         astroport_pool: astroport_pool.clone(),
-        prev_assets: assets.try_into().unwrap(),
+        prev_assets: [
+            Asset {
+                amount: first_asset_info.query_pool(&deps.querier, env.contract.address.clone())?,
+                info: first_asset_info,
+            },
+            Asset {
+                amount: second_asset_info
+                    .query_pool(&deps.querier, env.contract.address.clone())?,
+                info: second_asset_info,
+            },
+        ],
         slippage_tolerance,
     }
     .to_cosmos_msg(&env)?;
@@ -986,7 +1489,7 @@ pub fn handle_stake_lp_tokens(
         return Err(StdError::generic_err("Unauthorized"));
     }
 
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
+    let terraswap_lp_token = validate_lp_token(deps.as_ref(), &terraswap_lp_token)?;
 
     // CHECK ::: Is LP Token Pool supported or not ?
     let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
@@ -1046,7 +1549,7 @@ pub fn handle_stake_lp_tokens(
         ]))
 }
 
-/// Hook function to increase Lockup position size when any of the supported LP Tokens are sent to the contract by the user. Returns a default object of type [`Response`].
+/// Increases Lockup position size with native LP tokens sent along with the message. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
@@ -1054,6 +1557,55 @@ pub fn handle_stake_lp_tokens(
 ///
 /// * **info** is an object of type [`MessageInfo`].
 ///
+/// * **duration** is an object of type [`u64`]. Number of weeks the LP token is locked for (lockup period begins post the withdrawal window closure).
+pub fn handle_increase_native_lockup(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    duration: u64,
+) -> StdResult<Response> {
+    if info.funds.len() != 1 {
+        return Err(StdError::generic_err(
+            "Exactly one native LP token should be sent",
+        ));
+    }
+
+    let fund = &info.funds[0];
+
+    // CHECK :: Tokens sent > 0
+    if fund.amount.is_zero() {
+        return Err(StdError::generic_err(
+            "Number of tokens sent should be > 0 ",
+        ));
+    }
+
+    // CHECK ::: Native LP Token supported or not ?
+    let terraswap_lp_token = pool_key(&AssetInfo::NativeToken {
+        denom: fund.denom.clone(),
+    });
+    match ASSET_POOLS.may_load(deps.storage, &terraswap_lp_token)? {
+        Some(pool_info) if pool_info.native_lp_token => {}
+        _ => return Err(StdError::generic_err("Unsupported native LP token")),
+    }
+
+    handle_increase_lockup(
+        deps,
+        env,
+        terraswap_lp_token,
+        info.sender,
+        duration,
+        fund.amount,
+    )
+}
+
+/// Hook function to increase Lockup position size when any of the supported LP Tokens are sent to the contract by the user. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **terraswap_lp_token** is an object of type [`String`]. Key of the pool whose LP tokens are locked.
+///
 /// * **user_address** is an object of type [`Addr`]. User which sent the following LP token
 ///
 /// * **duration** is an object of type [`u64`]. Number of weeks the LP token is locked for (lockup period begins post the withdrawal window closure).
@@ -1062,40 +1614,56 @@ pub fn handle_stake_lp_tokens(
 pub fn handle_increase_lockup(
     deps: DepsMut,
     env: Env,
-    info: MessageInfo,
+    terraswap_lp_token: String,
     user_address: Addr,
     duration: u64,
     amount: Uint128,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
-    let terraswap_lp_token = info.sender;
 
     // CHECK ::: LP Token supported or not ?
     let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+    let pool_config = config.for_pool(&pool_info);
     let mut user_info = USER_INFO
         .may_load(deps.storage, &user_address)?
         .unwrap_or_default();
 
     // CHECK :: Lockdrop deposit window open
     let current_time = env.block.time.seconds();
-    if current_time < config.init_timestamp
-        || current_time >= config.init_timestamp + config.deposit_window
+    if current_time < pool_config.init_timestamp
+        || current_time >= pool_config.init_timestamp + pool_config.deposit_window
     {
         return Err(StdError::generic_err("Deposit window closed"));
     }
 
     // CHECK :: Valid Lockup Duration
-    if duration > config.max_lock_duration || duration < config.min_lock_duration {
+    if duration > pool_config.max_lock_duration || duration < pool_config.min_lock_duration {
         return Err(StdError::generic_err(format!(
             "Lockup duration needs to be between {} and {}",
-            config.min_lock_duration, config.max_lock_duration
+            pool_config.min_lock_duration, pool_config.max_lock_duration
         )));
     }
 
     pool_info.weighted_amount += calculate_weight(amount, duration, &config)?;
     pool_info.terraswap_amount_in_lockups += amount;
 
-    let lockup_key = (&terraswap_lp_token, &user_address, U64Key::new(duration));
+    // CHECK :: Pool lockup cap
+    if let Some(max_lockup_amount) = pool_info.lockup_params.max_lockup_amount {
+        if pool_info.terraswap_amount_in_lockups > max_lockup_amount {
+            return Err(StdError::generic_err(format!(
+                "Pool lockup cap of {} LP units exceeded",
+                max_lockup_amount
+            )));
+        }
+    }
+
+    let lockup_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(duration),
+    );
+    let token_id = lockup_position_token_id(&terraswap_lp_token, &user_address, duration);
+    let mut cosmos_msgs = vec![];
 
     let lockup_info = match LOCKUP_INFO.compatible_may_load(
         deps.as_ref(),
@@ -1103,6 +1671,15 @@ pub fn handle_increase_lockup(
         &config.generator,
     )? {
         Some(mut li) => {
+            // CHECK :: The user can't increase a lockup position whose NFT is owned by another address
+            if let Some(position_nft) = &config.position_nft {
+                if query_position_nft_owner(&deps.querier, position_nft, token_id)? != user_address
+                {
+                    return Err(StdError::generic_err(
+                        "Position NFT is owned by another address",
+                    ));
+                }
+            }
             li.lp_units_locked = li.lp_units_locked.checked_add(amount)?;
             li
         }
@@ -1116,17 +1693,26 @@ pub fn handle_increase_lockup(
             // Update number of lockup positions the user is having
             user_info.lockup_positions_index += 1;
 
+            // COSMOS_MSG :: Mint the lockup position NFT to the user
+            if let Some(position_nft) = &config.position_nft {
+                cosmos_msgs.push(build_mint_position_nft_msg(
+                    position_nft,
+                    token_id,
+                    &user_address,
+                )?);
+            }
+
             LockupInfoV2 {
                 lp_units_locked: amount,
                 astroport_lp_transferred: None,
                 astro_rewards: Uint128::zero(),
-                unlock_timestamp: config.init_timestamp
-                    + config.deposit_window
-                    + config.withdrawal_window
+                unlock_timestamp: config.pool_windows_end(&pool_info)
                     + (duration * SECONDS_PER_WEEK),
                 generator_astro_debt: Uint128::zero(),
                 generator_proxy_debt: Default::default(),
                 withdrawal_flag: false,
+                penalty_astro_debt: Uint128::zero(),
+                partially_unlocked: false,
             }
         }
     };
@@ -1136,16 +1722,19 @@ pub fn handle_increase_lockup(
     ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
     USER_INFO.save(deps.storage, &user_address, &user_info)?;
 
-    Ok(Response::new().add_attributes(vec![
-        attr("action", "increase_lockup_position"),
-        attr("terraswap_lp_token", terraswap_lp_token),
-        attr("user", user_address),
-        attr("duration", duration.to_string()),
-        attr("amount", amount),
-    ]))
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "increase_lockup_position"),
+            attr("terraswap_lp_token", terraswap_lp_token),
+            attr("user", user_address),
+            attr("duration", duration.to_string()),
+            attr("amount", amount),
+        ]))
 }
 
-/// Withdraws LP Tokens from an existing Lockup position. Returns a default object of type [`Response`].
+/// Moves LP units of a lockup position to a position with a longer lockup duration during the deposit window.
+/// LP units are merged into the position at the new duration if the user already has it. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
@@ -1153,101 +1742,181 @@ pub fn handle_increase_lockup(
 ///
 /// * **info** is an object of type [`MessageInfo`].
 ///
-/// * **terraswap_lp_token** is an object of type [`String`]. Terraswap Lp token address to identify the LP pool against which withdrawal has to be made
+/// * **terraswap_lp_token** is an object of type [`String`]. LP token to identify the pool of the lockup position.
 ///
-/// * **duration** is an object of type [`u64`]. Duration of the lockup position from which withdrawal is to be made.
+/// * **from_duration** is an object of type [`u64`]. Current lockup duration of the LP units (number of weeks).
 ///
-/// * **amount** is an object of type [`Uint128`]. Number of LP tokens to be withdrawn.
-pub fn handle_withdraw_from_lockup(
+/// * **to_duration** is an object of type [`u64`]. New lockup duration of the LP units (number of weeks).
+///
+/// * **amount** is an object of type [`Uint128`]. Number of LP units to move.
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Lockup owner, defaults to the sender.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_extend_lockup(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     terraswap_lp_token: String,
-    duration: u64,
+    from_duration: u64,
+    to_duration: u64,
     amount: Uint128,
+    user_address: Option<String>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
-    // CHECK :: Valid Withdraw Amount
-    if amount.is_zero() {
-        return Err(StdError::generic_err("Invalid withdrawal request"));
-    }
-
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
-
-    // CHECK ::: LP Token supported or not ?
+    let terraswap_lp_token = validate_lp_token(deps.as_ref(), &terraswap_lp_token)?;
     let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+    let pool_config = config.for_pool(&pool_info);
 
-    // Retrieve Lockup position
-    let user_address = info.sender;
-    let lockup_key = (&terraswap_lp_token, &user_address, U64Key::new(duration));
-    let mut lockup_info =
-        LOCKUP_INFO.compatible_load(deps.as_ref(), lockup_key.clone(), &config.generator)?;
+    // CHECK :: Lockdrop deposit window open
+    let current_time = env.block.time.seconds();
+    if current_time < pool_config.init_timestamp
+        || current_time >= pool_config.init_timestamp + pool_config.deposit_window
+    {
+        return Err(StdError::generic_err("Deposit window closed"));
+    }
 
-    // CHECK :: Has user already withdrawn LP tokens once post the deposit window closure state
-    if lockup_info.withdrawal_flag {
+    // CHECK :: Valid Lockup Duration
+    if to_duration <= from_duration {
         return Err(StdError::generic_err(
-            "Withdrawal already happened. No more withdrawals accepted",
+            "New lockup duration should be greater than the current one",
         ));
     }
-
-    // Check :: Amount should be within the allowed withdrawal limit bounds
-    let max_withdrawal_percent =
-        calculate_max_withdrawal_percent_allowed(env.block.time.seconds(), &config);
-    let max_withdrawal_allowed = lockup_info.lp_units_locked * max_withdrawal_percent;
-    if amount > max_withdrawal_allowed {
+    if to_duration > pool_config.max_lock_duration {
         return Err(StdError::generic_err(format!(
-            "Amount exceeds maximum allowed withdrawal limit of {}",
-            max_withdrawal_allowed
+            "Lockup duration needs to be between {} and {}",
+            pool_config.min_lock_duration, pool_config.max_lock_duration
         )));
     }
 
-    // Update withdrawal flag after the deposit window
-    if env.block.time.seconds() >= config.init_timestamp + config.deposit_window {
-        lockup_info.withdrawal_flag = true;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Amount should be > 0"));
     }
 
-    // STATE :: RETRIEVE --> UPDATE
-    lockup_info.lp_units_locked -= amount;
-    pool_info.weighted_amount -= calculate_weight(amount, duration, &config)?;
-    pool_info.terraswap_amount_in_lockups -= amount;
+    // CHECK :: The sender should manage the lockup position
+    let user_address = resolve_lockup_user_address(
+        deps.as_ref(),
+        &env,
+        &config,
+        &info.sender,
+        user_address,
+        &terraswap_lp_token,
+        from_duration,
+    )?;
+    let mut user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
 
-    // Remove Lockup position from the list of user positions if Lp_Locked balance == 0
-    if lockup_info.lp_units_locked.is_zero() {
-        LOCKUP_INFO.remove(deps.storage, lockup_key);
-        // decrement number of user's lockup positions
-        let mut user_info = USER_INFO
-            .may_load(deps.storage, &user_address)?
-            .unwrap_or_default();
+    let from_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(from_duration),
+    );
+    let mut from_lockup_info =
+        LOCKUP_INFO.compatible_load(deps.as_ref(), from_key.clone(), &config.generator)?;
+
+    if amount > from_lockup_info.lp_units_locked {
+        return Err(StdError::generic_err(format!(
+            "Amount exceeds {} LP units locked",
+            from_lockup_info.lp_units_locked
+        )));
+    }
+
+    // STATE :: Move LP units weight to the new duration
+    pool_info.weighted_amount -= calculate_weight(amount, from_duration, &config)?;
+    pool_info.weighted_amount += calculate_weight(amount, to_duration, &config)?;
+
+    let mut cosmos_msgs = vec![];
+
+    from_lockup_info.lp_units_locked -= amount;
+    if from_lockup_info.lp_units_locked.is_zero() {
+        LOCKUP_INFO.remove(deps.storage, from_key);
+        // COSMOS_MSG :: Burn the lockup position NFT
+        if let Some(position_nft) = &config.position_nft {
+            cosmos_msgs.push(build_burn_position_nft_msg(
+                position_nft,
+                lockup_position_token_id(&terraswap_lp_token, &user_address, from_duration),
+            )?);
+        }
         user_info.lockup_positions_index -= 1;
-        USER_INFO.save(deps.storage, &user_address, &user_info)?;
     } else {
-        LOCKUP_INFO.save(deps.storage, lockup_key, &lockup_info)?;
+        LOCKUP_INFO.save(deps.storage, from_key, &from_lockup_info)?;
     }
 
-    // SAVE Updated States
-    ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
+    let to_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(to_duration),
+    );
+    let to_token_id = lockup_position_token_id(&terraswap_lp_token, &user_address, to_duration);
+    let to_lockup_info =
+        match LOCKUP_INFO.compatible_may_load(deps.as_ref(), to_key.clone(), &config.generator)? {
+            Some(mut li) => {
+                // CHECK :: The sender can't merge into a lockup position whose NFT is owned by another address
+                if let Some(position_nft) = &config.position_nft {
+                    if query_position_nft_owner(&deps.querier, position_nft, to_token_id)?
+                        != info.sender
+                    {
+                        return Err(StdError::generic_err(
+                            "Position NFT is owned by another address",
+                        ));
+                    }
+                }
+                li.lp_units_locked = li.lp_units_locked.checked_add(amount)?;
+                li
+            }
+            None => {
+                if config.max_positions_per_user == user_info.lockup_positions_index {
+                    return Err(StdError::generic_err(format!(
+                        "Users can only have max {} lockup positions",
+                        config.max_positions_per_user
+                    )));
+                }
+                user_info.lockup_positions_index += 1;
+
+                // COSMOS_MSG :: Mint the lockup position NFT to the holder of the extended position NFT
+                if let Some(position_nft) = &config.position_nft {
+                    cosmos_msgs.push(build_mint_position_nft_msg(
+                        position_nft,
+                        to_token_id,
+                        &info.sender,
+                    )?);
+                }
 
-    // COSMOS_MSG ::TRANSFER WITHDRAWN LP Tokens
-    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: terraswap_lp_token.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: user_address.to_string(),
-            amount,
-        })?,
-        funds: vec![],
-    });
+                LockupInfoV2 {
+                    lp_units_locked: amount,
+                    astroport_lp_transferred: None,
+                    astro_rewards: Uint128::zero(),
+                    unlock_timestamp: config.pool_windows_end(&pool_info)
+                        + (to_duration * SECONDS_PER_WEEK),
+                    generator_astro_debt: Uint128::zero(),
+                    generator_proxy_debt: Default::default(),
+                    withdrawal_flag: false,
+                    penalty_astro_debt: Uint128::zero(),
+                    partially_unlocked: false,
+                }
+            }
+        };
 
-    Ok(Response::new().add_message(msg).add_attributes(vec![
-        attr("action", "withdraw_from_lockup"),
-        attr("terraswap_lp_token", terraswap_lp_token),
-        attr("user_address", user_address),
-        attr("duration", duration.to_string()),
-        attr("amount", amount),
-    ]))
+    // SAVE UPDATED STATE
+    LOCKUP_INFO.save(deps.storage, to_key, &to_lockup_info)?;
+    ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
+    USER_INFO.save(deps.storage, &user_address, &user_info)?;
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "extend_lockup"),
+            attr("terraswap_lp_token", terraswap_lp_token),
+            attr("user", user_address),
+            attr("from_duration", from_duration.to_string()),
+            attr("to_duration", to_duration.to_string()),
+            attr("amount", amount),
+        ]))
 }
 
-/// Delegates part of the ASTRO rewards to be used for LP Bootstrapping via auction. Returns a default object of type [`Response`].
+/// Moves a lockup position to another address together with its ASTRO rewards and reward debts.
+/// Pending generator and asset rewards of the position are claimed to the current owner first. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
@@ -1255,34 +1924,371 @@ pub fn handle_withdraw_from_lockup(
 ///
 /// * **info** is an object of type [`MessageInfo`].
 ///
-/// * **amount** is an object of type [`Uint128`]. Number of ASTRO to delegate.
-pub fn handle_delegate_astro_to_auction(
+/// * **terraswap_lp_token** is an object of type [`String`]. LP token to identify the pool of the lockup position.
+///
+/// * **duration** is an object of type [`u64`]. Lockup duration (number of weeks).
+///
+/// * **recipient** is an object of type [`String`]. New owner of the lockup position.
+pub fn handle_transfer_lockup(
     mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    amount: Uint128,
+    terraswap_lp_token: String,
+    duration: u64,
+    recipient: String,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
-    let mut state = STATE.load(deps.storage)?;
-    let user_address = info.sender;
+    let state = STATE.load(deps.storage)?;
 
-    // CHECK :: Have the deposit / withdraw windows concluded
-    if env.block.time.seconds()
-        < (config.init_timestamp + config.deposit_window + config.withdrawal_window)
-    {
+    let terraswap_lp_token = validate_lp_token(deps.as_ref(), &terraswap_lp_token)?;
+    let pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+
+    // CHECK :: The sender should manage the lockup position
+    let user_address = resolve_lockup_user_address(
+        deps.as_ref(),
+        &env,
+        &config,
+        &info.sender,
+        None,
+        &terraswap_lp_token,
+        duration,
+    )?;
+    let recipient = addr_validate_to_lower(deps.api, &recipient)?;
+    if recipient == user_address {
         return Err(StdError::generic_err(
-            "Deposit / withdraw windows not closed yet",
+            "Lockup position can't be transferred to its owner",
         ));
     }
 
-    // CHECK :: Can users withdraw their ASTRO tokens ? -> if so, then delegation is no longer allowed
-    if state.are_claims_allowed {
-        return Err(StdError::generic_err("Delegation window over"));
+    let lockup_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(duration),
+    );
+    let lockup_info =
+        LOCKUP_INFO.compatible_load(deps.as_ref(), lockup_key.clone(), &config.generator)?;
+    if lockup_info.is_unlocked() {
+        return Err(StdError::generic_err(
+            "Lockup position has already been unlocked",
+        ));
     }
 
-    let mut user_info = USER_INFO
-        .may_load(deps.storage, &user_address)?
-        .unwrap_or_default();
+    // CHECK :: ASTRO rewards owed to the position NFT owner stay with the NFT
+    if DETACHED_ASTRO_REWARDS.has(deps.storage, lockup_key.clone()) {
+        return Err(StdError::generic_err(
+            "ASTRO rewards of the lockup position are paid to its NFT owner, transfer the position NFT instead",
+        ));
+    }
+
+    let recipient_lockup_key = (
+        terraswap_lp_token.as_str(),
+        &recipient,
+        U64Key::new(duration),
+    );
+    if LOCKUP_INFO
+        .compatible_may_load(deps.as_ref(), recipient_lockup_key, &config.generator)?
+        .is_some()
+    {
+        return Err(StdError::generic_err(
+            "Recipient already has a lockup position with the same duration",
+        ));
+    }
+
+    let mut user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+    let mut recipient_info = USER_INFO
+        .may_load(deps.storage, &recipient)?
+        .unwrap_or_default();
+
+    if config.max_positions_per_user == recipient_info.lockup_positions_index {
+        return Err(StdError::generic_err(format!(
+            "Users can only have max {} lockup positions",
+            config.max_positions_per_user
+        )));
+    }
+    user_info.lockup_positions_index -= 1;
+    recipient_info.lockup_positions_index += 1;
+
+    let mut cosmos_msgs = vec![];
+
+    // ASTRO rewards of lockup positions are final once the deposit / withdraw windows are closed
+    if env.block.time.seconds() >= config.windows_end(&state) {
+        if user_info.total_astro_rewards.is_zero() {
+            user_info.total_astro_rewards = update_user_lockup_positions_and_calc_rewards(
+                deps.branch(),
+                &config,
+                &state,
+                &user_address,
+            )?;
+        }
+        if recipient_info.total_astro_rewards.is_zero() {
+            recipient_info.total_astro_rewards = update_user_lockup_positions_and_calc_rewards(
+                deps.branch(),
+                &config,
+                &state,
+                &recipient,
+            )?;
+        }
+        detach_transferred_positions_astro_rewards(
+            deps.branch(),
+            &config,
+            &user_address,
+            &mut user_info,
+        )?;
+
+        let astro_rewards = LOCKUP_INFO
+            .compatible_load(deps.as_ref(), lockup_key, &config.generator)?
+            .astro_rewards;
+
+        if user_info.astro_transferred {
+            // CHECK :: Claimed ASTRO rewards can't be claimed by the recipient once again
+            if !recipient_info.astro_transferred && !astro_rewards.is_zero() {
+                return Err(StdError::generic_err(
+                    "ASTRO rewards of the lockup have already been claimed, the recipient should claim its ASTRO rewards first",
+                ));
+            }
+        } else {
+            // CHECK :: ASTRO rewards of the lockup shouldn't be delegated, paid as a penalty or owed to other NFT owners
+            if astro_rewards > user_info.unclaimed_astro_rewards()? {
+                return Err(StdError::generic_err(
+                    "Delegated ASTRO rewards of the lockup can't be transferred",
+                ));
+            }
+
+            // COSMOS_MSG :: The recipient has already claimed its ASTRO rewards, so ASTRO rewards of the lockup are sent directly
+            if recipient_info.astro_transferred && !astro_rewards.is_zero() {
+                cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: config
+                        .astro_token
+                        .as_ref()
+                        .ok_or_else(|| StdError::generic_err("Astro token should be set!"))?
+                        .to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.to_string(),
+                        amount: astro_rewards,
+                    })?,
+                }));
+            }
+        }
+
+        user_info.total_astro_rewards = user_info.total_astro_rewards.checked_sub(astro_rewards)?;
+        recipient_info.total_astro_rewards = recipient_info
+            .total_astro_rewards
+            .checked_add(astro_rewards)?;
+    }
+
+    USER_INFO.save(deps.storage, &user_address, &user_info)?;
+    USER_INFO.save(deps.storage, &recipient, &recipient_info)?;
+
+    // Pending rewards of the lockup position are claimed to the current owner
+    if let Some(MigrationInfo {
+        astroport_lp_token, ..
+    }) = &pool_info.migration_info
+    {
+        if pool_info.is_staked {
+            cosmos_msgs.extend(build_claim_generator_rewards_msgs(
+                deps.as_ref(),
+                &env,
+                &config,
+                &terraswap_lp_token,
+                astroport_lp_token,
+            )?);
+        }
+
+        if pool_info.has_asset_rewards {
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ClaimAssetReward {
+                    recipient: Some(user_address.to_string()),
+                    terraswap_lp_token: terraswap_lp_token.to_string(),
+                    duration,
+                    user_address: Some(user_address.to_string()),
+                })?,
+            }));
+        }
+    }
+
+    cosmos_msgs.push(
+        CallbackMsg::TransferLockupCallback {
+            terraswap_lp_token,
+            user_address,
+            recipient,
+            duration,
+        }
+        .to_cosmos_msg(&env)?,
+    );
+
+    Ok(Response::new().add_messages(cosmos_msgs))
+}
+
+/// Withdraws LP Tokens from an existing Lockup position. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **terraswap_lp_token** is an object of type [`String`]. Terraswap Lp token address to identify the LP pool against which withdrawal has to be made
+///
+/// * **duration** is an object of type [`u64`]. Duration of the lockup position from which withdrawal is to be made.
+///
+/// * **amount** is an object of type [`Uint128`]. Number of LP tokens to be withdrawn.
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Lockup owner, defaults to the sender.
+pub fn handle_withdraw_from_lockup(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    terraswap_lp_token: String,
+    duration: u64,
+    amount: Uint128,
+    user_address: Option<String>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: Valid Withdraw Amount
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Invalid withdrawal request"));
+    }
+
+    let terraswap_lp_token = validate_lp_token(deps.as_ref(), &terraswap_lp_token)?;
+
+    // CHECK ::: LP Token supported or not ?
+    let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+    let pool_config = config.for_pool(&pool_info);
+
+    // Retrieve Lockup position
+    let user_address = resolve_lockup_user_address(
+        deps.as_ref(),
+        &env,
+        &config,
+        &info.sender,
+        user_address,
+        &terraswap_lp_token,
+        duration,
+    )?;
+    let lockup_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(duration),
+    );
+    let mut lockup_info =
+        LOCKUP_INFO.compatible_load(deps.as_ref(), lockup_key.clone(), &config.generator)?;
+
+    // CHECK :: Has user already withdrawn LP tokens once post the deposit window closure state
+    if lockup_info.withdrawal_flag {
+        return Err(StdError::generic_err(
+            "Withdrawal already happened. No more withdrawals accepted",
+        ));
+    }
+
+    // Check :: Amount should be within the allowed withdrawal limit bounds
+    let max_withdrawal_percent =
+        calculate_max_withdrawal_percent_allowed(env.block.time.seconds(), &pool_config);
+    let max_withdrawal_allowed = lockup_info.lp_units_locked * max_withdrawal_percent;
+    if amount > max_withdrawal_allowed {
+        return Err(StdError::generic_err(format!(
+            "Amount exceeds maximum allowed withdrawal limit of {}",
+            max_withdrawal_allowed
+        )));
+    }
+
+    // Update withdrawal flag after the deposit window
+    if env.block.time.seconds() >= pool_config.init_timestamp + pool_config.deposit_window {
+        lockup_info.withdrawal_flag = true;
+    }
+
+    // STATE :: RETRIEVE --> UPDATE
+    lockup_info.lp_units_locked -= amount;
+    pool_info.weighted_amount -= calculate_weight(amount, duration, &config)?;
+    pool_info.terraswap_amount_in_lockups -= amount;
+
+    let mut cosmos_msgs = vec![];
+
+    // Remove Lockup position from the list of user positions if Lp_Locked balance == 0
+    if lockup_info.lp_units_locked.is_zero() {
+        LOCKUP_INFO.remove(deps.storage, lockup_key);
+        // COSMOS_MSG :: Burn the lockup position NFT
+        if let Some(position_nft) = &config.position_nft {
+            cosmos_msgs.push(build_burn_position_nft_msg(
+                position_nft,
+                lockup_position_token_id(&terraswap_lp_token, &user_address, duration),
+            )?);
+        }
+        // decrement number of user's lockup positions
+        let mut user_info = USER_INFO
+            .may_load(deps.storage, &user_address)?
+            .unwrap_or_default();
+        user_info.lockup_positions_index -= 1;
+        USER_INFO.save(deps.storage, &user_address, &user_info)?;
+    } else {
+        LOCKUP_INFO.save(deps.storage, lockup_key, &lockup_info)?;
+    }
+
+    // SAVE Updated States
+    ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
+
+    // COSMOS_MSG ::TRANSFER WITHDRAWN LP Tokens
+    cosmos_msgs.push(
+        Asset {
+            info: pool_info.lp_token_info(&terraswap_lp_token),
+            amount,
+        }
+        .into_msg(&deps.querier, info.sender.clone())?,
+    );
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(vec![
+            attr("action", "withdraw_from_lockup"),
+            attr("terraswap_lp_token", terraswap_lp_token),
+            attr("user_address", user_address),
+            attr("duration", duration.to_string()),
+            attr("amount", amount),
+        ]))
+}
+
+/// Delegates part of the ASTRO rewards to be used for LP Bootstrapping via auction. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **amount** is an object of type [`Uint128`]. Number of ASTRO to delegate.
+///
+/// * **allowlist_proof** is an [`Option`] of type [`AllowlistProof`]. Proof that the user is allowlisted by the auction.
+pub fn handle_delegate_astro_to_auction(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    allowlist_proof: Option<AllowlistProof>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+    let user_address = info.sender;
+
+    // CHECK :: Have the deposit / withdraw windows concluded
+    if env.block.time.seconds() < config.windows_end(&state) {
+        return Err(StdError::generic_err(
+            "Deposit / withdraw windows not closed yet",
+        ));
+    }
+
+    // CHECK :: Can users withdraw their ASTRO tokens ? -> if so, then delegation is no longer allowed
+    if state.are_claims_allowed {
+        return Err(StdError::generic_err("Delegation window over"));
+    }
+
+    let mut user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
 
     // If user's total ASTRO rewards == 0 :: We update all of the user's lockup positions to calculate ASTRO rewards and for each alongwith their equivalent Astroport LP Shares
     if user_info.total_astro_rewards == Uint128::zero() {
@@ -1294,10 +2300,16 @@ pub fn handle_delegate_astro_to_auction(
         )?;
     }
 
+    // ASTRO rewards of the lockup positions sold with their NFTs can't be delegated
+    detach_transferred_positions_astro_rewards(
+        deps.branch(),
+        &config,
+        &user_address,
+        &mut user_info,
+    )?;
+
     // CHECK :: ASTRO to delegate cannot exceed user's unclaimed ASTRO balance
-    let max_delegable_astro = user_info
-        .total_astro_rewards
-        .checked_sub(user_info.delegated_astro_rewards)?;
+    let max_delegable_astro = user_info.unclaimed_astro_rewards()?;
 
     if amount > max_delegable_astro {
         return Err(StdError::generic_err(format!("ASTRO to delegate cannot exceed user's unclaimed ASTRO balance. ASTRO to delegate = {}, Max delegable ASTRO = {}. ", amount, max_delegable_astro)));
@@ -1325,6 +2337,7 @@ pub fn handle_delegate_astro_to_auction(
                 .to_string(),
             msg: to_binary(&DelegateAstroTokens {
                 user_address: user_address.to_string(),
+                allowlist_proof,
             })?,
             amount,
         })?,
@@ -1337,6 +2350,54 @@ pub fn handle_delegate_astro_to_auction(
     ]))
 }
 
+/// Returns ASTRO undelegated from the Bootstrap auction to the user's ASTRO rewards. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **sender** is an object of type [`Addr`]. Address which sent ASTRO tokens.
+///
+/// * **user_address** is an object of type [`String`]. User whose ASTRO is undelegated.
+///
+/// * **amount** is an object of type [`Uint128`]. Number of undelegated ASTRO.
+pub fn handle_undelegate_astro_tokens(
+    deps: DepsMut,
+    info: MessageInfo,
+    sender: Addr,
+    user_address: String,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut state = STATE.load(deps.storage)?;
+
+    if Some(&info.sender) != config.astro_token.as_ref() {
+        return Err(StdError::generic_err("Only astro tokens are received!"));
+    }
+
+    // CHECK :: Only the auction contract can return delegated ASTRO
+    if Some(&sender) != config.auction_contract.as_ref() {
+        return Err(StdError::generic_err("Unauthorized"));
+    }
+
+    let user_address = addr_validate_to_lower(deps.api, &user_address)?;
+    let mut user_info = USER_INFO.load(deps.storage, &user_address)?;
+
+    // UPDATE STATE
+    user_info.delegated_astro_rewards = user_info.delegated_astro_rewards.checked_sub(amount)?;
+    state.total_astro_delegated = state.total_astro_delegated.checked_sub(amount)?;
+
+    // SAVE UPDATED STATE
+    STATE.save(deps.storage, &state)?;
+    USER_INFO.save(deps.storage, &user_address, &user_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "undelegate_astro_from_auction"),
+        attr("user_address", user_address),
+        attr("amount", amount),
+    ]))
+}
+
 /// Claims user Rewards for a particular Lockup position. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
@@ -1350,6 +2411,13 @@ pub fn handle_delegate_astro_to_auction(
 /// * **duration** is an object of type [`u64`]. Lockup duration (number of weeks).
 ///
 /// * **withdraw_lp_stake** is an object of type [`bool`]. Boolean value indicating if the LP tokens are to be withdrawn or not.
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Lockup owner, defaults to the sender.
+///
+/// * **force_unlock** is an object of type [`bool`]. Whether the lockup is unlocked before its unlock timestamp for a penalty.
+///
+/// * **amount** is an [`Option`] of type [`Uint128`]. Astroport LP amount to unlock, the whole position is unlocked by default.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_claim_rewards_and_unlock_for_lockup(
     mut deps: DepsMut,
     env: Env,
@@ -1357,25 +2425,54 @@ pub fn handle_claim_rewards_and_unlock_for_lockup(
     terraswap_lp_token: String,
     duration: u64,
     withdraw_lp_stake: bool,
+    user_address: Option<String>,
+    force_unlock: bool,
+    amount: Option<Uint128>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
 
+    if force_unlock && !withdraw_lp_stake {
+        return Err(StdError::generic_err(
+            "Forced unlock requires withdraw_lp_stake",
+        ));
+    }
+
+    if let Some(amount) = amount {
+        if !withdraw_lp_stake {
+            return Err(StdError::generic_err(
+                "Unlock amount requires withdraw_lp_stake",
+            ));
+        }
+        if force_unlock {
+            return Err(StdError::generic_err("Forced unlocks can't be partial"));
+        }
+        if amount.is_zero() {
+            return Err(StdError::generic_err("Amount should be > 0"));
+        }
+    }
+
     if !state.are_claims_allowed {
         return Err(StdError::generic_err("Reward claim not allowed"));
     }
 
-    if env.block.time.seconds()
-        < config.init_timestamp + config.deposit_window + config.withdrawal_window
-    {
+    if env.block.time.seconds() < config.windows_end(&state) {
         return Err(StdError::generic_err(
             "Deposit / withdraw windows are still open",
         ));
     }
 
-    let user_address = info.sender;
+    let terraswap_lp_token = validate_lp_token(deps.as_ref(), &terraswap_lp_token)?;
 
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
+    let user_address = resolve_lockup_user_address(
+        deps.as_ref(),
+        &env,
+        &config,
+        &info.sender,
+        user_address,
+        &terraswap_lp_token,
+        duration,
+    )?;
 
     // CHECK ::: Is LP Token Pool supported or not ?
     let pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
@@ -1394,22 +2491,58 @@ pub fn handle_claim_rewards_and_unlock_for_lockup(
         )?;
     }
 
-    USER_INFO.save(deps.storage, &user_address, &user_info)?;
+    // ASTRO rewards of the lockup positions sold with their NFTs are paid to the NFT owners
+    detach_transferred_positions_astro_rewards(
+        deps.branch(),
+        &config,
+        &user_address,
+        &mut user_info,
+    )?;
 
     // Check is there lockup or not ?
-    let lockup_key = (&terraswap_lp_token, &user_address, U64Key::new(duration));
+    let lockup_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(duration),
+    );
     let lockup_info =
         LOCKUP_INFO.compatible_load(deps.as_ref(), lockup_key.clone(), &config.generator)?;
+    let mut lockup_astro_rewards_owed = DETACHED_ASTRO_REWARDS
+        .may_load(deps.storage, lockup_key.clone())?
+        .unwrap_or_default();
+
+    // CHECK :: Liquidity of native LP tokens isn't migrated, so their lockups are unlocked in full once matured
+    if withdraw_lp_stake && pool_info.migration_info.is_none() {
+        if !pool_info.native_lp_token {
+            return Err(StdError::generic_err("Pool should be migrated!"));
+        }
+        if force_unlock || amount.is_some() {
+            return Err(StdError::generic_err(
+                "Lockups of native LP tokens can only be unlocked in full once matured",
+            ));
+        }
+    }
 
     // CHECK :: Can the Lockup position be unlocked or not ?
+    let mut force_unlock_penalty = Uint128::zero();
     if withdraw_lp_stake && env.block.time.seconds() < lockup_info.unlock_timestamp {
-        return Err(StdError::generic_err(format!(
-            "{} seconds to unlock",
-            lockup_info.unlock_timestamp - env.block.time.seconds()
-        )));
+        let remaining_seconds = lockup_info.unlock_timestamp - env.block.time.seconds();
+        if !force_unlock {
+            return Err(StdError::generic_err(format!(
+                "{} seconds to unlock",
+                remaining_seconds
+            )));
+        }
+
+        let force_unlock_params = config
+            .force_unlock_params
+            .as_ref()
+            .ok_or_else(|| StdError::generic_err("Forced unlocks are disabled"))?;
+        force_unlock_penalty =
+            force_unlock_params.penalty_rate(remaining_seconds) * lockup_info.astro_rewards;
     }
 
-    if lockup_info.astroport_lp_transferred.is_some() {
+    if lockup_info.is_unlocked() {
         return Err(StdError::generic_err(
             "Astro LP Tokens have already been claimed!",
         ));
@@ -1417,81 +2550,67 @@ pub fn handle_claim_rewards_and_unlock_for_lockup(
 
     let mut cosmos_msgs = vec![];
 
-    if let Some(MigrationInfo {
-        astroport_lp_token, ..
-    }) = &pool_info.migration_info
-    {
-        if pool_info.is_staked {
-            let generator = config
-                .generator
-                .as_ref()
-                .ok_or_else(|| StdError::generic_err("Generator should be set at this moment!"))?;
-
-            // QUERY :: Check if there are any pending staking rewards
-            let pending_rewards: PendingTokenResponse = deps.querier.query_wasm_smart(
-                generator,
-                &GenQueryMsg::PendingToken {
-                    lp_token: astroport_lp_token.to_string(),
-                    user: env.contract.address.to_string(),
-                },
+    if !force_unlock_penalty.is_zero() {
+        // ASTRO rewards of the lockup owed to the NFT owner and the lockup owner's unclaimed ASTRO are used first,
+        // the rest of the penalty is transferred from the sender
+        let penalty_from_astro_owed = lockup_astro_rewards_owed.min(force_unlock_penalty);
+        if !penalty_from_astro_owed.is_zero() {
+            lockup_astro_rewards_owed -= penalty_from_astro_owed;
+            DETACHED_ASTRO_REWARDS.save(
+                deps.storage,
+                lockup_key.clone(),
+                &lockup_astro_rewards_owed,
             )?;
+        }
 
-            let pending_on_proxy = &pending_rewards.pending_on_proxy.unwrap_or_default();
-
-            if !pending_rewards.pending.is_zero()
-                || pending_on_proxy.iter().any(|asset| !asset.amount.is_zero())
-            {
-                let rwi: RewardInfoResponse = deps.querier.query_wasm_smart(
-                    generator,
-                    &GenQueryMsg::RewardInfo {
-                        lp_token: astroport_lp_token.to_string(),
-                    },
-                )?;
-
-                let astro_balance = {
-                    let res: BalanceResponse = deps.querier.query_wasm_smart(
-                        rwi.base_reward_token,
-                        &Cw20QueryMsg::Balance {
-                            address: env.contract.address.to_string(),
-                        },
-                    )?;
-                    res.balance
-                };
-
-                let prev_proxy_reward_balances: Vec<Asset> = pending_on_proxy
-                    .iter()
-                    .map(|asset| {
-                        let balance = asset
-                            .info
-                            .query_pool(&deps.querier, env.contract.address.clone())
-                            .unwrap_or_default();
+        let penalty_from_unclaimed_astro =
+            if info.sender == user_address && !user_info.astro_transferred {
+                user_info
+                    .unclaimed_astro_rewards()?
+                    .min(force_unlock_penalty - penalty_from_astro_owed)
+            } else {
+                Uint128::zero()
+            };
+        user_info.astro_penalty_paid += penalty_from_unclaimed_astro;
 
-                        Asset {
-                            info: asset.info.clone(),
-                            amount: balance,
-                        }
-                    })
-                    .collect();
+        let penalty_to_transfer =
+            force_unlock_penalty - penalty_from_astro_owed - penalty_from_unclaimed_astro;
+        if !penalty_to_transfer.is_zero() {
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config
+                    .astro_token
+                    .as_ref()
+                    .ok_or_else(|| StdError::generic_err("Astro token should be set!"))?
+                    .to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: info.sender.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount: penalty_to_transfer,
+                })?,
+            }));
+        }
+    }
 
-                cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
-                    contract_addr: generator.to_string(),
-                    funds: vec![],
-                    msg: to_binary(&GenExecuteMsg::Withdraw {
-                        lp_token: astroport_lp_token.to_string(),
-                        amount: Uint128::zero(),
-                    })?,
-                }));
+    USER_INFO.save(deps.storage, &user_address, &user_info)?;
 
-                cosmos_msgs.push(
-                    CallbackMsg::UpdatePoolOnDualRewardsClaim {
-                        terraswap_lp_token: terraswap_lp_token.clone(),
-                        prev_astro_balance: astro_balance,
-                        prev_proxy_reward_balances,
-                    }
-                    .to_cosmos_msg(&env)?,
-                );
-            }
-        } else if user_info.astro_transferred && !withdraw_lp_stake {
+    if let Some(MigrationInfo {
+        astroport_lp_token, ..
+    }) = &pool_info.migration_info
+    {
+        if pool_info.is_staked {
+            cosmos_msgs.extend(build_claim_generator_rewards_msgs(
+                deps.as_ref(),
+                &env,
+                &config,
+                &terraswap_lp_token,
+                astroport_lp_token,
+            )?);
+        } else if user_info.astro_transferred
+            && !withdraw_lp_stake
+            && lockup_astro_rewards_owed.is_zero()
+            && calc_pending_penalty_reward(&pool_info, &lockup_info)?.is_zero()
+        {
             return Err(StdError::generic_err("No rewards available to claim!"));
         }
 
@@ -1501,25 +2620,265 @@ pub fn handle_claim_rewards_and_unlock_for_lockup(
                 contract_addr: env.contract.address.to_string(),
                 funds: vec![],
                 msg: to_binary(&ExecuteMsg::ClaimAssetReward {
-                    recipient: Some(user_address.to_string()),
+                    recipient: Some(info.sender.to_string()),
                     terraswap_lp_token: terraswap_lp_token.to_string(),
                     duration,
+                    user_address: Some(user_address.to_string()),
                 })?,
             }));
         };
     }
 
-    cosmos_msgs.push(
-        CallbackMsg::WithdrawUserLockupRewardsCallback {
-            terraswap_lp_token,
-            user_address,
-            duration,
-            withdraw_lp_stake,
+    cosmos_msgs.push(
+        CallbackMsg::WithdrawUserLockupRewardsCallback {
+            terraswap_lp_token,
+            user_address,
+            recipient: info.sender,
+            duration,
+            withdraw_lp_stake,
+            force_unlock_penalty,
+            amount,
+        }
+        .to_cosmos_msg(&env)?,
+    );
+
+    Ok(Response::new().add_messages(cosmos_msgs))
+}
+
+/// Claims user Rewards for all the sender's lockup positions. Generator rewards are claimed once per pool.
+/// Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **info** is an object of type [`MessageInfo`].
+///
+/// * **withdraw_matured_lp** is an object of type [`bool`]. Boolean value indicating if the lockup positions whose lockup duration has concluded are to be unlocked or not.
+pub fn handle_claim_all(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    withdraw_matured_lp: bool,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let state = STATE.load(deps.storage)?;
+
+    if !state.are_claims_allowed {
+        return Err(StdError::generic_err("Reward claim not allowed"));
+    }
+
+    if env.block.time.seconds() < config.windows_end(&state) {
+        return Err(StdError::generic_err(
+            "Deposit / withdraw windows are still open",
+        ));
+    }
+
+    let user_address = info.sender;
+    let mut user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+
+    // If user's total ASTRO rewards == 0 :: We update all of the user's lockup positions to calculate ASTRO rewards and for each alongwith their equivalent Astroport LP Shares
+    if user_info.total_astro_rewards == Uint128::zero() {
+        user_info.total_astro_rewards = update_user_lockup_positions_and_calc_rewards(
+            deps.branch(),
+            &config,
+            &state,
+            &user_address,
+        )?;
+    }
+
+    // ASTRO rewards of the lockup positions sold with their NFTs are paid to the NFT owners
+    detach_transferred_positions_astro_rewards(
+        deps.branch(),
+        &config,
+        &user_address,
+        &mut user_info,
+    )?;
+    USER_INFO.save(deps.storage, &user_address, &user_info)?;
+
+    let mut cosmos_msgs = vec![];
+    let mut lockups: Vec<(String, u64)> = vec![];
+
+    for terraswap_lp_token in ASSET_POOLS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<String>, StdError>>()?
+    {
+        let pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+
+        // Staking rewards are only available in pools migrated to Astroport
+        let astroport_lp_token = match &pool_info.migration_info {
+            Some(migration_info) => &migration_info.astroport_lp_token,
+            None => continue,
+        };
+
+        let mut pool_lockups = vec![];
+        for duration in LOCKUP_INFO
+            .prefix((terraswap_lp_token.as_str(), &user_address))
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<Result<Vec<u64>, StdError>>()?
+        {
+            let lockup_info = LOCKUP_INFO.compatible_load(
+                deps.as_ref(),
+                (
+                    terraswap_lp_token.as_str(),
+                    &user_address,
+                    U64Key::new(duration),
+                ),
+                &config.generator,
+            )?;
+            if lockup_info.is_unlocked() {
+                continue;
+            }
+
+            // Lockup positions whose NFT is owned by another address are managed by the NFT owner
+            if let Some(position_nft) = &config.position_nft {
+                let token_id =
+                    lockup_position_token_id(&terraswap_lp_token, &user_address, duration);
+                if query_position_nft_owner(&deps.querier, position_nft, token_id)? != user_address
+                {
+                    continue;
+                }
+            }
+
+            // claim asset rewards of the unlocked lockup positions if the pool supports them
+            if withdraw_matured_lp
+                && pool_info.has_asset_rewards
+                && env.block.time.seconds() >= lockup_info.unlock_timestamp
+            {
+                cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&ExecuteMsg::ClaimAssetReward {
+                        recipient: Some(user_address.to_string()),
+                        terraswap_lp_token: terraswap_lp_token.to_string(),
+                        duration,
+                        user_address: Some(user_address.to_string()),
+                    })?,
+                }));
+            }
+
+            pool_lockups.push((terraswap_lp_token.clone(), duration));
+        }
+
+        if !pool_lockups.is_empty() && pool_info.is_staked {
+            cosmos_msgs.extend(build_claim_generator_rewards_msgs(
+                deps.as_ref(),
+                &env,
+                &config,
+                &terraswap_lp_token,
+                astroport_lp_token,
+            )?);
+        }
+        lockups.extend(pool_lockups);
+    }
+
+    if lockups.is_empty() && user_info.astro_transferred {
+        return Err(StdError::generic_err("No rewards available to claim!"));
+    }
+
+    cosmos_msgs.push(
+        CallbackMsg::ClaimAllCallback {
+            user_address,
+            lockups,
+            withdraw_matured_lp,
+        }
+        .to_cosmos_msg(&env)?,
+    );
+
+    Ok(Response::new().add_messages(cosmos_msgs))
+}
+
+/// Returns messages which claim pending generator rewards of the pool's staked Astroport LP tokens and update
+/// the pool's reward indexes with the received rewards. Returns an empty vector if there are no pending rewards.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **terraswap_lp_token** is an object of type [`str`]. Pool identifier.
+///
+/// * **astroport_lp_token** is an object of type [`Addr`]. Astroport LP token staked with the generator.
+fn build_claim_generator_rewards_msgs(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    terraswap_lp_token: &str,
+    astroport_lp_token: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let generator = config
+        .generator
+        .as_ref()
+        .ok_or_else(|| StdError::generic_err("Generator should be set at this moment!"))?;
+
+    // QUERY :: Check if there are any pending staking rewards
+    let pending_rewards: PendingTokenResponse = deps.querier.query_wasm_smart(
+        generator,
+        &GenQueryMsg::PendingToken {
+            lp_token: astroport_lp_token.to_string(),
+            user: env.contract.address.to_string(),
+        },
+    )?;
+
+    let pending_on_proxy = &pending_rewards.pending_on_proxy.unwrap_or_default();
+
+    if pending_rewards.pending.is_zero()
+        && pending_on_proxy.iter().all(|asset| asset.amount.is_zero())
+    {
+        return Ok(vec![]);
+    }
+
+    let rwi: RewardInfoResponse = deps.querier.query_wasm_smart(
+        generator,
+        &GenQueryMsg::RewardInfo {
+            lp_token: astroport_lp_token.to_string(),
+        },
+    )?;
+
+    let astro_balance = {
+        let res: BalanceResponse = deps.querier.query_wasm_smart(
+            rwi.base_reward_token,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        res.balance
+    };
+
+    let prev_proxy_reward_balances: Vec<Asset> = pending_on_proxy
+        .iter()
+        .map(|asset| {
+            let balance = asset
+                .info
+                .query_pool(&deps.querier, env.contract.address.clone())
+                .unwrap_or_default();
+
+            Asset {
+                info: asset.info.clone(),
+                amount: balance,
+            }
+        })
+        .collect();
+
+    Ok(vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: generator.to_string(),
+            funds: vec![],
+            msg: to_binary(&GenExecuteMsg::Withdraw {
+                lp_token: astroport_lp_token.to_string(),
+                amount: Uint128::zero(),
+            })?,
+        }),
+        CallbackMsg::UpdatePoolOnDualRewardsClaim {
+            terraswap_lp_token: terraswap_lp_token.to_string(),
+            prev_astro_balance: astro_balance,
+            prev_proxy_reward_balances,
         }
-        .to_cosmos_msg(&env)?,
-    );
-
-    Ok(Response::new().add_messages(cosmos_msgs))
+        .to_cosmos_msg(env)?,
+    ])
 }
 
 /// Collects assets reward from LP and distribute reward to user if all requirements are met. Returns a default object of type [`Response`].
@@ -1528,22 +2887,35 @@ pub fn handle_claim_rewards_and_unlock_for_lockup(
 ///
 /// * **env** is an object of type [`Env`].
 ///
-/// * **user_address** is an object of type [`Addr`].
+/// * **sender** is an object of type [`Addr`].
 ///
 /// * **recipient** is an object of type [`Addr`].
 ///
 /// * **terraswap_lp_token** is an object of type [`String`].
 ///
 /// * **lock_duration** is an object of type [`u64`].
+///
+/// * **user_address** is an [`Option`] of type [`String`]. Lockup owner, defaults to the sender.
 fn handle_claim_asset_reward(
     deps: Deps,
     env: Env,
-    user_address: Addr,
+    sender: Addr,
     recipient: Addr,
     terraswap_lp_token: String,
     lock_duration: u64,
+    user_address: Option<String>,
 ) -> StdResult<Response> {
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
+    let config = CONFIG.load(deps.storage)?;
+    let terraswap_lp_token = validate_lp_token(deps, &terraswap_lp_token)?;
+    let user_address = resolve_lockup_user_address(
+        deps,
+        &env,
+        &config,
+        &sender,
+        user_address,
+        &terraswap_lp_token,
+        lock_duration,
+    )?;
 
     let pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
     if !pool_info.has_asset_rewards {
@@ -1554,20 +2926,26 @@ fn handle_claim_asset_reward(
         .migration_info
         .ok_or_else(|| StdError::generic_err("The pool was not migrated to astroport"))?;
     let pair_info = pair_info_by_pool(&deps.querier, migration_info.astroport_lp_token)?;
+    let asset_rewards = pool_asset_rewards(&pool_info.asset_rewards, &env.contract.address)?;
     let pool_claim_msg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: pair_info.contract_addr.to_string(),
-        msg: to_binary(&astroport::pair_stable_bluna::ExecuteMsg::ClaimReward { receiver: None })?,
+        msg: asset_rewards.claim_msg,
         funds: vec![],
     });
 
-    let previous_balance = astroport::querier::query_balance(
-        &deps.querier,
-        env.contract.address.clone(),
-        "uusd".to_string(),
-    )?;
+    let previous_balances = asset_rewards
+        .reward_assets
+        .into_iter()
+        .map(|info| {
+            Ok(Asset {
+                amount: info.query_pool(&deps.querier, env.contract.address.clone())?,
+                info,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
 
     let distribute_callback_msg = CallbackMsg::DistributeAssetReward {
-        previous_balance,
+        previous_balances,
         terraswap_lp_token,
         user_address,
         recipient,
@@ -1579,19 +2957,26 @@ fn handle_claim_asset_reward(
 }
 
 /// Sets `enable` flag for liquidity pool. Returns a default object of type [`Response`].
+/// Reward assets can't be removed from the pool once rewards in them were distributed, as the reward indexes of lockup positions would be orphaned.
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
+/// * **env** is an object of type [`Env`].
+///
 /// * **info** is an object of type [`MessageInfo`].
 ///
 /// * **terraswap_lp_token** is an object of type [`String`].
 ///
 /// * **enable** is an object of type [`bool`].
+///
+/// * **asset_rewards** is an [`Option`] of type [`AssetRewardsConfig`]. New source of the pool's asset rewards.
 fn handle_toggle_rewards(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     terraswap_lp_token: String,
     enable: bool,
+    asset_rewards: Option<AssetRewardsConfig>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     // CHECK ::: Only owner can call this function
@@ -1599,24 +2984,45 @@ fn handle_toggle_rewards(
         return Err(StdError::generic_err("Unauthorized"));
     }
 
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
-    ASSET_POOLS
-        .update(deps.storage, &terraswap_lp_token, |pool_info_opt| {
-            let mut pool_info =
-                pool_info_opt.ok_or_else(|| StdError::generic_err("Pool was not found"))?;
-            pool_info.has_asset_rewards = enable;
-            Ok(pool_info)
-        })
-        .map(|pool_info| {
-            Response::default().add_attributes(vec![
-                ("action", "toggle_pool_rewards"),
-                ("lp_address", pool_info.terraswap_pool.as_str()),
-                (
-                    "has_asset_rewards",
-                    &pool_info.has_asset_rewards.to_string(),
-                ),
-            ])
-        })
+    if let Some(asset_rewards) = &asset_rewards {
+        asset_rewards.validate(deps.api)?;
+    }
+
+    let terraswap_lp_token = validate_lp_token(deps.as_ref(), &terraswap_lp_token)?;
+    let mut pool_info = ASSET_POOLS
+        .may_load(deps.storage, &terraswap_lp_token)?
+        .ok_or_else(|| StdError::generic_err("Pool was not found"))?;
+
+    if let Some(asset_rewards) = asset_rewards {
+        // CHECK :: Reward assets with distributed rewards can't be removed
+        let current_asset_rewards =
+            pool_asset_rewards(&pool_info.asset_rewards, &env.contract.address)?;
+        for reward_asset in current_asset_rewards.reward_assets {
+            if !asset_rewards.reward_assets.contains(&reward_asset)
+                && AssetRewardIndexes::new(&reward_asset)
+                    .total()
+                    .has(deps.storage, &terraswap_lp_token)
+            {
+                return Err(StdError::generic_err(format!(
+                    "Reward asset {} can't be removed once its rewards were distributed",
+                    reward_asset
+                )));
+            }
+        }
+        pool_info.asset_rewards = Some(asset_rewards);
+    }
+
+    pool_info.has_asset_rewards = enable;
+    ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
+
+    Ok(Response::default().add_attributes(vec![
+        ("action", "toggle_pool_rewards"),
+        ("lp_address", pool_info.terraswap_pool.as_str()),
+        (
+            "has_asset_rewards",
+            &pool_info.has_asset_rewards.to_string(),
+        ),
+    ]))
 }
 
 /// Updates contract state after dual staking rewards are claimed from the generator contract. Returns a default object of type [`Response`].
@@ -1633,7 +3039,7 @@ fn handle_toggle_rewards(
 pub fn update_pool_on_dual_rewards_claim(
     deps: DepsMut,
     env: Env,
-    terraswap_lp_token: Addr,
+    terraswap_lp_token: String,
     prev_astro_balance: Uint128,
     prev_proxy_reward_balances: Vec<Asset>,
 ) -> StdResult<Response> {
@@ -1711,36 +3117,54 @@ pub fn update_pool_on_dual_rewards_claim(
 ///
 /// * **env** is an object of type [`Env`].
 ///
-/// * **terraswap_lp_token** is an object of type [`Addr`]. Pool identifier to identify the LP pool.
+/// * **terraswap_lp_token** is an object of type [`String`]. Pool identifier to identify the LP pool.
 ///
 /// * **user_address** is an object of type [`Addr`]. User address who is claiming the rewards / unlocking his lockup position.
 ///
+/// * **recipient** is an object of type [`Addr`]. Address which receives staking rewards and unlocked LP tokens of the lockup.
+///
 /// * **duration** is a vector of type [`u64`]. Duration of the lockup for which rewards have been claimed / position unlocked.
 ///
 /// * **withdraw_lp_stake** is an object of type [`bool`]. Boolean value indicating if the ASTRO LP Tokens are to be sent to the user or not.
+///
+/// * **force_unlock_penalty** is an object of type [`Uint128`]. ASTRO penalty paid for unlocking the position before its unlock timestamp.
+///
+/// * **amount** is an [`Option`] of type [`Uint128`]. Astroport LP tokens to unlock, the whole position is unlocked if not set.
+#[allow(clippy::too_many_arguments)]
 pub fn callback_withdraw_user_rewards_for_lockup_optional_withdraw(
     deps: DepsMut,
     env: Env,
-    terraswap_lp_token: Addr,
+    terraswap_lp_token: String,
     user_address: Addr,
+    recipient: Addr,
     duration: u64,
     withdraw_lp_stake: bool,
+    force_unlock_penalty: Uint128,
+    amount: Option<Uint128>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
     let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
-    let lockup_key = (&terraswap_lp_token, &user_address, U64Key::new(duration));
+    let lockup_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(duration),
+    );
     let mut lockup_info =
         LOCKUP_INFO.compatible_load(deps.as_ref(), lockup_key.clone(), &config.generator)?;
 
     let mut user_info = USER_INFO
         .may_load(deps.storage, &user_address)?
         .unwrap_or_default();
+    let lockup_astro_rewards_owed = DETACHED_ASTRO_REWARDS
+        .may_load(deps.storage, lockup_key.clone())?
+        .unwrap_or_default();
 
     let mut cosmos_msgs = vec![];
     let mut attributes = vec![
         attr("action", "withdraw_rewards_and_or_unlock"),
         attr("terraswap_lp_token", &terraswap_lp_token),
         attr("user_address", &user_address),
+        attr("recipient", &recipient),
         attr("duration", duration.to_string()),
     ];
 
@@ -1753,32 +3177,44 @@ pub fn callback_withdraw_user_rewards_for_lockup_optional_withdraw(
             .as_ref()
             .ok_or_else(|| StdError::generic_err("Generator should be set"))?;
 
+        // Claim forced unlock penalties of other lockers accrued to the lockup position
+        let pending_penalty_reward = calc_pending_penalty_reward(&pool_info, &lockup_info)?;
+        if !pending_penalty_reward.is_zero() {
+            lockup_info.penalty_astro_debt += pending_penalty_reward;
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config
+                    .astro_token
+                    .as_ref()
+                    .ok_or_else(|| StdError::generic_err("Astro token should be set!"))?
+                    .to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: pending_penalty_reward,
+                })?,
+            }));
+            attributes.push(attr("penalty_astro_reward", pending_penalty_reward));
+        }
+
         // Calculate Astro LP share for the lockup position
-        let astroport_lp_amount: Uint128 = {
-            let balance: Uint128 = if pool_info.is_staked {
-                deps.querier.query_wasm_smart(
-                    generator,
-                    &GenQueryMsg::Deposit {
-                        lp_token: astroport_lp_token.to_string(),
-                        user: env.contract.address.to_string(),
-                    },
-                )?
-            } else {
-                let res: BalanceResponse = deps.querier.query_wasm_smart(
-                    astroport_lp_token,
-                    &Cw20QueryMsg::Balance {
-                        address: env.contract.address.to_string(),
-                    },
-                )?;
-                res.balance
-            };
+        let astroport_lp_amount = calc_lockup_astroport_lp_amount(
+            deps.as_ref(),
+            &env,
+            generator,
+            &pool_info,
+            astroport_lp_token,
+            &lockup_info,
+        )?;
 
-            (lockup_info
-                .lp_units_locked
-                .full_mul(balance)
-                .checked_div(Uint256::from(pool_info.terraswap_amount_in_lockups))?)
-            .try_into()?
-        };
+        // Astroport LP amount to unlock, the rest of the position stays locked
+        let unlock_amount = amount.unwrap_or(astroport_lp_amount);
+        if unlock_amount > astroport_lp_amount {
+            return Err(StdError::generic_err(format!(
+                "Amount exceeds {} Astroport LP units of the lockup",
+                astroport_lp_amount
+            )));
+        }
+        let partial_unlock = unlock_amount < astroport_lp_amount;
 
         // If Astro LP tokens are staked with Astro generator
         if pool_info.is_staked {
@@ -1789,12 +3225,9 @@ pub fn callback_withdraw_user_rewards_for_lockup_optional_withdraw(
                 },
             )?;
 
-            // Calculate claimable Astro staking rewards for this lockup
-            let total_lockup_astro_rewards =
-                pool_info.generator_astro_per_share * astroport_lp_amount;
-            let pending_astro_rewards =
-                total_lockup_astro_rewards.checked_sub(lockup_info.generator_astro_debt)?;
-            lockup_info.generator_astro_debt = total_lockup_astro_rewards;
+            // Calculate claimable Astro and proxy staking rewards for this lockup
+            let (pending_astro_rewards, pending_proxy_rewards) =
+                update_lockup_generator_debts(&pool_info, &mut lockup_info, astroport_lp_amount)?;
 
             // If claimable Astro staking rewards > 0, claim them
             if pending_astro_rewards > Uint128::zero() {
@@ -1802,55 +3235,28 @@ pub fn callback_withdraw_user_rewards_for_lockup_optional_withdraw(
                     contract_addr: rwi.base_reward_token.to_string(),
                     funds: vec![],
                     msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                        recipient: user_address.to_string(),
+                        recipient: recipient.to_string(),
                         amount: pending_astro_rewards,
                     })?,
                 }));
             }
             attributes.push(attr("generator_astro_reward", pending_astro_rewards));
 
-            let mut pending_proxy_rewards: Vec<Asset> = vec![];
-            // If this LP token is getting dual incentives
-            // Calculate claimable proxy staking rewards for this lockup
-            lockup_info.generator_proxy_debt = lockup_info
-                .generator_proxy_debt
-                .inner_ref()
-                .iter()
-                .map(|(asset, debt)| {
-                    let generator_proxy_per_share = pool_info
-                        .generator_proxy_per_share
-                        .load(asset)
-                        .unwrap_or_default();
-                    let total_lockup_proxy_reward =
-                        generator_proxy_per_share.checked_mul_uint128(astroport_lp_amount)?;
-                    let pending_proxy_reward: Uint128 =
-                        total_lockup_proxy_reward.checked_sub(*debt)?;
-
-                    if !pending_proxy_reward.is_zero() {
-                        pending_proxy_rewards.push(Asset {
-                            info: asset.clone(),
-                            amount: pending_proxy_reward,
-                        });
-                    }
-                    Ok((asset.clone(), total_lockup_proxy_reward))
-                })
-                .collect::<StdResult<Vec<_>>>()?
-                .into();
-
             // If this is a void transaction (no state change), then return error.
             // Void tx scenario = ASTRO already claimed, 0 pending ASTRO staking reward, 0 pending proxy rewards, not unlocking LP tokens in this tx
             if !withdraw_lp_stake
                 && user_info.astro_transferred
+                && lockup_astro_rewards_owed.is_zero()
                 && pending_astro_rewards == Uint128::zero()
                 && pending_proxy_rewards.is_empty()
+                && pending_penalty_reward.is_zero()
             {
                 return Err(StdError::generic_err("No rewards available to claim!"));
             }
 
             // If claimable proxy staking rewards > 0, claim them
             for pending_proxy_reward in pending_proxy_rewards {
-                cosmos_msgs
-                    .push(pending_proxy_reward.into_msg(&deps.querier, user_address.clone())?);
+                cosmos_msgs.push(pending_proxy_reward.into_msg(&deps.querier, recipient.clone())?);
             }
 
             //  COSMOSMSG :: If LP Tokens are staked, we unstake the amount which needs to be returned to the user
@@ -1860,7 +3266,7 @@ pub fn callback_withdraw_user_rewards_for_lockup_optional_withdraw(
                     funds: vec![],
                     msg: to_binary(&GenExecuteMsg::Withdraw {
                         lp_token: astroport_lp_token.to_string(),
-                        amount: astroport_lp_amount,
+                        amount: unlock_amount,
                     })?,
                 }));
             }
@@ -1871,31 +3277,141 @@ pub fn callback_withdraw_user_rewards_for_lockup_optional_withdraw(
             cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: astroport_lp_token.to_string(),
                 msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: user_address.to_string(),
-                    amount: astroport_lp_amount,
+                    recipient: recipient.to_string(),
+                    amount: unlock_amount,
                 })?,
                 funds: vec![],
             }));
-            pool_info.terraswap_amount_in_lockups = pool_info
-                .terraswap_amount_in_lockups
-                .checked_sub(lockup_info.lp_units_locked)?;
+
+            if partial_unlock {
+                // Terraswap LP units of the unlocked part are rounded up
+                let lp_units_remaining = lockup_info
+                    .lp_units_locked
+                    .multiply_ratio(astroport_lp_amount - unlock_amount, astroport_lp_amount);
+                pool_info.terraswap_amount_in_lockups =
+                    pool_info.terraswap_amount_in_lockups.checked_sub(
+                        lockup_info
+                            .lp_units_locked
+                            .checked_sub(lp_units_remaining)?,
+                    )?;
+                lockup_info.lp_units_locked = lp_units_remaining;
+
+                // Rewards of the unlocked part have been paid, so the debts are set to the remaining part
+                reset_lockup_debts(
+                    &pool_info,
+                    &mut lockup_info,
+                    astroport_lp_amount - unlock_amount,
+                )?;
+            } else {
+                // COSMOSMSG :: Burn the lockup position NFT as the lockup is closed
+                if let Some(position_nft) = &config.position_nft {
+                    cosmos_msgs.push(build_burn_position_nft_msg(
+                        position_nft,
+                        lockup_position_token_id(&terraswap_lp_token, &user_address, duration),
+                    )?);
+                }
+                pool_info.terraswap_amount_in_lockups = pool_info
+                    .terraswap_amount_in_lockups
+                    .checked_sub(lockup_info.lp_units_locked)?;
+            }
+
+            if !force_unlock_penalty.is_zero() {
+                let astro_token = config
+                    .astro_token
+                    .as_ref()
+                    .ok_or_else(|| StdError::generic_err("Astro token should be set!"))?;
+                let penalty_destination = config
+                    .force_unlock_params
+                    .as_ref()
+                    .map(|params| params.penalty_destination.clone())
+                    .unwrap_or(PenaltyDestination::Burn);
+
+                // Penalty is burned if there are no lockers left in the pool
+                if penalty_destination == PenaltyDestination::Lockers
+                    && !pool_info.terraswap_amount_in_lockups.is_zero()
+                {
+                    pool_info.penalty_astro_per_share = pool_info.penalty_astro_per_share
+                        + Decimal::from_ratio(
+                            force_unlock_penalty,
+                            pool_info.terraswap_amount_in_lockups,
+                        );
+                    attributes.push(attr("penalty_distributed", force_unlock_penalty));
+                } else {
+                    cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: astro_token.to_string(),
+                        funds: vec![],
+                        msg: to_binary(&Cw20ExecuteMsg::Burn {
+                            amount: force_unlock_penalty,
+                        })?,
+                    }));
+                    attributes.push(attr("penalty_burned", force_unlock_penalty));
+                }
+            }
             ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
 
-            attributes.push(attr("astroport_lp_unlocked", astroport_lp_amount));
-            lockup_info.astroport_lp_transferred = Some(astroport_lp_amount);
+            attributes.push(attr("astroport_lp_unlocked", unlock_amount));
+            lockup_info.astroport_lp_transferred = Some(
+                lockup_info
+                    .astroport_lp_transferred
+                    .unwrap_or_default()
+                    .checked_add(unlock_amount)?,
+            );
+            lockup_info.partially_unlocked = partial_unlock;
         }
-        LOCKUP_INFO.save(deps.storage, lockup_key, &lockup_info)?;
+        LOCKUP_INFO.save(deps.storage, lockup_key.clone(), &lockup_info)?;
     } else if withdraw_lp_stake {
-        return Err(StdError::generic_err("Pool should be migrated!"));
+        // Liquidity of native LP tokens isn't migrated, so the locked LP tokens are returned
+        if !pool_info.native_lp_token {
+            return Err(StdError::generic_err("Pool should be migrated!"));
+        }
+
+        // COSMOSMSG :: Returns native LP tokens locked by the user in the current lockup position
+        cosmos_msgs.push(
+            Asset {
+                info: pool_info.lp_token_info(&terraswap_lp_token),
+                amount: lockup_info.lp_units_locked,
+            }
+            .into_msg(&deps.querier, recipient.clone())?,
+        );
+
+        // COSMOSMSG :: Burn the lockup position NFT as the lockup is closed
+        if let Some(position_nft) = &config.position_nft {
+            cosmos_msgs.push(build_burn_position_nft_msg(
+                position_nft,
+                lockup_position_token_id(&terraswap_lp_token, &user_address, duration),
+            )?);
+        }
+        pool_info.terraswap_amount_in_lockups = pool_info
+            .terraswap_amount_in_lockups
+            .checked_sub(lockup_info.lp_units_locked)?;
+        ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
+
+        attributes.push(attr("native_lp_unlocked", lockup_info.lp_units_locked));
+        // No Astroport LP tokens are transferred, the lockup is marked as unlocked
+        lockup_info.astroport_lp_transferred = Some(Uint128::zero());
+        lockup_info.partially_unlocked = false;
+        LOCKUP_INFO.save(deps.storage, lockup_key.clone(), &lockup_info)?;
     }
 
-    // Transfers claimable one time ASTRO rewards to the user that the user gets for all his lock
     if let Some(astro_token) = &config.astro_token {
+        // Transfers one time ASTRO rewards of the lockup which are owed to the position NFT owner
+        if !lockup_astro_rewards_owed.is_zero() {
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: astro_token.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: lockup_astro_rewards_owed,
+                })?,
+            }));
+            DETACHED_ASTRO_REWARDS.save(deps.storage, lockup_key, &Uint128::zero())?;
+            attributes.push(attr("lockup_astro_reward", lockup_astro_rewards_owed));
+        }
+
+        // Transfers claimable one time ASTRO rewards to the user that the user gets for all his lock
         if !user_info.astro_transferred {
             // Calculating how much Astro user can claim (from total one time reward)
-            let total_claimable_astro_rewards = user_info
-                .total_astro_rewards
-                .checked_sub(user_info.delegated_astro_rewards)?;
+            let total_claimable_astro_rewards = user_info.unclaimed_astro_rewards()?;
             if total_claimable_astro_rewards > Uint128::zero() {
                 cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
                     contract_addr: astro_token.to_string(),
@@ -1920,25 +3436,276 @@ pub fn callback_withdraw_user_rewards_for_lockup_optional_withdraw(
         .add_attributes(attributes))
 }
 
-/// Deposits Liquidity in Astroport after its withdrawn from terraswap. Returns a default object of type [`Response`].
+/// Withdraws user rewards of the given lockup positions in aggregated transfers and unlocks the matured ones after claims.
+/// Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **user_address** is an object of type [`Addr`]. User address who is claiming the rewards / unlocking his lockup positions.
+///
+/// * **lockups** is a vector of type [`(String, u64)`]. Pool identifiers and durations of the lockup positions grouped by pools.
+///
+/// * **withdraw_matured_lp** is an object of type [`bool`]. Boolean value indicating if the lockup positions whose lockup duration has concluded are to be unlocked or not.
+pub fn callback_claim_all(
+    deps: DepsMut,
+    env: Env,
+    user_address: Addr,
+    lockups: Vec<(String, u64)>,
+    withdraw_matured_lp: bool,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut user_info = USER_INFO
+        .may_load(deps.storage, &user_address)?
+        .unwrap_or_default();
+
+    let mut rewards: Vec<Asset> = vec![];
+    let mut cosmos_msgs = vec![];
+    let mut attributes = vec![
+        attr("action", "claim_all"),
+        attr("user_address", &user_address),
+    ];
+
+    let mut pools: Vec<(String, Vec<u64>)> = vec![];
+    for (terraswap_lp_token, duration) in lockups {
+        match pools.last_mut() {
+            Some((pool, durations)) if *pool == terraswap_lp_token => durations.push(duration),
+            _ => pools.push((terraswap_lp_token, vec![duration])),
+        }
+    }
+
+    let mut lp_unlocked = false;
+    for (terraswap_lp_token, durations) in pools {
+        let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+        let astroport_lp_token = pool_info
+            .migration_info
+            .as_ref()
+            .map(|migration_info| migration_info.astroport_lp_token.clone())
+            .ok_or_else(|| StdError::generic_err("Pool should be migrated!"))?;
+        let generator = config
+            .generator
+            .as_ref()
+            .ok_or_else(|| StdError::generic_err("Generator should be set"))?;
+
+        let base_reward_token = if pool_info.is_staked {
+            let rwi: RewardInfoResponse = deps.querier.query_wasm_smart(
+                generator,
+                &GenQueryMsg::RewardInfo {
+                    lp_token: astroport_lp_token.to_string(),
+                },
+            )?;
+            Some(rwi.base_reward_token)
+        } else {
+            None
+        };
+
+        // Astroport LP shares of all the lockups are calculated before the pool's LP units are reduced
+        let mut lp_units_unlocked = Uint128::zero();
+        let mut astroport_lp_unlocked = Uint128::zero();
+        for duration in durations {
+            let lockup_key = (
+                terraswap_lp_token.as_str(),
+                &user_address,
+                U64Key::new(duration),
+            );
+            let mut lockup_info = LOCKUP_INFO.compatible_load(
+                deps.as_ref(),
+                lockup_key.clone(),
+                &config.generator,
+            )?;
+
+            // Claim forced unlock penalties of other lockers accrued to the lockup position
+            let pending_penalty_reward = calc_pending_penalty_reward(&pool_info, &lockup_info)?;
+            if !pending_penalty_reward.is_zero() {
+                lockup_info.penalty_astro_debt += pending_penalty_reward;
+                add_reward(
+                    &mut rewards,
+                    Asset {
+                        info: AssetInfo::Token {
+                            contract_addr: config.astro_token.clone().ok_or_else(|| {
+                                StdError::generic_err("Astro token should be set!")
+                            })?,
+                        },
+                        amount: pending_penalty_reward,
+                    },
+                );
+            }
+
+            // Claim one time ASTRO rewards of the lockup which are owed to the position NFT owner
+            if let Some(astro_rewards_owed) =
+                DETACHED_ASTRO_REWARDS.may_load(deps.storage, lockup_key.clone())?
+            {
+                if !astro_rewards_owed.is_zero() {
+                    add_reward(
+                        &mut rewards,
+                        Asset {
+                            info: AssetInfo::Token {
+                                contract_addr: config.astro_token.clone().ok_or_else(|| {
+                                    StdError::generic_err("Astro token should be set!")
+                                })?,
+                            },
+                            amount: astro_rewards_owed,
+                        },
+                    );
+                    DETACHED_ASTRO_REWARDS.save(
+                        deps.storage,
+                        lockup_key.clone(),
+                        &Uint128::zero(),
+                    )?;
+                }
+            }
+
+            // Calculate Astro LP share for the lockup position
+            let astroport_lp_amount = calc_lockup_astroport_lp_amount(
+                deps.as_ref(),
+                &env,
+                generator,
+                &pool_info,
+                &astroport_lp_token,
+                &lockup_info,
+            )?;
+
+            // Calculate claimable Astro and proxy staking rewards for this lockup
+            if let Some(base_reward_token) = &base_reward_token {
+                let (pending_astro_rewards, pending_proxy_rewards) = update_lockup_generator_debts(
+                    &pool_info,
+                    &mut lockup_info,
+                    astroport_lp_amount,
+                )?;
+                add_reward(
+                    &mut rewards,
+                    Asset {
+                        info: AssetInfo::Token {
+                            contract_addr: base_reward_token.clone(),
+                        },
+                        amount: pending_astro_rewards,
+                    },
+                );
+                for pending_proxy_reward in pending_proxy_rewards {
+                    add_reward(&mut rewards, pending_proxy_reward);
+                }
+            }
+
+            if withdraw_matured_lp && env.block.time.seconds() >= lockup_info.unlock_timestamp {
+                lp_units_unlocked += lockup_info.lp_units_locked;
+                astroport_lp_unlocked += astroport_lp_amount;
+                lockup_info.astroport_lp_transferred = Some(
+                    lockup_info
+                        .astroport_lp_transferred
+                        .unwrap_or_default()
+                        .checked_add(astroport_lp_amount)?,
+                );
+                lockup_info.partially_unlocked = false;
+                // COSMOSMSG :: Burn the lockup position NFT as the lockup is closed
+                if let Some(position_nft) = &config.position_nft {
+                    cosmos_msgs.push(build_burn_position_nft_msg(
+                        position_nft,
+                        lockup_position_token_id(&terraswap_lp_token, &user_address, duration),
+                    )?);
+                }
+                attributes.push(attr(
+                    "unlocked_lockup",
+                    lockup_position_token_id(&terraswap_lp_token, &user_address, duration),
+                ));
+            }
+
+            LOCKUP_INFO.save(deps.storage, lockup_key, &lockup_info)?;
+        }
+
+        if !lp_units_unlocked.is_zero() {
+            lp_unlocked = true;
+
+            //  COSMOSMSG :: If LP Tokens are staked, we unstake the amount which needs to be returned to the user
+            if pool_info.is_staked {
+                cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: generator.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&GenExecuteMsg::Withdraw {
+                        lp_token: astroport_lp_token.to_string(),
+                        amount: astroport_lp_unlocked,
+                    })?,
+                }));
+            }
+
+            // COSMOSMSG :: Returns LP units of all the unlocked lockup positions of the pool
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: astroport_lp_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: user_address.to_string(),
+                    amount: astroport_lp_unlocked,
+                })?,
+                funds: vec![],
+            }));
+
+            pool_info.terraswap_amount_in_lockups = pool_info
+                .terraswap_amount_in_lockups
+                .checked_sub(lp_units_unlocked)?;
+            ASSET_POOLS.save(deps.storage, &terraswap_lp_token, &pool_info)?;
+
+            attributes.push(attr("astroport_lp_unlocked", astroport_lp_unlocked));
+        }
+    }
+
+    // Adds claimable one time ASTRO rewards that the user gets for all his lock
+    let astro_was_transferred = user_info.astro_transferred;
+    if let Some(astro_token) = &config.astro_token {
+        if !user_info.astro_transferred {
+            // Calculating how much Astro user can claim (from total one time reward)
+            let total_claimable_astro_rewards = user_info.unclaimed_astro_rewards()?;
+            add_reward(
+                &mut rewards,
+                Asset {
+                    info: AssetInfo::Token {
+                        contract_addr: astro_token.clone(),
+                    },
+                    amount: total_claimable_astro_rewards,
+                },
+            );
+            user_info.astro_transferred = true;
+            attributes.push(attr(
+                "total_claimable_astro_reward",
+                total_claimable_astro_rewards,
+            ));
+            USER_INFO.save(deps.storage, &user_address, &user_info)?;
+        }
+    }
+
+    // If this is a void transaction (no state change), then return error.
+    if rewards.is_empty() && !lp_unlocked && astro_was_transferred {
+        return Err(StdError::generic_err("No rewards available to claim!"));
+    }
+
+    // COSMOSMSG :: Transfer the aggregated rewards
+    for reward in rewards {
+        attributes.push(attr("claimed_reward", reward.to_string()));
+        cosmos_msgs.push(reward.into_msg(&deps.querier, user_address.clone())?);
+    }
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(attributes))
+}
+
+/// Deposits Liquidity in Astroport after its withdrawn from the source DEX. Returns a default object of type [`Response`].
 /// ## Params
 /// * **deps** is an object of type [`DepsMut`].
 ///
 /// * **env** is an object of type [`Env`].
 ///
-/// * **terraswap_lp_token** is an object of type [`Addr`]. Pool identifier to identify the LP pool.
+/// * **terraswap_lp_token** is an object of type [`String`]. Pool identifier to identify the LP pool.
 ///
 /// * **astroport_pool** is an object of type [`Addr`]. Astroport Pool details to which the liquidity is to be migrated.
 ///
-/// * **prev_assets** is a array of type [`terraswap::asset::Asset`]. Balances of terraswap pool assets before liquidity was withdrawn.
+/// * **prev_assets** is a array of type [`Asset`]. Balances of the source pool assets before liquidity was withdrawn.
 ///
 /// * **slippage_tolerance** is an optional object of type [`Decimal`].
 pub fn callback_deposit_liquidity_in_astroport(
     deps: DepsMut,
     env: Env,
-    terraswap_lp_token: Addr,
+    terraswap_lp_token: String,
     astroport_pool: Addr,
-    prev_assets: [terraswap::asset::Asset; 2],
+    prev_assets: [Asset; 2],
     slippage_tolerance: Option<Decimal>,
 ) -> StdResult<Response> {
     let mut cosmos_msgs = vec![];
@@ -1948,17 +3715,15 @@ pub fn callback_deposit_liquidity_in_astroport(
 
     for prev_asset in prev_assets.iter() {
         match prev_asset.info.clone() {
-            terraswap::asset::AssetInfo::NativeToken { denom } => {
-                let mut new_asset = astroport::asset::Asset {
-                    info: astroport::asset::AssetInfo::NativeToken {
+            AssetInfo::NativeToken { denom } => {
+                let mut new_asset = Asset {
+                    info: AssetInfo::NativeToken {
                         denom: denom.clone(),
                     },
-                    amount: terraswap::querier::query_balance(
-                        &deps.querier,
-                        env.contract.address.clone(),
-                        denom.clone(),
-                    )?
-                    .checked_sub(prev_asset.amount)?,
+                    amount: prev_asset
+                        .info
+                        .query_pool(&deps.querier, env.contract.address.clone())?
+                        .checked_sub(prev_asset.amount)?,
                 };
 
                 new_asset.amount -= new_asset.compute_tax(&deps.querier)?;
@@ -1969,13 +3734,11 @@ pub fn callback_deposit_liquidity_in_astroport(
                 });
                 assets.push(new_asset);
             }
-            terraswap::asset::AssetInfo::Token { contract_addr } => {
-                let amount = terraswap::querier::query_token_balance(
-                    &deps.querier,
-                    addr_validate_to_lower(deps.api, &contract_addr)?,
-                    env.contract.address.clone(),
-                )?
-                .checked_sub(prev_asset.amount)?;
+            AssetInfo::Token { contract_addr } => {
+                let amount = prev_asset
+                    .info
+                    .query_pool(&deps.querier, env.contract.address.clone())?
+                    .checked_sub(prev_asset.amount)?;
 
                 cosmos_msgs.push(
                     WasmMsg::Execute {
@@ -1990,10 +3753,8 @@ pub fn callback_deposit_liquidity_in_astroport(
                     .into(),
                 );
 
-                assets.push(astroport::asset::Asset {
-                    info: astroport::asset::AssetInfo::Token {
-                        contract_addr: addr_validate_to_lower(deps.api, &contract_addr)?,
-                    },
+                assets.push(Asset {
+                    info: AssetInfo::Token { contract_addr },
                     amount,
                 });
             }
@@ -2029,9 +3790,9 @@ pub fn callback_deposit_liquidity_in_astroport(
 ///
 /// * **env** is an object of type [`Env`].
 ///
-/// * **previous_balance** is an object of type [`Uint128`].
+/// * **previous_balances** is a vector of type [`Asset`]. Balances of the reward assets before the rewards were claimed.
 ///
-/// * **terraswap_lp_token** is an object of type [`Addr`].
+/// * **terraswap_lp_token** is an object of type [`String`].
 ///
 /// * **user_address** is an object of type [`Addr`].
 ///
@@ -2041,94 +3802,273 @@ pub fn callback_deposit_liquidity_in_astroport(
 fn callback_distribute_asset_reward(
     deps: DepsMut,
     env: Env,
-    previous_balance: Uint128,
-    terraswap_lp_token: Addr,
+    previous_balances: Vec<Asset>,
+    terraswap_lp_token: String,
     user_address: Addr,
     recipient: Addr,
     lock_duration: u64,
 ) -> StdResult<Response> {
-    let reward_balance =
-        astroport::querier::query_balance(&deps.querier, env.contract.address, "uusd".to_string())?;
-    let latest_reward_amount = reward_balance - previous_balance;
+    let pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+    let config = CONFIG.load(deps.storage)?;
 
-    let mut response = Response::new()
-        .add_attribute("lockdrop_claimed_reward", latest_reward_amount)
-        .add_attribute("user", user_address.clone());
+    let lockup_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(lock_duration),
+    );
+    // get only lockups that have not yet been withdrawn
+    let lockup_info_opt = LOCKUP_INFO
+        .compatible_may_load(deps.as_ref(), lockup_key.clone(), &config.generator)?
+        .filter(|lock_info| !lock_info.is_unlocked());
+
+    // Pools without a configured source keep the attributes of the uusd rewards of the bLUNA stable pair
+    let is_legacy_source = pool_info.asset_rewards.is_none();
+    let mut claimed_attributes = vec![];
+    let mut sent_attributes = vec![];
+    let mut messages = vec![];
+    for previous_balance in previous_balances {
+        let reward_balance = previous_balance
+            .info
+            .query_pool(&deps.querier, env.contract.address.clone())?;
+        let latest_reward_amount = reward_balance.checked_sub(previous_balance.amount)?;
 
-    let pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
-    let total_asset_reward_path = TOTAL_ASSET_REWARD_INDEX.key(&terraswap_lp_token);
-    let total_asset_reward_index = match total_asset_reward_path.may_load(deps.storage)? {
-        Some(current_index) if !latest_reward_amount.is_zero() => {
-            let val = current_index
-                + Decimal256::from_ratio(
+        let reward_indexes = AssetRewardIndexes::new(&previous_balance.info);
+        let total_asset_reward_path = reward_indexes.total().key(&terraswap_lp_token);
+        let total_asset_reward_index = match total_asset_reward_path.may_load(deps.storage)? {
+            Some(current_index) if !latest_reward_amount.is_zero() => {
+                let val = current_index
+                    + Decimal256::from_ratio(
+                        Uint256::from(latest_reward_amount),
+                        pool_info.terraswap_amount_in_lockups,
+                    );
+                total_asset_reward_path.save(deps.storage, &val)?;
+                val
+            }
+            None => {
+                let val = Decimal256::from_ratio(
                     Uint256::from(latest_reward_amount),
                     pool_info.terraswap_amount_in_lockups,
                 );
-            total_asset_reward_path.save(deps.storage, &val)?;
-            val
-        }
-        None => {
-            let val = Decimal256::from_ratio(
-                Uint256::from(latest_reward_amount),
+                total_asset_reward_path.save(deps.storage, &val)?;
+                val
+            }
+            Some(current_index) => current_index,
+        };
+        claimed_attributes.push(attr(
+            "lockdrop_claimed_reward",
+            if is_legacy_source {
+                latest_reward_amount.to_string()
+            } else {
+                Asset {
+                    info: previous_balance.info.clone(),
+                    amount: latest_reward_amount,
+                }
+                .to_string()
+            },
+        ));
+
+        let mut user_reward = Uint128::zero();
+        if let Some(lockup_info) = &lockup_info_opt {
+            let user_index_lp_path = reward_indexes.users().key(lockup_key.clone());
+            user_reward = calc_user_reward(
+                deps.storage,
+                &user_index_lp_path,
+                lockup_info.lp_units_locked,
                 pool_info.terraswap_amount_in_lockups,
-            );
-            total_asset_reward_path.save(deps.storage, &val)?;
-            val
+                total_asset_reward_index,
+            )?;
+            user_index_lp_path.save(deps.storage, &total_asset_reward_index)?;
         }
-        Some(current_index) => current_index,
-    };
 
-    let config = CONFIG.load(deps.storage)?;
+        let user_reward = Asset {
+            info: previous_balance.info,
+            amount: user_reward,
+        };
+        sent_attributes.push(if is_legacy_source {
+            attr("sent_bluna_reward", user_reward.amount)
+        } else {
+            attr("sent_asset_reward", user_reward.to_string())
+        });
+        if !user_reward.amount.is_zero() {
+            messages.push(user_reward.into_msg(&deps.querier, recipient.clone())?);
+        }
+    }
+
+    Ok(Response::new()
+        .add_attributes(claimed_attributes)
+        .add_attribute("user", user_address)
+        .add_attributes(sent_attributes)
+        .add_messages(messages))
+}
 
+/// Moves the lockup position to the recipient after its pending rewards are claimed. Returns a default object of type [`Response`].
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **terraswap_lp_token** is an object of type [`String`]. Pool identifier to identify the LP pool.
+///
+/// * **user_address** is an object of type [`Addr`]. Current owner of the lockup position.
+///
+/// * **recipient** is an object of type [`Addr`]. New owner of the lockup position.
+///
+/// * **duration** is an object of type [`u64`]. Lockup duration (number of weeks).
+pub fn callback_transfer_lockup(
+    deps: DepsMut,
+    env: Env,
+    terraswap_lp_token: String,
+    user_address: Addr,
+    recipient: Addr,
+    duration: u64,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
     let lockup_key = (
-        &terraswap_lp_token,
+        terraswap_lp_token.as_str(),
         &user_address,
-        U64Key::new(lock_duration),
+        U64Key::new(duration),
     );
-    let mut user_reward = Uint128::zero();
-    // get only lockups that have not yet been withdrawn
-    let lockup_info_opt = LOCKUP_INFO
-        .compatible_may_load(deps.as_ref(), lockup_key.clone(), &config.generator)?
-        .filter(|lock_info| lock_info.astroport_lp_transferred.is_none());
-    if let Some(lockup_info) = lockup_info_opt {
-        let user_index_lp_path = USERS_ASSET_REWARD_INDEX.key(lockup_key);
-        user_reward = calc_user_reward(
-            deps.storage,
-            &user_index_lp_path,
-            lockup_info.lp_units_locked,
-            pool_info.terraswap_amount_in_lockups,
-            total_asset_reward_index,
-        )?;
-        user_index_lp_path.save(deps.storage, &total_asset_reward_index)?;
+    let mut lockup_info =
+        LOCKUP_INFO.compatible_load(deps.as_ref(), lockup_key.clone(), &config.generator)?;
 
-        if !user_reward.is_zero() {
-            response.messages.push(SubMsg::new(
-                Asset {
-                    info: AssetInfo::NativeToken {
-                        denom: "uusd".to_string(),
+    let mut cosmos_msgs = vec![];
+    let mut attributes = vec![
+        attr("action", "transfer_lockup"),
+        attr("terraswap_lp_token", &terraswap_lp_token),
+        attr("user_address", &user_address),
+        attr("recipient", &recipient),
+        attr("duration", duration.to_string()),
+    ];
+
+    if let Some(MigrationInfo {
+        astroport_lp_token, ..
+    }) = &pool_info.migration_info
+    {
+        // Claim forced unlock penalties of other lockers accrued to the lockup position
+        let pending_penalty_reward = calc_pending_penalty_reward(&pool_info, &lockup_info)?;
+        if !pending_penalty_reward.is_zero() {
+            lockup_info.penalty_astro_debt += pending_penalty_reward;
+            cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config
+                    .astro_token
+                    .as_ref()
+                    .ok_or_else(|| StdError::generic_err("Astro token should be set!"))?
+                    .to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: user_address.to_string(),
+                    amount: pending_penalty_reward,
+                })?,
+            }));
+            attributes.push(attr("penalty_astro_reward", pending_penalty_reward));
+        }
+
+        // Claim generator rewards of the lockup position, generator debts are moved together with the lockup
+        if pool_info.is_staked {
+            let generator = config
+                .generator
+                .as_ref()
+                .ok_or_else(|| StdError::generic_err("Generator should be set"))?;
+            let astroport_lp_amount = calc_lockup_astroport_lp_amount(
+                deps.as_ref(),
+                &env,
+                generator,
+                &pool_info,
+                astroport_lp_token,
+                &lockup_info,
+            )?;
+            let (pending_astro_rewards, pending_proxy_rewards) =
+                update_lockup_generator_debts(&pool_info, &mut lockup_info, astroport_lp_amount)?;
+
+            if !pending_astro_rewards.is_zero() {
+                let rwi: RewardInfoResponse = deps.querier.query_wasm_smart(
+                    generator,
+                    &GenQueryMsg::RewardInfo {
+                        lp_token: astroport_lp_token.to_string(),
                     },
-                    amount: user_reward,
-                }
-                .into_msg(&deps.querier, recipient)?,
-            ));
+                )?;
+                cosmos_msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: rwi.base_reward_token.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: user_address.to_string(),
+                        amount: pending_astro_rewards,
+                    })?,
+                }));
+            }
+            attributes.push(attr("generator_astro_reward", pending_astro_rewards));
+
+            for pending_proxy_reward in pending_proxy_rewards {
+                cosmos_msgs
+                    .push(pending_proxy_reward.into_msg(&deps.querier, user_address.clone())?);
+            }
         }
     }
 
-    Ok(response.add_attribute("sent_bluna_reward", user_reward))
+    let recipient_lockup_key = (
+        terraswap_lp_token.as_str(),
+        &recipient,
+        U64Key::new(duration),
+    );
+
+    // Move the asset reward indexes together with the lockup position. Reward assets with distributed
+    // rewards can't be removed from the pool, so the current assets cover every index of the position
+    let asset_rewards = pool_asset_rewards(&pool_info.asset_rewards, &env.contract.address)?;
+    for reward_asset in &asset_rewards.reward_assets {
+        let reward_indexes = AssetRewardIndexes::new(reward_asset);
+        let users_asset_reward_index = reward_indexes.users();
+        if let Some(user_asset_reward_index) =
+            users_asset_reward_index.may_load(deps.storage, lockup_key.clone())?
+        {
+            users_asset_reward_index.remove(deps.storage, lockup_key.clone());
+            users_asset_reward_index.save(
+                deps.storage,
+                recipient_lockup_key.clone(),
+                &user_asset_reward_index,
+            )?;
+        }
+    }
+
+    LOCKUP_INFO.remove(deps.storage, lockup_key);
+    LOCKUP_INFO.save(deps.storage, recipient_lockup_key, &lockup_info)?;
+
+    // COSMOS_MSG :: Reissue the lockup position NFT to the recipient
+    if let Some(position_nft) = &config.position_nft {
+        cosmos_msgs.push(build_burn_position_nft_msg(
+            position_nft,
+            lockup_position_token_id(&terraswap_lp_token, &user_address, duration),
+        )?);
+        cosmos_msgs.push(build_mint_position_nft_msg(
+            position_nft,
+            lockup_position_token_id(&terraswap_lp_token, &recipient, duration),
+            &recipient,
+        )?);
+    }
+
+    Ok(Response::new()
+        .add_messages(cosmos_msgs)
+        .add_attributes(attributes))
 }
 
 /// Returns the contract's State.
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
 pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
+    let config = CONFIG.load(deps.storage)?;
     let state: State = STATE.load(deps.storage)?;
     Ok(StateResponse {
         total_incentives_share: state.total_incentives_share,
         total_astro_delegated: state.total_astro_delegated,
         are_claims_allowed: state.are_claims_allowed,
         supported_pairs_list: ASSET_POOLS
-            .keys(deps.storage, None, None, Order::Ascending)
-            .collect::<Result<Vec<Addr>, StdError>>()?,
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (pool, pool_info) = item?;
+                Ok(pool_info.lp_token_info(&pool).to_string())
+            })
+            .collect::<StdResult<Vec<String>>>()?,
+        windows_end: config.windows_end(&state),
     })
 }
 
@@ -2138,7 +4078,7 @@ pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
 ///
 /// * **terraswap_lp_token** is an object of type [`String`].
 pub fn query_pool(deps: Deps, terraswap_lp_token: String) -> StdResult<PoolInfo> {
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
+    let terraswap_lp_token = validate_lp_token(deps, &terraswap_lp_token)?;
     let pool_info: PoolInfo = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
     Ok(pool_info)
 }
@@ -2162,10 +4102,10 @@ pub fn query_user_info(deps: Deps, env: Env, user: String) -> StdResult<UserInfo
     let mut claimable_generator_astro_debt = Uint128::zero();
     for pool in ASSET_POOLS
         .keys(deps.storage, None, None, Order::Ascending)
-        .collect::<Result<Vec<Addr>, StdError>>()?
+        .collect::<Result<Vec<String>, StdError>>()?
     {
         for duration in LOCKUP_INFO
-            .prefix((&pool, &user_address))
+            .prefix((pool.as_str(), &user_address))
             .keys(deps.storage, None, None, Order::Ascending)
             .collect::<Result<Vec<u64>, StdError>>()?
         {
@@ -2183,6 +4123,8 @@ pub fn query_user_info(deps: Deps, env: Env, user: String) -> StdResult<UserInfo
         lockup_infos,
         claimable_generator_astro_debt,
         lockup_positions_index: user_info.lockup_positions_index,
+        astro_penalty_paid: user_info.astro_penalty_paid,
+        detached_astro_rewards: user_info.detached_astro_rewards,
     })
 }
 
@@ -2207,15 +4149,19 @@ pub fn query_user_info_with_lockups_list(
 
     for pool in ASSET_POOLS
         .keys(deps.storage, None, None, Order::Ascending)
-        .collect::<Result<Vec<Addr>, StdError>>()?
+        .collect::<Result<Vec<String>, StdError>>()?
     {
+        let lp_token = ASSET_POOLS
+            .load(deps.storage, &pool)?
+            .lp_token_info(&pool)
+            .to_string();
         for duration in LOCKUP_INFO
-            .prefix((&pool, &user_address))
+            .prefix((pool.as_str(), &user_address))
             .keys(deps.storage, None, None, Order::Ascending)
             .collect::<Result<Vec<u64>, StdError>>()?
         {
             lockup_infos.push(LockUpInfoSummary {
-                pool_address: pool.to_string(),
+                pool_address: lp_token.clone(),
                 duration,
             });
         }
@@ -2230,6 +4176,73 @@ pub fn query_user_info_with_lockups_list(
     })
 }
 
+/// Returns metadata of the lockup position NFT which is the lockup position info.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **token_id** is an object of type [`String`]. Position NFT ID in the `terraswap_lp_token:user_address:duration` format,
+/// where `terraswap_lp_token` is the pool key which may contain `:` itself.
+pub fn query_position_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+) -> StdResult<NftInfoResponse<LockUpInfoResponse>> {
+    let invalid_token_id = || StdError::generic_err(format!("Invalid token ID: {}", token_id));
+
+    let mut parts = token_id.rsplitn(3, ':');
+    let duration = parts
+        .next()
+        .and_then(|duration| duration.parse::<u64>().ok())
+        .ok_or_else(invalid_token_id)?;
+    let user_address = parts.next().ok_or_else(invalid_token_id)?;
+    let terraswap_lp_token = parts.next().ok_or_else(invalid_token_id)?;
+
+    Ok(NftInfoResponse {
+        token_uri: None,
+        extension: query_lockup_info(
+            deps,
+            &env,
+            user_address,
+            terraswap_lp_token.to_string(),
+            duration,
+        )?,
+    })
+}
+
+/// Returns the weight of a lockup position of `amount` LP units locked for `duration` weeks.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **amount** is an object of type [`Uint128`]. Number of LP units.
+///
+/// * **duration** is an object of type [`u64`]. Number of weeks.
+pub fn query_simulate_weight(
+    deps: Deps,
+    amount: Uint128,
+    duration: u64,
+) -> StdResult<SimulateWeightResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // CHECK :: Lockup duration should be within the allowed range
+    if duration > config.max_lock_duration || duration < config.min_lock_duration {
+        return Err(StdError::generic_err(format!(
+            "Lockup duration needs to be between {} and {}",
+            config.min_lock_duration, config.max_lock_duration
+        )));
+    }
+
+    Ok(SimulateWeightResponse {
+        boost: config.weight_curve.boost(
+            duration,
+            config.weekly_multiplier,
+            config.weekly_divider,
+        )?,
+        weight: calculate_weight(amount, duration, &config)?,
+    })
+}
+
 /// Returns summarized details regarding the user
 /// ## Params
 /// * **deps** is an object of type [`Deps`].
@@ -2251,20 +4264,26 @@ pub fn query_lockup_info(
     let config = CONFIG.load(deps.storage)?;
     let state = STATE.load(deps.storage)?;
 
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
+    let terraswap_lp_token = validate_lp_token(deps, &terraswap_lp_token)?;
     let user_address = addr_validate_to_lower(deps.api, user_address)?;
 
-    let lockup_key = (&terraswap_lp_token, &user_address, U64Key::new(duration));
+    let lockup_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(duration),
+    );
     let mut pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
+    let lp_token = pool_info.lp_token_info(&terraswap_lp_token);
     let mut lockup_info = LOCKUP_INFO.compatible_load(deps, lockup_key, &config.generator)?;
+    let claimable_penalty_astro_reward = calc_pending_penalty_reward(&pool_info, &lockup_info)?;
 
     let mut lockup_astroport_lp_units_opt: Option<Uint128> = None;
     let mut astroport_lp_token_opt: Option<Addr> = None;
     let mut claimable_generator_astro_debt = Uint128::zero();
     let mut claimable_generator_proxy_debt: RestrictedVector<AssetInfo, Uint128> =
         RestrictedVector::default();
-    if let Some(astroport_lp_transferred) = lockup_info.astroport_lp_transferred {
-        lockup_astroport_lp_units_opt = Some(astroport_lp_transferred);
+    if lockup_info.is_unlocked() {
+        lockup_astroport_lp_units_opt = lockup_info.astroport_lp_transferred;
         astroport_lp_token_opt = pool_info.migration_info.map(|v| v.astroport_lp_token);
     } else if let Some(MigrationInfo {
         astroport_lp_token, ..
@@ -2362,7 +4381,7 @@ pub fn query_lockup_info(
     }
 
     Ok(LockUpInfoResponse {
-        terraswap_lp_token,
+        terraswap_lp_token: lp_token.to_string(),
         lp_units_locked: lockup_info.lp_units_locked,
         withdrawal_flag: lockup_info.withdrawal_flag,
         astro_rewards: lockup_info.astro_rewards,
@@ -2370,6 +4389,7 @@ pub fn query_lockup_info(
         claimable_generator_astro_debt,
         generator_proxy_debt: lockup_info.generator_proxy_debt,
         claimable_generator_proxy_debt,
+        claimable_penalty_astro_reward,
         unlock_timestamp: lockup_info.unlock_timestamp,
         astroport_lp_units: lockup_astroport_lp_units_opt,
         astroport_lp_token: astroport_lp_token_opt,
@@ -2399,15 +4419,19 @@ pub fn query_pending_asset_reward(
     let config = CONFIG.load(deps.storage)?;
 
     let user_address = addr_validate_to_lower(deps.api, &user_address)?;
-    let terraswap_lp_token = addr_validate_to_lower(deps.api, &terraswap_lp_token)?;
+    let terraswap_lp_token = validate_lp_token(deps, &terraswap_lp_token)?;
 
-    let lockup_key = (&terraswap_lp_token, &user_address, U64Key::new(duration));
+    let lockup_key = (
+        terraswap_lp_token.as_str(),
+        &user_address,
+        U64Key::new(duration),
+    );
 
     let lockup_info_opt = LOCKUP_INFO
         .compatible_may_load(deps, lockup_key.clone(), &config.generator)?
-        .filter(|lock_info| lock_info.astroport_lp_transferred.is_none());
+        .filter(|lock_info| !lock_info.is_unlocked());
 
-    let mut user_reward = Uint128::zero();
+    let mut rewards = vec![];
     if let Some(lockup_info) = lockup_info_opt {
         let pool_info = ASSET_POOLS.load(deps.storage, &terraswap_lp_token)?;
         if !pool_info.has_asset_rewards {
@@ -2421,37 +4445,88 @@ pub fn query_pending_asset_reward(
             .as_ref()
             .ok_or_else(|| StdError::generic_err("The pool was not migrated to astroport"))?;
         let pair_info = pair_info_by_pool(&deps.querier, astroport_lp_token.clone())?;
-        let pending_rewards: Asset = deps.querier.query_wasm_smart(
-            pair_info.contract_addr,
-            &astroport::pair_stable_bluna::QueryMsg::PendingReward {
-                user: env.contract.address.to_string(),
+        let asset_rewards = pool_asset_rewards(&pool_info.asset_rewards, &env.contract.address)?;
+        let pending_rewards = match asset_rewards.pending_rewards_query {
+            Some(msg) => match deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: pair_info.contract_addr.to_string(),
+                msg,
+            }))? {
+                PendingRewardsResponse::Single(asset) => vec![asset],
+                PendingRewardsResponse::Multiple(assets) => assets,
             },
-        )?;
+            None => vec![],
+        };
 
-        let reward_index = Decimal256::from_ratio(
-            Uint256::from(pending_rewards.amount),
-            pool_info.terraswap_amount_in_lockups,
-        );
+        for reward_asset in asset_rewards.reward_assets {
+            let pending_reward = pending_rewards
+                .iter()
+                .find(|asset| asset.info == reward_asset)
+                .map_or_else(Uint128::zero, |asset| asset.amount);
+            let reward_index = Decimal256::from_ratio(
+                Uint256::from(pending_reward),
+                pool_info.terraswap_amount_in_lockups,
+            );
 
-        let user_asset_reward_path = USERS_ASSET_REWARD_INDEX.key(lockup_key);
-        let total_asset_reward_path = TOTAL_ASSET_REWARD_INDEX.key(&terraswap_lp_token);
-        let total_asset_reward_index = match total_asset_reward_path.may_load(deps.storage)? {
-            Some(current_index) => reward_index + current_index,
-            None => reward_index,
-        };
+            let reward_indexes = AssetRewardIndexes::new(&reward_asset);
+            let user_asset_reward_path = reward_indexes.users().key(lockup_key.clone());
+            let total_asset_reward_index = match reward_indexes
+                .total()
+                .may_load(deps.storage, &terraswap_lp_token)?
+            {
+                Some(current_index) => reward_index + current_index,
+                None => reward_index,
+            };
 
-        user_reward = calc_user_reward(
-            deps.storage,
-            &user_asset_reward_path,
-            lockup_info.lp_units_locked,
-            pool_info.terraswap_amount_in_lockups,
-            total_asset_reward_index,
-        )?;
+            let user_reward = calc_user_reward(
+                deps.storage,
+                &user_asset_reward_path,
+                lockup_info.lp_units_locked,
+                pool_info.terraswap_amount_in_lockups,
+                total_asset_reward_index,
+            )?;
+            rewards.push(Asset {
+                info: reward_asset,
+                amount: user_reward,
+            });
+        }
     }
 
-    Ok(PendingAssetRewardResponse {
-        amount: user_reward,
-    })
+    Ok(PendingAssetRewardResponse { rewards })
+}
+
+/// Pending rewards of an Astroport pair, some pairs respond with a single reward asset
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PendingRewardsResponse {
+    Single(Asset),
+    Multiple(Vec<Asset>),
+}
+
+/// Returns the source of the pool's asset rewards. Pools without one receive uusd rewards of the bLUNA stable pair.
+/// ## Params
+/// * **asset_rewards** is an [`Option`] of type [`AssetRewardsConfig`]. Source of the pool's asset rewards.
+///
+/// * **contract_address** is an object of type [`Addr`]. Address of the lockdrop contract.
+fn pool_asset_rewards(
+    asset_rewards: &Option<AssetRewardsConfig>,
+    contract_address: &Addr,
+) -> StdResult<AssetRewardsConfig> {
+    match asset_rewards {
+        Some(asset_rewards) => Ok(asset_rewards.clone()),
+        None => Ok(AssetRewardsConfig {
+            claim_msg: to_binary(&astroport::pair_stable_bluna::ExecuteMsg::ClaimReward {
+                receiver: None,
+            })?,
+            pending_rewards_query: Some(to_binary(
+                &astroport::pair_stable_bluna::QueryMsg::PendingReward {
+                    user: contract_address.to_string(),
+                },
+            )?),
+            reward_assets: vec![AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            }],
+        }),
+    }
 }
 
 /// Calculates maximum % of LP balances deposited that can be withdrawn
@@ -2525,14 +4600,180 @@ pub fn calculate_astro_incentives_for_lockup(
 ///
 /// * **duration** is an object of type [`u64`]. Number of weeks.
 ///
-/// * **config** is an object of type [`Config`]. Config with weekly multiplier and divider.
-fn calculate_weight(amount: Uint128, duration: u64, config: &Config) -> StdResult<Uint256> {
-    let lock_weight = Decimal256::one()
-        + Decimal256::from_ratio(
-            (duration - 1) * config.weekly_multiplier,
-            config.weekly_divider,
-        );
-    Ok(lock_weight.checked_mul_uint256(amount.into())?.into())
+/// * **config** is an object of type [`Config`]. Config with the lockup weight curve.
+fn calculate_weight(amount: Uint128, duration: u64, config: &Config) -> StdResult<Uint256> {
+    let lock_weight =
+        config
+            .weight_curve
+            .boost(duration, config.weekly_multiplier, config.weekly_divider)?;
+    Ok(lock_weight.checked_mul_uint256(amount.into())?.into())
+}
+
+/// Returns Astroport LP tokens of the lockup position, calculated as its share of the pool's Astroport LP tokens.
+/// ## Params
+/// * **deps** is an object of type [`Deps`].
+///
+/// * **env** is an object of type [`Env`].
+///
+/// * **generator** is an object of type [`Addr`].
+///
+/// * **pool_info** is an object of type [`PoolInfo`].
+///
+/// * **astroport_lp_token** is an object of type [`Addr`].
+///
+/// * **lockup_info** is an object of type [`LockupInfoV2`].
+fn calc_lockup_astroport_lp_amount(
+    deps: Deps,
+    env: &Env,
+    generator: &Addr,
+    pool_info: &PoolInfo,
+    astroport_lp_token: &Addr,
+    lockup_info: &LockupInfoV2,
+) -> StdResult<Uint128> {
+    let balance: Uint128 = if pool_info.is_staked {
+        deps.querier.query_wasm_smart(
+            generator,
+            &GenQueryMsg::Deposit {
+                lp_token: astroport_lp_token.to_string(),
+                user: env.contract.address.to_string(),
+            },
+        )?
+    } else {
+        let res: BalanceResponse = deps.querier.query_wasm_smart(
+            astroport_lp_token,
+            &Cw20QueryMsg::Balance {
+                address: env.contract.address.to_string(),
+            },
+        )?;
+        res.balance
+    };
+
+    Ok((lockup_info
+        .lp_units_locked
+        .full_mul(balance)
+        .checked_div(Uint256::from(pool_info.terraswap_amount_in_lockups))?)
+    .try_into()?)
+}
+
+/// Updates generator debts of the lockup position to the pool's reward indexes.
+/// Returns pending generator ASTRO and proxy rewards of the lockup position.
+/// ## Params
+/// * **pool_info** is an object of type [`PoolInfo`].
+///
+/// * **lockup_info** is an object of type [`LockupInfoV2`].
+///
+/// * **astroport_lp_amount** is an object of type [`Uint128`]. Astroport LP tokens of the lockup position.
+fn update_lockup_generator_debts(
+    pool_info: &PoolInfo,
+    lockup_info: &mut LockupInfoV2,
+    astroport_lp_amount: Uint128,
+) -> StdResult<(Uint128, Vec<Asset>)> {
+    let total_lockup_astro_rewards = pool_info.generator_astro_per_share * astroport_lp_amount;
+    let pending_astro_rewards =
+        total_lockup_astro_rewards.checked_sub(lockup_info.generator_astro_debt)?;
+    lockup_info.generator_astro_debt = total_lockup_astro_rewards;
+
+    let mut pending_proxy_rewards: Vec<Asset> = vec![];
+    // If this LP token is getting dual incentives
+    // Calculate claimable proxy staking rewards for this lockup
+    lockup_info.generator_proxy_debt = lockup_info
+        .generator_proxy_debt
+        .inner_ref()
+        .iter()
+        .map(|(asset, debt)| {
+            let generator_proxy_per_share = pool_info
+                .generator_proxy_per_share
+                .load(asset)
+                .unwrap_or_default();
+            let total_lockup_proxy_reward =
+                generator_proxy_per_share.checked_mul_uint128(astroport_lp_amount)?;
+            let pending_proxy_reward: Uint128 = total_lockup_proxy_reward.checked_sub(*debt)?;
+
+            if !pending_proxy_reward.is_zero() {
+                pending_proxy_rewards.push(Asset {
+                    info: asset.clone(),
+                    amount: pending_proxy_reward,
+                });
+            }
+            Ok((asset.clone(), total_lockup_proxy_reward))
+        })
+        .collect::<StdResult<Vec<_>>>()?
+        .into();
+
+    Ok((pending_astro_rewards, pending_proxy_rewards))
+}
+
+/// Sets the reward debts of the lockup position to the rewards accrued to its remaining LP tokens.
+/// Pending rewards of the lockup position should be paid before.
+/// ## Params
+/// * **pool_info** is an object of type [`PoolInfo`].
+///
+/// * **lockup_info** is an object of type [`LockupInfoV2`].
+///
+/// * **astroport_lp_amount** is an object of type [`Uint128`]. Astroport LP tokens remaining in the lockup position.
+fn reset_lockup_debts(
+    pool_info: &PoolInfo,
+    lockup_info: &mut LockupInfoV2,
+    astroport_lp_amount: Uint128,
+) -> StdResult<()> {
+    lockup_info.generator_astro_debt = pool_info.generator_astro_per_share * astroport_lp_amount;
+    lockup_info.generator_proxy_debt = lockup_info
+        .generator_proxy_debt
+        .inner_ref()
+        .iter()
+        .map(|(asset, _)| {
+            let generator_proxy_per_share = pool_info
+                .generator_proxy_per_share
+                .load(asset)
+                .unwrap_or_default();
+            Ok((
+                asset.clone(),
+                generator_proxy_per_share.checked_mul_uint128(astroport_lp_amount)?,
+            ))
+        })
+        .collect::<StdResult<Vec<_>>>()?
+        .into();
+    lockup_info.penalty_astro_debt = pool_info
+        .penalty_astro_per_share
+        .checked_mul_uint128(lockup_info.lp_units_locked)?;
+
+    Ok(())
+}
+
+/// Adds the reward to the list of rewards, the amounts of the same asset are summed up. Zero rewards are skipped.
+/// ## Params
+/// * **rewards** is a vector of type [`Asset`].
+///
+/// * **reward** is an object of type [`Asset`].
+fn add_reward(rewards: &mut Vec<Asset>, reward: Asset) {
+    if reward.amount.is_zero() {
+        return;
+    }
+
+    match rewards.iter_mut().find(|asset| asset.info == reward.info) {
+        Some(asset) => asset.amount += reward.amount,
+        None => rewards.push(reward),
+    }
+}
+
+/// Returns forced unlock penalties of other lockers which the lockup position can claim.
+/// ## Params
+/// * **pool_info** is an object of type [`PoolInfo`].
+///
+/// * **lockup_info** is an object of type [`LockupInfoV2`].
+fn calc_pending_penalty_reward(
+    pool_info: &PoolInfo,
+    lockup_info: &LockupInfoV2,
+) -> StdResult<Uint128> {
+    // Unlocked positions don't receive penalties anymore
+    if lockup_info.is_unlocked() {
+        return Ok(Uint128::zero());
+    }
+
+    Ok(pool_info
+        .penalty_astro_per_share
+        .checked_mul_uint128(lockup_info.lp_units_locked)?
+        .checked_sub(lockup_info.penalty_astro_debt)?)
 }
 
 /// Calculates bLuna user reward according to his share in LP.
@@ -2585,14 +4826,14 @@ fn update_user_lockup_positions_and_calc_rewards(
 ) -> StdResult<Uint128> {
     let mut total_astro_rewards = Uint128::zero();
 
-    let mut keys: Vec<(Addr, u64)> = vec![];
+    let mut keys: Vec<(String, u64)> = vec![];
 
     for pool_key in ASSET_POOLS
         .keys(deps.storage, None, None, Order::Ascending)
-        .collect::<Result<Vec<Addr>, StdError>>()?
+        .collect::<Result<Vec<String>, StdError>>()?
     {
         for duration in LOCKUP_INFO
-            .prefix((&pool_key, user_address))
+            .prefix((pool_key.as_str(), user_address))
             .keys(deps.storage, None, None, Order::Ascending)
             .collect::<Result<Vec<u64>, StdError>>()?
         {
@@ -2601,7 +4842,7 @@ fn update_user_lockup_positions_and_calc_rewards(
     }
     for (pool, duration) in keys {
         let pool_info = ASSET_POOLS.load(deps.storage, &pool)?;
-        let lockup_key = (&pool, user_address, U64Key::new(duration));
+        let lockup_key = (pool.as_str(), user_address, U64Key::new(duration));
         let mut lockup_info =
             LOCKUP_INFO.compatible_load(deps.as_ref(), lockup_key.clone(), &config.generator)?;
 
@@ -2631,6 +4872,104 @@ fn update_user_lockup_positions_and_calc_rewards(
     Ok(total_astro_rewards)
 }
 
+/// Detaches ASTRO rewards of the lockup position from the rewards of its user, so they are paid to the position NFT owner.
+/// Returns the ASTRO rewards of the lockup position which are still owed to the NFT owner.
+/// ## Params
+/// * **storage** is an object of type [`Storage`].
+///
+/// * **user_info** is an object of type [`UserInfo`]. Info of the user who opened the lockup position.
+///
+/// * **lockup_key** is an object of type [`(&str, &Addr, U64Key)`]. Key of the lockup position.
+///
+/// * **astro_rewards** is an object of type [`Uint128`]. ASTRO rewards of the lockup position.
+fn detach_lockup_astro_rewards(
+    storage: &mut dyn Storage,
+    user_info: &mut UserInfo,
+    lockup_key: (&str, &Addr, U64Key),
+    astro_rewards: Uint128,
+) -> StdResult<Uint128> {
+    if let Some(astro_rewards_owed) =
+        DETACHED_ASTRO_REWARDS.may_load(storage, lockup_key.clone())?
+    {
+        return Ok(astro_rewards_owed);
+    }
+
+    // ASTRO rewards claimed by the user before the NFT was transferred aren't paid again
+    if user_info.astro_transferred {
+        return Ok(Uint128::zero());
+    }
+
+    // Delegated ASTRO rewards can't be paid to the NFT owner
+    let astro_rewards_owed = astro_rewards.min(user_info.unclaimed_astro_rewards()?);
+    user_info.detached_astro_rewards += astro_rewards_owed;
+    DETACHED_ASTRO_REWARDS.save(storage, lockup_key, &astro_rewards_owed)?;
+
+    Ok(astro_rewards_owed)
+}
+
+/// Detaches ASTRO rewards of the user's lockup positions whose NFTs are owned by other addresses, so the user
+/// can't claim or delegate them. ASTRO rewards of the user's lockup positions should be calculated before.
+/// ## Params
+/// * **deps** is an object of type [`DepsMut`].
+///
+/// * **config** is an object of type [`Config`].
+///
+/// * **user_address** is an object of type [`Addr`].
+///
+/// * **user_info** is an object of type [`UserInfo`].
+fn detach_transferred_positions_astro_rewards(
+    deps: DepsMut,
+    config: &Config,
+    user_address: &Addr,
+    user_info: &mut UserInfo,
+) -> StdResult<()> {
+    let position_nft = match &config.position_nft {
+        Some(position_nft) => position_nft,
+        None => return Ok(()),
+    };
+    if user_info.astro_transferred {
+        return Ok(());
+    }
+
+    for terraswap_lp_token in ASSET_POOLS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<String>, StdError>>()?
+    {
+        for duration in LOCKUP_INFO
+            .prefix((terraswap_lp_token.as_str(), user_address))
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<Result<Vec<u64>, StdError>>()?
+        {
+            let lockup_key = (
+                terraswap_lp_token.as_str(),
+                user_address,
+                U64Key::new(duration),
+            );
+            let lockup_info = LOCKUP_INFO.compatible_load(
+                deps.as_ref(),
+                lockup_key.clone(),
+                &config.generator,
+            )?;
+            // The NFT of the closed lockup position is burnt
+            if lockup_info.is_unlocked() {
+                continue;
+            }
+
+            let token_id = lockup_position_token_id(&terraswap_lp_token, user_address, duration);
+            if query_position_nft_owner(&deps.querier, position_nft, token_id)? != *user_address {
+                detach_lockup_astro_rewards(
+                    deps.storage,
+                    user_info,
+                    lockup_key,
+                    lockup_info.astro_rewards,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod unit_tests {
     use super::*;
@@ -2658,6 +4997,7 @@ mod unit_tests {
             weekly_multiplier: 1u64,
             weekly_divider: 12u64,
             max_positions_per_user: 14,
+            weight_curve: None,
         };
         instantiate(
             deps.as_mut(),
@@ -2669,7 +5009,7 @@ mod unit_tests {
 
         let user_addr = Addr::unchecked("user");
         let astroport_lp_token = Addr::unchecked("astro_lp_addr");
-        let terraswap_lp_addr = Addr::unchecked("tswp_lp_token");
+        let terraswap_lp_addr = "tswp_lp_token".to_string();
         let migration_info = MigrationInfo {
             terraswap_migrated_amount: Uint128::from(100_000000u128),
             astroport_lp_token: astroport_lp_token.clone(),
@@ -2684,6 +5024,11 @@ mod unit_tests {
             generator_proxy_per_share: RestrictedVector::default(),
             is_staked: false,
             has_asset_rewards: false,
+            source_dex: SourceDex::Terraswap,
+            native_lp_token: false,
+            penalty_astro_per_share: Decimal::zero(),
+            lockup_params: PoolLockupParams::default(),
+            asset_rewards: None,
         };
         ASSET_POOLS
             .save(deps.as_mut().storage, &terraswap_lp_addr, &pool_info)
@@ -2698,6 +5043,7 @@ mod unit_tests {
             user_addr.clone(),
             terraswap_lp_addr.to_string(),
             lock_duration,
+            None,
         )
         .unwrap_err();
         assert_eq!(
@@ -2708,9 +5054,11 @@ mod unit_tests {
         // enabling rewards
         handle_toggle_rewards(
             deps.as_mut(),
+            env.clone(),
             mock_info("owner", &[]),
             terraswap_lp_addr.to_string(),
             true,
+            None,
         )
         .unwrap();
 
@@ -2721,6 +5069,7 @@ mod unit_tests {
             user_addr.clone(),
             terraswap_lp_addr.to_string(),
             lock_duration,
+            None,
         )
         .unwrap();
 
@@ -2748,7 +5097,12 @@ mod unit_tests {
                 user_address: user_addr.clone(),
                 recipient: user_addr,
                 lock_duration: 10,
-                previous_balance: init_uusd_balance,
+                previous_balances: vec![Asset {
+                    info: AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: init_uusd_balance,
+                }],
             });
             assert_eq!(from_binary::<ExecuteMsg>(&msg).unwrap(), real_message);
         } else {
@@ -2761,20 +5115,29 @@ mod unit_tests {
         let mut deps = mock_dependencies(&[]);
         let terraswap_lp_token = Addr::unchecked("lp_token_addr");
         let total_lp_amount = Uint128::from(1000u128);
+        let reward_indexes = AssetRewardIndexes::new(&AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        });
         // user1 with 10% share
         let user1 = Addr::unchecked("user1");
         let user1_path =
-            USERS_ASSET_REWARD_INDEX.key((&terraswap_lp_token, &user1, U64Key::new(10)));
+            reward_indexes
+                .users()
+                .key((terraswap_lp_token.as_str(), &user1, U64Key::new(10)));
         let user1_lp_amount = Uint128::from(100u128);
         // user2 with 70% share
         let user2 = Addr::unchecked("user2");
         let user2_path =
-            USERS_ASSET_REWARD_INDEX.key((&terraswap_lp_token, &user2, U64Key::new(10)));
+            reward_indexes
+                .users()
+                .key((terraswap_lp_token.as_str(), &user2, U64Key::new(10)));
         let user2_lp_amount = Uint128::from(700u128);
         // user3 with 20% share
         let user3 = Addr::unchecked("user3");
         let user3_path =
-            USERS_ASSET_REWARD_INDEX.key((&terraswap_lp_token, &user3, U64Key::new(10)));
+            reward_indexes
+                .users()
+                .key((terraswap_lp_token.as_str(), &user3, U64Key::new(10)));
         let user3_lp_amount = Uint128::from(200u128);
         let mut total_reward_index = Decimal256::one();
 
@@ -2879,6 +5242,14 @@ mod unit_tests {
 
     #[test]
     fn check_distribute_asset_reward() {
+        let uusd_balances = |amount: Uint128| {
+            vec![Asset {
+                info: AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                amount,
+            }]
+        };
         let mut uusd_balance = Uint128::from(100u128);
         let mut deps = mock_dependencies(&[Coin {
             denom: "uusd".to_string(),
@@ -2897,6 +5268,7 @@ mod unit_tests {
             weekly_multiplier: 1u64,
             weekly_divider: 12u64,
             max_positions_per_user: 14,
+            weight_curve: None,
         };
         instantiate(
             deps.as_mut(),
@@ -2909,7 +5281,7 @@ mod unit_tests {
         let user_addr = Addr::unchecked("user");
         let lock_duration = 10;
         let astroport_lp_token = Addr::unchecked("astro_lp_addr");
-        let terraswap_lp_addr = Addr::unchecked("tswp_lp_token");
+        let terraswap_lp_addr = "tswp_lp_token".to_string();
         let migration_info = MigrationInfo {
             terraswap_migrated_amount: Uint128::from(100_000000u128),
             astroport_lp_token: astroport_lp_token.clone(),
@@ -2924,6 +5296,11 @@ mod unit_tests {
             generator_proxy_per_share: RestrictedVector::default(),
             is_staked: false,
             has_asset_rewards: true,
+            source_dex: SourceDex::Terraswap,
+            native_lp_token: false,
+            penalty_astro_per_share: Decimal::zero(),
+            lockup_params: PoolLockupParams::default(),
+            asset_rewards: None,
         };
         ASSET_POOLS
             .save(deps.as_mut().storage, &terraswap_lp_addr, &pool_info)
@@ -2937,8 +5314,14 @@ mod unit_tests {
             generator_astro_debt: Default::default(),
             generator_proxy_debt: Default::default(),
             unlock_timestamp: 0,
+            penalty_astro_debt: Uint128::zero(),
+            partially_unlocked: false,
         };
-        let lockup_key = (&terraswap_lp_addr, &user_addr, U64Key::new(lock_duration));
+        let lockup_key = (
+            terraswap_lp_addr.as_str(),
+            &user_addr,
+            U64Key::new(lock_duration),
+        );
         LOCKUP_INFO
             .save(deps.as_mut().storage, lockup_key, &lockup)
             .unwrap();
@@ -2947,7 +5330,7 @@ mod unit_tests {
         let resp = callback_distribute_asset_reward(
             deps.as_mut(),
             env.clone(),
-            uusd_balance,
+            uusd_balances(uusd_balance),
             terraswap_lp_addr.clone(),
             user_addr.clone(),
             user_addr.clone(),
@@ -2982,7 +5365,7 @@ mod unit_tests {
         let resp = callback_distribute_asset_reward(
             deps.as_mut(),
             env.clone(),
-            uusd_balance,
+            uusd_balances(uusd_balance),
             terraswap_lp_addr.clone(),
             user_addr.clone(),
             user_addr.clone(),
@@ -3027,7 +5410,7 @@ mod unit_tests {
         let resp = callback_distribute_asset_reward(
             deps.as_mut(),
             env.clone(),
-            uusd_balance,
+            uusd_balances(uusd_balance),
             terraswap_lp_addr.clone(),
             user_addr.clone(),
             user_addr.clone(),
@@ -3057,7 +5440,7 @@ mod unit_tests {
         let resp = callback_distribute_asset_reward(
             deps.as_mut(),
             env.clone(),
-            uusd_balance,
+            uusd_balances(uusd_balance),
             terraswap_lp_addr.clone(),
             user_addr.clone(),
             user_addr.clone(),
@@ -3073,4 +5456,356 @@ mod unit_tests {
             }
         );
     }
+
+    #[test]
+    fn check_distribute_multiple_asset_rewards() {
+        let native_balances = |luna: u128, krw: u128| {
+            vec![
+                Coin {
+                    denom: "uluna".to_string(),
+                    amount: Uint128::from(luna),
+                },
+                Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(krw),
+                },
+            ]
+        };
+        let mut deps = mock_dependencies(&native_balances(100, 100));
+        let owner = "owner";
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(0);
+        let lockdrop_instantiate_msg = InstantiateMsg {
+            owner: Some(owner.to_string()),
+            init_timestamp: 100_000,
+            deposit_window: 10_000_000,
+            withdrawal_window: 500_000,
+            min_lock_duration: 1u64,
+            max_lock_duration: 52u64,
+            weekly_multiplier: 1u64,
+            weekly_divider: 12u64,
+            max_positions_per_user: 14,
+            weight_curve: None,
+        };
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            lockdrop_instantiate_msg,
+        )
+        .unwrap();
+
+        let user_addr = Addr::unchecked("user");
+        let lock_duration = 10;
+        let terraswap_lp_addr = "tswp_lp_token".to_string();
+        let pool_info = PoolInfo {
+            terraswap_pool: Addr::unchecked(terraswap_lp_addr.clone()),
+            terraswap_amount_in_lockups: Uint128::from(1000u128),
+            migration_info: Some(MigrationInfo {
+                terraswap_migrated_amount: Uint128::from(100_000000u128),
+                astroport_lp_token: Addr::unchecked("astro_lp_addr"),
+            }),
+            incentives_share: 0,
+            weighted_amount: Default::default(),
+            generator_astro_per_share: Default::default(),
+            generator_proxy_per_share: RestrictedVector::default(),
+            is_staked: false,
+            has_asset_rewards: false,
+            source_dex: SourceDex::Terraswap,
+            native_lp_token: false,
+            penalty_astro_per_share: Decimal::zero(),
+            lockup_params: PoolLockupParams::default(),
+            asset_rewards: None,
+        };
+        ASSET_POOLS
+            .save(deps.as_mut().storage, &terraswap_lp_addr, &pool_info)
+            .unwrap();
+
+        let lockup = LockupInfoV2 {
+            lp_units_locked: Uint128::from(100u128),
+            astroport_lp_transferred: None,
+            withdrawal_flag: false,
+            astro_rewards: Default::default(),
+            generator_astro_debt: Default::default(),
+            generator_proxy_debt: Default::default(),
+            unlock_timestamp: 0,
+            penalty_astro_debt: Uint128::zero(),
+            partially_unlocked: false,
+        };
+        LOCKUP_INFO
+            .save(
+                deps.as_mut().storage,
+                (
+                    terraswap_lp_addr.as_str(),
+                    &user_addr,
+                    U64Key::new(lock_duration),
+                ),
+                &lockup,
+            )
+            .unwrap();
+
+        let luna = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let krw = AssetInfo::NativeToken {
+            denom: "ukrw".to_string(),
+        };
+        let claim_msg =
+            to_binary(&astroport::pair_stable_bluna::ExecuteMsg::ClaimReward { receiver: None })
+                .unwrap();
+        let asset_rewards = |reward_assets: Vec<AssetInfo>| AssetRewardsConfig {
+            claim_msg: claim_msg.clone(),
+            pending_rewards_query: None,
+            reward_assets,
+        };
+
+        // check invalid reward assets
+        let err = handle_toggle_rewards(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            terraswap_lp_addr.to_string(),
+            true,
+            Some(asset_rewards(vec![])),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Reward assets should not be empty"
+        );
+
+        let err = handle_toggle_rewards(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            terraswap_lp_addr.to_string(),
+            true,
+            Some(asset_rewards(vec![luna.clone(), krw.clone(), luna.clone()])),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Duplicated reward asset uluna"
+        );
+
+        // enabling rewards in two assets
+        handle_toggle_rewards(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            terraswap_lp_addr.to_string(),
+            true,
+            Some(asset_rewards(vec![luna.clone(), krw.clone()])),
+        )
+        .unwrap();
+
+        let res = handle_claim_asset_reward(
+            deps.as_ref(),
+            env.clone(),
+            user_addr.clone(),
+            user_addr.clone(),
+            terraswap_lp_addr.to_string(),
+            lock_duration,
+            None,
+        )
+        .unwrap();
+
+        // check dispatched messages
+        if let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) = &res.messages[0].msg
+        {
+            assert_eq!(contract_addr.to_owned(), "minter_address".to_string());
+            assert_eq!(msg, &claim_msg);
+        } else {
+            panic!("Wrong message")
+        }
+
+        let previous_balances = vec![
+            Asset {
+                info: luna.clone(),
+                amount: Uint128::from(100u128),
+            },
+            Asset {
+                info: krw.clone(),
+                amount: Uint128::from(100u128),
+            },
+        ];
+        if let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) = &res.messages[1].msg
+        {
+            assert_eq!(contract_addr.to_owned(), env.contract.address.to_string());
+            let real_message = ExecuteMsg::Callback(CallbackMsg::DistributeAssetReward {
+                terraswap_lp_token: terraswap_lp_addr.clone(),
+                user_address: user_addr.clone(),
+                recipient: user_addr.clone(),
+                lock_duration,
+                previous_balances: previous_balances.clone(),
+            });
+            assert_eq!(from_binary::<ExecuteMsg>(&msg).unwrap(), real_message);
+        } else {
+            panic!("Wrong message")
+        }
+
+        // emulating newly arrived rewards
+        deps.querier.with_balance(&[(
+            &env.contract.address.to_string(),
+            &native_balances(200, 1100)[..],
+        )]);
+
+        let resp = callback_distribute_asset_reward(
+            deps.as_mut(),
+            env.clone(),
+            previous_balances,
+            terraswap_lp_addr.clone(),
+            user_addr.clone(),
+            user_addr.clone(),
+            lock_duration,
+        )
+        .unwrap();
+        assert_eq!(resp.messages.len(), 2);
+        assert_eq!(
+            resp.attributes,
+            vec![
+                attr("lockdrop_claimed_reward", "100uluna"),
+                attr("lockdrop_claimed_reward", "1000ukrw"),
+                attr("user", "user"),
+                attr("sent_asset_reward", "10uluna"),
+                attr("sent_asset_reward", "100ukrw"),
+            ]
+        );
+
+        // every reward asset has its own reward index
+        assert_eq!(
+            AssetRewardIndexes::new(&luna)
+                .total()
+                .load(&deps.storage, &terraswap_lp_addr)
+                .unwrap(),
+            Decimal256::from_ratio(1u128, 10u128)
+        );
+        assert_eq!(
+            AssetRewardIndexes::new(&krw)
+                .total()
+                .load(&deps.storage, &terraswap_lp_addr)
+                .unwrap(),
+            Decimal256::one()
+        );
+
+        // reward assets with distributed rewards can't be removed
+        let err = handle_toggle_rewards(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            terraswap_lp_addr.to_string(),
+            true,
+            Some(asset_rewards(vec![krw.clone()])),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Generic error: Reward asset uluna can't be removed once its rewards were distributed"
+        );
+
+        // new reward assets can be added
+        let uusd = AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        };
+        handle_toggle_rewards(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            terraswap_lp_addr.to_string(),
+            true,
+            Some(asset_rewards(vec![krw.clone(), uusd, luna.clone()])),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn migrate_from_v120() {
+        use crate::migration::{ConfigV120, PoolInfoV120, StateV120, CONFIG_V120, STATE_V120};
+
+        let mut deps = mock_dependencies(&[]);
+        let terraswap_lp_addr = Addr::unchecked("tswp_lp_token");
+
+        CONFIG_V120
+            .save(
+                deps.as_mut().storage,
+                &ConfigV120 {
+                    owner: Addr::unchecked("owner"),
+                    astro_token: Some(Addr::unchecked("astro_token")),
+                    auction_contract: Some(Addr::unchecked("auction")),
+                    generator: Some(Addr::unchecked("generator")),
+                    init_timestamp: 100_000,
+                    deposit_window: 10_000_000,
+                    withdrawal_window: 500_000,
+                    min_lock_duration: 1u64,
+                    max_lock_duration: 52u64,
+                    weekly_multiplier: 1u64,
+                    weekly_divider: 12u64,
+                    lockdrop_incentives: Uint128::from(1_000_000u64),
+                    max_positions_per_user: 14,
+                },
+            )
+            .unwrap();
+        STATE_V120
+            .save(
+                deps.as_mut().storage,
+                &StateV120 {
+                    total_incentives_share: 10_000,
+                    total_astro_delegated: Uint128::from(100u64),
+                    are_claims_allowed: true,
+                },
+            )
+            .unwrap();
+        ASSET_POOLS_V120
+            .save(
+                deps.as_mut().storage,
+                &terraswap_lp_addr,
+                &PoolInfoV120 {
+                    terraswap_pool: Addr::unchecked("tswp_pool"),
+                    terraswap_amount_in_lockups: Uint128::from(1_000u64),
+                    migration_info: None,
+                    incentives_share: 10_000,
+                    weighted_amount: Uint256::from(2_000u64),
+                    generator_astro_per_share: Decimal::zero(),
+                    generator_proxy_per_share: RestrictedVector::default(),
+                    is_staked: false,
+                    has_asset_rewards: true,
+                },
+            )
+            .unwrap();
+        set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "1.2.0").unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        // the new fields are stored rather than defaulted on every load
+        let raw_config = String::from_utf8(deps.as_ref().storage.get(b"config").unwrap()).unwrap();
+        assert!(raw_config.ends_with(
+            r#""position_nft":null,"force_unlock_params":null,"weight_curve":{"linear":{}}}"#
+        ));
+        let raw_state = String::from_utf8(deps.as_ref().storage.get(b"state").unwrap()).unwrap();
+        assert!(raw_state.ends_with(r#""max_pool_windows_end":10600000}"#));
+
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.max_positions_per_user, 14);
+        assert_eq!(config.weight_curve, WeightCurve::Linear {});
+
+        let state = STATE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(state.total_incentives_share, 10_000);
+        assert!(state.are_claims_allowed);
+        assert_eq!(state.max_pool_windows_end, 10_600_000);
+
+        let pool_info = ASSET_POOLS
+            .load(deps.as_ref().storage, terraswap_lp_addr.as_str())
+            .unwrap();
+        assert!(pool_info.has_asset_rewards);
+        assert_eq!(pool_info.source_dex, SourceDex::Terraswap);
+        assert!(!pool_info.native_lp_token);
+        assert_eq!(pool_info.lockup_params, PoolLockupParams::default());
+
+        let version = get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.version, CONTRACT_VERSION);
+    }
 }
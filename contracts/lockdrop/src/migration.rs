@@ -1,10 +1,11 @@
 use astroport::asset::AssetInfo;
 use astroport::generator::{PoolInfoResponse, QueryMsg as GenQueryMsg};
-use astroport_periphery::lockdrop::MigrationInfo;
-use cosmwasm_std::{Addr, Decimal, DepsMut, StdResult, Uint128, Uint256};
-use cw_storage_plus::Map;
+use astroport_periphery::lockdrop::{Config, MigrationInfo, State, WeightCurve};
+use cosmwasm_std::{Addr, Decimal, DepsMut, StdResult, Storage, Uint128, Uint256};
+use cw_storage_plus::{Item, Map};
 
 use crate::raw_queries::raw_proxy_asset;
+use crate::state::{CONFIG, STATE};
 use astroport::restricted_vector::RestrictedVector;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -45,8 +46,113 @@ pub struct PoolInfoV111 {
     pub has_asset_rewards: bool,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfoV120 {
+    pub terraswap_pool: Addr,
+    pub terraswap_amount_in_lockups: Uint128,
+    pub migration_info: Option<MigrationInfo>,
+    /// Share of total ASTRO incentives allocated to this pool
+    pub incentives_share: u64,
+    /// Weighted LP Token balance used to calculate ASTRO rewards a particular user can claim
+    pub weighted_amount: Uint256,
+    /// Ratio of Generator ASTRO rewards accured to astroport pool share
+    pub generator_astro_per_share: Decimal,
+    /// Ratio of Generator Proxy rewards accured to astroport pool share
+    pub generator_proxy_per_share: RestrictedVector<AssetInfo, Decimal>,
+    /// Boolean value indicating if the LP Tokens are staked with the Generator contract or not
+    pub is_staked: bool,
+    /// Flag defines whether the asset has rewards or not
+    pub has_asset_rewards: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigV120 {
+    /// Account which can update the config
+    pub owner: Addr,
+    /// ASTRO Token address
+    pub astro_token: Option<Addr>,
+    /// Bootstrap Auction contract address
+    pub auction_contract: Option<Addr>,
+    /// Generator (Staking for dual rewards) contract address
+    pub generator: Option<Addr>,
+    /// Timestamp when Contract will start accepting LP Token deposits
+    pub init_timestamp: u64,
+    /// Number of seconds during which lockup deposits will be accepted
+    pub deposit_window: u64,
+    /// Withdrawal Window Length :: Post the deposit window
+    pub withdrawal_window: u64,
+    /// Min. no. of weeks allowed for lockup
+    pub min_lock_duration: u64,
+    /// Max. no. of weeks allowed for lockup
+    pub max_lock_duration: u64,
+    /// Lockdrop Reward multiplier
+    pub weekly_multiplier: u64,
+    /// Lockdrop Reward divider
+    pub weekly_divider: u64,
+    /// Total ASTRO lockdrop incentives to be distributed among the users
+    pub lockdrop_incentives: Uint128,
+    /// Max lockup positions a user can have
+    pub max_positions_per_user: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateV120 {
+    /// Total ASTRO incentives share
+    pub total_incentives_share: u64,
+    /// ASTRO Tokens delegated to the bootstrap auction contract
+    pub total_astro_delegated: Uint128,
+    /// Boolean value indicating if the user can withdraw their ASTRO rewards or not
+    pub are_claims_allowed: bool,
+}
+
 pub const ASSET_POOLS_V101: Map<&Addr, PoolInfoV101> = Map::new("LiquidityPools");
 pub const ASSET_POOLS_V111: Map<&Addr, PoolInfoV111> = Map::new("LiquidityPools");
+pub const ASSET_POOLS_V120: Map<&Addr, PoolInfoV120> = Map::new("LiquidityPools");
+pub const CONFIG_V120: Item<ConfigV120> = Item::new("config");
+pub const STATE_V120: Item<StateV120> = Item::new("state");
+
+/// Saves the config and the state with the fields added in v1.3.0 set to the values which keep the v1.2.0 behaviour:
+/// no position NFTs, forced unlocks disabled, the linear weight curve and no pool with its own lockup windows.
+pub fn migrate_config_and_state_to_v130(storage: &mut dyn Storage) -> StdResult<()> {
+    let config = CONFIG_V120.load(storage)?;
+    let state = STATE_V120.load(storage)?;
+
+    let max_pool_windows_end =
+        config.init_timestamp + config.deposit_window + config.withdrawal_window;
+
+    CONFIG.save(
+        storage,
+        &Config {
+            owner: config.owner,
+            astro_token: config.astro_token,
+            auction_contract: config.auction_contract,
+            generator: config.generator,
+            init_timestamp: config.init_timestamp,
+            deposit_window: config.deposit_window,
+            withdrawal_window: config.withdrawal_window,
+            min_lock_duration: config.min_lock_duration,
+            max_lock_duration: config.max_lock_duration,
+            weekly_multiplier: config.weekly_multiplier,
+            weekly_divider: config.weekly_divider,
+            lockdrop_incentives: config.lockdrop_incentives,
+            max_positions_per_user: config.max_positions_per_user,
+            position_nft: None,
+            force_unlock_params: None,
+            weight_curve: WeightCurve::Linear {},
+        },
+    )?;
+    STATE.save(
+        storage,
+        &State {
+            total_incentives_share: state.total_incentives_share,
+            total_astro_delegated: state.total_astro_delegated,
+            are_claims_allowed: state.are_claims_allowed,
+            max_pool_windows_end,
+        },
+    )?;
+
+    Ok(())
+}
 
 pub fn migrate_generator_proxy_per_share_to_v120(
     deps: &DepsMut,
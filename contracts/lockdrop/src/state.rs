@@ -1,3 +1,4 @@
+use astroport::asset::AssetInfo;
 use astroport::common::OwnershipProposal;
 use astroport::generator::PoolInfoResponse;
 use astroport::generator::QueryMsg as GenQueryMsg;
@@ -6,7 +7,7 @@ use astroport_periphery::lockdrop::{
     Config, LockupInfoV1, LockupInfoV2, PoolInfo, State, UserInfo,
 };
 use astroport_periphery::U64Key;
-use cosmwasm_std::{Addr, Decimal256, Deps, StdError, StdResult};
+use cosmwasm_std::{Addr, Decimal256, Deps, StdError, StdResult, Uint128};
 use cw_storage_plus::{Item, Map};
 
 use crate::raw_queries::raw_proxy_asset;
@@ -14,19 +15,57 @@ use crate::raw_queries::raw_proxy_asset;
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const STATE: Item<State> = Item::new("state");
 
-/// Key is an Terraswap LP token address
-pub const ASSET_POOLS: Map<&Addr, PoolInfo> = Map::new("LiquidityPools");
+/// Key is an LP token, see [`astroport_periphery::lockdrop::pool_key`]
+pub const ASSET_POOLS: Map<&str, PoolInfo> = Map::new("LiquidityPools");
 /// Key is an user address
 pub const USER_INFO: Map<&Addr, UserInfo> = Map::new("users");
-/// Key consists of an Terraswap LP token address, an user address, and a duration
-pub const LOCKUP_INFO: Map<(&Addr, &Addr, U64Key), LockupInfoV2> = Map::new("lockup_position");
+/// Key consists of an LP token pool key (see [`astroport_periphery::lockdrop::pool_key`]), an user address, and a duration
+pub const LOCKUP_INFO: Map<(&str, &Addr, U64Key), LockupInfoV2> = Map::new("lockup_position");
 /// Old LOCKUP_INFO storage interface for backward compatibility
-pub const OLD_LOCKUP_INFO: Map<(&Addr, &Addr, U64Key), LockupInfoV1> = Map::new("lockup_position");
-/// Total received asset reward by lockdrop contract per lp token share
-pub const TOTAL_ASSET_REWARD_INDEX: Map<&Addr, Decimal256> = Map::new("total_asset_reward_index");
-/// Last used total asset reward index for user claim ( lp_addr -> user -> duration )
-pub const USERS_ASSET_REWARD_INDEX: Map<(&Addr, &Addr, U64Key), Decimal256> =
-    Map::new("users_asset_reward_index");
+pub const OLD_LOCKUP_INFO: Map<(&str, &Addr, U64Key), LockupInfoV1> = Map::new("lockup_position");
+/// ASTRO rewards of lockup positions detached from the rewards of their users, which are still owed to the position
+/// NFT owners. Key is the LOCKUP_INFO key
+pub const DETACHED_ASTRO_REWARDS: Map<(&str, &Addr, U64Key), Uint128> =
+    Map::new("detached_astro_rewards");
+/// Namespace of TOTAL_ASSET_REWARD_INDEX of uusd rewards, other reward assets get a suffixed one
+const TOTAL_ASSET_REWARD_INDEX_NAMESPACE: &str = "total_asset_reward_index";
+/// Namespace of USERS_ASSET_REWARD_INDEX of uusd rewards, other reward assets get a suffixed one
+const USERS_ASSET_REWARD_INDEX_NAMESPACE: &str = "users_asset_reward_index";
+
+/// Reward index storages of a reward asset
+pub struct AssetRewardIndexes {
+    total_namespace: String,
+    users_namespace: String,
+}
+
+impl AssetRewardIndexes {
+    /// Returns the reward index storages of the asset. uusd rewards keep the storages used
+    /// before asset rewards were generalized.
+    /// ## Params
+    /// * **asset** is an object of type [`AssetInfo`].
+    pub fn new(asset: &AssetInfo) -> Self {
+        match asset {
+            AssetInfo::NativeToken { denom } if denom == "uusd" => Self {
+                total_namespace: TOTAL_ASSET_REWARD_INDEX_NAMESPACE.to_string(),
+                users_namespace: USERS_ASSET_REWARD_INDEX_NAMESPACE.to_string(),
+            },
+            _ => Self {
+                total_namespace: format!("{}_{}", TOTAL_ASSET_REWARD_INDEX_NAMESPACE, asset),
+                users_namespace: format!("{}_{}", USERS_ASSET_REWARD_INDEX_NAMESPACE, asset),
+            },
+        }
+    }
+
+    /// Total received asset reward by lockdrop contract per lp token share
+    pub fn total(&self) -> Map<&str, Decimal256> {
+        Map::new(&self.total_namespace)
+    }
+
+    /// Last used total asset reward index for user claim ( lp_addr -> user -> duration )
+    pub fn users(&self) -> Map<(&str, &Addr, U64Key), Decimal256> {
+        Map::new(&self.users_namespace)
+    }
+}
 
 pub trait CompatibleLoader<K, R> {
     fn compatible_load(&self, deps: Deps, key: K, generator: &Option<Addr>) -> StdResult<R>;
@@ -39,13 +78,13 @@ pub trait CompatibleLoader<K, R> {
     ) -> StdResult<Option<R>>;
 }
 
-impl CompatibleLoader<(&Addr, &Addr, U64Key), LockupInfoV2>
-    for Map<'_, (&Addr, &Addr, U64Key), LockupInfoV2>
+impl CompatibleLoader<(&str, &Addr, U64Key), LockupInfoV2>
+    for Map<'_, (&str, &Addr, U64Key), LockupInfoV2>
 {
     fn compatible_load(
         &self,
         deps: Deps,
-        key: (&Addr, &Addr, U64Key),
+        key: (&str, &Addr, U64Key),
         generator: &Option<Addr>,
     ) -> StdResult<LockupInfoV2> {
         self.load(deps.storage, key.clone()).or_else(|_| {
@@ -84,6 +123,8 @@ impl CompatibleLoader<(&Addr, &Addr, U64Key), LockupInfoV2>
                 generator_astro_debt: old_lockup_info.generator_astro_debt,
                 generator_proxy_debt,
                 unlock_timestamp: old_lockup_info.unlock_timestamp,
+                penalty_astro_debt: Uint128::zero(),
+                partially_unlocked: false,
             };
 
             Ok(lockup_info)
@@ -93,7 +134,7 @@ impl CompatibleLoader<(&Addr, &Addr, U64Key), LockupInfoV2>
     fn compatible_may_load(
         &self,
         deps: Deps,
-        key: (&Addr, &Addr, U64Key),
+        key: (&str, &Addr, U64Key),
         generator: &Option<Addr>,
     ) -> StdResult<Option<LockupInfoV2>> {
         if !OLD_LOCKUP_INFO.has(deps.storage, key.clone()) {
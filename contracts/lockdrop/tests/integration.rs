@@ -4,18 +4,21 @@ use astroport_governance::utils::EPOCH_START;
 use astroport_periphery::{
     auction::{ExecuteMsg as AuctionExecuteMsg, UpdateConfigMsg as AuctionUpdateConfigMsg},
     lockdrop::{
-        self, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrationInfo, QueryMsg, StateResponse,
-        UpdateConfigMsg, UserInfoResponse,
+        self, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrationInfo, QueryMsg, SourceDex,
+        StateResponse, UpdateConfigMsg, UserInfoResponse, WeightCurve,
     },
 };
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
 use cosmwasm_std::{
-    attr, to_binary, Addr, Coin, Decimal, Timestamp, Uint128, Uint256 as CUint256, Uint64,
+    attr, to_binary, Addr, Binary, Coin, Decimal, Decimal256, Deps, DepsMut, Empty, Env,
+    MessageInfo, Response, StdError, StdResult, Timestamp, Uint128, Uint256 as CUint256, Uint64,
 };
 
 use astroport::token::InstantiateMsg as TokenInstantiateMsg;
 use astroport_periphery::lockdrop::{Config, PoolInfo};
 use cw20::{Cw20ExecuteMsg, Cw20QueryMsg};
+use cw721::{NftInfoResponse, OwnerOfResponse};
+use cw721_base::{ContractError as Cw721ContractError, Cw721Contract};
 use cw_multi_test::{App, AppBuilder, BankKeeper, ContractWrapper, Executor};
 
 fn mock_app() -> App {
@@ -33,6 +36,115 @@ fn mock_app() -> App {
         .build(|_, _, _| {})
 }
 
+fn position_nft_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::ExecuteMsg<Empty>,
+) -> Result<Response, Cw721ContractError> {
+    Cw721Contract::<Empty, Empty>::default().execute(deps, env, info, msg)
+}
+
+fn position_nft_instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw721_base::InstantiateMsg,
+) -> StdResult<Response> {
+    Cw721Contract::<Empty, Empty>::default().instantiate(deps, env, info, msg)
+}
+
+fn position_nft_query(deps: Deps, env: Env, msg: cw721_base::QueryMsg) -> StdResult<Binary> {
+    Cw721Contract::<Empty, Empty>::default().query(deps, env, msg)
+}
+
+fn native_pair_execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn native_pair_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    lp_denom: String,
+) -> StdResult<Response> {
+    deps.storage.set(b"lp_denom", lp_denom.as_bytes());
+    Ok(Response::default())
+}
+
+fn native_pair_query(deps: Deps, env: Env, msg: astroport::pair::QueryMsg) -> StdResult<Binary> {
+    match msg {
+        astroport::pair::QueryMsg::Pair {} => {
+            let lp_denom = String::from_utf8(deps.storage.get(b"lp_denom").unwrap_or_default())?;
+            to_binary(&astroport::asset::PairInfo {
+                asset_infos: [
+                    AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                ],
+                contract_addr: env.contract.address,
+                liquidity_token: Addr::unchecked(lp_denom),
+                pair_type: astroport::factory::PairType::Xyk {},
+            })
+        }
+        _ => Err(StdError::generic_err("Unsupported query")),
+    }
+}
+
+// Instantiate a mock pair which issues native LP tokens
+fn instantiate_native_pair(app: &mut App, owner: Addr, lp_denom: &str) -> Addr {
+    let native_pair_contract = Box::new(ContractWrapper::new(
+        native_pair_execute,
+        native_pair_instantiate,
+        native_pair_query,
+    ));
+
+    let native_pair_code_id = app.store_code(native_pair_contract);
+
+    app.instantiate_contract(
+        native_pair_code_id,
+        owner,
+        &lp_denom.to_string(),
+        &[],
+        String::from("Native pair"),
+        None,
+    )
+    .unwrap()
+}
+
+// Instantiate Position NFT Contract
+fn instantiate_position_nft(app: &mut App, owner: Addr, minter: &Addr) -> Addr {
+    let position_nft_contract = Box::new(ContractWrapper::new(
+        position_nft_execute,
+        position_nft_instantiate,
+        position_nft_query,
+    ));
+
+    let position_nft_code_id = app.store_code(position_nft_contract);
+
+    app.instantiate_contract(
+        position_nft_code_id,
+        owner,
+        &cw721_base::InstantiateMsg {
+            name: String::from("Lockdrop positions"),
+            symbol: String::from("LOCKUP"),
+            minter: minter.to_string(),
+        },
+        &[],
+        String::from("Position NFT"),
+        None,
+    )
+    .unwrap()
+}
+
 // Instantiate ASTRO Token Contract
 fn instantiate_astro_token(app: &mut App, owner: Addr) -> Addr {
     let astro_token_contract = Box::new(ContractWrapper::new_with_empty(
@@ -383,9 +495,17 @@ fn instantiate_auction_contract(
         airdrop_contract_address: airdrop_instance.to_string(),
         lockdrop_contract_address: lockdrop_instance.to_string(),
         lp_tokens_vesting_duration: 7776000u64,
+        astro_incentives_vesting_duration: None,
+        lp_vesting_schedules: None,
         init_timestamp: EPOCH_START + 10_600_000,
         deposit_window: 100_00_0,
         withdrawal_window: 5_00_00,
+        astro_delegators_incentives_share: Decimal::percent(50),
+        time_weight: None,
+        keeper: None,
+        init_pool_slippage: None,
+        compound_slippage: None,
+        allowlist_merkle_root: None,
     };
 
     // Init contract
@@ -408,6 +528,10 @@ fn instantiate_auction_contract(
                 astro_ust_pair_address: Some(pair_instance.to_string()),
                 owner: None,
                 generator_contract: Some(generator_instance.to_string()),
+                keeper: None,
+                init_pool_slippage: None,
+                compound_slippage: None,
+                allowlist_merkle_root: None,
             },
         },
         &[],
@@ -436,6 +560,7 @@ fn instantiate_lockdrop_contract(app: &mut App, owner: Addr) -> (Addr, Instantia
         weekly_multiplier: 1u64,
         weekly_divider: 12u64,
         max_positions_per_user: 14,
+        weight_curve: None,
     };
 
     app.update_block(|b| {
@@ -678,6 +803,9 @@ fn initialize_and_migrate_liquidity_for_pool(
         &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
             terraswap_lp_token: terraswap_token_instance.to_string(),
             incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
         },
         &[],
     )
@@ -1178,6 +1306,9 @@ fn test_initialize_pool() {
     let initialize_pool_msg = astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
         terraswap_lp_token: terraswap_token_instance.to_string(),
         incentives_share: 10000000u64,
+        source_dex: None,
+        native_lp_pool: None,
+        lockup_params: None,
     };
 
     // ######    ERROR :: Unauthorized     ######
@@ -1208,7 +1339,7 @@ fn test_initialize_pool() {
     assert_eq!(Uint128::zero(), state_resp.total_astro_delegated);
     assert_eq!(false, state_resp.are_claims_allowed);
     assert_eq!(
-        vec![terraswap_token_instance.clone()],
+        vec![terraswap_token_instance.to_string()],
         state_resp.supported_pairs_list
     );
     // check Pool Info
@@ -1272,6 +1403,9 @@ fn test_initialize_pool() {
         &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
             terraswap_lp_token: terraswap_token_instance2.to_string(),
             incentives_share: 10400000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
         },
         &[],
     )
@@ -1284,8 +1418,8 @@ fn test_initialize_pool() {
     assert_eq!(20400000u64, state_resp.total_incentives_share);
     assert_eq!(
         vec![
-            terraswap_token_instance.clone(),
-            terraswap_token_instance2.clone(),
+            terraswap_token_instance.to_string(),
+            terraswap_token_instance2.to_string(),
         ],
         state_resp.supported_pairs_list
     );
@@ -1366,6 +1500,9 @@ fn test_update_pool() {
         &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
             terraswap_lp_token: terraswap_token_instance.to_string(),
             incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
         },
         &[],
     )
@@ -1405,7 +1542,7 @@ fn test_update_pool() {
     assert_eq!(Uint128::zero(), state_resp.total_astro_delegated);
     assert_eq!(false, state_resp.are_claims_allowed);
     assert_eq!(
-        vec![terraswap_token_instance.clone()],
+        vec![terraswap_token_instance.to_string()],
         state_resp.supported_pairs_list
     );
     // check Pool Info
@@ -1455,6 +1592,165 @@ fn test_update_pool() {
     );
 }
 
+#[test]
+fn test_weight_curve() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (lockdrop_instance, _) = instantiate_lockdrop_contract(&mut app, owner.clone());
+
+    let simulate_weight =
+        |app: &App, duration: u64| -> StdResult<lockdrop::SimulateWeightResponse> {
+            app.wrap().query_wasm_smart(
+                &lockdrop_instance,
+                &QueryMsg::SimulateWeight {
+                    amount: Uint128::from(1000u64),
+                    duration,
+                },
+            )
+        };
+
+    // Linear curve is used by default
+    let resp = simulate_weight(&app, 13).unwrap();
+    assert_eq!(resp.boost, Decimal256::from_ratio(2u64, 1u64));
+    assert_eq!(resp.weight, CUint256::from(2000u64));
+
+    let err = simulate_weight(&app, 53).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Querier contract error: Generic error: Lockup duration needs to be between 1 and 52"
+    );
+
+    // ######    ERROR :: Unauthorized     ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked("wrong_owner"),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateWeightCurve {
+                weight_curve: WeightCurve::Quadratic {},
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    ERROR :: Invalid curves     ######
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateWeightCurve {
+                weight_curve: WeightCurve::Capped {
+                    max_boost: Decimal256::percent(50),
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Max weight boost cannot be lower than 1"
+    );
+
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateWeightCurve {
+                weight_curve: WeightCurve::Table {
+                    boosts: vec![Decimal256::one(); 51],
+                },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Weight curve table should contain a boost for every lockup week up to 52"
+    );
+
+    let mut boosts = vec![Decimal256::one(); 52];
+    boosts[10] = Decimal256::percent(200);
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateWeightCurve {
+                weight_curve: WeightCurve::Table { boosts },
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Weight boosts should be non-decreasing with the lockup duration"
+    );
+
+    // ######    SUCCESS :: Curves are replaced before deposits open     ######
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateWeightCurve {
+            weight_curve: WeightCurve::Capped {
+                max_boost: Decimal256::percent(150),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    let resp = simulate_weight(&app, 13).unwrap();
+    assert_eq!(resp.boost, Decimal256::percent(150));
+    assert_eq!(resp.weight, CUint256::from(1500u64));
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateWeightCurve {
+            weight_curve: WeightCurve::Quadratic {},
+        },
+        &[],
+    )
+    .unwrap();
+    let resp = simulate_weight(&app, 13).unwrap();
+    assert_eq!(resp.boost, Decimal256::from_ratio(13u64, 1u64));
+    assert_eq!(resp.weight, CUint256::from(13000u64));
+
+    let boosts: Vec<Decimal256> = (1..=52u64)
+        .map(|week| Decimal256::from_ratio(week, 4u64))
+        .collect();
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateWeightCurve {
+            weight_curve: WeightCurve::Table { boosts },
+        },
+        &[],
+    )
+    .unwrap();
+    let resp = simulate_weight(&app, 6).unwrap();
+    assert_eq!(resp.boost, Decimal256::percent(150));
+    assert_eq!(resp.weight, CUint256::from(1500u64));
+
+    // ######    ERROR :: Curve is frozen once deposits open     ######
+    app.update_block(|b| {
+        b.time = Timestamp::from_seconds(EPOCH_START + 100_000);
+    });
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateWeightCurve {
+                weight_curve: WeightCurve::Linear {},
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Weight curve can't be changed once deposits are open"
+    );
+}
+
 #[test]
 fn test_increase_lockup() {
     let mut app = mock_app();
@@ -1519,6 +1815,9 @@ fn test_increase_lockup() {
         &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
             terraswap_lp_token: terraswap_token_instance.to_string(),
             incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
         },
         &[],
     )
@@ -1857,12 +2156,11 @@ fn test_increase_lockup() {
 }
 
 #[test]
-fn test_withdraw_from_lockup() {
+fn test_pool_lockup_params() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
 
-    let (_, lockdrop_instance, _, _, _update_msg) =
-        instantiate_all_contracts(&mut app, owner.clone());
+    let (_, lockdrop_instance, _, _, _) = instantiate_all_contracts(&mut app, owner.clone());
 
     // Terraswap LP Token
     let terraswap_token_contract = Box::new(ContractWrapper::new_with_empty(
@@ -1872,7 +2170,6 @@ fn test_withdraw_from_lockup() {
     ));
     let terraswap_token_code_id = app.store_code(terraswap_token_contract);
 
-    // LP Token #1
     let terraswap_token_instance = app
         .instantiate_contract(
             terraswap_token_code_id,
@@ -1893,267 +2190,248 @@ fn test_withdraw_from_lockup() {
         )
         .unwrap();
 
-    // SUCCESSFULLY INITIALIZES POOL
-    app.execute_contract(
-        owner.clone(),
-        lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
-            terraswap_lp_token: terraswap_token_instance.to_string(),
-            incentives_share: 10000000u64,
-        },
-        &[],
-    )
-    .unwrap();
-
-    let user_address = "user".to_string();
-    let _user2_address = "user2".to_string();
-
-    // Mint some LP tokens to user#1
-    app.execute_contract(
-        Addr::unchecked("pair_instance".to_string()),
-        terraswap_token_instance.clone(),
-        &cw20::Cw20ExecuteMsg::Mint {
-            recipient: user_address.clone(),
-            amount: Uint128::from(124231343u128),
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Deposit into Lockup Position
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
-    });
-
-    app.execute_contract(
-        Addr::unchecked(user_address.clone()),
-        terraswap_token_instance.clone(),
-        &cw20::Cw20ExecuteMsg::Send {
-            contract: lockdrop_instance.clone().to_string(),
-            amount: Uint128::from(10000000u128),
-            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
-        },
-        &[],
-    )
-    .unwrap();
-
-    // ######    ERROR :: Invalid withdrawal request   ######
-
-    let err = app
-        .execute_contract(
-            Addr::unchecked(user_address.clone()),
-            lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawFromLockup {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
-                amount: Uint128::from(0u128),
-                duration: 1u64,
-            },
-            &[],
-        )
-        .unwrap_err();
-    assert_eq!(
-        err.root_cause().to_string(),
-        "Generic error: Invalid withdrawal request"
-    );
-
-    // ######    ERROR :: LP Token not supported   ######
+    let lockup_params = lockdrop::PoolLockupParams {
+        min_lock_duration: Some(4u64),
+        max_lock_duration: Some(26u64),
+        init_timestamp: Some(EPOCH_START + 5_000_000),
+        deposit_window: Some(10_000_000),
+        withdrawal_window: Some(400_000),
+        max_lockup_amount: Some(Uint128::from(1000u64)),
+    };
 
+    // ######    ERROR :: Invalid pool lockup params    ######
     let err = app
         .execute_contract(
-            Addr::unchecked(user_address.clone()),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawFromLockup {
-                terraswap_lp_token: "wrong_terraswap_token_instance".to_string(),
-                amount: Uint128::from(10u128),
-                duration: 1u64,
+            &ExecuteMsg::InitializePool {
+                terraswap_lp_token: terraswap_token_instance.to_string(),
+                incentives_share: 10000000u64,
+                source_dex: None,
+                native_lp_pool: None,
+                lockup_params: Some(lockdrop::PoolLockupParams {
+                    init_timestamp: Some(EPOCH_START),
+                    ..lockup_params.clone()
+                }),
             },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "astroport_periphery::lockdrop::PoolInfo not found"
+        "Generic error: Pool lockup windows cannot open before the lockdrop windows"
     );
 
-    // ######    ERROR :: Invalid lockup position   ######
-
     let err = app
         .execute_contract(
-            Addr::unchecked(user_address.clone()),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawFromLockup {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
-                amount: Uint128::from(10u128),
-                duration: 1u64,
+            &ExecuteMsg::InitializePool {
+                terraswap_lp_token: terraswap_token_instance.to_string(),
+                incentives_share: 10000000u64,
+                source_dex: None,
+                native_lp_pool: None,
+                lockup_params: Some(lockdrop::PoolLockupParams {
+                    min_lock_duration: Some(27u64),
+                    ..lockup_params.clone()
+                }),
             },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "astroport_periphery::lockdrop::LockupInfoV1 not found"
+        "Generic error: Invalid Lockup durations"
     );
 
-    // ######    SUCCESS :: SHOULD SUCCESSFULLY WITHDRAW LP TOKENS FROM POOL     ######
+    // SUCCESSFULLY INITIALIZES POOL
     app.execute_contract(
-        Addr::unchecked(user_address.clone()),
+        owner.clone(),
         lockdrop_instance.clone(),
-        &ExecuteMsg::WithdrawFromLockup {
-            terraswap_lp_token: terraswap_token_instance.clone().to_string(),
-            amount: Uint128::from(10000000u128),
-            duration: 10u64,
+        &ExecuteMsg::InitializePool {
+            terraswap_lp_token: terraswap_token_instance.to_string(),
+            incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: Some(lockup_params.clone()),
         },
         &[],
     )
     .unwrap();
 
-    // check Pool Info
     let pool_resp: PoolInfo = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
             &QueryMsg::Pool {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+                terraswap_lp_token: terraswap_token_instance.to_string(),
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(0u128), pool_resp.terraswap_amount_in_lockups);
-    assert_eq!(CUint256::from(0u64), pool_resp.weighted_amount);
+    assert_eq!(pool_resp.lockup_params, lockup_params);
 
-    // check User Info
-    let user_resp: UserInfoResponse = app
+    // The pool windows end after the windows of the config
+    let state_resp: StateResponse = app
         .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
-        )
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
         .unwrap();
-    assert_eq!(Uint128::from(0u128), user_resp.total_astro_rewards);
-    assert_eq!(Uint128::zero(), user_resp.delegated_astro_rewards);
-    assert_eq!(0, user_resp.lockup_infos.len());
+    assert_eq!(state_resp.windows_end, EPOCH_START + 15_400_000);
 
-    // Deposit Again into Lockup
+    let user_address = "user".to_string();
     app.execute_contract(
-        Addr::unchecked(user_address.clone()),
+        Addr::unchecked("pair_instance".to_string()),
         terraswap_token_instance.clone(),
-        &cw20::Cw20ExecuteMsg::Send {
-            contract: lockdrop_instance.clone().to_string(),
-            amount: Uint128::from(10000000u128),
-            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: user_address.clone(),
+            amount: Uint128::from(2000u64),
         },
         &[],
     )
     .unwrap();
 
-    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}    ######
-    // First half of withdrawal window
+    let increase_lockup = |app: &mut App, duration: u64, amount: u64| {
+        app.execute_contract(
+            Addr::unchecked(user_address.clone()),
+            terraswap_token_instance.clone(),
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: lockdrop_instance.to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&Cw20HookMsg::IncreaseLockup { duration }).unwrap(),
+            },
+            &[],
+        )
+    };
+
+    // ######    ERROR :: Pool deposit window hasn't opened yet    ######
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 10350000)
+        b.time = Timestamp::from_seconds(EPOCH_START + 100_000)
+    });
+    let err = increase_lockup(&mut app, 4, 100).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Deposit window closed"
+    );
+
+    // ######    ERROR :: Lockup duration is out of the pool bounds    ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 5_000_000)
     });
+    let err = increase_lockup(&mut app, 3, 100).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Lockup duration needs to be between 4 and 26"
+    );
+    let err = increase_lockup(&mut app, 27, 100).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Lockup duration needs to be between 4 and 26"
+    );
+
+    // ######    ERROR :: Pool lockup cap exceeded    ######
+    let err = increase_lockup(&mut app, 4, 1001).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Pool lockup cap of 1000 LP units exceeded"
+    );
+
+    increase_lockup(&mut app, 4, 600).unwrap();
+    let err = increase_lockup(&mut app, 26, 401).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Pool lockup cap of 1000 LP units exceeded"
+    );
+    increase_lockup(&mut app, 26, 400).unwrap();
+
+    // Unlock timestamps follow the windows of the pool
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        EPOCH_START + 15_400_000 + 4 * 7 * 86400,
+        user_resp.lockup_infos[0].unlock_timestamp
+    );
 
+    // ######    ERROR :: Liquidity can't be migrated before the pool windows end    ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 11_000_000)
+    });
     let err = app
         .execute_contract(
-            Addr::unchecked(user_address.clone()),
+            owner.clone(),
             lockdrop_instance.clone(),
-            &ExecuteMsg::WithdrawFromLockup {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
-                amount: Uint128::from(5000001u128),
-                duration: 10u64,
+            &ExecuteMsg::MigrateLiquidity {
+                terraswap_lp_token: terraswap_token_instance.to_string(),
+                astroport_pool_addr: "astroport_pool".to_string(),
+                slippage_tolerance: None,
             },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "Generic error: Amount exceeds maximum allowed withdrawal limit of 5000000"
+        "Generic error: Deposit / Withdrawal windows not closed"
     );
 
-    // 2nd half of withdrawal window
+    // ######    ERROR :: Pool deposit window is closed, only 50% can be withdrawn    ######
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 10390000)
+        b.time = Timestamp::from_seconds(EPOCH_START + 15_000_000)
     });
+    let err = increase_lockup(&mut app, 4, 100).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Deposit window closed"
+    );
 
     let err = app
         .execute_contract(
             Addr::unchecked(user_address.clone()),
             lockdrop_instance.clone(),
             &ExecuteMsg::WithdrawFromLockup {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
-                amount: Uint128::from(5000001u128),
-                duration: 10u64,
+                terraswap_lp_token: terraswap_token_instance.to_string(),
+                duration: 4u64,
+                amount: Uint128::from(301u64),
+                user_address: None,
             },
             &[],
         )
         .unwrap_err();
     assert_eq!(
         err.root_cause().to_string(),
-        "Generic error: Amount exceeds maximum allowed withdrawal limit of 4200000"
+        "Generic error: Amount exceeds maximum allowed withdrawal limit of 300"
     );
 
-    // ######    SUCCESS :: SHOULD SUCCESSFULLY WITHDRAW LP TOKENS FROM POOL     ######
     app.execute_contract(
         Addr::unchecked(user_address.clone()),
         lockdrop_instance.clone(),
         &ExecuteMsg::WithdrawFromLockup {
-            terraswap_lp_token: terraswap_token_instance.clone().to_string(),
-            amount: Uint128::from(4200000u128),
-            duration: 10u64,
+            terraswap_lp_token: terraswap_token_instance.to_string(),
+            duration: 4u64,
+            amount: Uint128::from(300u64),
+            user_address: None,
         },
         &[],
     )
     .unwrap();
 
-    // check Pool Info
-    let pool_resp: PoolInfo = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::Pool {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
-            },
-        )
-        .unwrap();
-    assert_eq!(
-        Uint128::from(5800000u128),
-        pool_resp.terraswap_amount_in_lockups
-    );
-    assert_eq!(CUint256::from(10150000u64), pool_resp.weighted_amount);
-
-    // check User Info
-    let user_resp: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
-        )
-        .unwrap();
-    assert_eq!(Uint128::from(1000000000u128), user_resp.total_astro_rewards);
-    assert_eq!(1, user_resp.lockup_infos.len());
-    assert_eq!(1, user_resp.lockup_infos.len());
-    assert_eq!(
-        Uint128::from(5800000u128),
-        user_resp.lockup_infos[0].lp_units_locked
-    );
-    assert_eq!(true, user_resp.lockup_infos[0].withdrawal_flag);
-
-    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}    ######
-
     let err = app
         .execute_contract(
             Addr::unchecked(user_address.clone()),
             lockdrop_instance.clone(),
             &ExecuteMsg::WithdrawFromLockup {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
-                amount: Uint128::from(1u128),
-                duration: 10u64,
+                terraswap_lp_token: terraswap_token_instance.to_string(),
+                duration: 4u64,
+                amount: Uint128::from(1u64),
+                user_address: None,
             },
             &[],
         )
@@ -2162,45 +2440,25 @@ fn test_withdraw_from_lockup() {
         err.root_cause().to_string(),
         "Generic error: Withdrawal already happened. No more withdrawals accepted"
     );
+
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(pool_resp.terraswap_amount_in_lockups, Uint128::from(700u64));
 }
 
 #[test]
-fn test_migrate_liquidity() {
-    let owner = Addr::unchecked("contract_owner");
+fn test_extend_lockup() {
     let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
 
-    let (_, lockdrop_instance, astroport_factory_instance, _, _) =
-        instantiate_all_contracts(&mut app, owner.clone());
-
-    // CW20 TOKEN :: Dummy token
-    let cw20_contract = Box::new(ContractWrapper::new_with_empty(
-        astroport_token::contract::execute,
-        astroport_token::contract::instantiate,
-        astroport_token::contract::query,
-    ));
-
-    let cw20_code_id = app.store_code(cw20_contract);
-
-    let anc_instance = app
-        .instantiate_contract(
-            cw20_code_id,
-            owner.clone(),
-            &TokenInstantiateMsg {
-                name: String::from("ANC"),
-                symbol: String::from("ANC"),
-                decimals: 6,
-                initial_balances: vec![],
-                mint: Some(cw20::MinterResponse {
-                    minter: owner.to_string(),
-                    cap: None,
-                }),
-                marketing: None,
-            },
-            &[],
-            String::from("ANC"),
-            None,
-        )
-        .unwrap();
+    let (_, lockdrop_instance, _, _, _) = instantiate_all_contracts(&mut app, owner.clone());
 
     // Terraswap LP Token
     let terraswap_token_contract = Box::new(ContractWrapper::new_with_empty(
@@ -2210,392 +2468,3019 @@ fn test_migrate_liquidity() {
     ));
     let terraswap_token_code_id = app.store_code(terraswap_token_contract);
 
-    // Terraswap Pair
-    let terraswap_pair_contract = Box::new(
-        ContractWrapper::new_with_empty(
-            terraswap_pair::contract::execute,
-            terraswap_pair::contract::instantiate,
-            terraswap_pair::contract::query,
-        )
-        .with_reply_empty(terraswap_pair::contract::reply),
-    );
-    let terraswap_pair_code_id = app.store_code(terraswap_pair_contract);
-
-    // LP POOL INSTANCE
-    let terraswap_pool_instance = app
+    let terraswap_token_instance = app
         .instantiate_contract(
-            terraswap_pair_code_id,
+            terraswap_token_code_id,
             Addr::unchecked("user".to_string()),
-            &terraswap::pair::InstantiateMsg {
-                asset_infos: [
-                    terraswap::asset::AssetInfo::Token {
-                        contract_addr: anc_instance.clone().to_string(),
-                    },
-                    terraswap::asset::AssetInfo::NativeToken {
-                        denom: "uusd".to_string(),
-                    },
-                ],
-                token_code_id: terraswap_token_code_id,
-                asset_decimals: [6, 6],
+            &terraswap::token::InstantiateMsg {
+                name: "terraswap liquidity token".to_string(),
+                symbol: "uLP".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: "pair_instance".to_string(),
+                    cap: None,
+                }),
             },
             &[],
-            String::from("terraswap_pool"),
+            String::from("terraswap_lp_token"),
             None,
         )
         .unwrap();
 
-    // Query LP Token
-    let pair_response: terraswap::asset::PairInfo = app
-        .wrap()
-        .query_wasm_smart(
-            &terraswap_pool_instance,
-            &terraswap::pair::QueryMsg::Pair {},
-        )
-        .unwrap();
-    let terraswap_token_instance = pair_response.liquidity_token;
-
-    // SUCCESSFULLY INITIALIZES POOL
     app.execute_contract(
         owner.clone(),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
+        &ExecuteMsg::InitializePool {
             terraswap_lp_token: terraswap_token_instance.to_string(),
             incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
         },
         &[],
     )
     .unwrap();
 
     let user_address = "user".to_string();
-    let user2_address = "user2".to_string();
 
-    // Mint ANC to users
     app.execute_contract(
-        owner.clone(),
-        anc_instance.clone(),
+        Addr::unchecked("pair_instance".to_string()),
+        terraswap_token_instance.clone(),
         &cw20::Cw20ExecuteMsg::Mint {
             recipient: user_address.clone(),
-            amount: Uint128::from(10000_000000u64),
+            amount: Uint128::from(12000u128),
         },
         &[],
     )
     .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
+    });
+
     app.execute_contract(
-        owner.clone(),
-        anc_instance.clone(),
-        &cw20::Cw20ExecuteMsg::Mint {
-            recipient: user2_address.clone(),
-            amount: Uint128::from(10000_000000u64),
+        Addr::unchecked(user_address.clone()),
+        terraswap_token_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: lockdrop_instance.clone().to_string(),
+            amount: Uint128::from(12000u128),
+            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 2u64 }).unwrap(),
         },
         &[],
     )
     .unwrap();
 
-    // Set UST user balances
-    app.init_modules(|router, _, storage| {
-        router
-            .bank
-            .init_balance(
-                storage,
-                &Addr::unchecked(user_address.clone()),
-                vec![Coin::new(1000000_000000, "uusd")],
-            )
-            .unwrap();
-        router
-            .bank
-            .init_balance(
-                storage,
-                &Addr::unchecked(user2_address.clone()),
-                vec![Coin::new(1000000_000000, "uusd")],
-            )
-            .unwrap();
-    });
-
-    // user#1 adds liquidity to Terraswap Pool and locks that in Lockdrop contract
-    // increase allowance
-    app.execute_contract(
-        Addr::unchecked(user_address.clone()),
-        anc_instance.clone(),
-        &Cw20ExecuteMsg::IncreaseAllowance {
-            spender: terraswap_pool_instance.clone().to_string(),
-            amount: Uint128::new(1000_000000),
-            expires: None,
-        },
-        &[],
-    )
-    .unwrap();
+    let extend_lockup_msg =
+        |from_duration: u64, to_duration: u64, amount: u128| ExecuteMsg::ExtendLockup {
+            terraswap_lp_token: terraswap_token_instance.to_string(),
+            from_duration,
+            to_duration,
+            amount: Uint128::from(amount),
+            user_address: None,
+        };
 
-    // add Liquidity to Terraswap pool
-    app.execute_contract(
-        Addr::unchecked(user_address.clone()),
-        terraswap_pool_instance.clone(),
-        &terraswap::pair::ExecuteMsg::ProvideLiquidity {
-            assets: [
-                terraswap::asset::Asset {
-                    info: terraswap::asset::AssetInfo::Token {
-                        contract_addr: anc_instance.clone().to_string(),
-                    },
-                    amount: Uint128::from(1000_000000u64),
-                },
-                terraswap::asset::Asset {
-                    info: terraswap::asset::AssetInfo::NativeToken {
-                        denom: "uusd".to_string(),
-                    },
-                    amount: Uint128::from(1000_000000u64),
-                },
-            ],
-            slippage_tolerance: None,
-            receiver: None,
-        },
-        &[Coin::new(1000_000000, "uusd")],
-    )
-    .unwrap();
+    // ######    ERROR :: New lockup duration should be greater   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &extend_lockup_msg(2, 2, 6000),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: New lockup duration should be greater than the current one"
+    );
 
-    // Query LP balance
-    let lp_balance_res: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &terraswap_token_instance.clone(),
-            &cw20::Cw20QueryMsg::Balance {
-                address: user_address.clone(),
-            },
+    // ######    ERROR :: Lockup duration needs to be between 1 and 52   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &extend_lockup_msg(2, 53, 6000),
+            &[],
         )
-        .unwrap();
-    let user_lp_balance = lp_balance_res.balance;
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Lockup duration needs to be between 1 and 52"
+    );
 
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
-    });
+    // ######    ERROR :: Amount exceeds LP units locked   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &extend_lockup_msg(2, 10, 12001),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Amount exceeds 12000 LP units locked"
+    );
 
-    // Lock LP Tokens into Lockup Position
+    // ######    SUCCESS :: Part of the position is moved to a new position   ######
     app.execute_contract(
         Addr::unchecked(user_address.clone()),
-        Addr::unchecked(terraswap_token_instance.clone()),
-        &cw20::Cw20ExecuteMsg::Send {
-            contract: lockdrop_instance.clone().to_string(),
-            amount: user_lp_balance,
-            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Increase timestamp for window closure
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
-    });
-
-    // Create Astroport Pair
-    app.execute_contract(
-        Addr::unchecked("user"),
-        astroport_factory_instance.clone(),
-        &astroport::factory::ExecuteMsg::CreatePair {
-            asset_infos: [
-                astroport::asset::AssetInfo::Token {
-                    contract_addr: anc_instance.clone(),
-                },
-                astroport::asset::AssetInfo::NativeToken {
-                    denom: "uusd".to_string(),
-                },
-            ],
-            init_params: None,
-            pair_type: astroport::factory::PairType::Xyk {},
-        },
+        lockdrop_instance.clone(),
+        &extend_lockup_msg(2, 10, 6000),
         &[],
     )
     .unwrap();
 
-    // Query Astroport addresses
-    let pair_resp: astroport::asset::PairInfo = app
-        .wrap()
-        .query_wasm_smart(
-            &astroport_factory_instance,
-            &astroport::factory::QueryMsg::Pair {
-                asset_infos: [
-                    astroport::asset::AssetInfo::Token {
-                        contract_addr: anc_instance.clone(),
-                    },
-                    astroport::asset::AssetInfo::NativeToken {
-                        denom: "uusd".to_string(),
-                    },
-                ],
-            },
-        )
-        .unwrap();
-    let astro_pool_address = pair_resp.contract_addr;
-    let astro_lp_address = pair_resp.liquidity_token;
-
-    // astro LP token balance (Lockdrop)
-    let terraswap_balance_resp: cw20::BalanceResponse = app
+    let pool_resp: PoolInfo = app
         .wrap()
         .query_wasm_smart(
-            &terraswap_token_instance,
-            &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(
+        Uint128::from(12000u128),
+        pool_resp.terraswap_amount_in_lockups
+    );
+    // 6000 * (1 + 1/12) + 6000 * (1 + 9/12)
+    assert_eq!(CUint256::from(17000u64), pool_resp.weighted_amount);
 
-    // Query pool before migration
-    let pool_resp_before_migration: PoolInfo = app
+    let user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
-            &QueryMsg::Pool {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
             },
         )
         .unwrap();
-
+    assert_eq!(2, user_resp.lockup_positions_index);
+    assert_eq!(2u64, user_resp.lockup_infos[0].duration);
     assert_eq!(
-        terraswap_balance_resp.balance,
-        pool_resp_before_migration.terraswap_amount_in_lockups
+        Uint128::from(6000u128),
+        user_resp.lockup_infos[0].lp_units_locked
     );
+    assert_eq!(10u64, user_resp.lockup_infos[1].duration);
     assert_eq!(
-        CUint256::from(1750000000u128),
-        pool_resp_before_migration.weighted_amount
+        Uint128::from(6000u128),
+        user_resp.lockup_infos[1].lp_units_locked
+    );
+    assert_eq!(
+        EPOCH_START + 16648000u64,
+        user_resp.lockup_infos[1].unlock_timestamp
     );
-    assert_eq!(false, pool_resp_before_migration.is_staked);
-    assert_eq!(None, pool_resp_before_migration.migration_info);
 
-    // Migrate Liquidity
+    // ######    SUCCESS :: The rest of the position is merged into the existing position   ######
     app.execute_contract(
-        owner.clone(),
+        Addr::unchecked(user_address.clone()),
         lockdrop_instance.clone(),
-        &ExecuteMsg::MigrateLiquidity {
-            terraswap_lp_token: terraswap_token_instance.clone(),
-            astroport_pool_addr: astro_pool_address.to_string(),
-            slippage_tolerance: None,
-        },
+        &extend_lockup_msg(2, 10, 6000),
         &[],
     )
     .unwrap();
 
-    // astro LP token balance (Lockdrop)
-    let astro_balance_resp: cw20::BalanceResponse = app
+    let pool_resp: PoolInfo = app
         .wrap()
         .query_wasm_smart(
-            &astro_lp_address,
-            &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.to_string(),
             },
         )
         .unwrap();
+    assert_eq!(
+        Uint128::from(12000u128),
+        pool_resp.terraswap_amount_in_lockups
+    );
+    assert_eq!(CUint256::from(21000u64), pool_resp.weighted_amount);
 
-    // Query pool after migration
-    let pool_resp_after_migration: PoolInfo = app
+    let user_resp: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
-            &QueryMsg::Pool {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
             },
         )
         .unwrap();
-
-    assert_eq!(
-        pool_resp_before_migration.terraswap_pool,
-        pool_resp_after_migration.terraswap_pool
-    );
-    assert_eq!(
-        pool_resp_before_migration.terraswap_amount_in_lockups,
-        pool_resp_after_migration.terraswap_amount_in_lockups
-    );
+    assert_eq!(1, user_resp.lockup_positions_index);
+    assert_eq!(1, user_resp.lockup_infos.len());
+    assert_eq!(10u64, user_resp.lockup_infos[0].duration);
     assert_eq!(
-        pool_resp_before_migration.incentives_share,
-        pool_resp_after_migration.incentives_share
+        Uint128::from(12000u128),
+        user_resp.lockup_infos[0].lp_units_locked
     );
 
+    // ######    ERROR :: Deposit window closed   ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 900_000000)
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &extend_lockup_msg(10, 20, 6000),
+            &[],
+        )
+        .unwrap_err();
     assert_eq!(
-        pool_resp_before_migration.incentives_share,
-        pool_resp_after_migration.incentives_share
-    );
-    assert_eq!(
-        Decimal::zero(),
-        pool_resp_after_migration.generator_astro_per_share
-    );
-    assert_eq!(
-        RestrictedVector::default(),
-        pool_resp_after_migration.generator_proxy_per_share
-    );
-    assert_eq!(
-        MigrationInfo {
-            terraswap_migrated_amount: astro_balance_resp.balance,
-            astroport_lp_token: astro_lp_address
-        },
-        pool_resp_after_migration.migration_info.unwrap()
+        err.root_cause().to_string(),
+        "Generic error: Deposit window closed"
     );
 }
 
 #[test]
-fn test_migrate_liquidity_uusd_uluna_pool() {
+fn test_transfer_lockup() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
 
-    let (_, lockdrop_instance, astroport_factory_instance, _, _) =
+    let (_, lockdrop_instance, astroport_factory_instance, _, update_msg) =
         instantiate_all_contracts(&mut app, owner.clone());
+    let astro_token = Addr::unchecked(update_msg.astro_token_address.clone().unwrap());
 
-    // Terraswap LP Token
-    let terraswap_token_contract = Box::new(ContractWrapper::new_with_empty(
-        cw20_base::contract::execute,
-        cw20_base::contract::instantiate,
-        cw20_base::contract::query,
-    ));
+    let cw20_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+
+    let cw20_code_id = app.store_code(cw20_contract);
+
+    let token_instance = app
+        .instantiate_contract(
+            cw20_code_id,
+            owner.clone(),
+            &TokenInstantiateMsg {
+                name: String::from("ANC"),
+                symbol: String::from("ANC"),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: owner.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            String::from("ANC"),
+            None,
+        )
+        .unwrap();
+
+    // Initialize and migrate liquidity for a pool
+    let (terraswap_token_instance, astro_lp_address, _) = initialize_and_migrate_liquidity_for_pool(
+        &mut app,
+        owner.clone(),
+        token_instance,
+        lockdrop_instance.clone(),
+        astroport_factory_instance,
+    );
+
+    // Add pool to ASTRO Generator
+    app.execute_contract(
+        owner.clone(),
+        Addr::unchecked(update_msg.generator_address.clone().unwrap()),
+        &astroport::generator::ExecuteMsg::SetupPools {
+            pools: vec![(astro_lp_address.to_string(), Uint128::from(10u128))],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Stake LP Tokens with Generator
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::StakeLpTokens {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_address = "user".to_string();
+    let user2_address = "user2".to_string();
+    let user3_address = "user3".to_string();
+
+    let transfer_lockup_msg = |recipient: &str| ExecuteMsg::TransferLockup {
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        duration: 10u64,
+        recipient: recipient.to_string(),
+    };
+
+    // ######    ERROR :: Lockup position can't be transferred to its owner   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &transfer_lockup_msg(&user_address),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Lockup position can't be transferred to its owner"
+    );
+
+    // ######    ERROR :: Recipient already has a lockup position with the same duration   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &transfer_lockup_msg(&user2_address),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Recipient already has a lockup position with the same duration"
+    );
+
+    // ######    ERROR :: Delegated ASTRO rewards of the lockup can't be transferred   ######
+    app.execute_contract(
+        Addr::unchecked(user2_address.clone()),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(1000u64),
+            allowlist_proof: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user2_address.clone()),
+            lockdrop_instance.clone(),
+            &transfer_lockup_msg(&user3_address),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Delegated ASTRO rewards of the lockup can't be transferred"
+    );
+
+    // ######    SUCCESS :: Pending generator rewards are claimed and the position is moved   ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10700001)
+    });
+
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    let lockup_info = user_info.lockup_infos[0].clone();
+    assert!(!lockup_info.claimable_generator_astro_debt.is_zero());
+
+    let user_astro_balance_before: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_token,
+            &Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &transfer_lockup_msg(&user3_address),
+        &[],
+    )
+    .unwrap();
+
+    let user_astro_balance_after: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_token,
+            &Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        lockup_info.claimable_generator_astro_debt,
+        user_astro_balance_after.balance - user_astro_balance_before.balance
+    );
+
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::zero(), user_info.total_astro_rewards);
+    assert_eq!(0, user_info.lockup_positions_index);
+    assert!(user_info.lockup_infos.is_empty());
+
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user3_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(1, user_info.lockup_positions_index);
+    assert_eq!(
+        lockup_info.lp_units_locked,
+        user_info.lockup_infos[0].lp_units_locked
+    );
+    assert_eq!(
+        lockup_info.astro_rewards,
+        user_info.lockup_infos[0].astro_rewards
+    );
+    assert_eq!(
+        lockup_info.astroport_lp_units,
+        user_info.lockup_infos[0].astroport_lp_units
+    );
+    assert_eq!(
+        lockup_info.claimable_generator_astro_debt,
+        user_info.lockup_infos[0].generator_astro_debt
+    );
+    assert_eq!(
+        Uint128::zero(),
+        user_info.lockup_infos[0].claimable_generator_astro_debt
+    );
+
+    // ######    ERROR :: The previous owner doesn't have the lockup position anymore   ######
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &transfer_lockup_msg(&user3_address),
+        &[],
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn test_withdraw_from_lockup() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, _, _, _update_msg) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    // Terraswap LP Token
+    let terraswap_token_contract = Box::new(ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ));
     let terraswap_token_code_id = app.store_code(terraswap_token_contract);
 
-    // Terraswap Pair
-    let terraswap_pair_contract = Box::new(
-        ContractWrapper::new_with_empty(
-            terraswap_pair::contract::execute,
-            terraswap_pair::contract::instantiate,
-            terraswap_pair::contract::query,
+    // LP Token #1
+    let terraswap_token_instance = app
+        .instantiate_contract(
+            terraswap_token_code_id,
+            Addr::unchecked("user".to_string()),
+            &terraswap::token::InstantiateMsg {
+                name: "terraswap liquidity token".to_string(),
+                symbol: "uLP".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: "pair_instance".to_string(),
+                    cap: None,
+                }),
+            },
+            &[],
+            String::from("terraswap_lp_token"),
+            None,
+        )
+        .unwrap();
+
+    // SUCCESSFULLY INITIALIZES POOL
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
+            terraswap_lp_token: terraswap_token_instance.to_string(),
+            incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_address = "user".to_string();
+    let _user2_address = "user2".to_string();
+
+    // Mint some LP tokens to user#1
+    app.execute_contract(
+        Addr::unchecked("pair_instance".to_string()),
+        terraswap_token_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: user_address.clone(),
+            amount: Uint128::from(124231343u128),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Deposit into Lockup Position
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
+    });
+
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        terraswap_token_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: lockdrop_instance.clone().to_string(),
+            amount: Uint128::from(10000000u128),
+            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Invalid withdrawal request   ######
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawFromLockup {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+                amount: Uint128::from(0u128),
+                duration: 1u64,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Invalid withdrawal request"
+    );
+
+    // ######    ERROR :: LP Token not supported   ######
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawFromLockup {
+                terraswap_lp_token: "wrong_terraswap_token_instance".to_string(),
+                amount: Uint128::from(10u128),
+                duration: 1u64,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "astroport_periphery::lockdrop::PoolInfo not found"
+    );
+
+    // ######    ERROR :: Invalid lockup position   ######
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawFromLockup {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+                amount: Uint128::from(10u128),
+                duration: 1u64,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "astroport_periphery::lockdrop::LockupInfoV1 not found"
+    );
+
+    // ######    SUCCESS :: SHOULD SUCCESSFULLY WITHDRAW LP TOKENS FROM POOL     ######
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::WithdrawFromLockup {
+            terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            amount: Uint128::from(10000000u128),
+            duration: 10u64,
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // check Pool Info
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(0u128), pool_resp.terraswap_amount_in_lockups);
+    assert_eq!(CUint256::from(0u64), pool_resp.weighted_amount);
+
+    // check User Info
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(0u128), user_resp.total_astro_rewards);
+    assert_eq!(Uint128::zero(), user_resp.delegated_astro_rewards);
+    assert_eq!(0, user_resp.lockup_infos.len());
+
+    // Deposit Again into Lockup
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        terraswap_token_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: lockdrop_instance.clone().to_string(),
+            amount: Uint128::from(10000000u128),
+            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}    ######
+    // First half of withdrawal window
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10350000)
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawFromLockup {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+                amount: Uint128::from(5000001u128),
+                duration: 10u64,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Amount exceeds maximum allowed withdrawal limit of 5000000"
+    );
+
+    // 2nd half of withdrawal window
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10390000)
+    });
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawFromLockup {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+                amount: Uint128::from(5000001u128),
+                duration: 10u64,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Amount exceeds maximum allowed withdrawal limit of 4200000"
+    );
+
+    // ######    SUCCESS :: SHOULD SUCCESSFULLY WITHDRAW LP TOKENS FROM POOL     ######
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::WithdrawFromLockup {
+            terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            amount: Uint128::from(4200000u128),
+            duration: 10u64,
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // check Pool Info
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(5800000u128),
+        pool_resp.terraswap_amount_in_lockups
+    );
+    assert_eq!(CUint256::from(10150000u64), pool_resp.weighted_amount);
+
+    // check User Info
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(1000000000u128), user_resp.total_astro_rewards);
+    assert_eq!(1, user_resp.lockup_infos.len());
+    assert_eq!(1, user_resp.lockup_infos.len());
+    assert_eq!(
+        Uint128::from(5800000u128),
+        user_resp.lockup_infos[0].lp_units_locked
+    );
+    assert_eq!(true, user_resp.lockup_infos[0].withdrawal_flag);
+
+    // ######    ERROR :: Amount exceeds maximum allowed withdrawal limit of {}    ######
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawFromLockup {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+                amount: Uint128::from(1u128),
+                duration: 10u64,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Withdrawal already happened. No more withdrawals accepted"
+    );
+}
+
+#[test]
+fn test_native_lp_token_lockup() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, _, _, update_msg) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    let lp_denom = "factory/native_pair/lp".to_string();
+    let user_address = Addr::unchecked("user");
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &user_address,
+                vec![
+                    Coin::new(1000_000000, lp_denom.clone()),
+                    Coin::new(1000_000000, "uusd"),
+                ],
+            )
+            .unwrap();
+    });
+
+    let native_pair_instance = instantiate_native_pair(&mut app, owner.clone(), &lp_denom);
+
+    // ######    ERROR :: The pair doesn't issue the LP token     ######
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::InitializePool {
+                terraswap_lp_token: "factory/other_pair/lp".to_string(),
+                incentives_share: 10000000u64,
+                source_dex: Some(SourceDex::Astroport),
+                native_lp_pool: Some(native_pair_instance.to_string()),
+                lockup_params: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: The pair doesn't issue the LP token"
+    );
+
+    // SUCCESSFULLY INITIALIZES POOL
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::InitializePool {
+            terraswap_lp_token: lp_denom.clone(),
+            incentives_share: 10000000u64,
+            source_dex: Some(SourceDex::Astroport),
+            native_lp_pool: Some(native_pair_instance.to_string()),
+            lockup_params: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: lp_denom.clone(),
+            },
+        )
+        .unwrap();
+    assert!(pool_resp.native_lp_token);
+    assert_eq!(native_pair_instance, pool_resp.terraswap_pool);
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
+    });
+
+    // ######    ERROR :: Unsupported native LP token     ######
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::IncreaseLockup { duration: 10u64 },
+            &[Coin::new(1000, "uusd")],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Unsupported native LP token"
+    );
+
+    // ######    ERROR :: Exactly one native LP token should be sent     ######
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::IncreaseLockup { duration: 10u64 },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Exactly one native LP token should be sent"
+    );
+
+    // ######    SUCCESS :: Native LP tokens locked     ######
+    app.execute_contract(
+        user_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::IncreaseLockup { duration: 10u64 },
+        &[Coin::new(1000_000000, lp_denom.clone())],
+    )
+    .unwrap();
+
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: lp_denom.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(1000_000000u64),
+        pool_resp.terraswap_amount_in_lockups
+    );
+
+    let user_resp: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(1, user_resp.lockup_infos.len());
+    assert_eq!(
+        Uint128::from(1000_000000u64),
+        user_resp.lockup_infos[0].lp_units_locked
+    );
+    assert_eq!(lp_denom, user_resp.lockup_infos[0].terraswap_lp_token);
+
+    let state_resp: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(vec![lp_denom.clone()], state_resp.supported_pairs_list);
+
+    // The pool key of a native LP token can't be confused with a cw20 LP token address
+    let native_pool_key = lockdrop::pool_key(&AssetInfo::NativeToken {
+        denom: lp_denom.clone(),
+    });
+    assert_eq!("native:factory/native_pair/lp", native_pool_key);
+    let nft_info: NftInfoResponse<lockdrop::LockUpInfoResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::PositionNftInfo {
+                token_id: lockdrop::lockup_position_token_id(&native_pool_key, &user_address, 10),
+            },
+        )
+        .unwrap();
+    assert_eq!(user_resp.lockup_infos[0], nft_info.extension);
+
+    // ######    SUCCESS :: Native LP tokens withdrawn from the lockup     ######
+    app.execute_contract(
+        user_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::WithdrawFromLockup {
+            terraswap_lp_token: lp_denom.clone(),
+            duration: 10u64,
+            amount: Uint128::from(400_000000u64),
+            user_address: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let balance = app
+        .wrap()
+        .query_balance(&user_address, lp_denom.clone())
+        .unwrap();
+    assert_eq!(Uint128::from(400_000000u64), balance.amount);
+
+    let balance = app
+        .wrap()
+        .query_balance(&lockdrop_instance, lp_denom.clone())
+        .unwrap();
+    assert_eq!(Uint128::from(600_000000u64), balance.amount);
+
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: lp_denom.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(600_000000u64),
+        pool_resp.terraswap_amount_in_lockups
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10_600_001)
+    });
+
+    // ######    ERROR :: Liquidity of native LP tokens can't be migrated     ######
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::MigrateLiquidity {
+                terraswap_lp_token: lp_denom.clone(),
+                astroport_pool_addr: "astroport_pair".to_string(),
+                slippage_tolerance: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Liquidity of native LP tokens can't be migrated"
+    );
+
+    app.execute_contract(
+        Addr::unchecked(update_msg.auction_contract_address.unwrap()),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::EnableClaims {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: The lockup isn't matured yet     ######
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+                terraswap_lp_token: lp_denom.clone(),
+                duration: 10u64,
+                withdraw_lp_stake: true,
+                user_address: None,
+                force_unlock: false,
+                amount: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: 6047999 seconds to unlock"
+    );
+
+    // ######    ERROR :: Native LP lockups can't be unlocked partially     ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 16_648_001)
+    });
+
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+                terraswap_lp_token: lp_denom.clone(),
+                duration: 10u64,
+                withdraw_lp_stake: true,
+                user_address: None,
+                force_unlock: false,
+                amount: Some(Uint128::from(100_000000u64)),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Lockups of native LP tokens can only be unlocked in full once matured"
+    );
+
+    // ######    SUCCESS :: The matured lockup returns native LP tokens     ######
+    app.execute_contract(
+        user_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+            terraswap_lp_token: lp_denom.clone(),
+            duration: 10u64,
+            withdraw_lp_stake: true,
+            user_address: None,
+            force_unlock: false,
+            amount: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let balance = app
+        .wrap()
+        .query_balance(&user_address, lp_denom.clone())
+        .unwrap();
+    assert_eq!(Uint128::from(1000_000000u64), balance.amount);
+
+    let balance = app
+        .wrap()
+        .query_balance(&lockdrop_instance, lp_denom.clone())
+        .unwrap();
+    assert!(balance.amount.is_zero());
+
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: lp_denom.clone(),
+            },
+        )
+        .unwrap();
+    assert!(pool_resp.terraswap_amount_in_lockups.is_zero());
+
+    // ######    ERROR :: The lockup is already unlocked     ######
+    let err = app
+        .execute_contract(
+            user_address,
+            lockdrop_instance,
+            &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+                terraswap_lp_token: lp_denom,
+                duration: 10u64,
+                withdraw_lp_stake: true,
+                user_address: None,
+                force_unlock: false,
+                amount: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Astro LP Tokens have already been claimed!"
+    );
+}
+
+#[test]
+fn test_position_nft() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, _, _, _update_msg) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    // Terraswap LP Token
+    let terraswap_token_contract = Box::new(ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ));
+    let terraswap_token_code_id = app.store_code(terraswap_token_contract);
+
+    let terraswap_token_instance = app
+        .instantiate_contract(
+            terraswap_token_code_id,
+            Addr::unchecked("user".to_string()),
+            &terraswap::token::InstantiateMsg {
+                name: "terraswap liquidity token".to_string(),
+                symbol: "uLP".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: "pair_instance".to_string(),
+                    cap: None,
+                }),
+            },
+            &[],
+            String::from("terraswap_lp_token"),
+            None,
+        )
+        .unwrap();
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::InitializePool {
+            terraswap_lp_token: terraswap_token_instance.to_string(),
+            incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_address = Addr::unchecked("user");
+    let user2_address = Addr::unchecked("user2");
+
+    app.execute_contract(
+        Addr::unchecked("pair_instance".to_string()),
+        terraswap_token_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: user_address.to_string(),
+            amount: Uint128::from(124231343u128),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let position_nft = instantiate_position_nft(&mut app, owner.clone(), &lockdrop_instance);
+    let set_position_nft_msg = ExecuteMsg::SetPositionNft {
+        contract: position_nft.to_string(),
+    };
+
+    // ######    ERROR :: Unauthorized     ######
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &set_position_nft_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    SUCCESS :: Position NFT contract is set     ######
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &set_position_nft_msg,
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Position NFT contract already set     ######
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &set_position_nft_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Position NFT contract already set"
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
+    });
+
+    let increase_lockup_msg = cw20::Cw20ExecuteMsg::Send {
+        contract: lockdrop_instance.to_string(),
+        amount: Uint128::from(10000000u128),
+        msg: to_binary(&Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
+    };
+
+    // ######    SUCCESS :: Lockup mints the position NFT to the user     ######
+    app.execute_contract(
+        user_address.clone(),
+        terraswap_token_instance.clone(),
+        &increase_lockup_msg,
+        &[],
+    )
+    .unwrap();
+
+    let token_id =
+        lockdrop::lockup_position_token_id(terraswap_token_instance.as_str(), &user_address, 10);
+    let owner_resp: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &position_nft,
+            &cw721_base::QueryMsg::OwnerOf {
+                token_id: token_id.clone(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_resp.owner, user_address.to_string());
+
+    // Position NFT metadata is the lockup position info
+    let nft_info: NftInfoResponse<lockdrop::LockUpInfoResponse> = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::PositionNftInfo {
+                token_id: token_id.clone(),
+            },
+        )
+        .unwrap();
+    let lockup_info: lockdrop::LockUpInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::LockUpInfo {
+                user_address: user_address.to_string(),
+                terraswap_lp_token: terraswap_token_instance.to_string(),
+                duration: 10u64,
+            },
+        )
+        .unwrap();
+    assert_eq!(nft_info.extension, lockup_info);
+    assert_eq!(
+        nft_info.extension.lp_units_locked,
+        Uint128::from(10000000u128)
+    );
+
+    // The lockup position is sold to user2
+    app.execute_contract(
+        user_address.clone(),
+        position_nft.clone(),
+        &cw721_base::ExecuteMsg::<Empty>::TransferNft {
+            recipient: user2_address.to_string(),
+            token_id: token_id.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: The user can't increase the sold lockup position     ######
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            terraswap_token_instance.clone(),
+            &increase_lockup_msg,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Position NFT is owned by another address"
+    );
+
+    // ######    ERROR :: The user can't withdraw from the sold lockup position     ######
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::WithdrawFromLockup {
+                terraswap_lp_token: terraswap_token_instance.to_string(),
+                amount: Uint128::from(10000000u128),
+                duration: 10u64,
+                user_address: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Sender doesn't own the position"
+    );
+
+    let extend_lockup_msg = |user_address: Option<String>| ExecuteMsg::ExtendLockup {
+        terraswap_lp_token: terraswap_token_instance.to_string(),
+        from_duration: 10u64,
+        to_duration: 20u64,
+        amount: Uint128::from(4000000u128),
+        user_address,
+    };
+
+    // ######    ERROR :: The user can't extend the sold lockup position     ######
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &extend_lockup_msg(None),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Sender doesn't own the position"
+    );
+
+    // ######    SUCCESS :: The NFT owner extends a part of the lockup position     ######
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &extend_lockup_msg(Some(user_address.to_string())),
+        &[],
+    )
+    .unwrap();
+
+    // The NFT of the extended position is minted to the NFT owner
+    let owner_resp: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &position_nft,
+            &cw721_base::QueryMsg::OwnerOf {
+                token_id: lockdrop::lockup_position_token_id(
+                    terraswap_token_instance.as_str(),
+                    &user_address,
+                    20,
+                ),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner_resp.owner, user2_address.to_string());
+
+    // ######    SUCCESS :: The NFT owner withdraws LP tokens from the lockup position     ######
+    app.execute_contract(
+        user2_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::WithdrawFromLockup {
+            terraswap_lp_token: terraswap_token_instance.to_string(),
+            amount: Uint128::from(6000000u128),
+            duration: 10u64,
+            user_address: Some(user_address.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let balance_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &terraswap_token_instance,
+            &Cw20QueryMsg::Balance {
+                address: user2_address.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance_resp.balance, Uint128::from(6000000u128));
+
+    // The lockup position is closed, so its NFT is burnt
+    app.wrap()
+        .query_wasm_smart::<OwnerOfResponse>(
+            &position_nft,
+            &cw721_base::QueryMsg::OwnerOf {
+                token_id,
+                include_expired: None,
+            },
+        )
+        .unwrap_err();
+}
+
+#[test]
+fn test_migrate_liquidity() {
+    let owner = Addr::unchecked("contract_owner");
+    let mut app = mock_app();
+
+    let (_, lockdrop_instance, astroport_factory_instance, _, _) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    // CW20 TOKEN :: Dummy token
+    let cw20_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+
+    let cw20_code_id = app.store_code(cw20_contract);
+
+    let anc_instance = app
+        .instantiate_contract(
+            cw20_code_id,
+            owner.clone(),
+            &TokenInstantiateMsg {
+                name: String::from("ANC"),
+                symbol: String::from("ANC"),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: owner.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            String::from("ANC"),
+            None,
+        )
+        .unwrap();
+
+    // Terraswap LP Token
+    let terraswap_token_contract = Box::new(ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ));
+    let terraswap_token_code_id = app.store_code(terraswap_token_contract);
+
+    // Terraswap Pair
+    let terraswap_pair_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            terraswap_pair::contract::execute,
+            terraswap_pair::contract::instantiate,
+            terraswap_pair::contract::query,
+        )
+        .with_reply_empty(terraswap_pair::contract::reply),
+    );
+    let terraswap_pair_code_id = app.store_code(terraswap_pair_contract);
+
+    // LP POOL INSTANCE
+    let terraswap_pool_instance = app
+        .instantiate_contract(
+            terraswap_pair_code_id,
+            Addr::unchecked("user".to_string()),
+            &terraswap::pair::InstantiateMsg {
+                asset_infos: [
+                    terraswap::asset::AssetInfo::Token {
+                        contract_addr: anc_instance.clone().to_string(),
+                    },
+                    terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                ],
+                token_code_id: terraswap_token_code_id,
+                asset_decimals: [6, 6],
+            },
+            &[],
+            String::from("terraswap_pool"),
+            None,
+        )
+        .unwrap();
+
+    // Query LP Token
+    let pair_response: terraswap::asset::PairInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &terraswap_pool_instance,
+            &terraswap::pair::QueryMsg::Pair {},
+        )
+        .unwrap();
+    let terraswap_token_instance = pair_response.liquidity_token;
+
+    // SUCCESSFULLY INITIALIZES POOL
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
+            terraswap_lp_token: terraswap_token_instance.to_string(),
+            incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_address = "user".to_string();
+    let user2_address = "user2".to_string();
+
+    // Mint ANC to users
+    app.execute_contract(
+        owner.clone(),
+        anc_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: user_address.clone(),
+            amount: Uint128::from(10000_000000u64),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        owner.clone(),
+        anc_instance.clone(),
+        &cw20::Cw20ExecuteMsg::Mint {
+            recipient: user2_address.clone(),
+            amount: Uint128::from(10000_000000u64),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Set UST user balances
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(user_address.clone()),
+                vec![Coin::new(1000000_000000, "uusd")],
+            )
+            .unwrap();
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(user2_address.clone()),
+                vec![Coin::new(1000000_000000, "uusd")],
+            )
+            .unwrap();
+    });
+
+    // user#1 adds liquidity to Terraswap Pool and locks that in Lockdrop contract
+    // increase allowance
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        anc_instance.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: terraswap_pool_instance.clone().to_string(),
+            amount: Uint128::new(1000_000000),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // add Liquidity to Terraswap pool
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        terraswap_pool_instance.clone(),
+        &terraswap::pair::ExecuteMsg::ProvideLiquidity {
+            assets: [
+                terraswap::asset::Asset {
+                    info: terraswap::asset::AssetInfo::Token {
+                        contract_addr: anc_instance.clone().to_string(),
+                    },
+                    amount: Uint128::from(1000_000000u64),
+                },
+                terraswap::asset::Asset {
+                    info: terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::from(1000_000000u64),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: None,
+        },
+        &[Coin::new(1000_000000, "uusd")],
+    )
+    .unwrap();
+
+    // Query LP balance
+    let lp_balance_res: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &terraswap_token_instance.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    let user_lp_balance = lp_balance_res.balance;
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
+    });
+
+    // Lock LP Tokens into Lockup Position
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        Addr::unchecked(terraswap_token_instance.clone()),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: lockdrop_instance.clone().to_string(),
+            amount: user_lp_balance,
+            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Increase timestamp for window closure
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
+    });
+
+    // Create Astroport Pair
+    app.execute_contract(
+        Addr::unchecked("user"),
+        astroport_factory_instance.clone(),
+        &astroport::factory::ExecuteMsg::CreatePair {
+            asset_infos: [
+                astroport::asset::AssetInfo::Token {
+                    contract_addr: anc_instance.clone(),
+                },
+                astroport::asset::AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+            ],
+            init_params: None,
+            pair_type: astroport::factory::PairType::Xyk {},
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Query Astroport addresses
+    let pair_resp: astroport::asset::PairInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &astroport_factory_instance,
+            &astroport::factory::QueryMsg::Pair {
+                asset_infos: [
+                    astroport::asset::AssetInfo::Token {
+                        contract_addr: anc_instance.clone(),
+                    },
+                    astroport::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                ],
+            },
+        )
+        .unwrap();
+    let astro_pool_address = pair_resp.contract_addr;
+    let astro_lp_address = pair_resp.liquidity_token;
+
+    // astro LP token balance (Lockdrop)
+    let terraswap_balance_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &terraswap_token_instance,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Query pool before migration
+    let pool_resp_before_migration: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        terraswap_balance_resp.balance,
+        pool_resp_before_migration.terraswap_amount_in_lockups
+    );
+    assert_eq!(
+        CUint256::from(1750000000u128),
+        pool_resp_before_migration.weighted_amount
+    );
+    assert_eq!(false, pool_resp_before_migration.is_staked);
+    assert_eq!(None, pool_resp_before_migration.migration_info);
+
+    // Migrate Liquidity
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::MigrateLiquidity {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+            astroport_pool_addr: astro_pool_address.to_string(),
+            slippage_tolerance: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // astro LP token balance (Lockdrop)
+    let astro_balance_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_lp_address,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Query pool after migration
+    let pool_resp_after_migration: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        pool_resp_before_migration.terraswap_pool,
+        pool_resp_after_migration.terraswap_pool
+    );
+    assert_eq!(
+        pool_resp_before_migration.terraswap_amount_in_lockups,
+        pool_resp_after_migration.terraswap_amount_in_lockups
+    );
+    assert_eq!(
+        pool_resp_before_migration.incentives_share,
+        pool_resp_after_migration.incentives_share
+    );
+
+    assert_eq!(
+        pool_resp_before_migration.incentives_share,
+        pool_resp_after_migration.incentives_share
+    );
+    assert_eq!(
+        Decimal::zero(),
+        pool_resp_after_migration.generator_astro_per_share
+    );
+    assert_eq!(
+        RestrictedVector::default(),
+        pool_resp_after_migration.generator_proxy_per_share
+    );
+    assert_eq!(
+        MigrationInfo {
+            terraswap_migrated_amount: astro_balance_resp.balance,
+            astroport_lp_token: astro_lp_address
+        },
+        pool_resp_after_migration.migration_info.unwrap()
+    );
+}
+
+#[test]
+fn test_migrate_liquidity_uusd_uluna_pool() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, astroport_factory_instance, _, _) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    // Terraswap LP Token
+    let terraswap_token_contract = Box::new(ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ));
+    let terraswap_token_code_id = app.store_code(terraswap_token_contract);
+
+    // Terraswap Pair
+    let terraswap_pair_contract = Box::new(
+        ContractWrapper::new_with_empty(
+            terraswap_pair::contract::execute,
+            terraswap_pair::contract::instantiate,
+            terraswap_pair::contract::query,
+        )
+        .with_reply_empty(terraswap_pair::contract::reply),
+    );
+    let terraswap_pair_code_id = app.store_code(terraswap_pair_contract);
+
+    // LP POOL INSTANCE
+    let terraswap_pool_instance = app
+        .instantiate_contract(
+            terraswap_pair_code_id,
+            Addr::unchecked("user".to_string()),
+            &terraswap::pair::InstantiateMsg {
+                asset_infos: [
+                    terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                ],
+                token_code_id: terraswap_token_code_id,
+                asset_decimals: [6, 6],
+            },
+            &[],
+            String::from("terraswap_pool"),
+            None,
+        )
+        .unwrap();
+
+    // Query LP Token
+    let pair_response: terraswap::asset::PairInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &terraswap_pool_instance,
+            &terraswap::pair::QueryMsg::Pair {},
+        )
+        .unwrap();
+    let terraswap_token_instance = pair_response.liquidity_token;
+
+    // SUCCESSFULLY INITIALIZES POOL
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
+            terraswap_lp_token: terraswap_token_instance.to_string(),
+            incentives_share: 10000000u64,
+            source_dex: None,
+            native_lp_pool: None,
+            lockup_params: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_address = "user".to_string();
+    let user2_address = "user2".to_string();
+
+    // Set UST user balances
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(user_address.clone()),
+                vec![
+                    Coin::new(1000000_000000, "uusd"),
+                    Coin::new(1000000_000000, "uluna"),
+                ],
+            )
+            .unwrap();
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(user2_address.clone()),
+                vec![
+                    Coin::new(1000000_000000, "uusd"),
+                    Coin::new(1000000_000000, "uluna"),
+                ],
+            )
+            .unwrap();
+    });
+
+    // user#1 adds liquidity to Terraswap Pool and locks that in Lockdrop contract
+
+    // add Liquidity to Terraswap pool
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        terraswap_pool_instance.clone(),
+        &terraswap::pair::ExecuteMsg::ProvideLiquidity {
+            assets: [
+                terraswap::asset::Asset {
+                    info: terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::from(1000_000000u64),
+                },
+                terraswap::asset::Asset {
+                    info: terraswap::asset::AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    amount: Uint128::from(1000_000000u64),
+                },
+            ],
+            slippage_tolerance: None,
+            receiver: None,
+        },
+        &[
+            Coin::new(1000_000000, "uluna"),
+            Coin::new(1000_000000, "uusd"),
+        ],
+    )
+    .unwrap();
+
+    // Query LP balance
+    let lp_balance_res: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &terraswap_token_instance.clone(),
+            &cw20::Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    let user_lp_balance = lp_balance_res.balance;
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
+    });
+
+    // Lock LP Tokens into Lockup Position
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        Addr::unchecked(terraswap_token_instance.clone()),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: lockdrop_instance.clone().to_string(),
+            amount: user_lp_balance,
+            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Increase timestamp for window closure
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
+    });
+
+    // Create Astroport Pair
+    app.execute_contract(
+        Addr::unchecked("user"),
+        astroport_factory_instance.clone(),
+        &astroport::factory::ExecuteMsg::CreatePair {
+            asset_infos: [
+                astroport::asset::AssetInfo::NativeToken {
+                    denom: "uusd".to_string(),
+                },
+                astroport::asset::AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            ],
+            init_params: None,
+            pair_type: astroport::factory::PairType::Xyk {},
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Query Astroport addresses
+    let pair_resp: astroport::asset::PairInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &astroport_factory_instance,
+            &astroport::factory::QueryMsg::Pair {
+                asset_infos: [
+                    astroport::asset::AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    astroport::asset::AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                ],
+            },
+        )
+        .unwrap();
+
+    let astro_pool_address = pair_resp.contract_addr;
+    let astro_lp_address = pair_resp.liquidity_token;
+
+    // astro LP token balance (Lockdrop)
+    let terraswap_balance_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &terraswap_token_instance,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Query pool before migration
+    let pool_resp_before_migration: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        terraswap_balance_resp.balance,
+        pool_resp_before_migration.terraswap_amount_in_lockups
+    );
+    assert_eq!(
+        CUint256::from(1750000000u128),
+        pool_resp_before_migration.weighted_amount
+    );
+    assert_eq!(false, pool_resp_before_migration.is_staked);
+    assert_eq!(None, pool_resp_before_migration.migration_info);
+
+    // Migrate Liquidity
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::MigrateLiquidity {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+            astroport_pool_addr: astro_pool_address.to_string(),
+            slippage_tolerance: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // astro LP token balance (Lockdrop)
+    let astro_balance_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_lp_address,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    // Query pool after migration
+    let pool_resp_after_migration: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        pool_resp_before_migration.terraswap_pool,
+        pool_resp_after_migration.terraswap_pool
+    );
+    assert_eq!(
+        pool_resp_before_migration.terraswap_amount_in_lockups,
+        pool_resp_after_migration.terraswap_amount_in_lockups
+    );
+    assert_eq!(
+        pool_resp_before_migration.incentives_share,
+        pool_resp_after_migration.incentives_share
+    );
+
+    assert_eq!(
+        pool_resp_before_migration.incentives_share,
+        pool_resp_after_migration.incentives_share
+    );
+    assert_eq!(
+        Decimal::zero(),
+        pool_resp_after_migration.generator_astro_per_share
+    );
+    assert_eq!(
+        RestrictedVector::default(),
+        pool_resp_after_migration.generator_proxy_per_share
+    );
+    assert_eq!(
+        MigrationInfo {
+            terraswap_migrated_amount: astro_balance_resp.balance,
+            astroport_lp_token: astro_lp_address
+        },
+        pool_resp_after_migration.migration_info.unwrap()
+    );
+}
+
+#[test]
+fn test_migrate_liquidity_from_astroport_source_dex() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, astroport_factory_instance, _, _) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    let asset_infos = [
+        AssetInfo::NativeToken {
+            denom: "uusd".to_string(),
+        },
+        AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        },
+    ];
+
+    // Source pair with the Astroport interface
+    app.execute_contract(
+        Addr::unchecked("user"),
+        astroport_factory_instance.clone(),
+        &astroport::factory::ExecuteMsg::CreatePair {
+            asset_infos: asset_infos.clone(),
+            init_params: None,
+            pair_type: astroport::factory::PairType::Xyk {},
+        },
+        &[],
+    )
+    .unwrap();
+    let source_pair_resp: astroport::asset::PairInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &astroport_factory_instance,
+            &astroport::factory::QueryMsg::Pair {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap();
+    let source_pool_instance = source_pair_resp.contract_addr;
+    let source_lp_token = source_pair_resp.liquidity_token;
+
+    // SUCCESSFULLY INITIALIZES POOL
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::InitializePool {
+            terraswap_lp_token: source_lp_token.to_string(),
+            incentives_share: 10000000u64,
+            source_dex: Some(SourceDex::Astroport),
+            native_lp_pool: None,
+            lockup_params: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: source_lp_token.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(SourceDex::Astroport, pool_resp.source_dex);
+    assert_eq!(source_pool_instance, pool_resp.terraswap_pool);
+
+    let user_address = Addr::unchecked("user");
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &user_address,
+                vec![
+                    Coin::new(1000000_000000, "uusd"),
+                    Coin::new(1000000_000000, "uluna"),
+                ],
+            )
+            .unwrap();
+    });
+
+    // user adds liquidity to the source pool and locks LP tokens in the Lockdrop contract
+    app.execute_contract(
+        user_address.clone(),
+        source_pool_instance.clone(),
+        &astroport::pair::ExecuteMsg::ProvideLiquidity {
+            assets: [
+                astroport::asset::Asset {
+                    info: asset_infos[0].clone(),
+                    amount: Uint128::from(1000_000000u64),
+                },
+                astroport::asset::Asset {
+                    info: asset_infos[1].clone(),
+                    amount: Uint128::from(1000_000000u64),
+                },
+            ],
+            slippage_tolerance: None,
+            auto_stake: None,
+            receiver: None,
+        },
+        &[
+            Coin::new(1000_000000, "uluna"),
+            Coin::new(1000_000000, "uusd"),
+        ],
+    )
+    .unwrap();
+
+    let lp_balance_res: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &source_lp_token,
+            &Cw20QueryMsg::Balance {
+                address: user_address.to_string(),
+            },
+        )
+        .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
+    });
+
+    app.execute_contract(
+        user_address.clone(),
+        source_lp_token.clone(),
+        &Cw20ExecuteMsg::Send {
+            contract: lockdrop_instance.to_string(),
+            amount: lp_balance_res.balance,
+            msg: to_binary(&Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Increase timestamp for window closure
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
+    });
+
+    // Destination Astroport pair
+    app.execute_contract(
+        user_address,
+        astroport_factory_instance.clone(),
+        &astroport::factory::ExecuteMsg::CreatePair {
+            asset_infos: asset_infos.clone(),
+            init_params: Some(to_binary(&astroport::pair::StablePoolParams { amp: 100 }).unwrap()),
+            pair_type: astroport::factory::PairType::Stable {},
+        },
+        &[],
+    )
+    .unwrap();
+    let pairs_resp: astroport::factory::PairsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astroport_factory_instance,
+            &astroport::factory::QueryMsg::Pairs {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    let astro_pair = pairs_resp
+        .pairs
+        .into_iter()
+        .find(|pair| pair.pair_type == astroport::factory::PairType::Stable {})
+        .unwrap();
+
+    // Migrate Liquidity
+    app.execute_contract(
+        owner,
+        lockdrop_instance.clone(),
+        &ExecuteMsg::MigrateLiquidity {
+            terraswap_lp_token: source_lp_token.to_string(),
+            astroport_pool_addr: astro_pair.contract_addr.to_string(),
+            slippage_tolerance: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Source LP tokens are burnt and liquidity is provided to the destination pair
+    let source_balance_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &source_lp_token,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::zero(), source_balance_resp.balance);
+
+    let astro_balance_resp: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_pair.liquidity_token,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(!astro_balance_resp.balance.is_zero());
+
+    let pool_resp: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: source_lp_token.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        MigrationInfo {
+            terraswap_migrated_amount: lp_balance_res.balance,
+            astroport_lp_token: astro_pair.liquidity_token,
+        },
+        pool_resp.migration_info.unwrap()
+    );
+}
+
+#[test]
+fn test_stake_lp_tokens() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, astroport_factory_instance, _, update_msg) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    let cw20_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+
+    let cw20_code_id = app.store_code(cw20_contract);
+
+    let token_instance = app
+        .instantiate_contract(
+            cw20_code_id,
+            owner.clone(),
+            &TokenInstantiateMsg {
+                name: String::from("ANC"),
+                symbol: String::from("ANC"),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: owner.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            String::from("ANC"),
+            None,
+        )
+        .unwrap();
+
+    // Initialize and migrate liquidity for a pool
+    let (terraswap_token_instance, astro_lp_address, _) = initialize_and_migrate_liquidity_for_pool(
+        &mut app,
+        owner.clone(),
+        token_instance,
+        lockdrop_instance.clone(),
+        astroport_factory_instance,
+    );
+
+    // Add pool to ASTRO Generator
+    app.execute_contract(
+        Addr::unchecked(owner.clone()),
+        Addr::unchecked(update_msg.clone().generator_address.unwrap()),
+        &astroport::generator::ExecuteMsg::SetupPools {
+            pools: vec![(astro_lp_address.to_string(), Uint128::from(10u128))],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Unauthorized    ######
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("not_owner".to_string()),
+            lockdrop_instance.clone(),
+            &astroport_periphery::lockdrop::ExecuteMsg::StakeLpTokens {
+                terraswap_lp_token: terraswap_token_instance.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+
+    // ######    SHOULD SUCCESSFULLY STAKE LP TOKENS WITH GENERATOR   ######
+
+    // astro LP token balance (Lockdrop)
+    let lockdrop_astro_balance_before_migration: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_lp_address,
+            &Cw20QueryMsg::Balance {
+                address: lockdrop_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(owner.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::StakeLpTokens {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // astro LP token balance (Generator)
+    let generator_astro_balance_after_migration: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_lp_address,
+            &Cw20QueryMsg::Balance {
+                address: update_msg.clone().generator_address.unwrap().to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        lockdrop_astro_balance_before_migration.balance,
+        generator_astro_balance_after_migration.balance
+    );
+
+    // Query pool after migration
+    let pool_resp_after_migration: PoolInfo = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::Pool {
+                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(true, pool_resp_after_migration.is_staked);
+}
+
+#[test]
+fn test_claim_rewards() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, astroport_factory_instance, _, update_msg) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    let cw20_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+
+    let cw20_code_id = app.store_code(cw20_contract);
+
+    let token_instance = app
+        .instantiate_contract(
+            cw20_code_id,
+            owner.clone(),
+            &TokenInstantiateMsg {
+                name: String::from("ANC"),
+                symbol: String::from("ANC"),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: owner.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            String::from("ANC"),
+            None,
+        )
+        .unwrap();
+
+    // Initialize and migrate liquidity for a pool
+    let (terraswap_token_instance, astro_lp_address, _) = initialize_and_migrate_liquidity_for_pool(
+        &mut app,
+        owner.clone(),
+        token_instance,
+        lockdrop_instance.clone(),
+        astroport_factory_instance,
+    );
+
+    // Add pool to ASTRO Generator
+    app.execute_contract(
+        Addr::unchecked(owner.clone()),
+        Addr::unchecked(update_msg.clone().generator_address.unwrap()),
+        &astroport::generator::ExecuteMsg::SetupPools {
+            pools: vec![(astro_lp_address.to_string(), Uint128::from(10u128))],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Stake LP Tokens with Generator
+    app.execute_contract(
+        Addr::unchecked(owner.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::StakeLpTokens {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_address = "user".to_string();
+    let user2_address = "user2".to_string();
+
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(false, user_info.astro_transferred);
+    assert_eq!(Uint128::zero(), user_info.delegated_astro_rewards);
+    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
+        lp_units_locked: Uint128::from(1000000000u64),
+        withdrawal_flag: false,
+        astro_rewards: Uint128::from(500000000u64),
+        duration: 10u64,
+        generator_astro_debt: Uint128::from(0u64),
+        claimable_generator_astro_debt: Uint128::from(0u64),
+        generator_proxy_debt: RestrictedVector::default(),
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
+        unlock_timestamp: EPOCH_START + 16648000u64,
+        astroport_lp_units: Some(Uint128::from(1000000000u64)),
+        astroport_lp_token: Some(astro_lp_address.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        astroport_lp_transferred: None,
+    };
+    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
+    });
+
+    // DELEGATE ASTRO TO AUCTION
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(500000000u64),
+            allowlist_proof: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(user2_address.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(1000u64),
+            allowlist_proof: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Query state
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(500001000u64), state.total_astro_delegated);
+
+    // UNDELEGATE ASTRO FROM AUCTION
+    app.execute_contract(
+        Addr::unchecked(user2_address.clone()),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::UndelegateAstro {
+            amount: Uint128::from(1000u64),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), state.total_astro_delegated);
+
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::zero(), user_info.delegated_astro_rewards);
+
+    // DELEGATE UNDELEGATED ASTRO AGAIN
+    app.execute_contract(
+        Addr::unchecked(user2_address.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(1000u64),
+            allowlist_proof: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(500001000u64), state.total_astro_delegated);
+
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(
+        Uint128::from(500000000u64),
+        user_info.delegated_astro_rewards
+    );
+
+    // DEPOSIT UST INTO AUCTION
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(432423u128),
+        }],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Reward claim not allowed    ######
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+                terraswap_lp_token: terraswap_token_instance.clone(),
+                duration: 10u64,
+                withdraw_lp_stake: false,
+                user_address: None,
+                force_unlock: false,
+                amount: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Reward claim not allowed"
+    );
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10750001)
+    });
+
+    // INITIALIZE ASTRO-UST POOL TO ENABLE CLAIMS
+    app.execute_contract(
+        Addr::unchecked(owner.to_string()),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: Invalid Lockup    ######
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+                terraswap_lp_token: terraswap_token_instance.clone(),
+                duration: 9u64,
+                withdraw_lp_stake: false,
+                user_address: None,
+                force_unlock: false,
+                amount: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err.root_cause().to_string(),
+        "astroport_periphery::lockdrop::LockupInfoV1 not found"
+    );
+
+    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS   ######
+
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(
+        Uint128::from(500000000u64),
+        user_info.delegated_astro_rewards
+    );
+    assert_eq!(false, user_info.astro_transferred);
+    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
+        lp_units_locked: Uint128::from(1000000000u64),
+        withdrawal_flag: false,
+        astro_rewards: Uint128::from(500000000u64),
+        duration: 10u64,
+        generator_astro_debt: Uint128::from(0u64),
+        claimable_generator_astro_debt: Uint128::from(172800000000u64),
+        generator_proxy_debt: RestrictedVector::default(),
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
+        unlock_timestamp: EPOCH_START + 16648000u64,
+        astroport_lp_units: Some(Uint128::from(1000000000u64)),
+        astroport_lp_token: Some(astro_lp_address.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        astroport_lp_transferred: None,
+    };
+    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+
+    let user1_astro_balance_before: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &update_msg.astro_token_address.clone().unwrap(),
+            &Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+            duration: 10u64,
+            withdraw_lp_stake: false,
+            user_address: None,
+            force_unlock: false,
+            amount: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(
+        Uint128::from(500000000u64),
+        user_info.delegated_astro_rewards
+    );
+    assert_eq!(true, user_info.astro_transferred);
+    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
+        lp_units_locked: Uint128::from(1000000000u64),
+        withdrawal_flag: false,
+        astro_rewards: Uint128::from(500000000u64),
+        duration: 10u64,
+        generator_astro_debt: Uint128::from(172800000000u64),
+        claimable_generator_astro_debt: Uint128::from(0u64),
+        generator_proxy_debt: RestrictedVector::default(),
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
+        unlock_timestamp: EPOCH_START + 16648000u64,
+        astroport_lp_units: Some(Uint128::from(1000000000u64)),
+        astroport_lp_token: Some(astro_lp_address.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        astroport_lp_transferred: None,
+    };
+    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+
+    let user1_astro_balance_after: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &update_msg.astro_token_address.clone().unwrap(),
+            &Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+
+    let astro_reward_claimed =
+        user1_astro_balance_after.balance - user1_astro_balance_before.balance;
+    assert_eq!(
+        astro_reward_claimed,
+        user_info.total_astro_rewards - user_info.delegated_astro_rewards
+            + Uint128::from(172800000000u64)
+    );
+
+    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS :: user-2  ######
+
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
+
+    let user2_astro_balance_before: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &update_msg.astro_token_address.clone().unwrap(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(Uint128::from(1000u64), user_info.delegated_astro_rewards);
+    assert_eq!(false, user_info.astro_transferred);
+    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
+        lp_units_locked: Uint128::from(1000000000u64),
+        withdrawal_flag: false,
+        astro_rewards: Uint128::from(500000000u64),
+        duration: 10u64,
+        generator_astro_debt: Uint128::from(0u64),
+        claimable_generator_astro_debt: Uint128::from(172800000000u64),
+        generator_proxy_debt: RestrictedVector::default(),
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
+        unlock_timestamp: EPOCH_START + 16648000u64,
+        astroport_lp_units: Some(Uint128::from(1000000000u64)),
+        astroport_lp_token: Some(astro_lp_address.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        astroport_lp_transferred: None,
+    };
+    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+
+    app.execute_contract(
+        Addr::unchecked(user2_address.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+            duration: 10u64,
+            withdraw_lp_stake: false,
+            user_address: None,
+            force_unlock: false,
+            amount: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.clone(),
+            },
         )
-        .with_reply_empty(terraswap_pair::contract::reply),
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(Uint128::from(1000u64), user_info.delegated_astro_rewards);
+    assert_eq!(true, user_info.astro_transferred);
+    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
+        lp_units_locked: Uint128::from(1000000000u64),
+        withdrawal_flag: false,
+        astro_rewards: Uint128::from(500000000u64),
+        duration: 10u64,
+        generator_astro_debt: Uint128::from(172800000000u64),
+        claimable_generator_astro_debt: Uint128::from(0u64),
+        generator_proxy_debt: RestrictedVector::default(),
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
+        unlock_timestamp: EPOCH_START + 16648000u64,
+        astroport_lp_units: Some(Uint128::from(1000000000u64)),
+        astroport_lp_token: Some(astro_lp_address.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        astroport_lp_transferred: None,
+    };
+    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+
+    let user2_astro_balance_after: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &update_msg.astro_token_address.clone().unwrap(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
+
+    let astro_reward_claimed =
+        user2_astro_balance_after.balance - user2_astro_balance_before.balance;
+    assert_eq!(
+        astro_reward_claimed,
+        user_info.total_astro_rewards - user_info.delegated_astro_rewards
+            + Uint128::from(172800000000u64)
     );
-    let terraswap_pair_code_id = app.store_code(terraswap_pair_contract);
+}
 
-    // LP POOL INSTANCE
-    let terraswap_pool_instance = app
+#[test]
+fn test_claim_rewards_and_unlock() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, astroport_factory_instance, _, update_msg) =
+        instantiate_all_contracts(&mut app, owner.clone());
+
+    let cw20_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+
+    let cw20_code_id = app.store_code(cw20_contract);
+
+    let token_instance = app
         .instantiate_contract(
-            terraswap_pair_code_id,
-            Addr::unchecked("user".to_string()),
-            &terraswap::pair::InstantiateMsg {
-                asset_infos: [
-                    terraswap::asset::AssetInfo::NativeToken {
-                        denom: "uluna".to_string(),
-                    },
-                    terraswap::asset::AssetInfo::NativeToken {
-                        denom: "uusd".to_string(),
-                    },
-                ],
-                token_code_id: terraswap_token_code_id,
-                asset_decimals: [6, 6],
+            cw20_code_id,
+            owner.clone(),
+            &TokenInstantiateMsg {
+                name: String::from("ANC"),
+                symbol: String::from("ANC"),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: owner.to_string(),
+                    cap: None,
+                }),
+                marketing: None,
             },
             &[],
-            String::from("terraswap_pool"),
+            String::from("ANC"),
             None,
         )
         .unwrap();
 
-    // Query LP Token
-    let pair_response: terraswap::asset::PairInfo = app
-        .wrap()
-        .query_wasm_smart(
-            &terraswap_pool_instance,
-            &terraswap::pair::QueryMsg::Pair {},
-        )
-        .unwrap();
-    let terraswap_token_instance = pair_response.liquidity_token;
+    // Initialize and migrate liquidity for a pool
+    let (terraswap_token_instance, astro_lp_address, _) = initialize_and_migrate_liquidity_for_pool(
+        &mut app,
+        owner.clone(),
+        token_instance,
+        lockdrop_instance.clone(),
+        astroport_factory_instance,
+    );
 
-    // SUCCESSFULLY INITIALIZES POOL
+    // Add pool to ASTRO Generator
     app.execute_contract(
-        owner.clone(),
+        Addr::unchecked(owner.clone()),
+        Addr::unchecked(update_msg.clone().generator_address.unwrap()),
+        &astroport::generator::ExecuteMsg::SetupPools {
+            pools: vec![(astro_lp_address.to_string(), Uint128::from(10u128))],
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Stake LP Tokens with Generator
+    app.execute_contract(
+        Addr::unchecked(owner.clone()),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::InitializePool {
-            terraswap_lp_token: terraswap_token_instance.to_string(),
-            incentives_share: 10000000u64,
+        &astroport_periphery::lockdrop::ExecuteMsg::StakeLpTokens {
+            terraswap_lp_token: terraswap_token_instance.clone(),
         },
         &[],
     )
@@ -2604,381 +5489,651 @@ fn test_migrate_liquidity_uusd_uluna_pool() {
     let user_address = "user".to_string();
     let user2_address = "user2".to_string();
 
-    // Set UST user balances
-    app.init_modules(|router, _, storage| {
-        router
-            .bank
-            .init_balance(
-                storage,
-                &Addr::unchecked(user_address.clone()),
-                vec![
-                    Coin::new(1000000_000000, "uusd"),
-                    Coin::new(1000000_000000, "uluna"),
-                ],
-            )
-            .unwrap();
-        router
-            .bank
-            .init_balance(
-                storage,
-                &Addr::unchecked(user2_address.clone()),
-                vec![
-                    Coin::new(1000000_000000, "uusd"),
-                    Coin::new(1000000_000000, "uluna"),
-                ],
-            )
-            .unwrap();
-    });
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(false, user_info.astro_transferred);
+    assert_eq!(Uint128::zero(), user_info.delegated_astro_rewards);
+    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
+        lp_units_locked: Uint128::from(1000000000u64),
+        withdrawal_flag: false,
+        astro_rewards: Uint128::from(500000000u64),
+        duration: 10u64,
+        generator_astro_debt: Uint128::from(0u64),
+        claimable_generator_astro_debt: Uint128::from(0u64),
+        generator_proxy_debt: RestrictedVector::default(),
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
+        unlock_timestamp: EPOCH_START + 16648000u64,
+        astroport_lp_units: Some(Uint128::from(1000000000u64)),
+        astroport_lp_token: Some(astro_lp_address.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        astroport_lp_transferred: None,
+    };
+    assert_eq!(lockup_response, user_info.lockup_infos[0]);
 
-    // user#1 adds liquidity to Terraswap Pool and locks that in Lockdrop contract
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
+    });
 
-    // add Liquidity to Terraswap pool
+    // DELEGATE ASTRO TO AUCTION
     app.execute_contract(
         Addr::unchecked(user_address.clone()),
-        terraswap_pool_instance.clone(),
-        &terraswap::pair::ExecuteMsg::ProvideLiquidity {
-            assets: [
-                terraswap::asset::Asset {
-                    info: terraswap::asset::AssetInfo::NativeToken {
-                        denom: "uusd".to_string(),
-                    },
-                    amount: Uint128::from(1000_000000u64),
-                },
-                terraswap::asset::Asset {
-                    info: terraswap::asset::AssetInfo::NativeToken {
-                        denom: "uluna".to_string(),
-                    },
-                    amount: Uint128::from(1000_000000u64),
-                },
-            ],
-            slippage_tolerance: None,
-            receiver: None,
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(500000000u64),
+            allowlist_proof: None,
         },
-        &[
-            Coin::new(1000_000000, "uluna"),
-            Coin::new(1000_000000, "uusd"),
-        ],
+        &[],
     )
     .unwrap();
 
-    // Query LP balance
-    let lp_balance_res: cw20::BalanceResponse = app
+    app.execute_contract(
+        Addr::unchecked(user2_address.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(1000u64),
+            allowlist_proof: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    // Query state
+    let state: StateResponse = app
+        .wrap()
+        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
+        .unwrap();
+    assert_eq!(Uint128::from(500001000u64), state.total_astro_delegated);
+
+    // Query user
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &terraswap_token_instance.clone(),
-            &cw20::Cw20QueryMsg::Balance {
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
                 address: user_address.clone(),
             },
         )
         .unwrap();
-    let user_lp_balance = lp_balance_res.balance;
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(
+        Uint128::from(500000000u64),
+        user_info.delegated_astro_rewards
+    );
+
+    // DEPOSIT UST INTO AUCTION
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(432423u128),
+        }],
+    )
+    .unwrap();
 
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 1_000_00)
+        b.time = Timestamp::from_seconds(EPOCH_START + 10750001)
     });
 
-    // Lock LP Tokens into Lockup Position
+    // INITIALIZE ASTRO-UST POOL TO ENABLE CLAIMS
     app.execute_contract(
-        Addr::unchecked(user_address.clone()),
-        Addr::unchecked(terraswap_token_instance.clone()),
-        &cw20::Cw20ExecuteMsg::Send {
-            contract: lockdrop_instance.clone().to_string(),
-            amount: user_lp_balance,
-            msg: to_binary(&lockdrop::Cw20HookMsg::IncreaseLockup { duration: 10u64 }).unwrap(),
-        },
+        Addr::unchecked(owner.to_string()),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::InitPool {},
         &[],
     )
     .unwrap();
 
-    // Increase timestamp for window closure
+    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS AND UNLOCK   ######
+
     app.update_block(|b| {
         b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
+        b.time = Timestamp::from_seconds(EPOCH_START + 16648001)
     });
 
-    // Create Astroport Pair
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(
+        Uint128::from(500000000u64),
+        user_info.delegated_astro_rewards
+    );
+    assert_eq!(false, user_info.astro_transferred);
+    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
+        lp_units_locked: Uint128::from(1000000000u64),
+        withdrawal_flag: false,
+        astro_rewards: Uint128::from(500000000u64),
+        duration: 10u64,
+        generator_astro_debt: Uint128::from(0u64),
+        claimable_generator_astro_debt: Uint128::from(259200000000u64),
+        generator_proxy_debt: RestrictedVector::default(),
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
+        unlock_timestamp: EPOCH_START + 16648000u64,
+        astroport_lp_units: Some(Uint128::from(1000000000u64)),
+        astroport_lp_token: Some(astro_lp_address.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        astroport_lp_transferred: None,
+    };
+    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+
+    let user1_astro_balance_before: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &update_msg.astro_token_address.clone().unwrap(),
+            &Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+
+    let user1_astro_lp_balance_before: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_lp_address.clone(),
+            &Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+
     app.execute_contract(
-        Addr::unchecked("user"),
-        astroport_factory_instance.clone(),
-        &astroport::factory::ExecuteMsg::CreatePair {
-            asset_infos: [
-                astroport::asset::AssetInfo::NativeToken {
-                    denom: "uusd".to_string(),
-                },
-                astroport::asset::AssetInfo::NativeToken {
-                    denom: "uluna".to_string(),
-                },
-            ],
-            init_params: None,
-            pair_type: astroport::factory::PairType::Xyk {},
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+            duration: 10u64,
+            withdraw_lp_stake: true,
+            user_address: None,
+            force_unlock: false,
+            amount: None,
         },
         &[],
     )
     .unwrap();
 
-    // Query Astroport addresses
-    let pair_resp: astroport::asset::PairInfo = app
+    // Query user
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &astroport_factory_instance,
-            &astroport::factory::QueryMsg::Pair {
-                asset_infos: [
-                    astroport::asset::AssetInfo::NativeToken {
-                        denom: "uusd".to_string(),
-                    },
-                    astroport::asset::AssetInfo::NativeToken {
-                        denom: "uluna".to_string(),
-                    },
-                ],
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
             },
         )
         .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(
+        Uint128::from(500000000u64),
+        user_info.delegated_astro_rewards
+    );
+    assert_eq!(true, user_info.astro_transferred);
 
-    let astro_pool_address = pair_resp.contract_addr;
-    let astro_lp_address = pair_resp.liquidity_token;
-
-    // astro LP token balance (Lockdrop)
-    let terraswap_balance_resp: cw20::BalanceResponse = app
+    let user1_astro_balance_after: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &terraswap_token_instance,
+            &update_msg.astro_token_address.clone().unwrap(),
             &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
+                address: user_address.clone(),
             },
         )
         .unwrap();
 
-    // Query pool before migration
-    let pool_resp_before_migration: PoolInfo = app
+    let user1_astro_lp_balance_after: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::Pool {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            &astro_lp_address.clone(),
+            &Cw20QueryMsg::Balance {
+                address: user_address.clone(),
             },
         )
         .unwrap();
 
+    let astro_reward_claimed =
+        user1_astro_balance_after.balance - user1_astro_balance_before.balance;
+    let lp_tokens_withdrawn =
+        user1_astro_lp_balance_after.balance - user1_astro_lp_balance_before.balance;
     assert_eq!(
-        terraswap_balance_resp.balance,
-        pool_resp_before_migration.terraswap_amount_in_lockups
-    );
-    assert_eq!(
-        CUint256::from(1750000000u128),
-        pool_resp_before_migration.weighted_amount
+        astro_reward_claimed,
+        user_info.total_astro_rewards - user_info.delegated_astro_rewards
+            + Uint128::from(259200000000u64)
     );
-    assert_eq!(false, pool_resp_before_migration.is_staked);
-    assert_eq!(None, pool_resp_before_migration.migration_info);
+    assert_eq!(lp_tokens_withdrawn, Uint128::from(1000000000u64));
+
+    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS :: user-2  ######
+
+    // Query user
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
+
+    let user2_astro_balance_before: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &update_msg.astro_token_address.clone().unwrap(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
+
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(Uint128::from(1000u64), user_info.delegated_astro_rewards);
+    assert_eq!(false, user_info.astro_transferred);
+    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
+        lp_units_locked: Uint128::from(1000000000u64),
+        withdrawal_flag: false,
+        astro_rewards: Uint128::from(500000000u64),
+        duration: 10u64,
+        generator_astro_debt: Uint128::from(0u64),
+        claimable_generator_astro_debt: Uint128::from(259200000000u64),
+        generator_proxy_debt: RestrictedVector::default(),
+        claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
+        unlock_timestamp: EPOCH_START + 16648000u64,
+        astroport_lp_units: Some(Uint128::from(1000000000u64)),
+        astroport_lp_token: Some(astro_lp_address.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        astroport_lp_transferred: None,
+    };
+    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+
+    let user2_astro_lp_balance_before: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_lp_address.clone(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
 
-    // Migrate Liquidity
     app.execute_contract(
-        owner.clone(),
+        Addr::unchecked(user2_address.clone()),
         lockdrop_instance.clone(),
-        &ExecuteMsg::MigrateLiquidity {
+        &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
             terraswap_lp_token: terraswap_token_instance.clone(),
-            astroport_pool_addr: astro_pool_address.to_string(),
-            slippage_tolerance: None,
+            duration: 10u64,
+            withdraw_lp_stake: true,
+            user_address: None,
+            force_unlock: false,
+            amount: None,
         },
         &[],
     )
     .unwrap();
 
-    // astro LP token balance (Lockdrop)
-    let astro_balance_resp: cw20::BalanceResponse = app
+    // Query user
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &astro_lp_address,
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+    assert_eq!(Uint128::from(1000u64), user_info.delegated_astro_rewards);
+    assert_eq!(true, user_info.astro_transferred);
+
+    let user2_astro_balance_after: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &update_msg.astro_token_address.clone().unwrap(),
             &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
+                address: user2_address.clone(),
             },
         )
         .unwrap();
 
-    // Query pool after migration
-    let pool_resp_after_migration: PoolInfo = app
+    let user2_astro_lp_balance_after: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::Pool {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            &astro_lp_address.clone(),
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone(),
             },
         )
         .unwrap();
 
+    let astro_reward_claimed =
+        user2_astro_balance_after.balance - user2_astro_balance_before.balance;
     assert_eq!(
-        pool_resp_before_migration.terraswap_pool,
-        pool_resp_after_migration.terraswap_pool
-    );
-    assert_eq!(
-        pool_resp_before_migration.terraswap_amount_in_lockups,
-        pool_resp_after_migration.terraswap_amount_in_lockups
-    );
-    assert_eq!(
-        pool_resp_before_migration.incentives_share,
-        pool_resp_after_migration.incentives_share
-    );
-
-    assert_eq!(
-        pool_resp_before_migration.incentives_share,
-        pool_resp_after_migration.incentives_share
-    );
-    assert_eq!(
-        Decimal::zero(),
-        pool_resp_after_migration.generator_astro_per_share
-    );
-    assert_eq!(
-        RestrictedVector::default(),
-        pool_resp_after_migration.generator_proxy_per_share
-    );
-    assert_eq!(
-        MigrationInfo {
-            terraswap_migrated_amount: astro_balance_resp.balance,
-            astroport_lp_token: astro_lp_address
-        },
-        pool_resp_after_migration.migration_info.unwrap()
+        astro_reward_claimed,
+        user_info.total_astro_rewards - user_info.delegated_astro_rewards
+            + Uint128::from(259200000000u64)
     );
+    let lp_tokens_withdrawn =
+        user2_astro_lp_balance_after.balance - user2_astro_lp_balance_before.balance;
+    assert_eq!(lp_tokens_withdrawn, Uint128::from(1000000000u64));
 }
 
 #[test]
-fn test_stake_lp_tokens() {
+fn test_claim_all() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
 
     let (_, lockdrop_instance, astroport_factory_instance, _, update_msg) =
         instantiate_all_contracts(&mut app, owner.clone());
+    let astro_token = update_msg.astro_token_address.clone().unwrap();
 
     let cw20_contract = Box::new(ContractWrapper::new_with_empty(
         astroport_token::contract::execute,
         astroport_token::contract::instantiate,
         astroport_token::contract::query,
     ));
-
     let cw20_code_id = app.store_code(cw20_contract);
 
-    let token_instance = app
-        .instantiate_contract(
-            cw20_code_id,
+    // Initialize and migrate liquidity for two pools, users have a lockup position in each of them
+    let mut pools = vec![];
+    for symbol in ["ANC", "MIR"] {
+        let token_instance = app
+            .instantiate_contract(
+                cw20_code_id,
+                owner.clone(),
+                &TokenInstantiateMsg {
+                    name: String::from(symbol),
+                    symbol: String::from(symbol),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: Some(cw20::MinterResponse {
+                        minter: owner.to_string(),
+                        cap: None,
+                    }),
+                    marketing: None,
+                },
+                &[],
+                String::from(symbol),
+                None,
+            )
+            .unwrap();
+
+        let (terraswap_token_instance, astro_lp_address, _) =
+            initialize_and_migrate_liquidity_for_pool(
+                &mut app,
+                owner.clone(),
+                token_instance,
+                lockdrop_instance.clone(),
+                astroport_factory_instance.clone(),
+            );
+        pools.push((terraswap_token_instance, astro_lp_address));
+    }
+
+    // Add pools to ASTRO Generator and stake LP Tokens with Generator
+    app.execute_contract(
+        owner.clone(),
+        Addr::unchecked(update_msg.clone().generator_address.unwrap()),
+        &astroport::generator::ExecuteMsg::SetupPools {
+            pools: pools
+                .iter()
+                .map(|(_, astro_lp_address)| (astro_lp_address.to_string(), Uint128::from(10u128)))
+                .collect(),
+        },
+        &[],
+    )
+    .unwrap();
+    for (terraswap_token_instance, _) in &pools {
+        app.execute_contract(
             owner.clone(),
-            &TokenInstantiateMsg {
-                name: String::from("ANC"),
-                symbol: String::from("ANC"),
-                decimals: 6,
-                initial_balances: vec![],
-                mint: Some(cw20::MinterResponse {
-                    minter: owner.to_string(),
-                    cap: None,
-                }),
-                marketing: None,
+            lockdrop_instance.clone(),
+            &ExecuteMsg::StakeLpTokens {
+                terraswap_lp_token: terraswap_token_instance.clone(),
             },
             &[],
-            String::from("ANC"),
-            None,
         )
         .unwrap();
+    }
 
-    // Initialize and migrate liquidity for a pool
-    let (terraswap_token_instance, astro_lp_address, _) = initialize_and_migrate_liquidity_for_pool(
-        &mut app,
-        owner.clone(),
-        token_instance,
-        lockdrop_instance.clone(),
-        astroport_factory_instance,
-    );
+    let user_address = "user".to_string();
 
-    // Add pool to ASTRO Generator
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
+    });
+
+    // DELEGATE ASTRO TO AUCTION
     app.execute_contract(
-        Addr::unchecked(owner.clone()),
-        Addr::unchecked(update_msg.clone().generator_address.unwrap()),
-        &astroport::generator::ExecuteMsg::SetupPools {
-            pools: vec![(astro_lp_address.to_string(), Uint128::from(10u128))],
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(1000u64),
+            allowlist_proof: None,
         },
         &[],
     )
     .unwrap();
 
-    // ######    ERROR :: Unauthorized    ######
+    // DEPOSIT UST INTO AUCTION
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(432423u128),
+        }],
+    )
+    .unwrap();
+
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10750001)
+    });
 
+    // ######    ERROR :: Claims are not allowed yet   ######
     let err = app
         .execute_contract(
-            Addr::unchecked("not_owner".to_string()),
+            Addr::unchecked(user_address.clone()),
             lockdrop_instance.clone(),
-            &astroport_periphery::lockdrop::ExecuteMsg::StakeLpTokens {
-                terraswap_lp_token: terraswap_token_instance.clone(),
+            &ExecuteMsg::ClaimAll {
+                withdraw_matured_lp: false,
             },
             &[],
         )
         .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: Reward claim not allowed"
+    );
 
-    assert_eq!(err.root_cause().to_string(), "Generic error: Unauthorized");
+    // INITIALIZE ASTRO-UST POOL TO ENABLE CLAIMS
+    app.execute_contract(
+        owner.clone(),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
 
-    // ######    SHOULD SUCCESSFULLY STAKE LP TOKENS WITH GENERATOR   ######
+    let query_balance = |app: &App, token: &str| -> Uint128 {
+        let res: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: user_address.clone(),
+                },
+            )
+            .unwrap();
+        res.balance
+    };
 
-    // astro LP token balance (Lockdrop)
-    let lockdrop_astro_balance_before_migration: cw20::BalanceResponse = app
+    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS OF ALL LOCKUP POSITIONS   ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 11000001)
+    });
+
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &astro_lp_address,
-            &Cw20QueryMsg::Balance {
-                address: lockdrop_instance.clone().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
             },
         )
         .unwrap();
+    assert_eq!(user_info.lockup_infos.len(), 2);
+    assert!(user_info
+        .lockup_infos
+        .iter()
+        .all(|lockup_info| !lockup_info.claimable_generator_astro_debt.is_zero()));
+    let astro_balance_before = query_balance(&app, &astro_token);
 
     app.execute_contract(
-        Addr::unchecked(owner.clone()),
+        Addr::unchecked(user_address.clone()),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::StakeLpTokens {
-            terraswap_lp_token: terraswap_token_instance.clone(),
+        &ExecuteMsg::ClaimAll {
+            withdraw_matured_lp: false,
         },
         &[],
     )
     .unwrap();
 
-    // astro LP token balance (Generator)
-    let generator_astro_balance_after_migration: cw20::BalanceResponse = app
+    assert_eq!(
+        query_balance(&app, &astro_token) - astro_balance_before,
+        user_info.total_astro_rewards - user_info.delegated_astro_rewards
+            + user_info.claimable_generator_astro_debt
+    );
+
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &astro_lp_address,
-            &Cw20QueryMsg::Balance {
-                address: update_msg.clone().generator_address.unwrap().to_string(),
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(true, user_info.astro_transferred);
+    assert_eq!(Uint128::zero(), user_info.claimable_generator_astro_debt);
+    for lockup_info in &user_info.lockup_infos {
+        assert_eq!(lockup_info.astroport_lp_transferred, None);
+        let astro_lp_address = lockup_info.astroport_lp_token.clone().unwrap();
+        assert_eq!(
+            query_balance(&app, astro_lp_address.as_str()),
+            Uint128::zero()
+        );
+    }
+
+    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS AND UNLOCK ALL MATURED LOCKUP POSITIONS   ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 16648001)
+    });
+
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
             },
         )
         .unwrap();
+    let astro_balance_before = query_balance(&app, &astro_token);
+
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimAll {
+            withdraw_matured_lp: true,
+        },
+        &[],
+    )
+    .unwrap();
 
     assert_eq!(
-        lockdrop_astro_balance_before_migration.balance,
-        generator_astro_balance_after_migration.balance
+        query_balance(&app, &astro_token) - astro_balance_before,
+        user_info.claimable_generator_astro_debt
     );
+    for lockup_info in &user_info.lockup_infos {
+        let astro_lp_address = lockup_info.astroport_lp_token.clone().unwrap();
+        assert_eq!(
+            query_balance(&app, astro_lp_address.as_str()),
+            lockup_info.astroport_lp_units.unwrap()
+        );
+    }
 
-    // Query pool after migration
-    let pool_resp_after_migration: PoolInfo = app
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
-            &QueryMsg::Pool {
-                terraswap_lp_token: terraswap_token_instance.clone().to_string(),
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
             },
         )
         .unwrap();
+    assert!(user_info
+        .lockup_infos
+        .iter()
+        .all(|lockup_info| lockup_info.astroport_lp_transferred.is_some()));
 
-    assert_eq!(true, pool_resp_after_migration.is_staked);
+    // ######    ERROR :: All lockup positions are unlocked   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimAll {
+                withdraw_matured_lp: true,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: No rewards available to claim!"
+    );
 }
 
 #[test]
-fn test_claim_rewards() {
+fn test_partial_unlock() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
 
     let (_, lockdrop_instance, astroport_factory_instance, _, update_msg) =
         instantiate_all_contracts(&mut app, owner.clone());
+    let astro_token = update_msg.astro_token_address.clone().unwrap();
 
     let cw20_contract = Box::new(ContractWrapper::new_with_empty(
         astroport_token::contract::execute,
         astroport_token::contract::instantiate,
         astroport_token::contract::query,
     ));
-
     let cw20_code_id = app.store_code(cw20_contract);
 
     let token_instance = app
@@ -3011,9 +6166,9 @@ fn test_claim_rewards() {
         astroport_factory_instance,
     );
 
-    // Add pool to ASTRO Generator
+    // Add pool to ASTRO Generator and stake LP Tokens with Generator
     app.execute_contract(
-        Addr::unchecked(owner.clone()),
+        owner.clone(),
         Addr::unchecked(update_msg.clone().generator_address.unwrap()),
         &astroport::generator::ExecuteMsg::SetupPools {
             pools: vec![(astro_lp_address.to_string(), Uint128::from(10u128))],
@@ -3021,12 +6176,10 @@ fn test_claim_rewards() {
         &[],
     )
     .unwrap();
-
-    // Stake LP Tokens with Generator
     app.execute_contract(
-        Addr::unchecked(owner.clone()),
+        owner.clone(),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::StakeLpTokens {
+        &ExecuteMsg::StakeLpTokens {
             terraswap_lp_token: terraswap_token_instance.clone(),
         },
         &[],
@@ -3034,37 +6187,6 @@ fn test_claim_rewards() {
     .unwrap();
 
     let user_address = "user".to_string();
-    let user2_address = "user2".to_string();
-
-    // Query user
-    let user_info: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
-        )
-        .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
-    assert_eq!(false, user_info.astro_transferred);
-    assert_eq!(Uint128::zero(), user_info.delegated_astro_rewards);
-    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
-        lp_units_locked: Uint128::from(1000000000u64),
-        withdrawal_flag: false,
-        astro_rewards: Uint128::from(500000000u64),
-        duration: 10u64,
-        generator_astro_debt: Uint128::from(0u64),
-        claimable_generator_astro_debt: Uint128::from(0u64),
-        generator_proxy_debt: RestrictedVector::default(),
-        claimable_generator_proxy_debt: RestrictedVector::default(),
-        unlock_timestamp: EPOCH_START + 16648000u64,
-        astroport_lp_units: Some(Uint128::from(1000000000u64)),
-        astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
-        astroport_lp_transferred: None,
-    };
-    assert_eq!(lockup_response, user_info.lockup_infos[0]);
 
     app.update_block(|b| {
         b.height += 17280;
@@ -3073,53 +6195,24 @@ fn test_claim_rewards() {
 
     // DELEGATE ASTRO TO AUCTION
     app.execute_contract(
-        Addr::unchecked(user_address.clone()),
-        lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
-            amount: Uint128::from(500000000u64),
-        },
-        &[],
-    )
-    .unwrap();
-
-    app.execute_contract(
-        Addr::unchecked(user2_address.clone()),
+        Addr::unchecked(user_address.clone()),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
+        &ExecuteMsg::DelegateAstroToAuction {
             amount: Uint128::from(1000u64),
+            allowlist_proof: None,
         },
         &[],
     )
     .unwrap();
 
-    // Query state
-    let state: StateResponse = app
-        .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(Uint128::from(500001000u64), state.total_astro_delegated);
-
-    // Query user
-    let user_info: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
-        )
-        .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
-    assert_eq!(
-        Uint128::from(500000000u64),
-        user_info.delegated_astro_rewards
-    );
-
     // DEPOSIT UST INTO AUCTION
     app.execute_contract(
         Addr::unchecked(user_address.clone()),
         Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
-        &astroport_periphery::auction::ExecuteMsg::DepositUst {},
+        &astroport_periphery::auction::ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
             amount: Uint128::from(432423u128),
@@ -3127,26 +6220,6 @@ fn test_claim_rewards() {
     )
     .unwrap();
 
-    // ######    ERROR :: Reward claim not allowed    ######
-
-    let err = app
-        .execute_contract(
-            Addr::unchecked(user_address.clone()),
-            lockdrop_instance.clone(),
-            &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
-                terraswap_lp_token: terraswap_token_instance.clone(),
-                duration: 10u64,
-                withdraw_lp_stake: false,
-            },
-            &[],
-        )
-        .unwrap_err();
-
-    assert_eq!(
-        err.root_cause().to_string(),
-        "Generic error: Reward claim not allowed"
-    );
-
     app.update_block(|b| {
         b.height += 17280;
         b.time = Timestamp::from_seconds(EPOCH_START + 10750001)
@@ -3154,252 +6227,209 @@ fn test_claim_rewards() {
 
     // INITIALIZE ASTRO-UST POOL TO ENABLE CLAIMS
     app.execute_contract(
-        Addr::unchecked(owner.to_string()),
+        owner.clone(),
         Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
-        &astroport_periphery::auction::ExecuteMsg::InitPool { slippage: None },
+        &astroport_periphery::auction::ExecuteMsg::InitPool {},
         &[],
     )
     .unwrap();
 
-    // ######    ERROR :: Invalid Lockup    ######
+    let query_balance = |app: &App, token: &str| -> Uint128 {
+        let res: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: user_address.clone(),
+                },
+            )
+            .unwrap();
+        res.balance
+    };
+    let unlock_msg = |withdraw_lp_stake: bool, force_unlock: bool, amount: u128| {
+        ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+            duration: 10u64,
+            withdraw_lp_stake,
+            user_address: None,
+            force_unlock,
+            amount: Some(Uint128::from(amount)),
+        }
+    };
 
+    // ######    ERROR :: Forced unlocks can't be partial   ######
     let err = app
         .execute_contract(
             Addr::unchecked(user_address.clone()),
             lockdrop_instance.clone(),
-            &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
-                terraswap_lp_token: terraswap_token_instance.clone(),
-                duration: 9u64,
-                withdraw_lp_stake: false,
-            },
+            &unlock_msg(true, true, 400000000),
             &[],
         )
         .unwrap_err();
-
     assert_eq!(
         err.root_cause().to_string(),
-        "astroport_periphery::lockdrop::LockupInfoV1 not found"
+        "Generic error: Forced unlocks can't be partial"
     );
 
-    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS   ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 16648001)
+    });
 
-    // Query user
-    let user_info: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
+    // ######    ERROR :: Invalid unlock amounts   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &unlock_msg(false, false, 400000000),
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(500000000u64),
-        user_info.delegated_astro_rewards
+        err.root_cause().to_string(),
+        "Generic error: Unlock amount requires withdraw_lp_stake"
     );
-    assert_eq!(false, user_info.astro_transferred);
-    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
-        lp_units_locked: Uint128::from(1000000000u64),
-        withdrawal_flag: false,
-        astro_rewards: Uint128::from(500000000u64),
-        duration: 10u64,
-        generator_astro_debt: Uint128::from(0u64),
-        claimable_generator_astro_debt: Uint128::from(172800000000u64),
-        generator_proxy_debt: RestrictedVector::default(),
-        claimable_generator_proxy_debt: RestrictedVector::default(),
-        unlock_timestamp: EPOCH_START + 16648000u64,
-        astroport_lp_units: Some(Uint128::from(1000000000u64)),
-        astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
-        astroport_lp_transferred: None,
-    };
-    assert_eq!(lockup_response, user_info.lockup_infos[0]);
-
-    let user1_astro_balance_before: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &update_msg.astro_token_address.clone().unwrap(),
-            &Cw20QueryMsg::Balance {
-                address: user_address.clone(),
-            },
-        )
-        .unwrap();
-
-    app.execute_contract(
-        Addr::unchecked(user_address.clone()),
-        lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
-            terraswap_lp_token: terraswap_token_instance.clone(),
-            duration: 10u64,
-            withdraw_lp_stake: false,
-        },
-        &[],
-    )
-    .unwrap();
 
-    // Query user
-    let user_info: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &unlock_msg(true, false, 0),
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(500000000u64),
-        user_info.delegated_astro_rewards
+        err.root_cause().to_string(),
+        "Generic error: Amount should be > 0"
     );
-    assert_eq!(true, user_info.astro_transferred);
-    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
-        lp_units_locked: Uint128::from(1000000000u64),
-        withdrawal_flag: false,
-        astro_rewards: Uint128::from(500000000u64),
-        duration: 10u64,
-        generator_astro_debt: Uint128::from(172800000000u64),
-        claimable_generator_astro_debt: Uint128::from(0u64),
-        generator_proxy_debt: RestrictedVector::default(),
-        claimable_generator_proxy_debt: RestrictedVector::default(),
-        unlock_timestamp: EPOCH_START + 16648000u64,
-        astroport_lp_units: Some(Uint128::from(1000000000u64)),
-        astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
-        astroport_lp_transferred: None,
-    };
-    assert_eq!(lockup_response, user_info.lockup_infos[0]);
 
-    let user1_astro_balance_after: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &update_msg.astro_token_address.clone().unwrap(),
-            &Cw20QueryMsg::Balance {
-                address: user_address.clone(),
-            },
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &unlock_msg(true, false, 1000000001),
+            &[],
         )
-        .unwrap();
-
-    let astro_reward_claimed =
-        user1_astro_balance_after.balance - user1_astro_balance_before.balance;
+        .unwrap_err();
     assert_eq!(
-        astro_reward_claimed,
-        user_info.total_astro_rewards - user_info.delegated_astro_rewards
-            + Uint128::from(172800000000u64)
+        err.root_cause().to_string(),
+        "Generic error: Amount exceeds 1000000000 Astroport LP units of the lockup"
     );
 
-    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS :: user-2  ######
-
-    // Query user
+    // ######    SHOULD SUCCESSFULLY UNLOCK A PART OF THE LOCKUP POSITION   ######
     let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
             &QueryMsg::UserInfo {
-                address: user2_address.clone(),
-            },
-        )
-        .unwrap();
-
-    let user2_astro_balance_before: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &update_msg.astro_token_address.clone().unwrap(),
-            &Cw20QueryMsg::Balance {
-                address: user2_address.clone(),
+                address: user_address.clone(),
             },
         )
         .unwrap();
-
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
-    assert_eq!(Uint128::from(1000u64), user_info.delegated_astro_rewards);
-    assert_eq!(false, user_info.astro_transferred);
-    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
-        lp_units_locked: Uint128::from(1000000000u64),
-        withdrawal_flag: false,
-        astro_rewards: Uint128::from(500000000u64),
-        duration: 10u64,
-        generator_astro_debt: Uint128::from(0u64),
-        claimable_generator_astro_debt: Uint128::from(172800000000u64),
-        generator_proxy_debt: RestrictedVector::default(),
-        claimable_generator_proxy_debt: RestrictedVector::default(),
-        unlock_timestamp: EPOCH_START + 16648000u64,
-        astroport_lp_units: Some(Uint128::from(1000000000u64)),
-        astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
-        astroport_lp_transferred: None,
-    };
-    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+    let astro_balance_before = query_balance(&app, &astro_token);
 
     app.execute_contract(
-        Addr::unchecked(user2_address.clone()),
+        Addr::unchecked(user_address.clone()),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
-            terraswap_lp_token: terraswap_token_instance.clone(),
-            duration: 10u64,
-            withdraw_lp_stake: false,
-        },
+        &unlock_msg(true, false, 400000000),
         &[],
     )
     .unwrap();
 
-    // Query user
+    assert_eq!(
+        query_balance(&app, &astro_token) - astro_balance_before,
+        user_info.total_astro_rewards - user_info.delegated_astro_rewards
+            + user_info.lockup_infos[0].claimable_generator_astro_debt
+    );
+    assert_eq!(
+        query_balance(&app, astro_lp_address.as_str()),
+        Uint128::from(400000000u64)
+    );
+
     let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
             &QueryMsg::UserInfo {
-                address: user2_address.clone(),
+                address: user_address.clone(),
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
-    assert_eq!(Uint128::from(1000u64), user_info.delegated_astro_rewards);
-    assert_eq!(true, user_info.astro_transferred);
-    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
-        lp_units_locked: Uint128::from(1000000000u64),
-        withdrawal_flag: false,
-        astro_rewards: Uint128::from(500000000u64),
-        duration: 10u64,
-        generator_astro_debt: Uint128::from(172800000000u64),
-        claimable_generator_astro_debt: Uint128::from(0u64),
-        generator_proxy_debt: RestrictedVector::default(),
-        claimable_generator_proxy_debt: RestrictedVector::default(),
-        unlock_timestamp: EPOCH_START + 16648000u64,
-        astroport_lp_units: Some(Uint128::from(1000000000u64)),
-        astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
-        astroport_lp_transferred: None,
-    };
-    assert_eq!(lockup_response, user_info.lockup_infos[0]);
+    let lockup_info = &user_info.lockup_infos[0];
+    assert_eq!(lockup_info.lp_units_locked, Uint128::from(600000000u64));
+    assert_eq!(
+        lockup_info.astroport_lp_units,
+        Some(Uint128::from(600000000u64))
+    );
+    assert_eq!(
+        lockup_info.astroport_lp_transferred,
+        Some(Uint128::from(400000000u64))
+    );
+    assert_eq!(lockup_info.claimable_generator_astro_debt, Uint128::zero());
+
+    // ######    SHOULD SUCCESSFULLY UNLOCK THE REST OF THE LOCKUP POSITION   ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 16700001)
+    });
 
-    let user2_astro_balance_after: cw20::BalanceResponse = app
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+            terraswap_lp_token: terraswap_token_instance.clone(),
+            duration: 10u64,
+            withdraw_lp_stake: true,
+            user_address: None,
+            force_unlock: false,
+            amount: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(
+        query_balance(&app, astro_lp_address.as_str()),
+        Uint128::from(1000000000u64)
+    );
+
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &update_msg.astro_token_address.clone().unwrap(),
-            &Cw20QueryMsg::Balance {
-                address: user2_address.clone(),
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
+                address: user_address.clone(),
             },
         )
         .unwrap();
+    assert_eq!(
+        user_info.lockup_infos[0].astroport_lp_transferred,
+        Some(Uint128::from(1000000000u64))
+    );
 
-    let astro_reward_claimed =
-        user2_astro_balance_after.balance - user2_astro_balance_before.balance;
+    // ######    ERROR :: The lockup position is unlocked   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &unlock_msg(true, false, 1),
+            &[],
+        )
+        .unwrap_err();
     assert_eq!(
-        astro_reward_claimed,
-        user_info.total_astro_rewards - user_info.delegated_astro_rewards
-            + Uint128::from(172800000000u64)
+        err.root_cause().to_string(),
+        "Generic error: Astro LP Tokens have already been claimed!"
     );
 }
 
 #[test]
-fn test_claim_rewards_and_unlock() {
+fn test_force_unlock_with_penalty() {
     let mut app = mock_app();
     let owner = Addr::unchecked("contract_owner");
 
     let (_, lockdrop_instance, astroport_factory_instance, _, update_msg) =
         instantiate_all_contracts(&mut app, owner.clone());
+    let astro_token = Addr::unchecked(update_msg.astro_token_address.clone().unwrap());
 
     let cw20_contract = Box::new(ContractWrapper::new_with_empty(
         astroport_token::contract::execute,
@@ -3439,72 +6469,16 @@ fn test_claim_rewards_and_unlock() {
         astroport_factory_instance,
     );
 
-    // Add pool to ASTRO Generator
-    app.execute_contract(
-        Addr::unchecked(owner.clone()),
-        Addr::unchecked(update_msg.clone().generator_address.unwrap()),
-        &astroport::generator::ExecuteMsg::SetupPools {
-            pools: vec![(astro_lp_address.to_string(), Uint128::from(10u128))],
-        },
-        &[],
-    )
-    .unwrap();
-
-    // Stake LP Tokens with Generator
-    app.execute_contract(
-        Addr::unchecked(owner.clone()),
-        lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::StakeLpTokens {
-            terraswap_lp_token: terraswap_token_instance.clone(),
-        },
-        &[],
-    )
-    .unwrap();
-
     let user_address = "user".to_string();
     let user2_address = "user2".to_string();
 
-    // Query user
-    let user_info: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
-        )
-        .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
-    assert_eq!(false, user_info.astro_transferred);
-    assert_eq!(Uint128::zero(), user_info.delegated_astro_rewards);
-    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
-        lp_units_locked: Uint128::from(1000000000u64),
-        withdrawal_flag: false,
-        astro_rewards: Uint128::from(500000000u64),
-        duration: 10u64,
-        generator_astro_debt: Uint128::from(0u64),
-        claimable_generator_astro_debt: Uint128::from(0u64),
-        generator_proxy_debt: RestrictedVector::default(),
-        claimable_generator_proxy_debt: RestrictedVector::default(),
-        unlock_timestamp: EPOCH_START + 16648000u64,
-        astroport_lp_units: Some(Uint128::from(1000000000u64)),
-        astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
-        astroport_lp_transferred: None,
-    };
-    assert_eq!(lockup_response, user_info.lockup_infos[0]);
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 10600001)
-    });
-
     // DELEGATE ASTRO TO AUCTION
     app.execute_contract(
         Addr::unchecked(user_address.clone()),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
-            amount: Uint128::from(500000000u64),
+        &ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(100_000000u64),
+            allowlist_proof: None,
         },
         &[],
     )
@@ -3513,41 +6487,22 @@ fn test_claim_rewards_and_unlock() {
     app.execute_contract(
         Addr::unchecked(user2_address.clone()),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
+        &ExecuteMsg::DelegateAstroToAuction {
             amount: Uint128::from(1000u64),
+            allowlist_proof: None,
         },
         &[],
     )
     .unwrap();
 
-    // Query state
-    let state: StateResponse = app
-        .wrap()
-        .query_wasm_smart(&lockdrop_instance, &QueryMsg::State {})
-        .unwrap();
-    assert_eq!(Uint128::from(500001000u64), state.total_astro_delegated);
-
-    // Query user
-    let user_info: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
-        )
-        .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
-    assert_eq!(
-        Uint128::from(500000000u64),
-        user_info.delegated_astro_rewards
-    );
-
     // DEPOSIT UST INTO AUCTION
     app.execute_contract(
         Addr::unchecked(user_address.clone()),
         Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
-        &astroport_periphery::auction::ExecuteMsg::DepositUst {},
+        &astroport_periphery::auction::ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
+        },
         &[Coin {
             denom: "uusd".to_string(),
             amount: Uint128::from(432423u128),
@@ -3562,199 +6517,210 @@ fn test_claim_rewards_and_unlock() {
 
     // INITIALIZE ASTRO-UST POOL TO ENABLE CLAIMS
     app.execute_contract(
-        Addr::unchecked(owner.to_string()),
+        owner.clone(),
         Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
-        &astroport_periphery::auction::ExecuteMsg::InitPool { slippage: None },
+        &astroport_periphery::auction::ExecuteMsg::InitPool {},
         &[],
     )
     .unwrap();
 
-    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS AND UNLOCK   ######
-
-    app.update_block(|b| {
-        b.height += 17280;
-        b.time = Timestamp::from_seconds(EPOCH_START + 16648001)
-    });
+    let force_unlock_msg = ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+        terraswap_lp_token: terraswap_token_instance.clone(),
+        duration: 10u64,
+        withdraw_lp_stake: true,
+        user_address: None,
+        force_unlock: true,
+        amount: None,
+    };
 
-    // Query user
-    let user_info: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
-            },
+    // ######    ERROR :: Forced unlocks are disabled   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &force_unlock_msg,
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(500000000u64),
-        user_info.delegated_astro_rewards
+        err.to_string(),
+        "Generic error: Forced unlocks are disabled"
     );
-    assert_eq!(false, user_info.astro_transferred);
-    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
-        lp_units_locked: Uint128::from(1000000000u64),
-        withdrawal_flag: false,
-        astro_rewards: Uint128::from(500000000u64),
-        duration: 10u64,
-        generator_astro_debt: Uint128::from(0u64),
-        claimable_generator_astro_debt: Uint128::from(259200000000u64),
-        generator_proxy_debt: RestrictedVector::default(),
-        claimable_generator_proxy_debt: RestrictedVector::default(),
-        unlock_timestamp: EPOCH_START + 16648000u64,
-        astroport_lp_units: Some(Uint128::from(1000000000u64)),
-        astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
-        astroport_lp_transferred: None,
+
+    let force_unlock_params = lockdrop::ForceUnlockParams {
+        penalty_per_week: Decimal::percent(5),
+        max_penalty: Decimal::percent(30),
+        penalty_destination: lockdrop::PenaltyDestination::Lockers,
     };
-    assert_eq!(lockup_response, user_info.lockup_infos[0]);
 
-    let user1_astro_balance_before: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &update_msg.astro_token_address.clone().unwrap(),
-            &Cw20QueryMsg::Balance {
-                address: user_address.clone(),
+    // ######    ERROR :: Unauthorized   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateForceUnlockParams {
+                params: Some(force_unlock_params.clone()),
             },
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Generic error: Unauthorized");
 
-    let user1_astro_lp_balance_before: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &astro_lp_address.clone(),
-            &Cw20QueryMsg::Balance {
-                address: user_address.clone(),
+    // ######    ERROR :: Penalty share cannot be greater than 1   ######
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::UpdateForceUnlockParams {
+                params: Some(lockdrop::ForceUnlockParams {
+                    max_penalty: Decimal::percent(101),
+                    ..force_unlock_params.clone()
+                }),
             },
+            &[],
         )
-        .unwrap();
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Generic error: Penalty share cannot be greater than 1"
+    );
 
     app.execute_contract(
-        Addr::unchecked(user_address.clone()),
+        owner.clone(),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
-            terraswap_lp_token: terraswap_token_instance.clone(),
-            duration: 10u64,
-            withdraw_lp_stake: true,
+        &ExecuteMsg::UpdateForceUnlockParams {
+            params: Some(force_unlock_params),
         },
         &[],
     )
     .unwrap();
 
-    // Query user
-    let user_info: UserInfoResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &lockdrop_instance,
-            &QueryMsg::UserInfo {
-                address: user_address.clone(),
+    // ######    ERROR :: Forced unlock requires withdraw_lp_stake   ######
+    let err = app
+        .execute_contract(
+            Addr::unchecked(user_address.clone()),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+                terraswap_lp_token: terraswap_token_instance.clone(),
+                duration: 10u64,
+                withdraw_lp_stake: false,
+                user_address: None,
+                force_unlock: true,
+                amount: None,
             },
+            &[],
         )
-        .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
+        .unwrap_err();
     assert_eq!(
-        Uint128::from(500000000u64),
-        user_info.delegated_astro_rewards
+        err.to_string(),
+        "Generic error: Forced unlock requires withdraw_lp_stake"
     );
-    assert_eq!(true, user_info.astro_transferred);
 
-    let user1_astro_balance_after: cw20::BalanceResponse = app
-        .wrap()
-        .query_wasm_smart(
-            &update_msg.astro_token_address.clone().unwrap(),
-            &Cw20QueryMsg::Balance {
-                address: user_address.clone(),
-            },
-        )
-        .unwrap();
+    // ######    SHOULD FORCEFULLY UNLOCK :: penalty is taken from unclaimed ASTRO   ######
 
-    let user1_astro_lp_balance_after: cw20::BalanceResponse = app
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &astro_lp_address.clone(),
-            &Cw20QueryMsg::Balance {
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
                 address: user_address.clone(),
             },
         )
         .unwrap();
+    let astroport_lp_units = user_info.lockup_infos[0].astroport_lp_units.unwrap();
 
-    let astro_reward_claimed =
-        user1_astro_balance_after.balance - user1_astro_balance_before.balance;
-    let lp_tokens_withdrawn =
-        user1_astro_lp_balance_after.balance - user1_astro_lp_balance_before.balance;
-    assert_eq!(
-        astro_reward_claimed,
-        user_info.total_astro_rewards - user_info.delegated_astro_rewards
-            + Uint128::from(259200000000u64)
-    );
-    assert_eq!(lp_tokens_withdrawn, Uint128::from(1000000000u64));
-
-    // ######    SHOULD SUCCESSFULLY CLAIM REWARDS :: user-2  ######
+    app.execute_contract(
+        Addr::unchecked(user_address.clone()),
+        lockdrop_instance.clone(),
+        &force_unlock_msg,
+        &[],
+    )
+    .unwrap();
 
-    // Query user
+    // 10 weeks left to unlock, so the penalty is capped by 30% of 500 ASTRO lockup rewards
     let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
             &lockdrop_instance,
             &QueryMsg::UserInfo {
-                address: user2_address.clone(),
+                address: user_address.clone(),
             },
         )
         .unwrap();
+    assert_eq!(Uint128::from(150_000000u64), user_info.astro_penalty_paid);
+    assert!(user_info.astro_transferred);
+    assert_eq!(
+        Some(astroport_lp_units),
+        user_info.lockup_infos[0].astroport_lp_transferred
+    );
 
-    let user2_astro_balance_before: cw20::BalanceResponse = app
+    let user_astro_balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &update_msg.astro_token_address.clone().unwrap(),
+            &astro_token,
             &Cw20QueryMsg::Balance {
-                address: user2_address.clone(),
+                address: user_address.clone(),
             },
         )
         .unwrap();
+    assert_eq!(Uint128::from(250_000000u64), user_astro_balance.balance);
 
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
-    assert_eq!(Uint128::from(1000u64), user_info.delegated_astro_rewards);
-    assert_eq!(false, user_info.astro_transferred);
-    let lockup_response = astroport_periphery::lockdrop::LockUpInfoResponse {
-        lp_units_locked: Uint128::from(1000000000u64),
-        withdrawal_flag: false,
-        astro_rewards: Uint128::from(500000000u64),
-        duration: 10u64,
-        generator_astro_debt: Uint128::from(0u64),
-        claimable_generator_astro_debt: Uint128::from(259200000000u64),
-        generator_proxy_debt: RestrictedVector::default(),
-        claimable_generator_proxy_debt: RestrictedVector::default(),
-        unlock_timestamp: EPOCH_START + 16648000u64,
-        astroport_lp_units: Some(Uint128::from(1000000000u64)),
-        astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
-        astroport_lp_transferred: None,
-    };
-    assert_eq!(lockup_response, user_info.lockup_infos[0]);
-
-    let user2_astro_lp_balance_before: cw20::BalanceResponse = app
+    let user_astro_lp_balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &astro_lp_address.clone(),
+            &astro_lp_address,
             &Cw20QueryMsg::Balance {
+                address: user_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(astroport_lp_units, user_astro_lp_balance.balance);
+
+    // ######    SHOULD CLAIM THE PENALTY DISTRIBUTED AMONG REMAINING LOCKERS   ######
+
+    let user_info: UserInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
                 address: user2_address.clone(),
             },
         )
         .unwrap();
+    assert_eq!(
+        Uint128::from(150_000000u64),
+        user_info.lockup_infos[0].claimable_penalty_astro_reward
+    );
 
     app.execute_contract(
         Addr::unchecked(user2_address.clone()),
         lockdrop_instance.clone(),
-        &astroport_periphery::lockdrop::ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+        &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
             terraswap_lp_token: terraswap_token_instance.clone(),
             duration: 10u64,
-            withdraw_lp_stake: true,
+            withdraw_lp_stake: false,
+            user_address: None,
+            force_unlock: false,
+            amount: None,
         },
         &[],
     )
     .unwrap();
 
-    // Query user
+    let user2_astro_balance: cw20::BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &astro_token,
+            &Cw20QueryMsg::Balance {
+                address: user2_address.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        Uint128::from(500_000000u64 - 1000u64 + 150_000000u64),
+        user2_astro_balance.balance
+    );
+
     let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
@@ -3764,40 +6730,87 @@ fn test_claim_rewards_and_unlock() {
             },
         )
         .unwrap();
-    assert_eq!(Uint128::from(500000000u64), user_info.total_astro_rewards);
-    assert_eq!(Uint128::from(1000u64), user_info.delegated_astro_rewards);
-    assert_eq!(true, user_info.astro_transferred);
+    assert_eq!(
+        Uint128::zero(),
+        user_info.lockup_infos[0].claimable_penalty_astro_reward
+    );
 
-    let user2_astro_balance_after: cw20::BalanceResponse = app
+    // ######    SHOULD FORCEFULLY UNLOCK :: penalty is transferred from the user and burned   ######
+
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::UpdateForceUnlockParams {
+            params: Some(lockdrop::ForceUnlockParams {
+                penalty_per_week: Decimal::percent(5),
+                max_penalty: Decimal::percent(30),
+                penalty_destination: lockdrop::PenaltyDestination::Burn,
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ASTRO rewards have already been claimed, so the penalty should be approved
+    app.execute_contract(
+        Addr::unchecked(user2_address.clone()),
+        astro_token.clone(),
+        &Cw20ExecuteMsg::IncreaseAllowance {
+            spender: lockdrop_instance.to_string(),
+            amount: Uint128::from(150_000000u64),
+            expires: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let astro_token_info_before: cw20::TokenInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&astro_token, &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked(user2_address.clone()),
+        lockdrop_instance.clone(),
+        &force_unlock_msg,
+        &[],
+    )
+    .unwrap();
+
+    let astro_token_info_after: cw20::TokenInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&astro_token, &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(
+        astro_token_info_before.total_supply - Uint128::from(150_000000u64),
+        astro_token_info_after.total_supply
+    );
+
+    let user2_astro_balance: cw20::BalanceResponse = app
         .wrap()
         .query_wasm_smart(
-            &update_msg.astro_token_address.clone().unwrap(),
+            &astro_token,
             &Cw20QueryMsg::Balance {
                 address: user2_address.clone(),
             },
         )
         .unwrap();
+    assert_eq!(
+        Uint128::from(500_000000u64 - 1000u64),
+        user2_astro_balance.balance
+    );
 
-    let user2_astro_lp_balance_after: cw20::BalanceResponse = app
+    let user_info: UserInfoResponse = app
         .wrap()
         .query_wasm_smart(
-            &astro_lp_address.clone(),
-            &Cw20QueryMsg::Balance {
+            &lockdrop_instance,
+            &QueryMsg::UserInfo {
                 address: user2_address.clone(),
             },
         )
         .unwrap();
-
-    let astro_reward_claimed =
-        user2_astro_balance_after.balance - user2_astro_balance_before.balance;
-    assert_eq!(
-        astro_reward_claimed,
-        user_info.total_astro_rewards - user_info.delegated_astro_rewards
-            + Uint128::from(259200000000u64)
-    );
-    let lp_tokens_withdrawn =
-        user2_astro_lp_balance_after.balance - user2_astro_lp_balance_before.balance;
-    assert_eq!(lp_tokens_withdrawn, Uint128::from(1000000000u64));
+    assert_eq!(Uint128::zero(), user_info.astro_penalty_paid);
+    assert!(user_info.lockup_infos[0].astroport_lp_transferred.is_some());
 }
 
 #[test]
@@ -3893,10 +6906,11 @@ fn test_delegate_astro_to_auction() {
         claimable_generator_astro_debt: Uint128::from(0u64),
         generator_proxy_debt: RestrictedVector::default(),
         claimable_generator_proxy_debt: RestrictedVector::default(),
+        claimable_penalty_astro_reward: Uint128::zero(),
         unlock_timestamp: EPOCH_START + 16648000u64,
         astroport_lp_units: Some(Uint128::from(1000000000u64)),
         astroport_lp_token: Some(astro_lp_address.clone()),
-        terraswap_lp_token: Addr::unchecked(terraswap_token_instance.clone()),
+        terraswap_lp_token: terraswap_token_instance.clone(),
         astroport_lp_transferred: None,
     };
     assert_eq!(lockup_response, user_info.lockup_infos[0]);
@@ -3912,6 +6926,7 @@ fn test_delegate_astro_to_auction() {
         lockdrop_instance.clone(),
         &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
             amount: Uint128::from(500000000u64),
+            allowlist_proof: None,
         },
         &[],
     )
@@ -3922,6 +6937,7 @@ fn test_delegate_astro_to_auction() {
         lockdrop_instance.clone(),
         &astroport_periphery::lockdrop::ExecuteMsg::DelegateAstroToAuction {
             amount: Uint128::from(1000u64),
+            allowlist_proof: None,
         },
         &[],
     )
@@ -3950,3 +6966,269 @@ fn test_delegate_astro_to_auction() {
         user_info.delegated_astro_rewards
     );
 }
+
+#[test]
+fn test_position_nft_astro_rewards() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("contract_owner");
+
+    let (_, lockdrop_instance, astroport_factory_instance, _, update_msg) =
+        instantiate_all_contracts(&mut app, owner.clone());
+    let astro_token = update_msg.astro_token_address.clone().unwrap();
+
+    let position_nft = instantiate_position_nft(&mut app, owner.clone(), &lockdrop_instance);
+    app.execute_contract(
+        owner.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::SetPositionNft {
+            contract: position_nft.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let cw20_contract = Box::new(ContractWrapper::new_with_empty(
+        astroport_token::contract::execute,
+        astroport_token::contract::instantiate,
+        astroport_token::contract::query,
+    ));
+    let cw20_code_id = app.store_code(cw20_contract);
+
+    // Initialize and migrate liquidity for two pools, users have a lockup position in each of them
+    let mut pools = vec![];
+    for symbol in ["ANC", "MIR"] {
+        let token_instance = app
+            .instantiate_contract(
+                cw20_code_id,
+                owner.clone(),
+                &TokenInstantiateMsg {
+                    name: String::from(symbol),
+                    symbol: String::from(symbol),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: Some(cw20::MinterResponse {
+                        minter: owner.to_string(),
+                        cap: None,
+                    }),
+                    marketing: None,
+                },
+                &[],
+                String::from(symbol),
+                None,
+            )
+            .unwrap();
+
+        let (terraswap_token_instance, _, _) = initialize_and_migrate_liquidity_for_pool(
+            &mut app,
+            owner.clone(),
+            token_instance,
+            lockdrop_instance.clone(),
+            astroport_factory_instance.clone(),
+        );
+        pools.push(terraswap_token_instance);
+    }
+    let sold_lp_token = pools[1].clone();
+
+    let user_address = Addr::unchecked("user");
+    let buyer_address = Addr::unchecked("buyer");
+
+    let query_balance = |app: &App, address: &Addr| -> Uint128 {
+        let res: cw20::BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &astro_token,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.balance
+    };
+    let query_user_info = |app: &App| -> UserInfoResponse {
+        app.wrap()
+            .query_wasm_smart(
+                &lockdrop_instance,
+                &QueryMsg::UserInfo {
+                    address: user_address.to_string(),
+                },
+            )
+            .unwrap()
+    };
+
+    // The lockup position in the MIR pool is sold to the buyer
+    let sold_lockup_astro_rewards = query_user_info(&app)
+        .lockup_infos
+        .iter()
+        .find(|lockup_info| lockup_info.terraswap_lp_token.as_str() == sold_lp_token)
+        .unwrap()
+        .astro_rewards;
+    assert!(!sold_lockup_astro_rewards.is_zero());
+    app.execute_contract(
+        user_address.clone(),
+        position_nft.clone(),
+        &cw721_base::ExecuteMsg::<Empty>::TransferNft {
+            recipient: buyer_address.to_string(),
+            token_id: lockdrop::lockup_position_token_id(&sold_lp_token, &user_address, 10),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: ASTRO rewards of the sold lockup position can't be delegated     ######
+    let user_info = query_user_info(&app);
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::DelegateAstroToAuction {
+                amount: user_info.total_astro_rewards - sold_lockup_astro_rewards
+                    + Uint128::from(1u64),
+                allowlist_proof: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(err.root_cause().to_string().starts_with(
+        "Generic error: ASTRO to delegate cannot exceed user's unclaimed ASTRO balance"
+    ));
+
+    app.execute_contract(
+        user_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::DelegateAstroToAuction {
+            amount: Uint128::from(1000u64),
+            allowlist_proof: None,
+        },
+        &[],
+    )
+    .unwrap();
+
+    let user_info = query_user_info(&app);
+    assert_eq!(sold_lockup_astro_rewards, user_info.detached_astro_rewards);
+
+    // INITIALIZE ASTRO-UST POOL TO ENABLE CLAIMS
+    app.execute_contract(
+        user_address.clone(),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::DepositUst {
+            beneficiary: None,
+            allowlist_proof: None,
+        },
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(432423u128),
+        }],
+    )
+    .unwrap();
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 10750001)
+    });
+    app.execute_contract(
+        owner.clone(),
+        Addr::unchecked(update_msg.auction_contract_address.clone().unwrap()),
+        &astroport_periphery::auction::ExecuteMsg::InitPool {},
+        &[],
+    )
+    .unwrap();
+
+    // ######    ERROR :: The lockup position with ASTRO rewards owed to the NFT owner can't be transferred     ######
+    app.execute_contract(
+        buyer_address.clone(),
+        position_nft.clone(),
+        &cw721_base::ExecuteMsg::<Empty>::TransferNft {
+            recipient: user_address.to_string(),
+            token_id: lockdrop::lockup_position_token_id(&sold_lp_token, &user_address, 10),
+        },
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(
+            user_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::TransferLockup {
+                terraswap_lp_token: sold_lp_token.clone(),
+                duration: 10,
+                recipient: buyer_address.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: ASTRO rewards of the lockup position are paid to its NFT owner, transfer the position NFT instead"
+    );
+    app.execute_contract(
+        user_address.clone(),
+        position_nft.clone(),
+        &cw721_base::ExecuteMsg::<Empty>::TransferNft {
+            recipient: buyer_address.to_string(),
+            token_id: lockdrop::lockup_position_token_id(&sold_lp_token, &user_address, 10),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // ######    SHOULD SUCCESSFULLY CLAIM ASTRO REWARDS OF THE SOLD LOCKUP POSITION TO THE NFT OWNER     ######
+    app.update_block(|b| {
+        b.height += 17280;
+        b.time = Timestamp::from_seconds(EPOCH_START + 11000001)
+    });
+
+    let user_info = query_user_info(&app);
+    let user_balance_before = query_balance(&app, &user_address);
+    app.execute_contract(
+        buyer_address.clone(),
+        lockdrop_instance.clone(),
+        &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+            terraswap_lp_token: sold_lp_token.clone(),
+            duration: 10u64,
+            withdraw_lp_stake: false,
+            user_address: Some(user_address.to_string()),
+            force_unlock: false,
+            amount: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        sold_lockup_astro_rewards,
+        query_balance(&app, &buyer_address)
+    );
+
+    // The claim of the NFT owner sends the rest of the ASTRO rewards to the user
+    assert_eq!(
+        user_info.total_astro_rewards
+            - user_info.delegated_astro_rewards
+            - sold_lockup_astro_rewards,
+        query_balance(&app, &user_address) - user_balance_before
+    );
+    let user_info = query_user_info(&app);
+    assert_eq!(true, user_info.astro_transferred);
+
+    // ######    ERROR :: ASTRO rewards of the sold lockup position are paid once     ######
+    let err = app
+        .execute_contract(
+            buyer_address.clone(),
+            lockdrop_instance.clone(),
+            &ExecuteMsg::ClaimRewardsAndOptionallyUnlock {
+                terraswap_lp_token: sold_lp_token.clone(),
+                duration: 10u64,
+                withdraw_lp_stake: false,
+                user_address: Some(user_address.to_string()),
+                force_unlock: false,
+                amount: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        "Generic error: No rewards available to claim!"
+    );
+    assert_eq!(
+        sold_lockup_astro_rewards,
+        query_balance(&app, &buyer_address)
+    );
+}
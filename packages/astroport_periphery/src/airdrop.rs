@@ -1,3 +1,4 @@
+use crate::auction::AllowlistProof;
 use cosmwasm_std::{Addr, Uint128};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
@@ -36,6 +37,7 @@ pub enum ExecuteMsg {
     /// Allows users to delegate their ASTRO tokens to the LP Bootstrap auction contract
     DelegateAstroToBootstrapAuction {
         amount_to_delegate: Uint128,
+        allowlist_proof: Option<AllowlistProof>,
     },
     /// Allows users to withdraw their ASTRO tokens
     WithdrawAirdropReward {},
@@ -50,6 +52,10 @@ pub enum ExecuteMsg {
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
     IncreaseAstroIncentives {},
+    /// Called by the bootstrap auction contract to return ASTRO tokens undelegated by the user
+    UndelegateAstroTokens {
+        user_address: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -1,3 +1,5 @@
+use astroport::asset::{Asset, AssetInfo};
+use astroport::restricted_vector::RestrictedVector;
 use cosmwasm_std::{to_binary, Addr, CosmosMsg, Decimal, Env, StdResult, Uint128, WasmMsg};
 use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
@@ -10,9 +12,17 @@ pub struct InstantiateMsg {
     pub airdrop_contract_address: String,
     pub lockdrop_contract_address: String,
     pub lp_tokens_vesting_duration: u64,
+    pub astro_incentives_vesting_duration: Option<u64>,
+    pub lp_vesting_schedules: Option<LpVestingSchedules>,
     pub init_timestamp: u64,
     pub deposit_window: u64,
     pub withdrawal_window: u64,
+    pub astro_delegators_incentives_share: Decimal,
+    pub time_weight: Option<TimeWeight>,
+    pub keeper: Option<KeeperConfig>,
+    pub init_pool_slippage: Option<Decimal>,
+    pub compound_slippage: Option<Decimal>,
+    pub allowlist_merkle_root: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,6 +30,10 @@ pub struct UpdateConfigMsg {
     pub owner: Option<String>,
     pub astro_ust_pair_address: Option<String>,
     pub generator_contract: Option<String>,
+    pub keeper: Option<KeeperConfig>,
+    pub init_pool_slippage: Option<Decimal>,
+    pub compound_slippage: Option<Decimal>,
+    pub allowlist_merkle_root: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -31,26 +45,115 @@ pub struct PoolInfo {
     pub astro_ust_lp_token_address: Addr,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TimeWeight {
+    /// Bonus weight applied to deposits made at the very start of the deposit window.
+    /// It decreases linearly with the time left in the window and reaches zero at its end
+    pub max_bonus: Decimal,
+}
+
+/// Permissionless execution of `InitPool` and `StakeLpTokens`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct KeeperConfig {
+    /// Number of seconds after the withdrawal window end (for `InitPool`) or the pool initialization
+    /// (for `StakeLpTokens`) during which only the owner can execute them
+    pub grace_period: u64,
+    /// ASTRO paid from the auction incentives to a keeper for each executed operation
+    pub bounty: Uint128,
+}
+
+/// Proof that an address is a leaf of the allowlist merkle tree
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AllowlistProof {
+    /// Leaf amount. Caps UST deposited by the address
+    pub cap: Uint128,
+    /// Array of hashes to prove the leaf belongs to the merkle tree
+    pub merkle_proof: Vec<String>,
+}
+
+/// LP Tokens vesting schedules. Each schedule is a list of `(timestamp_offset, cumulative_percent)` points,
+/// where the offset is counted from the pool initialization. Nothing is unlocked before the first point,
+/// the cumulative percent of a point is unlocked once it is reached, and the unlocked percent grows linearly
+/// between consecutive points. The last point must unlock 100% of the LP Tokens
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct LpVestingSchedules {
+    /// Schedule applied to LP shares received for delegated ASTRO
+    pub astro_delegators: Vec<(u64, Decimal)>,
+    /// Schedule applied to LP shares received for deposited UST
+    pub ust_depositors: Vec<(u64, Decimal)>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
-    UpdateConfig { new_config: UpdateConfigMsg },
+    UpdateConfig {
+        new_config: UpdateConfigMsg,
+    },
+    SetPositionNft {
+        contract: String,
+    },
 
-    DepositUst {},
-    WithdrawUst { amount: Uint128 },
+    DepositUst {
+        beneficiary: Option<String>,
+        allowlist_proof: Option<AllowlistProof>,
+    },
+    WithdrawUst {
+        amount: Uint128,
+        user_address: Option<String>,
+    },
+    UndelegateAstro {
+        amount: Uint128,
+    },
 
-    InitPool { slippage: Option<Decimal> },
+    InitPool {},
     StakeLpTokens {},
+    MigrateGenerator {
+        generator: String,
+    },
+    EmergencyUnstake {},
+
+    ClaimRewards {
+        withdraw_lp_shares: Option<Uint128>,
+        user_address: Option<String>,
+    },
+
+    SetCompounding {
+        enabled: bool,
+        user_address: Option<String>,
+    },
+    Compound {},
+    WithdrawCompoundedLp {
+        units: Uint128,
+        user_address: Option<String>,
+    },
+
+    TransferPosition {
+        recipient: String,
+        fraction: Decimal,
+    },
+
+    ApproveOperator {
+        operator: String,
+    },
+    RevokeOperator {
+        operator: String,
+    },
 
-    ClaimRewards { withdraw_lp_shares: Option<Uint128> },
     Callback(CallbackMsg),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
-    DelegateAstroTokens { user_address: String },
+    DelegateAstroTokens {
+        user_address: String,
+        allowlist_proof: Option<AllowlistProof>,
+    },
     IncreaseAstroIncentives {},
 }
 
@@ -59,14 +162,44 @@ pub enum Cw20HookMsg {
 pub enum CallbackMsg {
     UpdateStateOnRewardClaim {
         prev_astro_balance: Uint128,
+        prev_proxy_reward_balances: Vec<Asset>,
     },
     UpdateStateOnLiquidityAdditionToPool {
         prev_lp_balance: Uint128,
     },
     WithdrawUserRewardsCallback {
         user_address: Addr,
+        recipient: Addr,
         withdraw_lp_shares: Option<Uint128>,
     },
+    CompoundAstroRewards {},
+    ProvideCompoundedLiquidity {
+        prev_ust_balance: Uint128,
+        astro_amount: Uint128,
+        users_astro: Uint128,
+        total_astro: Uint128,
+        compounding_lp_shares: Uint128,
+    },
+    StakeCompoundedLp {
+        prev_lp_balance: Uint128,
+        users_astro: Uint128,
+        total_astro: Uint128,
+        compounding_lp_shares: Uint128,
+    },
+    WithdrawCompoundedLpCallback {
+        user_address: Addr,
+        recipient: Addr,
+        units: Uint128,
+    },
+    TransferPositionCallback {
+        user_address: Addr,
+        recipient: Addr,
+        fraction: Decimal,
+    },
+    MigrateGeneratorCallback {
+        generator: Addr,
+        lp_amount: Uint128,
+    },
 }
 
 // Modified from
@@ -86,11 +219,36 @@ impl CallbackMsg {
 pub enum QueryMsg {
     Config {},
     State {},
-    UserInfo { address: String },
+    UserInfo {
+        address: String,
+    },
+    PositionNftInfo {
+        token_id: String,
+    },
+    Operators {
+        address: String,
+    },
+    AllowlistCap {
+        address: String,
+    },
+    Phase {
+        timestamp: Option<u64>,
+    },
+    SimulateInitPool {},
+    Users {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Totals {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    pub astro_incentives_vesting_duration: Option<u64>,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -111,12 +269,30 @@ pub struct Config {
     pub astro_incentive_amount: Option<Uint128>,
     ///  Number of seconds over which LP Tokens are vested
     pub lp_tokens_vesting_duration: u64,
+    /// Number of seconds over which ASTRO incentives are unlocked. If not set, incentives are unlocked alongside LP Tokens
+    pub astro_incentives_vesting_duration: Option<u64>,
+    /// LP Tokens vesting schedules. If not set, LP Tokens are vested linearly over lp_tokens_vesting_duration
+    pub lp_vesting_schedules: Option<LpVestingSchedules>,
     /// Timestamp since which ASTRO / UST deposits will be allowed
     pub init_timestamp: u64,
     /// Number of seconds post init_timestamp during which deposits / withdrawals will be allowed
     pub deposit_window: u64,
     /// Number of seconds post deposit_window completion during which only withdrawals are allowed
     pub withdrawal_window: u64,
+    /// Share of the ASTRO incentives allocated to ASTRO delegators. The rest goes to UST depositors
+    pub astro_delegators_incentives_share: Decimal,
+    /// Time-weighting applied to deposits to reward early participants
+    pub time_weight: Option<TimeWeight>,
+    /// Position NFT contract. If set, every position is mirrored by an NFT whose owner manages the position
+    pub position_nft: Option<Addr>,
+    /// Permissionless execution settings. If not set, only the owner can initialize the pool and stake LP Tokens
+    pub keeper: Option<KeeperConfig>,
+    /// Slippage tolerance used to provide liquidity to the ASTRO-UST Pool
+    pub init_pool_slippage: Option<Decimal>,
+    /// Max spread of the swap and slippage tolerance of the liquidity provision used for compounding
+    pub compound_slippage: Option<Decimal>,
+    /// Merkle root of the allowlist. If set, only allowlisted addresses can deposit UST and receive ASTRO delegations
+    pub allowlist_merkle_root: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
@@ -134,6 +310,38 @@ pub struct State {
     pub pool_init_timestamp: u64,
     /// Ratio of ASTRO rewards accrued to weighted_amount. Used to calculate ASTRO incentives accrued by each user
     pub generator_astro_per_share: Decimal,
+    /// Ratio of Generator Proxy rewards accrued to LP shares. Used to calculate Proxy rewards accrued by each user
+    pub generator_proxy_per_share: RestrictedVector<AssetInfo, Decimal>,
+    /// Total time-weighted ASTRO delegations. Used to calculate ASTRO incentives of ASTRO delegators
+    pub total_astro_weighted: Uint128,
+    /// Total time-weighted UST deposits. Used to calculate ASTRO incentives of UST depositors
+    pub total_ust_weighted: Uint128,
+    /// LP shares of users whose ASTRO staking rewards are compounded
+    pub compounding_lp_shares: Uint128,
+    /// LP shares of users who opted in to compounding and join the compounding pool after the next compounding round
+    pub pending_compounding_lp_shares: Uint128,
+    /// Number of executed compounding rounds
+    pub compound_round: u64,
+    /// Ratio of ASTRO rewards accrued to LP shares at the last compounding round
+    pub compound_astro_per_share: Decimal,
+    /// Ratio of compounding pool units minted to LP shares of compounding users. Used to calculate units of each user
+    pub compound_units_per_share: Decimal,
+    /// LP shares owned by the compounding pool
+    pub compounded_lp_shares: Uint128,
+    /// Total compounding pool units
+    pub total_compound_units: Uint128,
+    /// ASTRO of the auction incentives reserved for the keeper who stakes LP Tokens. Users' incentives are final
+    /// once LP Tokens are staked and the reserve is either paid to the keeper or left in the incentives
+    pub keeper_bounty_reserve: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct CompoundRound {
+    /// Ratio of ASTRO rewards accrued to LP shares at the end of the round
+    pub astro_per_share: Decimal,
+    /// Ratio of compounding pool units minted to LP shares of compounding users at the end of the round
+    pub units_per_share: Decimal,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
@@ -143,20 +351,44 @@ pub struct UserInfo {
     pub astro_delegated: Uint128,
     /// Total UST delegated by the user
     pub ust_delegated: Uint128,
+    /// Time-weighted ASTRO delegated by the user
+    pub astro_weighted: Uint128,
+    /// Time-weighted UST delegated by the user
+    pub ust_weighted: Uint128,
     /// Withdrawal counter to capture if the user already withdrew UST during the "only withdrawals" window
     pub ust_withdrawn: bool,
+    /// ASTRO Tokens delegated by the user through the airdrop contract which can be undelegated back to it
+    pub airdrop_astro_delegated: Uint128,
+    /// ASTRO Tokens delegated by the user through the lockdrop contract which can be undelegated back to it
+    pub lockdrop_astro_delegated: Uint128,
+    /// Withdrawal counter to capture if the user already undelegated ASTRO during the "only withdrawals" window
+    pub astro_undelegated: bool,
     /// User's LP share balance
     pub lp_shares: Option<Uint128>,
+    /// Part of user's LP shares received for delegated ASTRO. Vested by the ASTRO delegators LP vesting schedule
+    pub astro_lp_shares: Uint128,
     /// LP shares withdrawn by the user
     pub claimed_lp_shares: Uint128,
     /// User's ASTRO rewards for participating in the auction
     pub auction_incentive_amount: Option<Uint128>,
-    /// ASTRO tokens were transferred to user
-    pub astro_incentive_transferred: bool,
+    /// ASTRO auction incentives withdrawn by the user
+    pub claimed_incentives: Uint128,
     /// ASTRO staking incentives (LP token staking) withdrawn by the user
     pub generator_astro_debt: Uint128,
     /// Ratio of ASTRO rewards claimed to amount. Used to calculate ASTRO incentives claimable by each user
     pub user_gen_astro_per_share: Decimal,
+    /// Generator Proxy rewards (LP token staking) withdrawn by the user
+    pub generator_proxy_debt: RestrictedVector<AssetInfo, Uint128>,
+    /// Ratio of Proxy rewards claimed to amount. Used to calculate Proxy rewards claimable by each user
+    pub user_gen_proxy_per_share: RestrictedVector<AssetInfo, Decimal>,
+    /// Whether ASTRO staking rewards of the user are compounded
+    pub compounding: bool,
+    /// Compounding round after which the user's LP shares join the compounding pool. Zero once joined
+    pub compound_join_round: u64,
+    /// User's compounding pool units
+    pub compound_units: Uint128,
+    /// Ratio of compounding pool units accounted to the user's LP shares
+    pub user_compound_units_per_share: Decimal,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -166,22 +398,145 @@ pub struct UserInfoResponse {
     pub astro_delegated: Uint128,
     /// Total UST delegated by the user
     pub ust_delegated: Uint128,
+    /// Time-weighted ASTRO delegated by the user
+    pub astro_weighted: Uint128,
+    /// Time-weighted UST delegated by the user
+    pub ust_weighted: Uint128,
     /// Withdrawal counter to capture if the user already withdrew UST during the "only withdrawals" window
     pub ust_withdrawn: bool,
+    /// Withdrawal counter to capture if the user already undelegated ASTRO during the "only withdrawals" window
+    pub astro_undelegated: bool,
+    /// ASTRO Tokens delegated by the user through the airdrop contract which can be undelegated back to it
+    pub airdrop_astro_delegated: Uint128,
+    /// ASTRO Tokens delegated by the user through the lockdrop contract which can be undelegated back to it
+    pub lockdrop_astro_delegated: Uint128,
     /// User's LP share balance
     pub lp_shares: Option<Uint128>,
     /// LP shares withdrawn by the user
     pub claimed_lp_shares: Uint128,
     /// LP shares that are available to withdraw
     pub withdrawable_lp_shares: Option<Uint128>,
+    /// Timestamp of the next point of the LP vesting schedule
+    pub next_lp_unlock_timestamp: Option<u64>,
+    /// LP shares that will be unlocked by the next point of the LP vesting schedule
+    pub next_lp_unlock_amount: Option<Uint128>,
     /// User's ASTRO rewards for participating in the auction
     pub auction_incentive_amount: Option<Uint128>,
-    /// ASTRO tokens were transferred to user
-    pub astro_incentive_transferred: bool,
+    /// ASTRO auction incentives withdrawn by the user
+    pub claimed_incentives: Uint128,
+    /// ASTRO auction incentives that are unlocked and available to withdraw
+    pub claimable_incentives: Uint128,
     /// Claimable ASTRO staking rewards
     pub claimable_generator_astro: Uint128,
     /// ASTRO staking incentives (LP token staking) withdrawn by the user
     pub generator_astro_debt: Uint128,
     /// Ratio of ASTRO rewards claimed to amount. Used to calculate ASTRO incentives claimable by each user
     pub user_gen_astro_per_share: Decimal,
+    /// Claimable Generator Proxy rewards
+    pub claimable_generator_proxy_debt: RestrictedVector<AssetInfo, Uint128>,
+    /// Generator Proxy rewards (LP token staking) withdrawn by the user
+    pub generator_proxy_debt: RestrictedVector<AssetInfo, Uint128>,
+    /// Ratio of Proxy rewards claimed to amount. Used to calculate Proxy rewards claimable by each user
+    pub user_gen_proxy_per_share: RestrictedVector<AssetInfo, Decimal>,
+    /// Whether ASTRO staking rewards of the user are compounded
+    pub compounding: bool,
+    /// User's compounding pool units
+    pub compound_units: Uint128,
+    /// LP shares of the compounding pool owned by the user
+    pub compounded_lp_shares: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionPhase {
+    /// Deposit window hasn't been opened yet
+    NotStarted,
+    /// Deposits and withdrawals are allowed
+    Deposit,
+    /// Only limited withdrawals are allowed
+    WithdrawOnly,
+    /// Deposit and withdrawal windows are closed, liquidity hasn't been added to the pool yet
+    Closed,
+    /// Liquidity has been added to the ASTRO-UST pool
+    PoolInitialized,
+    /// ASTRO-UST LP Tokens are staked with the generator
+    Staked,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PhaseResponse {
+    /// Current phase of the auction
+    pub phase: AuctionPhase,
+    /// Timestamp since which deposits are allowed
+    pub deposit_start_timestamp: u64,
+    /// Timestamp since which only withdrawals are allowed
+    pub deposit_end_timestamp: u64,
+    /// Timestamp after which allowed withdrawals decrease linearly from 50% to 0%
+    pub withdrawal_decrease_timestamp: u64,
+    /// Timestamp since which withdrawals aren't allowed
+    pub withdrawal_end_timestamp: u64,
+    /// Timestamp at which liquidity was added to the ASTRO-UST pool
+    pub pool_init_timestamp: Option<u64>,
+    /// Percent of deposits which can be withdrawn at the current block
+    pub withdrawal_percent: Decimal,
+    /// Percent of deposits which can be withdrawn at the requested timestamp
+    pub projected_withdrawal_percent: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SimulateInitPoolResponse {
+    /// ASTRO tokens which will be provided to the ASTRO-UST pool
+    pub astro_amount: Uint128,
+    /// UST which will be provided to the ASTRO-UST pool after tax deduction
+    pub ust_amount: Uint128,
+    /// Initial ASTRO price in UST implied by the provided amounts
+    pub initial_price: Decimal,
+    /// LP tokens which the pair is expected to mint
+    pub lp_shares: Uint128,
+    /// LP tokens which will be shared among ASTRO delegators
+    pub astro_delegators_lp_shares: Uint128,
+    /// LP tokens which will be shared among UST depositors
+    pub ust_depositors_lp_shares: Uint128,
+    /// Whether the pair already holds liquidity, in which case the initial price won't hold
+    pub pool_has_liquidity: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct UserInfoWithAddressResponse {
+    /// Address of the user
+    pub address: Addr,
+    /// Position of the user
+    pub user_info: UserInfoResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct TotalsResponse {
+    /// Number of summed auction participants
+    pub users_count: u64,
+    /// Sum of ASTRO delegated by users
+    pub astro_delegated: Uint128,
+    /// Sum of UST deposited by users
+    pub ust_delegated: Uint128,
+    /// Sum of time-weighted ASTRO delegated by users
+    pub astro_weighted: Uint128,
+    /// Sum of time-weighted UST deposited by users
+    pub ust_weighted: Uint128,
+    /// Sum of users' LP shares. Zero until liquidity is added to the ASTRO-UST pool
+    pub lp_shares: Uint128,
+    /// Sum of users' ASTRO incentives for participating in the auction
+    pub auction_incentive_amount: Uint128,
+    /// Sum of users' compounding pool units
+    pub compound_units: Uint128,
+    /// Sum of users' LP tokens in the compounding pool
+    pub compounded_lp_shares: Uint128,
+    /// Last summed user. Passed as `start_after` to sum the next page of users, not set on the last page
+    pub last_user: Option<Addr>,
+    /// Whether the sums match the contract's state, set if the page covers all users. LP shares, incentives and
+    /// compounding pool units are allowed to be less than the contract's totals because of rounding.
+    /// Sums of multiple pages should be added up and checked against the contract's state by the client
+    pub is_consistent: Option<bool>,
 }
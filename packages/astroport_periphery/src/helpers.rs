@@ -1,8 +1,44 @@
 use cosmwasm_std::{
-    to_binary, Addr, Binary, CosmosMsg, QuerierWrapper, QueryRequest, StdResult, Uint128, WasmMsg,
-    WasmQuery,
+    to_binary, Addr, Binary, CosmosMsg, Empty, QuerierWrapper, QueryRequest, StdError, StdResult,
+    Uint128, WasmMsg, WasmQuery,
 };
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::cmp::Ordering;
+use std::convert::TryInto;
+
+/// Messages of the position NFT contract which aren't part of the cw721 spec.
+/// These follow the messages of the cw721-base contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionNftExecuteMsg {
+    Mint(PositionNftMintMsg),
+}
+
+/// Mints a new position NFT
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionNftMintMsg {
+    pub token_id: String,
+    pub owner: String,
+    pub token_uri: Option<String>,
+    pub extension: Empty,
+}
+
+/// Queries of the position NFT contract which aren't part of the cw721 spec
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionNftQueryMsg {
+    Minter {},
+}
+
+/// This structure describes the response of [`PositionNftQueryMsg::Minter`]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionNftMinterResponse {
+    pub minter: String,
+}
 
 /// @dev Helper function which returns a cosmos wasm msg to transfer cw20 tokens to a recipient address
 /// @param recipient : Address to be transferred cw20 tokens to
@@ -90,3 +126,165 @@ pub fn build_approve_cw20_msg(
         funds: vec![],
     }))
 }
+
+/// Helper function which returns a cosmos wasm msg to mint a position NFT
+/// ## Params
+/// * **nft_contract** is an object of type [`Addr`]. Position NFT contract address
+///
+/// * **token_id** is an object of type [`String`]. Identifier of the position
+///
+/// * **owner** is an object of type [`Addr`]. Address which receives the minted NFT
+pub fn build_mint_position_nft_msg(
+    nft_contract: &Addr,
+    token_id: String,
+    owner: &Addr,
+) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: nft_contract.to_string(),
+        msg: to_binary(&PositionNftExecuteMsg::Mint(PositionNftMintMsg {
+            token_id,
+            owner: owner.to_string(),
+            token_uri: None,
+            extension: Empty {},
+        }))?,
+        funds: vec![],
+    }))
+}
+
+/// Helper function which returns a cosmos wasm msg to burn a position NFT
+/// ## Params
+/// * **nft_contract** is an object of type [`Addr`]. Position NFT contract address
+///
+/// * **token_id** is an object of type [`String`]. Identifier of the position
+pub fn build_burn_position_nft_msg(nft_contract: &Addr, token_id: String) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: nft_contract.to_string(),
+        msg: to_binary(&Cw721ExecuteMsg::Burn { token_id })?,
+        funds: vec![],
+    }))
+}
+
+/// Helper function to get the current owner of a position NFT
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **nft_contract** is an object of type [`Addr`]. Position NFT contract address
+///
+/// * **token_id** is an object of type [`String`]. Identifier of the position
+pub fn query_position_nft_owner(
+    querier: &QuerierWrapper,
+    nft_contract: &Addr,
+    token_id: String,
+) -> StdResult<Addr> {
+    let res: OwnerOfResponse = querier.query_wasm_smart(
+        nft_contract,
+        &Cw721QueryMsg::OwnerOf {
+            token_id,
+            include_expired: None,
+        },
+    )?;
+
+    Ok(Addr::unchecked(res.owner))
+}
+
+/// Checks that the position NFT contract can be minted only by the given contract
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **nft_contract** is an object of type [`Addr`]. Position NFT contract address
+///
+/// * **minter** is an object of type [`Addr`]. Contract which mints position NFTs
+pub fn assert_position_nft_minter(
+    querier: &QuerierWrapper,
+    nft_contract: &Addr,
+    minter: &Addr,
+) -> StdResult<()> {
+    let res: PositionNftMinterResponse =
+        querier.query_wasm_smart(nft_contract, &PositionNftQueryMsg::Minter {})?;
+
+    if res.minter != minter.as_str() {
+        return Err(StdError::generic_err(
+            "Position NFTs should be minted by this contract",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that the sender is allowed to manage a position. If position NFTs are enabled, the sender
+/// should own the NFT of the position, otherwise the sender should be the user of the position.
+/// ## Params
+/// * **querier** is an object of type [`QuerierWrapper`].
+///
+/// * **position_nft** is an [`Option`] of type [`Addr`]. Position NFT contract address
+///
+/// * **token_id** is an object of type [`String`]. Identifier of the position
+///
+/// * **user_address** is an object of type [`Addr`]. User of the position
+///
+/// * **sender** is an object of type [`Addr`]. Address which manages the position
+pub fn assert_position_owner(
+    querier: &QuerierWrapper,
+    position_nft: &Option<Addr>,
+    token_id: String,
+    user_address: &Addr,
+    sender: &Addr,
+) -> StdResult<()> {
+    let owner = match position_nft {
+        Some(nft_contract) => query_position_nft_owner(querier, nft_contract, token_id)?,
+        None => user_address.clone(),
+    };
+
+    if owner != *sender {
+        return Err(StdError::generic_err("Sender doesn't own the position"));
+    }
+
+    Ok(())
+}
+
+/// Verify whether an account and an amount are a leaf of the Merkle Tree.
+/// Used for airdrop claims and auction allowlists.
+/// ## Params
+/// * **account** is an object of type [`Addr`]. Account which is a part of the leaf
+///
+/// * **amount** is an object of type [`Uint128`]. Amount which is a part of the leaf
+///
+/// * **merkle_proof** is a vector of type [`String`]. Array of hashes to prove the input is a leaf of the Merkle Tree
+///
+/// * **merkle_root** is an object of type [`str`]. Hash of Merkle tree's root
+pub fn verify_merkle_proof(
+    account: &Addr,
+    amount: Uint128,
+    merkle_proof: Vec<String>,
+    merkle_root: &str,
+) -> StdResult<bool> {
+    let leaf = account.to_string() + &amount.to_string();
+    let mut hash_buf = Keccak256::digest(leaf.as_bytes())
+        .as_slice()
+        .try_into()
+        .map_err(|_| StdError::generic_err("Wrong length"))?;
+    let mut hash_str: String;
+
+    for p in merkle_proof {
+        let mut proof_buf: [u8; 32] = [0; 32];
+        hex::decode_to_slice(p, &mut proof_buf)
+            .map_err(|_| StdError::generic_err("Invalid merkle proof"))?;
+        let proof_buf_str = hex::encode(proof_buf);
+        hash_str = hex::encode(hash_buf);
+
+        if proof_buf_str.cmp(&hash_str.clone()) == Ordering::Greater {
+            hash_buf = Keccak256::digest(&[hash_buf, proof_buf].concat())
+                .as_slice()
+                .try_into()
+                .map_err(|_| StdError::generic_err("Wrong length"))?
+        } else {
+            hash_buf = Keccak256::digest(&[proof_buf, hash_buf].concat())
+                .as_slice()
+                .try_into()
+                .map_err(|_| StdError::generic_err("Wrong length"))?
+        }
+    }
+
+    hash_str = hex::encode(hash_buf);
+    Ok(merkle_root == hash_str)
+}
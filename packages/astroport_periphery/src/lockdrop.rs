@@ -1,12 +1,16 @@
-use astroport::asset::{Asset, AssetInfo};
+use crate::auction::AllowlistProof;
+use astroport::asset::{addr_validate_to_lower, Asset, AssetInfo, PairInfo};
 use astroport::restricted_vector::RestrictedVector;
 use cosmwasm_std::{
-    to_binary, Addr, CosmosMsg, Decimal, Env, StdResult, Uint128, Uint256, WasmMsg,
+    to_binary, Addr, Api, Binary, CosmosMsg, Decimal, Decimal256, Env, QuerierWrapper, StdError,
+    StdResult, Uint128, Uint256, WasmMsg,
 };
-use cw20::Cw20ReceiveMsg;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+const SECONDS_PER_WEEK: u64 = 86400 * 7;
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     /// Account which can update config
@@ -27,6 +31,8 @@ pub struct InstantiateMsg {
     pub weekly_divider: u64,
     /// Max lockup positions a user can have
     pub max_positions_per_user: u32,
+    /// Curve of the lockup weight boost by lockup duration, linear if not set
+    pub weight_curve: Option<WeightCurve>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -44,17 +50,52 @@ pub struct UpdateConfigMsg {
 pub enum ExecuteMsg {
     // Receive hook used to accept LP Token deposits
     Receive(Cw20ReceiveMsg),
+    // Open a new user position or add to an existing position with native LP tokens sent along with the message
+    IncreaseLockup {
+        duration: u64,
+    },
+    // Move LP units of a position to a position with a longer lockup duration during the deposit window
+    ExtendLockup {
+        terraswap_lp_token: String,
+        from_duration: u64,
+        to_duration: u64,
+        amount: Uint128,
+        user_address: Option<String>,
+    },
+    // Move a lockup position to another address, pending rewards of the position are claimed first
+    TransferLockup {
+        terraswap_lp_token: String,
+        duration: u64,
+        recipient: String,
+    },
     // ADMIN Function ::: To update configuration
     UpdateConfig {
         new_config: UpdateConfigMsg,
     },
+    // ADMIN Function ::: To mirror lockup positions by NFTs of the given cw721 contract
+    SetPositionNft {
+        contract: String,
+    },
+    // ADMIN Function ::: To set parameters of forced unlocks, None disables them
+    UpdateForceUnlockParams {
+        params: Option<ForceUnlockParams>,
+    },
+    // ADMIN Function ::: To replace the lockup weight curve before the deposit window opens
+    UpdateWeightCurve {
+        weight_curve: WeightCurve,
+    },
     // Called by the bootstrap auction contract when liquidity is added to the
     // Pool to enable ASTRO withdrawals by users
     EnableClaims {},
     // ADMIN Function ::: Add new Pool (Only Terraswap Pools)
+    // native_lp_pool should be set if terraswap_lp_token is a native LP token denom
+    // lockup_params override the lockup duration bounds and windows of the config for the pool
     InitializePool {
         terraswap_lp_token: String,
         incentives_share: u64,
+        source_dex: Option<SourceDex>,
+        native_lp_pool: Option<String>,
+        lockup_params: Option<PoolLockupParams>,
     },
     // ADMIN Function ::: To set incentives_share for the Pool
     UpdatePool {
@@ -62,10 +103,12 @@ pub enum ExecuteMsg {
         incentives_share: u64,
     },
     // Function to facilitate LP Token withdrawals from lockups
+    // user_address is the lockup owner, it should be set by the holder of the lockup position NFT
     WithdrawFromLockup {
         terraswap_lp_token: String,
         duration: u64,
         amount: Uint128,
+        user_address: Option<String>,
     },
     // ADMIN Function ::: To Migrate liquidity from terraswap to astroport
     MigrateLiquidity {
@@ -80,25 +123,42 @@ pub enum ExecuteMsg {
     // Delegate ASTRO to Bootstrap via auction contract
     DelegateAstroToAuction {
         amount: Uint128,
+        allowlist_proof: Option<AllowlistProof>,
     },
     // Facilitates ASTRO reward withdrawal which have not been delegated to bootstrap auction along with optional Unlock (can be forceful)
     // If withdraw_lp_stake is true and force_unlock is false, it Unlocks the lockup position if its lockup duration has concluded
-    // If both withdraw_lp_stake and force_unlock are true, it forcefully unlocks the positon. user needs to approve ASTRO Token to
-    // be transferred by the lockdrop contract to itself for forceful unlock
+    // If both withdraw_lp_stake and force_unlock are true, it forcefully unlocks the positon. The ASTRO penalty is taken from
+    // the user's unclaimed lockdrop ASTRO, the rest of it has to be approved to be transferred by the lockdrop contract to itself
+    // amount is the Astroport LP amount to unlock from a matured position, the rest stays staked. The whole position is unlocked by default
     ClaimRewardsAndOptionallyUnlock {
         terraswap_lp_token: String,
         duration: u64,
         withdraw_lp_stake: bool,
+        user_address: Option<String>,
+        #[serde(default)]
+        force_unlock: bool,
+        #[serde(default)]
+        amount: Option<Uint128>,
+    },
+    // Facilitates ASTRO reward withdrawal along with generator staking rewards of all the sender's lockup positions,
+    // generator rewards are claimed once per pool and rewards are transferred in aggregated transfers
+    // If withdraw_matured_lp is true, it Unlocks all the lockup positions whose lockup duration has concluded
+    ClaimAll {
+        withdraw_matured_lp: bool,
     },
     ClaimAssetReward {
         recipient: Option<String>,
         terraswap_lp_token: String,
         duration: u64,
+        user_address: Option<String>,
     },
     // ADMIN Function ::: Toggle poll rewards
     TogglePoolRewards {
         terraswap_lp_token: String,
         enable: bool,
+        /// Source of the pool's asset rewards, the current one is kept if not set
+        #[serde(default)]
+        asset_rewards: Option<AssetRewardsConfig>,
     },
     /// Callbacks; only callable by the contract itself.
     Callback(CallbackMsg),
@@ -124,35 +184,53 @@ pub enum Cw20HookMsg {
         duration: u64,
     },
     IncreaseAstroIncentives {},
+    /// Called by the bootstrap auction contract to return ASTRO tokens undelegated by the user
+    UndelegateAstroTokens {
+        user_address: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CallbackMsg {
     UpdatePoolOnDualRewardsClaim {
-        terraswap_lp_token: Addr,
+        terraswap_lp_token: String,
         prev_astro_balance: Uint128,
         prev_proxy_reward_balances: Vec<Asset>,
     },
     WithdrawUserLockupRewardsCallback {
-        terraswap_lp_token: Addr,
+        terraswap_lp_token: String,
         user_address: Addr,
+        recipient: Addr,
         duration: u64,
         withdraw_lp_stake: bool,
+        force_unlock_penalty: Uint128,
+        amount: Option<Uint128>,
     },
-    WithdrawLiquidityFromTerraswapCallback {
-        terraswap_lp_token: Addr,
+    WithdrawLiquidityFromSourceDexCallback {
+        terraswap_lp_token: String,
         astroport_pool: Addr,
-        prev_assets: [terraswap::asset::Asset; 2],
+        prev_assets: [Asset; 2],
         slippage_tolerance: Option<Decimal>,
     },
     DistributeAssetReward {
-        previous_balance: Uint128,
-        terraswap_lp_token: Addr,
+        previous_balances: Vec<Asset>,
+        terraswap_lp_token: String,
         user_address: Addr,
         recipient: Addr,
         lock_duration: u64,
     },
+    TransferLockupCallback {
+        terraswap_lp_token: String,
+        user_address: Addr,
+        recipient: Addr,
+        duration: u64,
+    },
+    ClaimAllCallback {
+        user_address: Addr,
+        lockups: Vec<(String, u64)>,
+        withdraw_matured_lp: bool,
+    },
 }
 
 // Modified from
@@ -167,6 +245,210 @@ impl CallbackMsg {
     }
 }
 
+/// Parameters of forced unlocks. The ASTRO penalty for unlocking a position before its unlock timestamp is
+/// `min(penalty_per_week * remaining weeks, max_penalty)` of the ASTRO rewards of the lockup position,
+/// the remaining weeks are rounded up.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ForceUnlockParams {
+    /// Penalty share of the lockup ASTRO rewards per remaining week of the lockup
+    pub penalty_per_week: Decimal,
+    /// Max penalty share of the lockup ASTRO rewards
+    pub max_penalty: Decimal,
+    /// What happens to collected penalties
+    pub penalty_destination: PenaltyDestination,
+}
+
+impl ForceUnlockParams {
+    /// Returns the share of the lockup ASTRO rewards paid as a penalty.
+    /// ## Params
+    /// * **remaining_seconds** is an object of type [`u64`]. Time left until the lockup can be unlocked.
+    pub fn penalty_rate(&self, remaining_seconds: u64) -> Decimal {
+        let remaining_weeks = (remaining_seconds + SECONDS_PER_WEEK - 1) / SECONDS_PER_WEEK;
+        let rate = self.penalty_per_week * Decimal::from_ratio(remaining_weeks, 1u64);
+        rate.min(self.max_penalty)
+    }
+}
+
+/// What happens to ASTRO collected as forced unlock penalties
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PenaltyDestination {
+    /// Penalties are burned
+    Burn,
+    /// Penalties are distributed among the remaining lockers of the pool proportionally to their LP units
+    Lockers,
+}
+
+/// Curve of the lockup weight boost by lockup duration. The weight of a lockup position is
+/// `boost(duration) * LP units locked`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightCurve {
+    /// `1 + (duration - 1) * weekly_multiplier / weekly_divider`
+    Linear {},
+    /// `1 + (duration - 1)^2 * weekly_multiplier / weekly_divider`
+    Quadratic {},
+    /// Linear boost limited by `max_boost`
+    Capped { max_boost: Decimal256 },
+    /// Non-decreasing boost of every lockup week, `boosts[0]` is the boost of a 1 week lockup
+    Table { boosts: Vec<Decimal256> },
+}
+
+impl Default for WeightCurve {
+    fn default() -> Self {
+        WeightCurve::Linear {}
+    }
+}
+
+impl WeightCurve {
+    /// Returns the lockup weight boost for the given lockup duration.
+    /// ## Params
+    /// * **duration** is an object of type [`u64`]. Lockup duration in weeks.
+    ///
+    /// * **weekly_multiplier** is an object of type [`u64`].
+    ///
+    /// * **weekly_divider** is an object of type [`u64`].
+    pub fn boost(
+        &self,
+        duration: u64,
+        weekly_multiplier: u64,
+        weekly_divider: u64,
+    ) -> StdResult<Decimal256> {
+        let weeks = duration.saturating_sub(1);
+        let linear = || {
+            Decimal256::one() + Decimal256::from_ratio(weeks * weekly_multiplier, weekly_divider)
+        };
+        match self {
+            WeightCurve::Linear {} => Ok(linear()),
+            WeightCurve::Quadratic {} => Ok(Decimal256::one()
+                + Decimal256::from_ratio(weeks * weeks * weekly_multiplier, weekly_divider)),
+            WeightCurve::Capped { max_boost } => Ok(linear().min(*max_boost)),
+            WeightCurve::Table { boosts } => boosts.get(weeks as usize).cloned().ok_or_else(|| {
+                StdError::generic_err(format!("No weight boost for {} weeks lockup", duration))
+            }),
+        }
+    }
+}
+
+/// DEX whose LP tokens are locked in a pool and whose liquidity is migrated to Astroport
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceDex {
+    /// Pair with the Terraswap interface
+    Terraswap,
+    /// Pair with the Astroport interface, e.g. a pair of another Astroport deployment
+    Astroport,
+}
+
+impl Default for SourceDex {
+    fn default() -> Self {
+        SourceDex::Terraswap
+    }
+}
+
+impl SourceDex {
+    /// Returns the message which withdraws liquidity from the pair by sending LP tokens to it.
+    /// ## Params
+    /// * **lp_token** is an object of type [`str`]. cw20 LP token of the pair.
+    ///
+    /// * **pair** is an object of type [`Addr`]. Pair whose liquidity is to be withdrawn.
+    ///
+    /// * **amount** is an object of type [`Uint128`]. Amount of LP tokens to burn.
+    pub fn withdraw_liquidity_msg(
+        &self,
+        lp_token: &str,
+        pair: &Addr,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg> {
+        let hook_msg = match self {
+            SourceDex::Terraswap => to_binary(&terraswap::pair::Cw20HookMsg::WithdrawLiquidity {})?,
+            SourceDex::Astroport => to_binary(&astroport::pair::Cw20HookMsg::WithdrawLiquidity {})?,
+        };
+
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                msg: hook_msg,
+                amount,
+            })?,
+        }))
+    }
+
+    /// Returns assets of the pair converted to Astroport types.
+    /// ## Params
+    /// * **querier** is an object of type [`QuerierWrapper`].
+    ///
+    /// * **api** is an object of type [`Api`].
+    ///
+    /// * **pair** is an object of type [`Addr`]. Pair whose assets are queried.
+    pub fn query_pair_asset_infos(
+        &self,
+        querier: &QuerierWrapper,
+        api: &dyn Api,
+        pair: &Addr,
+    ) -> StdResult<[AssetInfo; 2]> {
+        match self {
+            SourceDex::Terraswap => {
+                let pair_info: terraswap::asset::PairInfo =
+                    querier.query_wasm_smart(pair, &terraswap::pair::QueryMsg::Pair {})?;
+                let [first, second] = pair_info.asset_infos;
+                Ok([
+                    convert_terraswap_asset_info(api, first)?,
+                    convert_terraswap_asset_info(api, second)?,
+                ])
+            }
+            SourceDex::Astroport => {
+                let pair_info: PairInfo =
+                    querier.query_wasm_smart(pair, &astroport::pair::QueryMsg::Pair {})?;
+                Ok(pair_info.asset_infos)
+            }
+        }
+    }
+
+    /// Returns the LP token of the pair, either a cw20 token address or a native denom.
+    /// ## Params
+    /// * **querier** is an object of type [`QuerierWrapper`].
+    ///
+    /// * **pair** is an object of type [`Addr`]. Pair whose LP token is queried.
+    pub fn query_pair_liquidity_token(
+        &self,
+        querier: &QuerierWrapper,
+        pair: &Addr,
+    ) -> StdResult<String> {
+        match self {
+            SourceDex::Terraswap => {
+                let pair_info: terraswap::asset::PairInfo =
+                    querier.query_wasm_smart(pair, &terraswap::pair::QueryMsg::Pair {})?;
+                Ok(pair_info.liquidity_token)
+            }
+            SourceDex::Astroport => {
+                let pair_info: PairInfo =
+                    querier.query_wasm_smart(pair, &astroport::pair::QueryMsg::Pair {})?;
+                Ok(pair_info.liquidity_token.to_string())
+            }
+        }
+    }
+}
+
+/// Converts Terraswap asset info to the Astroport one.
+/// ## Params
+/// * **api** is an object of type [`Api`].
+///
+/// * **asset_info** is an object of type [`terraswap::asset::AssetInfo`].
+fn convert_terraswap_asset_info(
+    api: &dyn Api,
+    asset_info: terraswap::asset::AssetInfo,
+) -> StdResult<AssetInfo> {
+    Ok(match asset_info {
+        terraswap::asset::AssetInfo::NativeToken { denom } => AssetInfo::NativeToken { denom },
+        terraswap::asset::AssetInfo::Token { contract_addr } => AssetInfo::Token {
+            contract_addr: addr_validate_to_lower(api, &contract_addr)?,
+        },
+    })
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
@@ -191,6 +473,13 @@ pub enum QueryMsg {
         terraswap_lp_token: String,
         duration: u64,
     },
+    PositionNftInfo {
+        token_id: String,
+    },
+    SimulateWeight {
+        amount: Uint128,
+        duration: u64,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -230,6 +519,52 @@ pub struct Config {
     pub lockdrop_incentives: Uint128,
     /// Max lockup positions a user can have
     pub max_positions_per_user: u32,
+    /// Position NFT contract. If set, every lockup position is mirrored by an NFT whose owner manages the lockup
+    #[serde(default)]
+    pub position_nft: Option<Addr>,
+    /// Parameters of forced unlocks. Forced unlocks are disabled if not set
+    #[serde(default)]
+    pub force_unlock_params: Option<ForceUnlockParams>,
+    /// Curve of the lockup weight boost by lockup duration
+    #[serde(default)]
+    pub weight_curve: WeightCurve,
+}
+
+impl Config {
+    /// Returns the config with the lockup duration bounds and windows overridden by the ones of the pool.
+    /// ## Params
+    /// * **pool_info** is an object of type [`PoolInfo`].
+    pub fn for_pool(&self, pool_info: &PoolInfo) -> Config {
+        let params = &pool_info.lockup_params;
+        Config {
+            init_timestamp: params.init_timestamp.unwrap_or(self.init_timestamp),
+            deposit_window: params.deposit_window.unwrap_or(self.deposit_window),
+            withdrawal_window: params.withdrawal_window.unwrap_or(self.withdrawal_window),
+            min_lock_duration: params.min_lock_duration.unwrap_or(self.min_lock_duration),
+            max_lock_duration: params.max_lock_duration.unwrap_or(self.max_lock_duration),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the timestamp when the deposit / withdrawal windows of both the config and the pool are closed.
+    /// Lockup positions of the pool unlock and its liquidity can be migrated after it.
+    /// ## Params
+    /// * **pool_info** is an object of type [`PoolInfo`].
+    pub fn pool_windows_end(&self, pool_info: &PoolInfo) -> u64 {
+        let pool_config = self.for_pool(pool_info);
+        (self.init_timestamp + self.deposit_window + self.withdrawal_window).max(
+            pool_config.init_timestamp + pool_config.deposit_window + pool_config.withdrawal_window,
+        )
+    }
+
+    /// Returns the timestamp when the deposit / withdrawal windows of the config and of every pool are closed.
+    /// ASTRO rewards of lockup positions are final after it.
+    /// ## Params
+    /// * **state** is an object of type [`State`].
+    pub fn windows_end(&self, state: &State) -> u64 {
+        (self.init_timestamp + self.deposit_window + self.withdrawal_window)
+            .max(state.max_pool_windows_end)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
@@ -240,6 +575,9 @@ pub struct State {
     pub total_astro_delegated: Uint128,
     /// Boolean value indicating if the user can withdraw their ASTRO rewards or not
     pub are_claims_allowed: bool,
+    /// Latest end of the deposit / withdrawal windows of the pools
+    #[serde(default)]
+    pub max_pool_windows_end: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -259,6 +597,90 @@ pub struct PoolInfo {
     pub is_staked: bool,
     /// Flag defines whether the asset has rewards or not
     pub has_asset_rewards: bool,
+    /// DEX whose LP tokens are locked in the pool
+    #[serde(default)]
+    pub source_dex: SourceDex,
+    /// Whether the pool's LP token is a native denom (e.g. issued by a token factory) rather than a cw20 token
+    #[serde(default)]
+    pub native_lp_token: bool,
+    /// Ratio of forced unlock ASTRO penalties accrued to LP units locked
+    #[serde(default)]
+    pub penalty_astro_per_share: Decimal,
+    /// Overrides of the lockup parameters of the config for the pool
+    #[serde(default)]
+    pub lockup_params: PoolLockupParams,
+    /// Source of the asset rewards, pools without it receive uusd rewards of the bLUNA stable pair
+    #[serde(default)]
+    pub asset_rewards: Option<AssetRewardsConfig>,
+}
+
+/// Source of the rewards which the Astroport pair of a pool emits to its liquidity providers
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetRewardsConfig {
+    /// Message executed on the Astroport pair to claim the rewards
+    pub claim_msg: Binary,
+    /// Query of the pending rewards on the Astroport pair, responding with an [`Asset`] or a list of them
+    pub pending_rewards_query: Option<Binary>,
+    /// Assets received as rewards
+    pub reward_assets: Vec<AssetInfo>,
+}
+
+impl AssetRewardsConfig {
+    /// Validates the reward assets of the config.
+    /// ## Params
+    /// * **api** is an object of type [`Api`].
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        if self.reward_assets.is_empty() {
+            return Err(StdError::generic_err("Reward assets should not be empty"));
+        }
+
+        for (i, asset) in self.reward_assets.iter().enumerate() {
+            if let AssetInfo::Token { contract_addr } = asset {
+                addr_validate_to_lower(api, contract_addr.as_str())?;
+            }
+            if self.reward_assets[..i].contains(asset) {
+                return Err(StdError::generic_err(format!(
+                    "Duplicated reward asset {}",
+                    asset
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Per-pool overrides of the lockup parameters of [`Config`]. Parameters which are not set are taken from the config.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PoolLockupParams {
+    /// Min. no. of weeks allowed for lockup
+    pub min_lock_duration: Option<u64>,
+    /// Max. no. of weeks allowed for lockup
+    pub max_lock_duration: Option<u64>,
+    /// Timestamp when the pool will start accepting LP Token deposits
+    pub init_timestamp: Option<u64>,
+    /// Number of seconds during which lockup deposits will be accepted
+    pub deposit_window: Option<u64>,
+    /// Withdrawal Window Length :: Post the deposit window
+    pub withdrawal_window: Option<u64>,
+    /// Max. LP units which can be locked in the pool
+    pub max_lockup_amount: Option<Uint128>,
+}
+
+impl PoolInfo {
+    /// Returns the LP token of the pool from the pool key, see [`pool_key`].
+    /// ## Params
+    /// * **terraswap_lp_token** is an object of type [`str`]. Key of the pool.
+    pub fn lp_token_info(&self, terraswap_lp_token: &str) -> AssetInfo {
+        match terraswap_lp_token.strip_prefix(NATIVE_POOL_KEY_PREFIX) {
+            Some(denom) if self.native_lp_token => AssetInfo::NativeToken {
+                denom: denom.to_string(),
+            },
+            _ => AssetInfo::Token {
+                contract_addr: Addr::unchecked(terraswap_lp_token),
+            },
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
@@ -271,6 +693,23 @@ pub struct UserInfo {
     pub astro_transferred: bool,
     /// Number of lockup positions the user is having
     pub lockup_positions_index: u32,
+    /// ASTRO rewards the user paid as forced unlock penalties
+    #[serde(default)]
+    pub astro_penalty_paid: Uint128,
+    /// ASTRO rewards of the user's lockup positions whose NFT is owned by another address. They are paid to the NFT owners
+    #[serde(default)]
+    pub detached_astro_rewards: Uint128,
+}
+
+impl UserInfo {
+    /// Returns the one-time ASTRO rewards which are neither delegated, paid as penalties nor detached with position NFTs.
+    pub fn unclaimed_astro_rewards(&self) -> StdResult<Uint128> {
+        Ok(self
+            .total_astro_rewards
+            .checked_sub(self.delegated_astro_rewards)?
+            .checked_sub(self.astro_penalty_paid)?
+            .checked_sub(self.detached_astro_rewards)?)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -305,6 +744,19 @@ pub struct LockupInfoV2 {
     pub generator_proxy_debt: RestrictedVector<AssetInfo, Uint128>,
     /// Timestamp beyond which this position can be unlocked
     pub unlock_timestamp: u64,
+    /// Forced unlock ASTRO penalties received by the lockup
+    #[serde(default)]
+    pub penalty_astro_debt: Uint128,
+    /// Boolean value indicating if a part of the position's Astroport LP tokens is transferred while the rest is still locked
+    #[serde(default)]
+    pub partially_unlocked: bool,
+}
+
+impl LockupInfoV2 {
+    /// Returns true if all Astroport LP tokens of the lockup position have been transferred to the user.
+    pub fn is_unlocked(&self) -> bool {
+        self.astroport_lp_transferred.is_some() && !self.partially_unlocked
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -315,8 +767,10 @@ pub struct StateResponse {
     pub total_astro_delegated: Uint128,
     /// Boolean value indicating if the user can withdraw thier ASTRO rewards or not
     pub are_claims_allowed: bool,
-    /// Vector containing LP addresses for all the supported LP Pools
-    pub supported_pairs_list: Vec<Addr>,
+    /// Vector containing LP tokens (cw20 addresses or native denoms) for all the supported LP Pools
+    pub supported_pairs_list: Vec<String>,
+    /// Timestamp when the deposit / withdrawal windows of the config and of every pool are closed
+    pub windows_end: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -333,6 +787,10 @@ pub struct UserInfoResponse {
     pub claimable_generator_astro_debt: Uint128,
     /// Number of lockup positions the user is having
     pub lockup_positions_index: u32,
+    /// ASTRO rewards the user paid as forced unlock penalties
+    pub astro_penalty_paid: Uint128,
+    /// ASTRO rewards of the user's lockup positions whose NFT is owned by another address
+    pub detached_astro_rewards: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -357,8 +815,8 @@ pub struct LockUpInfoSummary {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct LockUpInfoResponse {
-    /// Terraswap LP token
-    pub terraswap_lp_token: Addr,
+    /// Terraswap LP token (cw20 address or native denom)
+    pub terraswap_lp_token: String,
     /// Terraswap LP units locked by the user
     pub lp_units_locked: Uint128,
     /// Boolean value indicating if the user's has withdrawn funds post the only 1 withdrawal limit cutoff
@@ -374,6 +832,8 @@ pub struct LockUpInfoResponse {
     pub generator_proxy_debt: RestrictedVector<AssetInfo, Uint128>,
     /// Proxy tokens receivable as generator rewards that user can claim
     pub claimable_generator_proxy_debt: RestrictedVector<AssetInfo, Uint128>,
+    /// Forced unlock ASTRO penalties of other lockers that user can claim
+    pub claimable_penalty_astro_reward: Uint128,
     /// Timestamp beyond which this position can be unlocked
     pub unlock_timestamp: u64,
     /// User's Astroport LP units, calculated as lp_units_locked (terraswap) / total LP units locked (terraswap) * Astroport LP units minted post migration
@@ -384,5 +844,44 @@ pub struct LockUpInfoResponse {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PendingAssetRewardResponse {
-    pub amount: Uint128,
+    /// Pending rewards of every reward asset of the pool
+    pub rewards: Vec<Asset>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateWeightResponse {
+    /// Weight boost of the lockup duration
+    pub boost: Decimal256,
+    /// Weight of the lockup position
+    pub weight: Uint256,
+}
+
+/// Prefix of the pool keys of native LP tokens, so they can't be confused with cw20 LP token addresses
+pub const NATIVE_POOL_KEY_PREFIX: &str = "native:";
+
+/// Returns the key of the pool of the LP token. The key of a cw20 LP token is its address as before native
+/// LP tokens were supported, the key of a native LP token is its denom prefixed with [`NATIVE_POOL_KEY_PREFIX`].
+/// [`PoolInfo::lp_token_info`] restores the LP token from the key.
+/// ## Params
+/// * **lp_token** is an object of type [`AssetInfo`].
+pub fn pool_key(lp_token: &AssetInfo) -> String {
+    match lp_token {
+        AssetInfo::Token { contract_addr } => contract_addr.to_string(),
+        AssetInfo::NativeToken { denom } => format!("{}{}", NATIVE_POOL_KEY_PREFIX, denom),
+    }
+}
+
+/// Returns the ID of the NFT which mirrors the lockup position.
+/// ## Params
+/// * **terraswap_lp_token** is an object of type [`str`]. Key of the pool, see [`pool_key`].
+///
+/// * **user_address** is an object of type [`Addr`].
+///
+/// * **duration** is an object of type [`u64`].
+pub fn lockup_position_token_id(
+    terraswap_lp_token: &str,
+    user_address: &Addr,
+    duration: u64,
+) -> String {
+    format!("{}:{}:{}", terraswap_lp_token, user_address, duration)
 }